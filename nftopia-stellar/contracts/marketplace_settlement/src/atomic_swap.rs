@@ -1,11 +1,20 @@
 use soroban_sdk::{Env, Address, Vec, Map, Symbol, contracttype, symbol_short, Bytes};
 use crate::error::SettlementError;
-use crate::types::{Asset, ExecutionResult};
+use crate::types::{Asset, ExecutionResult, MultiPartyTrade, NFTItem, TradeTransaction, TransactionState};
 use crate::utils::asset_utils;
 use crate::security::reentrancy_guard::ReentrancyGuard;
+use crate::storage::DataKey;
+use crate::events::{emit_multi_trade_executed, emit_trade_counter_offer, MultiTradeExecutedEvent, TradeCounterOfferEvent};
 
 // Storage keys
 const ATOMIC_SWAPS: Symbol = symbol_short!("atom_swps");
+const NEXT_MULTI_TRADE: Symbol = symbol_short!("next_mtrd");
+
+/// Fewest parties in a multi-party trade; two parties use a regular trade
+const MIN_MULTI_TRADE_PARTIES: u32 = 3;
+
+/// Counter-offers allowed on one trade before it is treated as deadlocked
+pub const MAX_COUNTER_ROUNDS: u32 = 5;
 
 /// Represents an escrow holding
 #[contracttype]
@@ -167,6 +176,321 @@ impl AtomicSwapEngine {
         })
     }
 
+    /// Swap a trade's NFTs between its parties, settling any token top-up alongside
+    ///
+    /// A positive top-up is paid by the counterparty to the initiator and a negative one by
+    /// the initiator to the counterparty. Both parties must authorize, and any failed
+    /// transfer reverts the whole trade.
+    pub fn execute_trade(env: &Env, trade: &TradeTransaction) -> Result<(), SettlementError> {
+        let counterparty = trade.counterparty.clone().ok_or(SettlementError::InvalidState)?;
+        trade.initiator.require_auth();
+        counterparty.require_auth();
+
+        for item in trade.initiator_nfts.iter() {
+            asset_utils::transfer_nft(&item.nft_address, &trade.initiator, &counterparty, item.token_id, env)?;
+        }
+        for item in trade.counterparty_nfts.iter() {
+            asset_utils::transfer_nft(&item.nft_address, &counterparty, &trade.initiator, item.token_id, env)?;
+        }
+
+        if trade.top_up_amount != 0 {
+            let currency = trade.top_up_currency.clone().ok_or(SettlementError::InvalidCurrency)?;
+            let (payer, payee) = if trade.top_up_amount > 0 {
+                (&counterparty, &trade.initiator)
+            } else {
+                (&trade.initiator, &counterparty)
+            };
+            asset_utils::transfer_tokens_checked(&currency, payer, payee, trade.top_up_amount.abs(), env)?;
+        }
+
+        Ok(())
+    }
+
+    /// The deadline a trade must be accepted and executed by
+    ///
+    /// Each counter-offer replaces the original proposal's deadline with its own.
+    pub fn trade_deadline(trade: &TradeTransaction) -> u64 {
+        if trade.counter_rounds > 0 {
+            trade.counter_expires_at
+        } else {
+            trade.expires_at
+        }
+    }
+
+    /// Check `acceptor` may accept a trade's standing terms
+    ///
+    /// A counter-offer can only be accepted by the party it was made to. Otherwise the
+    /// named counterparty, or anyone but the initiator for an open trade, may accept.
+    pub fn check_acceptor(trade: &TradeTransaction, acceptor: &Address) -> Result<(), SettlementError> {
+        let allowed = match &trade.last_countered_by {
+            Some(countered_by) => {
+                countered_by != acceptor &&
+                    (*acceptor == trade.initiator || trade.counterparty.as_ref() == Some(acceptor))
+            }
+            None => *acceptor != trade.initiator && trade.counterparty.as_ref().is_none_or(|c| c == acceptor),
+        };
+        if !allowed {
+            return Err(SettlementError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Replace a trade's counterparty side with a counter-offer from either party
+    ///
+    /// The countered terms get a fresh window as long as the original proposal's, and the
+    /// other party has to accept them again. Simultaneous counters resolve in favour of
+    /// whichever lands last. After `MAX_COUNTER_ROUNDS` the trade is deadlocked and can only
+    /// be accepted as it stands or left to expire.
+    pub fn counter_offer(
+        env: &Env,
+        trade: &mut TradeTransaction,
+        party: &Address,
+        modified_nfts: Vec<NFTItem>,
+        modified_top_up: i128
+    ) -> Result<(), SettlementError> {
+        if trade.state != TransactionState::Pending && trade.state != TransactionState::Funded {
+            return Err(SettlementError::InvalidState);
+        }
+        if crate::utils::time_utils::is_expired(Self::trade_deadline(trade), env) {
+            return Err(SettlementError::Expired);
+        }
+
+        // The initiator can only counter someone; an open trade is countered by taking it up
+        let is_initiator = *party == trade.initiator;
+        if is_initiator && trade.counterparty.is_none() {
+            return Err(SettlementError::InvalidState);
+        }
+        if !is_initiator && trade.counterparty.as_ref().is_some_and(|c| c != party) {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        if trade.counter_rounds >= MAX_COUNTER_ROUNDS {
+            return Err(SettlementError::InvalidState);
+        }
+        if modified_top_up != 0 && trade.top_up_currency.is_none() {
+            return Err(SettlementError::InvalidCurrency);
+        }
+
+        let now = env.ledger().timestamp();
+        trade.counterparty_nfts = modified_nfts;
+        trade.top_up_amount = modified_top_up;
+        if !is_initiator {
+            trade.counterparty = Some(party.clone());
+        }
+        trade.counter_expires_at = now + (trade.expires_at - trade.created_at);
+        trade.counter_rounds += 1;
+        trade.last_countered_by = Some(party.clone());
+        trade.state = TransactionState::Pending;
+
+        emit_trade_counter_offer(env, TradeCounterOfferEvent {
+            trade_id: trade.trade_id,
+            countered_by: party.clone(),
+            round: trade.counter_rounds,
+            top_up_amount: modified_top_up,
+            counter_expires_at: trade.counter_expires_at,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Propose a trade between three or more parties, signed by the proposer
+    ///
+    /// Every offered NFT must be held by one of the parties and go to a different one.
+    /// Top-ups are per party and must net to zero across the trade.
+    pub fn propose_multi_trade(
+        env: &Env,
+        proposer: &Address,
+        parties: Vec<Address>,
+        offered_nfts: Vec<NFTItem>,
+        recipient_index: Vec<u32>,
+        top_up_amounts: Vec<i128>,
+        top_up_currency: Option<Address>,
+        duration_seconds: u64
+    ) -> Result<u64, SettlementError> {
+        if parties.len() < MIN_MULTI_TRADE_PARTIES {
+            return Err(SettlementError::InvalidAmount);
+        }
+        for (i, party) in parties.iter().enumerate() {
+            if parties.iter().skip(i + 1).any(|other| other == party) {
+                return Err(SettlementError::AlreadyExists);
+            }
+        }
+        if !parties.contains(proposer) {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        if offered_nfts.is_empty() || offered_nfts.len() != recipient_index.len() {
+            return Err(SettlementError::InvalidAmount);
+        }
+        let mut offerer_index = Vec::new(env);
+        for (item, recipient) in offered_nfts.iter().zip(recipient_index.iter()) {
+            if recipient >= parties.len() {
+                return Err(SettlementError::InvalidAmount);
+            }
+            let mut offerer = None;
+            for (i, party) in parties.iter().enumerate() {
+                if asset_utils::check_nft_ownership(&item.nft_address, item.token_id, &party, env)? {
+                    offerer = Some(i as u32);
+                    break;
+                }
+            }
+            let offerer = offerer.ok_or(SettlementError::Unauthorized)?;
+            if offerer == recipient {
+                return Err(SettlementError::InvalidState);
+            }
+            offerer_index.push_back(offerer);
+        }
+
+        if top_up_amounts.len() != parties.len() {
+            return Err(SettlementError::InvalidAmount);
+        }
+        let mut net = 0i128;
+        for amount in top_up_amounts.iter() {
+            net = crate::utils::math_utils::safe_add(net, amount, env)?;
+        }
+        if net != 0 {
+            return Err(SettlementError::InvalidAmount);
+        }
+        if top_up_currency.is_none() && top_up_amounts.iter().any(|amount| amount != 0) {
+            return Err(SettlementError::InvalidCurrency);
+        }
+
+        let trade_id: u64 = env.storage().instance().get(&NEXT_MULTI_TRADE).unwrap_or(1);
+        env.storage().instance().set(&NEXT_MULTI_TRADE, &(trade_id + 1));
+
+        let now = env.ledger().timestamp();
+        let trade = MultiPartyTrade {
+            trade_id,
+            parties,
+            offered_nfts,
+            offerer_index,
+            recipient_index,
+            top_up_amounts,
+            top_up_currency,
+            signatures: Vec::from_array(env, [proposer.clone()]),
+            state: TransactionState::Pending,
+            created_at: now,
+            expires_at: now + duration_seconds,
+        };
+        Self::escrow_top_up(env, &trade, proposer)?;
+        env.storage().persistent().set(&DataKey::MultiTrade(trade_id), &trade);
+        Ok(trade_id)
+    }
+
+    /// Get a multi-party trade
+    pub fn get_multi_trade(env: &Env, trade_id: u64) -> Result<MultiPartyTrade, SettlementError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MultiTrade(trade_id))
+            .ok_or(SettlementError::TransactionNotFound)
+    }
+
+    /// Sign a multi-party trade, executing it once the last party signs
+    ///
+    /// A party owing a top-up pays it into escrow as they sign. Returns whether this
+    /// signature executed the trade.
+    pub fn sign_multi_trade(env: &Env, trade_id: u64, party: &Address) -> Result<bool, SettlementError> {
+        let mut trade = Self::get_multi_trade(env, trade_id)?;
+
+        if trade.state != TransactionState::Pending {
+            return Err(SettlementError::InvalidState);
+        }
+        if crate::utils::time_utils::is_expired(trade.expires_at, env) {
+            return Err(SettlementError::Expired);
+        }
+        if !trade.parties.contains(party) {
+            return Err(SettlementError::Unauthorized);
+        }
+        if trade.signatures.contains(party) {
+            return Err(SettlementError::AlreadyExists);
+        }
+
+        Self::escrow_top_up(env, &trade, party)?;
+        trade.signatures.push_back(party.clone());
+        env.storage().persistent().set(&DataKey::MultiTrade(trade_id), &trade);
+
+        if trade.signatures.len() < trade.parties.len() {
+            return Ok(false);
+        }
+        Self::execute_multi_trade(env, trade_id)?;
+        Ok(true)
+    }
+
+    /// Execute a fully signed multi-party trade
+    ///
+    /// Every NFT moves and every top-up is paid out of escrow in one invocation, so a
+    /// single failed transfer reverts the entire trade.
+    pub fn execute_multi_trade(env: &Env, trade_id: u64) -> Result<(), SettlementError> {
+        let mut trade = Self::get_multi_trade(env, trade_id)?;
+
+        if trade.state != TransactionState::Pending || trade.signatures.len() < trade.parties.len() {
+            return Err(SettlementError::InvalidState);
+        }
+
+        let contract = env.current_contract_address();
+        for i in 0..trade.offered_nfts.len() {
+            let item = trade.offered_nfts.get(i).ok_or(SettlementError::NotFound)?;
+            let from = trade.parties.get(trade.offerer_index.get(i).unwrap_or(0)).ok_or(SettlementError::NotFound)?;
+            let to = trade.parties.get(trade.recipient_index.get(i).unwrap_or(0)).ok_or(SettlementError::NotFound)?;
+            asset_utils::transfer_nft(&item.nft_address, &from, &to, item.token_id, env)?;
+        }
+
+        if let Some(currency) = trade.top_up_currency.clone() {
+            for (party, amount) in trade.parties.iter().zip(trade.top_up_amounts.iter()) {
+                if amount < 0 {
+                    asset_utils::transfer_tokens(&currency, &contract, &party, -amount, env)?;
+                }
+            }
+        }
+
+        trade.state = TransactionState::Executed;
+        env.storage().persistent().set(&DataKey::MultiTrade(trade_id), &trade);
+
+        emit_multi_trade_executed(env, MultiTradeExecutedEvent {
+            trade_id,
+            parties: trade.parties,
+            timestamp: env.ledger().timestamp(),
+        });
+        Ok(())
+    }
+
+    /// Cancel a pending multi-party trade, refunding the top-ups already escrowed
+    pub fn cancel_multi_trade(env: &Env, trade_id: u64, party: &Address) -> Result<(), SettlementError> {
+        let mut trade = Self::get_multi_trade(env, trade_id)?;
+
+        if trade.state != TransactionState::Pending {
+            return Err(SettlementError::InvalidState);
+        }
+        if !trade.parties.contains(party) {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        if let Some(currency) = trade.top_up_currency.clone() {
+            let contract = env.current_contract_address();
+            for (signer, amount) in trade.parties.iter().zip(trade.top_up_amounts.iter()) {
+                if amount > 0 && trade.signatures.contains(&signer) {
+                    asset_utils::transfer_tokens(&currency, &contract, &signer, amount, env)?;
+                }
+            }
+        }
+
+        trade.state = TransactionState::Cancelled;
+        env.storage().persistent().set(&DataKey::MultiTrade(trade_id), &trade);
+        Ok(())
+    }
+
+    /// Internal: take a party's top-up into escrow if they owe one
+    fn escrow_top_up(env: &Env, trade: &MultiPartyTrade, party: &Address) -> Result<(), SettlementError> {
+        let index = trade.parties.first_index_of(party).ok_or(SettlementError::Unauthorized)?;
+        let amount = trade.top_up_amounts.get(index).unwrap_or(0);
+        match &trade.top_up_currency {
+            Some(currency) if amount > 0 => {
+                asset_utils::transfer_tokens_checked(currency, party, &env.current_contract_address(), amount, env)
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Cancel a swap and refund all parties
     pub fn cancel_swap(
         env: &Env,
@@ -193,18 +517,15 @@ impl AtomicSwapEngine {
     }
 
     /// Emergency withdrawal for stuck transactions
+    ///
+    /// The entrypoint has already authenticated `admin` against the admin config.
     pub fn emergency_withdraw(
         env: &Env,
         transaction_id: u64,
         admin: &Address,
         reason: &Bytes
     ) -> Result<(), SettlementError> {
-        // This would check admin permissions
         let swap = Self::get_swap_by_transaction(env, transaction_id)?;
-
-        // Log emergency withdrawal
-        // In production, this would have proper admin checks
-
         Self::refund_escrow_holdings(env, &swap)?;
 
         // Emit emergency withdrawal event
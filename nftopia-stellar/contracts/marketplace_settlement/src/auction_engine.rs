@@ -1,14 +1,29 @@
-use soroban_sdk::{Env, Address, Vec, Map, Symbol, symbol_short, Bytes, contracttype};
+use soroban_sdk::{Env, Address, Vec, Map, Symbol, symbol_short, Bytes, BytesN, contracttype};
 use crate::error::SettlementError;
-use crate::types::{AuctionTransaction, AuctionType, Bid, DutchAuctionData, TransactionState, Asset, RoyaltyDistribution};
-use crate::storage::auction_store::{AuctionStore, DutchAuctionStore};
-use crate::utils::{math_utils, time_utils};
+use crate::types::{
+    AuctionOptions, AuctionTransaction, AuctionType, Bid, BundleAuctionData, CandleAuctionData, DecayType,
+    DutchAuctionData, NFTItem, ProxyBid, SealedAuctionData, VickreyAuctionData,
+    TransactionState, Asset, RoyaltyDistribution, ExecutionResult
+};
+use crate::storage::stats_store::PlatformStatsStore;
+use crate::storage::auction_store::{
+    AuctionStore, BidEscrowStore, BundleAuctionStore, CandleAuctionStore, DutchAuctionStore, ProxyBidStore,
+    SealedAuctionStore, VickreyAuctionStore
+};
+use crate::utils::{asset_utils, math_utils, time_utils};
 use crate::security::frontrun_protection::{CommitRevealScheme, FrontRunningDetector};
+use crate::security::circuit_breaker::CircuitBreaker;
+use crate::fee_manager::FeeManager;
+use crate::royalty_distributor::RoyaltyDistributor;
 use crate::events::{
     emit_auction_created, emit_bid_placed, emit_bid_revealed,
-    emit_auction_ended, emit_auction_extended,
+    emit_auction_ended, emit_auction_extended, emit_vickrey_auction_settled, emit_proxy_bid_activated,
+    emit_auction_paused, emit_auction_resumed, emit_reveal_expired, emit_emergency_withdrawal,
+    emit_bundle_auction_ended, emit_auction_opened,
     AuctionCreatedEvent, BidPlacedEvent, BidRevealedEvent,
-    AuctionEndedEvent, AuctionExtendedEvent
+    AuctionEndedEvent, AuctionExtendedEvent, VickreyAuctionSettledEvent, ProxyBidActivatedEvent,
+    AuctionPausedEvent, AuctionResumedEvent, RevealExpiredEvent, EmergencyWithdrawalEvent,
+    BundleAuctionEndedEvent, AuctionOpenedEvent
 };
 
 // Storage keys
@@ -24,11 +39,19 @@ pub struct AuctionConfig {
     pub dutch_price_decrement: u64,   // Price decrement per time unit for Dutch auctions
     pub commit_reveal_enabled: u64,    // Whether commit-reveal is enabled (0 = false, 1 = true)
     pub reveal_period: u64,            // Time allowed for bid reveals
+    pub max_extensions: u32,           // Maximum anti-sniping extensions per auction
+    pub candle_window: u64,            // Window before end_time in which a candle auction may close
 }
 
 /// Auction engine for managing different auction types
 pub struct AuctionEngine;
 
+/// Royalty splits and platform fee owed out of a winning bid, worked out before anything moves
+struct WinningBidPayout {
+    platform_fee: i128,
+    items: Vec<(RoyaltyDistribution, i128)>,
+}
+
 impl AuctionEngine {
     /// Create a new auction
     pub fn create_auction(
@@ -41,7 +64,8 @@ impl AuctionEngine {
         reserve_price: i128,
         duration_seconds: u64,
         bid_increment: i128,
-        currency: &Asset
+        currency: &Asset,
+        options: &AuctionOptions
     ) -> Result<u64, SettlementError> {
         let config = Self::get_auction_config(env)?;
 
@@ -54,13 +78,36 @@ impl AuctionEngine {
             &config
         )?;
 
+        // Exponential decay only applies to Dutch auctions and needs a half-life
+        if let Some(half_life) = options.dutch_half_life {
+            if auction_type != AuctionType::Dutch {
+                return Err(SettlementError::InvalidState);
+            }
+            if half_life == 0 {
+                return Err(SettlementError::InvalidAmount);
+            }
+        }
+
+        // Buyout is only offered on English auctions and can't undercut the reserve
+        if let Some(buyout) = options.buyout_price {
+            if auction_type != AuctionType::English {
+                return Err(SettlementError::InvalidState);
+            }
+            if buyout <= 0 || buyout < reserve_price {
+                return Err(SettlementError::InvalidAmount);
+            }
+        }
+
         let auction_id = AuctionStore::next_id(env);
-        let start_time = env.ledger().timestamp();
-        let end_time = start_time + duration_seconds;
+        let start_time = options.start_time.unwrap_or(env.ledger().timestamp());
+        let end_time = time_utils::calculate_expiration(start_time, duration_seconds)?;
 
         // Validate timing
         time_utils::validate_auction_timing(start_time, end_time, config.extension_window, env)?;
 
+        // An auction starting later waits in the scheduled index until it's opened
+        let scheduled = start_time > env.ledger().timestamp();
+
         let auction = AuctionTransaction {
             auction_id,
             seller: seller.clone(),
@@ -73,7 +120,7 @@ impl AuctionEngine {
             bid_increment,
             start_time,
             end_time,
-            state: TransactionState::Pending,
+            state: if scheduled { TransactionState::Scheduled } else { TransactionState::Pending },
             bids: Vec::new(env),
             extension_window: config.extension_window,
             currency: currency.clone(),
@@ -86,9 +133,20 @@ impl AuctionEngine {
                 amounts: Map::new(env),
             }, // Would be set from NFT contract
             platform_fee: 0, // Would be calculated
+            buyout_price: options.buyout_price,
+            // Candle auctions never extend; their end is drawn up front
+            max_extensions: if auction_type == AuctionType::Candle { 0 } else { config.max_extensions },
+            extension_count: 0,
+            last_extended_at: 0,
+            allowed_bidders: options.allowed_bidders.clone(),
+            paused_at: 0,
         };
 
         AuctionStore::put(env, &auction)?;
+        PlatformStatsStore::record_created(env);
+        if scheduled {
+            AuctionStore::schedule(env, start_time, auction_id);
+        }
 
         // If Dutch auction, create Dutch auction data
         if auction_type == AuctionType::Dutch {
@@ -99,10 +157,43 @@ impl AuctionEngine {
                 time_unit: 3600, // 1 hour
                 current_price: starting_price,
                 last_price_update: start_time,
+                allowed_bidders: options.allowed_bidders.clone(),
+                decay_type: if options.dutch_half_life.is_some() { DecayType::Exponential } else { DecayType::Linear },
+                half_life_seconds: options.dutch_half_life.unwrap_or(0),
             };
             DutchAuctionStore::put(env, auction_id, &dutch_data)?;
         }
 
+        // If Vickrey auction, sealed bids are revealed after bidding closes
+        if auction_type == AuctionType::Vickrey {
+            let vickrey_data = VickreyAuctionData {
+                reveal_end: end_time + config.reveal_period,
+                committed_bidders: Vec::new(env),
+                revealed_bids: Map::new(env),
+                highest_bidder: None,
+                highest_bid: 0,
+                second_highest_bid: 0,
+            };
+            VickreyAuctionStore::put(env, auction_id, &vickrey_data)?;
+        }
+
+        // If sealed first-price auction, bids are revealed after bidding closes
+        if auction_type == AuctionType::SealedFirstPrice {
+            let sealed_data = SealedAuctionData {
+                reveal_end: end_time + config.reveal_period,
+                committed_bidders: Vec::new(env),
+                revealed_bids: Map::new(env),
+                highest_bidder: None,
+                highest_bid: 0,
+            };
+            SealedAuctionStore::put(env, auction_id, &sealed_data)?;
+        }
+
+        // If candle auction, secretly draw the closing time
+        if auction_type == AuctionType::Candle {
+            Self::draw_candle_end(env, auction_id, start_time, end_time, config.candle_window)?;
+        }
+
         // Emit auction created event
         let event = AuctionCreatedEvent {
             auction_id,
@@ -132,8 +223,18 @@ impl AuctionEngine {
         let mut auction = AuctionStore::get(env, auction_id)?;
 
         // Validate auction is active
-        if !Self::is_auction_active(&auction, env)? {
-            return Err(SettlementError::AuctionAlreadyEnded);
+        Self::ensure_accepting_bids(&auction, env)?;
+
+        // Private auctions only accept listed bidders
+        if let Some(ref allowed) = auction.allowed_bidders {
+            if !allowed.contains(bidder.clone()) {
+                return Err(SettlementError::Unauthorized);
+            }
+        }
+
+        // Vickrey and sealed first-price auctions only accept sealed bids
+        if Self::is_sealed_bid_auction(env, auction_id) {
+            return Self::place_sealed_bid(env, auction_id, bidder, commitment_hash);
         }
 
         // Validate bid amount
@@ -187,13 +288,18 @@ impl AuctionEngine {
         // Store bid
         AuctionStore::add_bid(env, auction_id, &bid)?;
 
-        // Update auction if direct bid
+        // Direct bids are escrowed and take the lead immediately
         if !bid.is_committed {
+            Self::escrow_bid(env, &auction, bidder, bid_amount)?;
+            Self::process_direct_bid(env, &mut auction, bidder, bid_amount, timestamp)?;
             AuctionStore::update(env, &auction)?;
         }
 
-        // Check if auction should be extended
-        if time_utils::should_extend_auction(auction.end_time, timestamp, auction.extension_window, env) {
+        // Check if auction should be extended, at most once per ledger and up to the cap
+        if auction.extension_count < auction.max_extensions
+            && auction.last_extended_at != timestamp
+            && time_utils::should_extend_auction(auction.end_time, timestamp, auction.extension_window, env)
+        {
             let new_end_time = time_utils::calculate_extended_end_time(
                 auction.end_time,
                 auction.extension_window,
@@ -201,13 +307,15 @@ impl AuctionEngine {
             );
 
             auction.end_time = new_end_time;
+            auction.extension_count += 1;
+            auction.last_extended_at = timestamp;
             AuctionStore::update(env, &auction)?;
 
             // Emit extension event
             let event = AuctionExtendedEvent {
                 auction_id,
                 new_end_time,
-                extension_reason: Bytes::from_slice(&env, "last_minute_bid".as_bytes()),
+                extension_reason: Bytes::from_slice(env, "last_minute_bid".as_bytes()),
                 timestamp,
             };
             emit_auction_extended(env, event);
@@ -223,9 +331,69 @@ impl AuctionEngine {
         };
         emit_bid_placed(env, event);
 
+        // Give standing proxy bids a chance to respond
+        if !bid.is_committed {
+            Self::run_proxy_bidding(env, auction_id)?;
+        }
+
         Ok(())
     }
 
+    /// Set or update a proxy bid that bids automatically up to `max_amount`
+    pub fn set_proxy_bid(
+        env: &Env,
+        auction_id: u64,
+        bidder: &Address,
+        max_amount: i128
+    ) -> Result<(), SettlementError> {
+        let auction = AuctionStore::get(env, auction_id)?;
+
+        Self::ensure_accepting_bids(&auction, env)?;
+        if let Some(ref allowed) = auction.allowed_bidders {
+            if !allowed.contains(bidder.clone()) {
+                return Err(SettlementError::Unauthorized);
+            }
+        }
+
+        // Sealed-bid auctions have no visible price to bid against
+        if Self::is_sealed_bid_auction(env, auction_id) {
+            return Err(SettlementError::InvalidState);
+        }
+
+        // The limit must allow at least the next valid bid, or improve on the leader's own bid
+        let is_leader = auction.highest_bidder.as_ref() == Some(bidder);
+        let floor = if is_leader {
+            auction.highest_bid + 1
+        } else {
+            Self::min_next_bid(&auction, auction.highest_bid, env)?
+        };
+        if max_amount < floor {
+            return Err(SettlementError::BidTooLow);
+        }
+
+        // Proxies are funded up front so they can bid without the bidder signing each time
+        let escrowed = BidEscrowStore::get(env, auction_id, bidder);
+        if max_amount > escrowed {
+            Self::escrow_bid(env, &auction, bidder, max_amount - escrowed)?;
+        }
+
+        let current_bid = ProxyBidStore::get(env, auction_id, bidder)
+            .map(|proxy| proxy.current_bid)
+            .unwrap_or(0);
+        ProxyBidStore::put(env, auction_id, &ProxyBid {
+            bidder: bidder.clone(),
+            max_amount,
+            current_bid,
+        });
+
+        Self::run_proxy_bidding(env, auction_id)
+    }
+
+    /// Get a bidder's proxy bid
+    pub fn get_proxy_bid(env: &Env, auction_id: u64, bidder: &Address) -> Option<ProxyBid> {
+        ProxyBidStore::get(env, auction_id, bidder)
+    }
+
     /// Reveal a committed bid
     pub fn reveal_bid(
         env: &Env,
@@ -252,6 +420,7 @@ impl AuctionEngine {
 
         // Process the revealed bid
         let timestamp = env.ledger().timestamp();
+        Self::escrow_bid(env, &auction, bidder, bid_amount)?;
         Self::process_direct_bid(env, &mut auction, bidder, bid_amount, timestamp)?;
 
         // Update the committed bid to revealed
@@ -277,177 +446,1241 @@ impl AuctionEngine {
         Ok(())
     }
 
-    /// End an auction
-    pub fn end_auction(env: &Env, auction_id: u64, _caller: &Address) -> Result<(), SettlementError> {
+    /// Buy an English auction outright at its buyout price, returning the price paid
+    ///
+    /// Returns `None` if the buyout tripped the circuit breaker, leaving the auction open.
+    pub fn buy_now(env: &Env, auction_id: u64, buyer: &Address) -> Result<Option<i128>, SettlementError> {
         let mut auction = AuctionStore::get(env, auction_id)?;
 
-        // Check if auction can be ended
-        if !Self::can_end_auction(&auction, env)? {
+        Self::ensure_accepting_bids(&auction, env)?;
+
+        let buyout_price = auction.buyout_price.ok_or(SettlementError::InvalidState)?;
+
+        // Bidding has already reached the buyout price
+        if auction.highest_bid >= buyout_price {
             return Err(SettlementError::InvalidState);
         }
 
-        let timestamp = env.ledger().timestamp();
-        let mut reason = "ended";
-
-        // Determine winner and final price
-        let (winner, final_price) = if auction.highest_bid >= auction.reserve_price {
-            (auction.highest_bidder.clone(), auction.highest_bid)
-        } else {
-            reason = "reserve_not_met";
-            (None, 0)
-        };
+        // An oversized buyout halts settlement and leaves the auction open
+        if CircuitBreaker::check(env, auction_id, buyout_price)? {
+            return Ok(None);
+        }
 
-        // Update auction state
+        let timestamp = env.ledger().timestamp();
+        auction.highest_bid = buyout_price;
+        auction.highest_bidder = Some(buyer.clone());
+        auction.end_time = timestamp;
         auction.state = TransactionState::Executed;
         AuctionStore::update(env, &auction)?;
+        PlatformStatsStore::record_completed(env, &auction.seller, buyer, buyout_price)?;
 
-        // Emit auction ended event
         let event = AuctionEndedEvent {
             auction_id,
-            winner,
-            final_price,
-            reason: Bytes::from_slice(env, reason.as_bytes()),
+            winner: Some(buyer.clone()),
+            final_price: buyout_price,
+            reason: Bytes::from_slice(env, "buy_now".as_bytes()),
             timestamp,
         };
         emit_auction_ended(env, event);
 
-        Ok(())
+        Ok(Some(buyout_price))
     }
 
-    /// Get current price for Dutch auction
-    pub fn get_dutch_auction_price(env: &Env, auction_id: u64) -> Result<i128, SettlementError> {
+    /// Reveal a sealed bid on a Vickrey auction
+    pub fn reveal_vickrey_bid(
+        env: &Env,
+        auction_id: u64,
+        bidder: &Address,
+        bid_amount: i128,
+        salt: &Bytes
+    ) -> Result<(), SettlementError> {
         let auction = AuctionStore::get(env, auction_id)?;
-        let dutch_data = DutchAuctionStore::get(env, auction_id)?;
+        let mut vickrey_data = VickreyAuctionStore::get(env, auction_id)?;
+        let timestamp = env.ledger().timestamp();
 
-        let current_time = env.ledger().timestamp();
+        // Reveals open once bidding has closed
+        if timestamp <= auction.end_time {
+            return Err(SettlementError::InvalidState);
+        }
+        if timestamp > vickrey_data.reveal_end || auction.state != TransactionState::Pending {
+            return Err(SettlementError::Expired);
+        }
 
-        // Update price if needed
-        let updated_price = math_utils::calculate_time_weighted_price(
-            dutch_data.last_price_update,
-            auction.end_time,
-            current_time,
-            dutch_data.current_price,
-            dutch_data.ending_price,
-            env
-        )?;
+        if !vickrey_data.committed_bidders.contains(bidder.clone()) {
+            return Err(SettlementError::NotFound);
+        }
+        if vickrey_data.revealed_bids.contains_key(bidder.clone()) {
+            return Err(SettlementError::AlreadyExists);
+        }
 
-        // Update stored price
-        let mut updated_data = dutch_data;
-        updated_data.current_price = updated_price;
-        updated_data.last_price_update = current_time;
-        DutchAuctionStore::update(env, auction_id, &updated_data)?;
+        CommitRevealScheme::reveal_commitment(env, bidder, auction_id, bid_amount, salt)?;
 
-        Ok(updated_price)
+        if bid_amount < auction.starting_price {
+            return Err(SettlementError::BidTooLow);
+        }
+
+        vickrey_data.revealed_bids.set(bidder.clone(), bid_amount);
+        if bid_amount > vickrey_data.highest_bid {
+            vickrey_data.second_highest_bid = vickrey_data.highest_bid;
+            vickrey_data.highest_bid = bid_amount;
+            vickrey_data.highest_bidder = Some(bidder.clone());
+        } else if bid_amount > vickrey_data.second_highest_bid {
+            vickrey_data.second_highest_bid = bid_amount;
+        }
+        VickreyAuctionStore::update(env, auction_id, &vickrey_data)?;
+
+        AuctionStore::update_bid(env, auction_id, bidder, &Bid {
+            bidder: bidder.clone(),
+            amount: bid_amount,
+            placed_at: timestamp,
+            is_committed: false,
+            commitment_hash: None,
+        })?;
+
+        let event = BidRevealedEvent {
+            auction_id,
+            bidder: bidder.clone(),
+            amount: bid_amount,
+            timestamp,
+        };
+        emit_bid_revealed(env, event);
+
+        Ok(())
     }
 
-    /// Cancel an auction
-    pub fn cancel_auction(
-        env: &Env,
-        auction_id: u64,
-        canceller: &Address
-    ) -> Result<(), SettlementError> {
+    /// Settle a Vickrey auction once the reveal period is over
+    pub fn end_vickrey_auction(env: &Env, auction_id: u64, _caller: &Address) -> Result<(), SettlementError> {
         let mut auction = AuctionStore::get(env, auction_id)?;
+        let vickrey_data = VickreyAuctionStore::get(env, auction_id)?;
+        let timestamp = env.ledger().timestamp();
 
-        // Only seller can cancel
-        if &auction.seller != canceller {
-            return Err(SettlementError::Unauthorized);
-        }
-
-        // Can only cancel if no bids placed
-        if auction.highest_bid > 0 {
+        if auction.state != TransactionState::Pending || timestamp <= vickrey_data.reveal_end {
             return Err(SettlementError::InvalidState);
         }
 
-        auction.state = TransactionState::Cancelled;
+        // Winner pays the second-highest revealed bid, never less than the starting price
+        let (winner, winning_bid, payment_amount, reason) =
+            if vickrey_data.highest_bidder.is_some() && vickrey_data.highest_bid >= auction.reserve_price {
+                (
+                    vickrey_data.highest_bidder.clone(),
+                    vickrey_data.highest_bid,
+                    vickrey_data.second_highest_bid.max(auction.starting_price),
+                    "ended"
+                )
+            } else {
+                (None, 0, 0, "reserve_not_met")
+            };
+
+        auction.highest_bidder = winner.clone();
+        auction.highest_bid = payment_amount;
+        auction.state = TransactionState::Executed;
         AuctionStore::update(env, &auction)?;
+        if let Some(ref winner_address) = winner {
+            PlatformStatsStore::record_completed(env, &auction.seller, winner_address, payment_amount)?;
+        }
 
-        Ok(())
-    }
+        let event = VickreyAuctionSettledEvent {
+            auction_id,
+            winner: winner.clone(),
+            winning_bid,
+            payment_amount,
+            timestamp,
+        };
+        emit_vickrey_auction_settled(env, event);
 
-    /// Get auction configuration
-    pub fn get_auction_config(env: &Env) -> Result<AuctionConfig, SettlementError> {
-        env.storage()
-            .instance()
-            .get(&AUCTION_CONFIG)
-            .ok_or(SettlementError::NotFound)
-    }
+        let event = AuctionEndedEvent {
+            auction_id,
+            winner,
+            final_price: payment_amount,
+            reason: Bytes::from_slice(env, reason.as_bytes()),
+            timestamp,
+        };
+        emit_auction_ended(env, event);
 
-    /// Update auction configuration
-    pub fn update_auction_config(
-        env: &Env,
-        config: &AuctionConfig,
-        _admin: &Address
-    ) -> Result<(), SettlementError> {
-        // Check admin permissions
-        env.storage().instance().set(&AUCTION_CONFIG, config);
         Ok(())
     }
 
-    /// Internal: Validate auction parameters
-    fn validate_auction_params(
-        starting_price: i128,
-        reserve_price: i128,
-        duration: u64,
-        bid_increment: i128,
-        config: &AuctionConfig
+    /// Reveal a bid on a sealed first-price auction, escrowing the revealed amount
+    pub fn reveal_sealed_bid(
+        env: &Env,
+        auction_id: u64,
+        bidder: &Address,
+        bid_amount: i128,
+        salt: &Bytes
     ) -> Result<(), SettlementError> {
-        if starting_price <= 0 {
-            return Err(SettlementError::InvalidAmount);
+        let auction = AuctionStore::get(env, auction_id)?;
+        let mut sealed_data = SealedAuctionStore::get(env, auction_id)?;
+        let timestamp = env.ledger().timestamp();
+
+        // Reveals open once bidding has closed
+        if timestamp <= auction.end_time {
+            return Err(SettlementError::InvalidState);
+        }
+        if timestamp > sealed_data.reveal_end || auction.state != TransactionState::Pending {
+            return Err(SettlementError::Expired);
         }
 
-        if reserve_price < 0 || reserve_price > starting_price {
-            return Err(SettlementError::InvalidAmount);
+        if !sealed_data.committed_bidders.contains(bidder.clone()) {
+            return Err(SettlementError::NotFound);
+        }
+        if sealed_data.revealed_bids.contains_key(bidder.clone()) {
+            return Err(SettlementError::AlreadyExists);
         }
 
-        if duration == 0 || duration > config.max_auction_duration {
-            return Err(SettlementError::InvalidAmount);
+        CommitRevealScheme::reveal_commitment(env, bidder, auction_id, bid_amount, salt)?;
+
+        if bid_amount < auction.starting_price {
+            return Err(SettlementError::BidTooLow);
         }
 
-        if bid_increment <= 0 {
-            return Err(SettlementError::InvalidBidIncrement);
+        Self::escrow_bid(env, &auction, bidder, bid_amount)?;
+
+        sealed_data.revealed_bids.set(bidder.clone(), bid_amount);
+        if bid_amount > sealed_data.highest_bid {
+            sealed_data.highest_bid = bid_amount;
+            sealed_data.highest_bidder = Some(bidder.clone());
         }
+        SealedAuctionStore::update(env, auction_id, &sealed_data)?;
+
+        AuctionStore::update_bid(env, auction_id, bidder, &Bid {
+            bidder: bidder.clone(),
+            amount: bid_amount,
+            placed_at: timestamp,
+            is_committed: false,
+            commitment_hash: None,
+        })?;
+
+        let event = BidRevealedEvent {
+            auction_id,
+            bidder: bidder.clone(),
+            amount: bid_amount,
+            timestamp,
+        };
+        emit_bid_revealed(env, event);
 
         Ok(())
     }
 
-    /// Internal: Check if auction is active
-    fn is_auction_active(auction: &AuctionTransaction, env: &Env) -> Result<bool, SettlementError> {
-        let current_time = env.ledger().timestamp();
-        Ok(current_time >= auction.start_time &&
-           current_time <= auction.end_time &&
-           auction.state == TransactionState::Pending)
-    }
+    /// Settle a sealed first-price auction after the reveal period.
+    ///
+    /// The highest revealed bid wins and pays in full; unrevealed commitments are forfeited.
+    pub fn end_sealed_auction(env: &Env, auction_id: u64, _caller: &Address) -> Result<(), SettlementError> {
+        let mut auction = AuctionStore::get(env, auction_id)?;
+        let sealed_data = SealedAuctionStore::get(env, auction_id)?;
+        let timestamp = env.ledger().timestamp();
 
-    /// Internal: Validate bid amount
-    fn validate_bid_amount(
-        auction: &AuctionTransaction,
-        bid_amount: i128,
-        env: &Env
-    ) -> Result<(), SettlementError> {
-        // Must be higher than current highest bid
-        if bid_amount <= auction.highest_bid {
-            return Err(SettlementError::BidTooLow);
+        if auction.state != TransactionState::Pending || timestamp <= sealed_data.reveal_end {
+            return Err(SettlementError::InvalidState);
         }
 
-        // Must meet minimum increment
-        if auction.highest_bid > 0 {
-            let min_increment = math_utils::calculate_bid_increment(
-                auction.highest_bid,
-                (auction.bid_increment.max(100) as u64).max(100), // At least 1%
-                env
-            )?;
-            if bid_amount < auction.highest_bid + min_increment {
-                return Err(SettlementError::BidTooLow);
-            }
-        } else {
-            // First bid must meet or exceed starting price
-            if bid_amount < auction.starting_price {
-                return Err(SettlementError::BidTooLow);
+        for bidder in sealed_data.committed_bidders.iter() {
+            if !sealed_data.revealed_bids.contains_key(bidder.clone()) {
+                let event = RevealExpiredEvent {
+                    auction_id,
+                    bidder,
+                    timestamp,
+                };
+                emit_reveal_expired(env, event);
             }
         }
 
-        Ok(())
-    }
+        let (winner, final_price, reason) =
+            if sealed_data.highest_bidder.is_some() && sealed_data.highest_bid >= auction.reserve_price {
+                (sealed_data.highest_bidder.clone(), sealed_data.highest_bid, "ended")
+            } else {
+                (None, 0, "reserve_not_met")
+            };
+
+        // The winning bid is paid out of the winner's escrow
+        if let Some(ref winner_address) = winner {
+            BidEscrowStore::release(env, auction_id, winner_address, final_price)?;
+        }
+
+        auction.highest_bidder = winner.clone();
+        auction.highest_bid = final_price;
+        auction.state = TransactionState::Executed;
+        AuctionStore::update(env, &auction)?;
+        if let Some(ref winner_address) = winner {
+            PlatformStatsStore::record_completed(env, &auction.seller, winner_address, final_price)?;
+        }
+
+        let event = AuctionEndedEvent {
+            auction_id,
+            winner,
+            final_price,
+            reason: Bytes::from_slice(env, reason.as_bytes()),
+            timestamp,
+        };
+        emit_auction_ended(env, event);
+
+        Ok(())
+    }
+
+    /// Get sealed first-price auction data
+    pub fn get_sealed_auction(env: &Env, auction_id: u64) -> Result<SealedAuctionData, SettlementError> {
+        SealedAuctionStore::get(env, auction_id)
+    }
+
+    /// Settle a candle auction once its window has closed.
+    ///
+    /// The winner is the highest bid placed strictly before the secretly drawn end time.
+    pub fn end_candle_auction(env: &Env, auction_id: u64, _caller: &Address) -> Result<(), SettlementError> {
+        let mut auction = AuctionStore::get(env, auction_id)?;
+        let mut candle_data = CandleAuctionStore::get(env, auction_id)?;
+        let timestamp = env.ledger().timestamp();
+
+        if auction.state != TransactionState::Pending || timestamp <= candle_data.latest_end {
+            return Err(SettlementError::InvalidState);
+        }
+
+        // Reveal the drawn end time and check it against the commitment
+        let (candle_end, salt) = CandleAuctionStore::take_secret(env, auction_id)?;
+        if Self::candle_commitment(env, candle_end, &salt) != candle_data.end_commitment {
+            return Err(SettlementError::CommitmentMismatch);
+        }
+        candle_data.revealed_end = candle_end;
+        CandleAuctionStore::update(env, auction_id, &candle_data)?;
+
+        let mut winner: Option<Address> = None;
+        let mut winning_bid: i128 = 0;
+        for bid in AuctionStore::get_bids(env, auction_id).iter() {
+            if bid.placed_at < candle_end && bid.amount > winning_bid {
+                winning_bid = bid.amount;
+                winner = Some(bid.bidder.clone());
+            }
+        }
+
+        let (winner, final_price, reason) = if winner.is_some() && winning_bid >= auction.reserve_price {
+            (winner, winning_bid, "candle_out")
+        } else {
+            (None, 0, "reserve_not_met")
+        };
+
+        // The winning bid is paid out of the winner's escrow
+        if let Some(ref winner_address) = winner {
+            BidEscrowStore::release(env, auction_id, winner_address, final_price)?;
+        }
+
+        auction.highest_bidder = winner.clone();
+        auction.highest_bid = final_price;
+        auction.state = TransactionState::Executed;
+        AuctionStore::update(env, &auction)?;
+        if let Some(ref winner_address) = winner {
+            PlatformStatsStore::record_completed(env, &auction.seller, winner_address, final_price)?;
+        }
+
+        let event = AuctionEndedEvent {
+            auction_id,
+            winner,
+            final_price,
+            reason: Bytes::from_slice(env, reason.as_bytes()),
+            timestamp,
+        };
+        emit_auction_ended(env, event);
+
+        Ok(())
+    }
+
+    /// Get candle auction data
+    pub fn get_candle_auction(env: &Env, auction_id: u64) -> Result<CandleAuctionData, SettlementError> {
+        CandleAuctionStore::get(env, auction_id)
+    }
+
+    /// Get Vickrey auction data
+    pub fn get_vickrey_auction(env: &Env, auction_id: u64) -> Result<VickreyAuctionData, SettlementError> {
+        VickreyAuctionStore::get(env, auction_id)
+    }
+
+    /// End an auction and settle the winning bid
+    ///
+    /// Pays royalties, the platform fee and the seller out of the winner's escrow and
+    /// delivers the NFT. If the split can't be paid or the NFT can't be delivered, the auction
+    /// is marked `Disputed` with nothing moved and the escrow left in place.
+    pub fn end_auction(
+        env: &Env,
+        auction_id: u64,
+        caller: &Address,
+        min_acceptable_price: i128
+    ) -> Result<ExecutionResult, SettlementError> {
+        let mut auction = AuctionStore::get(env, auction_id)?;
+
+        // Sealed-bid, candle and bundle auctions have their own settlement paths
+        if Self::is_sealed_bid_auction(env, auction_id)
+            || CandleAuctionStore::has(env, auction_id)
+            || BundleAuctionStore::has(env, auction_id)
+        {
+            return Err(SettlementError::InvalidState);
+        }
+
+        // Check if auction can be ended
+        if !Self::can_end_auction(&auction, env)? {
+            return Err(SettlementError::InvalidState);
+        }
+
+        let timestamp = env.ledger().timestamp();
+        let mut reason = "ended";
+        let mut result = ExecutionResult {
+            transaction_id: auction_id,
+            success: false,
+            transferred_nft: false,
+            transferred_payment: false,
+            distributed_royalties: false,
+            collected_platform_fee: false,
+            timestamp,
+        };
+
+        // Determine winner and final price
+        let (winner, final_price) = if auction.highest_bid >= auction.reserve_price {
+            (auction.highest_bidder.clone(), auction.highest_bid)
+        } else {
+            reason = "reserve_not_met";
+            (None, 0)
+        };
+
+        // Slippage protection is the seller's: only they can ask for a floor, and the
+        // entrypoint has already required the caller's signature
+        if min_acceptable_price > 0 && *caller != auction.seller {
+            return Err(SettlementError::Unauthorized);
+        }
+        if winner.is_some() && final_price < min_acceptable_price {
+            return Err(SettlementError::InvalidAmount);
+        }
+
+        // An oversized winning bid halts settlement and leaves the auction open
+        if CircuitBreaker::check(env, auction_id, final_price)? {
+            return Ok(result);
+        }
+
+        if let Some(ref winner_address) = winner {
+            // Only a split that can't be paid or an NFT that can't be delivered disputes the
+            // auction, and neither leaves anything moved. A later failure reverts the whole call.
+            let lot = Vec::from_array(env, [(auction.nft_address.clone(), auction.token_id)]);
+            let payout = match Self::price_winning_bid(env, &auction, &lot, winner_address, final_price) {
+                Ok(payout) if Self::deliver_lot(env, &auction, &lot, winner_address).is_ok() => Some(payout),
+                _ => None,
+            };
+            let Some(payout) = payout else {
+                auction.state = TransactionState::Disputed;
+                AuctionStore::update(env, &auction)?;
+
+                let event = EmergencyWithdrawalEvent {
+                    transaction_id: auction_id,
+                    reason: Bytes::from_slice(env, b"settlement_failed"),
+                    admin: caller.clone(),
+                    timestamp,
+                };
+                emit_emergency_withdrawal(env, event);
+
+                return Ok(result);
+            };
+            result.transferred_nft = true;
+            Self::pay_winning_bid(env, &auction, winner_address, &payout, &mut result)?;
+
+            // The winning bid has been paid out of the winner's escrow
+            BidEscrowStore::release(env, auction_id, winner_address, final_price)?;
+        }
+
+        // Update auction state
+        auction.state = TransactionState::Executed;
+        AuctionStore::update(env, &auction)?;
+        if let Some(ref winner_address) = winner {
+            PlatformStatsStore::record_completed(env, &auction.seller, winner_address, final_price)?;
+        }
+
+        // Emit auction ended event
+        let event = AuctionEndedEvent {
+            auction_id,
+            winner,
+            final_price,
+            reason: Bytes::from_slice(env, reason.as_bytes()),
+            timestamp,
+        };
+        emit_auction_ended(env, event);
+
+        result.success = true;
+        Ok(result)
+    }
+
+    /// Royalty split for an auction's sale price, using configured royalties when available
+    pub fn royalties_for(env: &Env, auction: &AuctionTransaction, price: i128) -> RoyaltyDistribution {
+        Self::item_royalties(env, auction, &auction.nft_address, auction.token_id, price)
+    }
+
+    /// Create an auction for several of the seller's NFTs sold together as one lot
+    ///
+    /// The lot is listed under its first item; bids are placed on it like any other auction.
+    pub fn create_bundle_auction(
+        env: &Env,
+        seller: &Address,
+        items: &Vec<NFTItem>,
+        starting_price: i128,
+        reserve_price: i128,
+        duration_seconds: u64,
+        bid_increment: i128,
+        currency: &Asset
+    ) -> Result<u64, SettlementError> {
+        if items.len() < 2 {
+            return Err(SettlementError::InvalidAmount);
+        }
+
+        for (index, item) in items.iter().enumerate() {
+            for other in items.iter().skip(index + 1) {
+                if other.nft_address == item.nft_address && other.token_id == item.token_id {
+                    return Err(SettlementError::AlreadyExists);
+                }
+            }
+            if !asset_utils::check_nft_ownership(&item.nft_address, item.token_id, seller, env)? {
+                return Err(SettlementError::Unauthorized);
+            }
+        }
+
+        let lead = items.get(0).ok_or(SettlementError::InvalidAmount)?;
+        let auction_id = Self::create_auction(
+            env,
+            AuctionType::Bundle,
+            seller,
+            &lead.nft_address,
+            lead.token_id,
+            starting_price,
+            reserve_price,
+            duration_seconds,
+            bid_increment,
+            currency,
+            &AuctionOptions {
+                buyout_price: None,
+                allowed_bidders: None,
+                dutch_half_life: None,
+                start_time: None,
+            }
+        )?;
+
+        let bundle_data = BundleAuctionData {
+            items: items.clone(),
+            starting_price,
+            reserve_price,
+        };
+        BundleAuctionStore::put(env, auction_id, &bundle_data)?;
+
+        Ok(auction_id)
+    }
+
+    /// End a bundle auction, delivering every NFT in the lot to the winner
+    ///
+    /// The seller must still hold the whole lot; if not, the auction is marked `Disputed`
+    /// with the winning bid left in escrow and nothing transferred. A transfer that fails
+    /// mid-batch aborts the call, so the lot is never split.
+    pub fn end_bundle_auction(env: &Env, auction_id: u64, caller: &Address) -> Result<ExecutionResult, SettlementError> {
+        let mut auction = AuctionStore::get(env, auction_id)?;
+        let bundle_data = BundleAuctionStore::get(env, auction_id)?;
+
+        if !Self::can_end_auction(&auction, env)? {
+            return Err(SettlementError::InvalidState);
+        }
+
+        let timestamp = env.ledger().timestamp();
+        let mut result = ExecutionResult {
+            transaction_id: auction_id,
+            success: false,
+            transferred_nft: false,
+            transferred_payment: false,
+            distributed_royalties: false,
+            collected_platform_fee: false,
+            timestamp,
+        };
+
+        let (winner, final_price, reason) = match auction.highest_bidder.clone() {
+            Some(bidder) if auction.highest_bid >= bundle_data.reserve_price => (Some(bidder), auction.highest_bid, "ended"),
+            _ => (None, 0, "reserve_not_met"),
+        };
+
+        // An oversized winning bid halts settlement and leaves the auction open
+        if CircuitBreaker::check(env, auction_id, final_price)? {
+            return Ok(result);
+        }
+
+        if let Some(ref winner_address) = winner {
+            let mut lot = Vec::new(env);
+            let mut complete = true;
+            for item in bundle_data.items.iter() {
+                complete &= asset_utils::check_nft_ownership(&item.nft_address, item.token_id, &auction.seller, env)?;
+                lot.push_back((item.nft_address, item.token_id));
+            }
+
+            if !complete {
+                auction.state = TransactionState::Disputed;
+                AuctionStore::update(env, &auction)?;
+
+                let event = EmergencyWithdrawalEvent {
+                    transaction_id: auction_id,
+                    reason: Bytes::from_slice(env, b"bundle_incomplete"),
+                    admin: caller.clone(),
+                    timestamp,
+                };
+                emit_emergency_withdrawal(env, event);
+
+                return Ok(result);
+            }
+
+            Self::settle_winning_bid(env, &auction, &lot, winner_address, final_price, &mut result)?;
+            BidEscrowStore::release(env, auction_id, winner_address, final_price)?;
+        }
+
+        auction.state = TransactionState::Executed;
+        AuctionStore::update(env, &auction)?;
+        if let Some(ref winner_address) = winner {
+            PlatformStatsStore::record_completed(env, &auction.seller, winner_address, final_price)?;
+        }
+
+        let event = BundleAuctionEndedEvent {
+            auction_id,
+            winner,
+            final_price,
+            item_count: bundle_data.items.len(),
+            reason: Bytes::from_slice(env, reason.as_bytes()),
+            timestamp,
+        };
+        emit_bundle_auction_ended(env, event);
+
+        result.success = true;
+        Ok(result)
+    }
+
+    /// Get bundle auction data
+    pub fn get_bundle_auction(env: &Env, auction_id: u64) -> Result<BundleAuctionData, SettlementError> {
+        BundleAuctionStore::get(env, auction_id)
+    }
+
+    /// Internal: Royalty split for one NFT of an auction, from the marketplace or the NFT contract,
+    /// falling back to the auction's default split
+    fn item_royalties(
+        env: &Env,
+        auction: &AuctionTransaction,
+        nft_address: &Address,
+        token_id: u64,
+        price: i128
+    ) -> RoyaltyDistribution {
+        match RoyaltyDistributor::royalties_for_sale(env, nft_address, token_id, price) {
+            Ok(distribution) => distribution,
+            Err(_) => {
+                let mut distribution = auction.royalty_info.clone();
+                distribution.total_amount = price;
+                distribution
+            }
+        }
+    }
+
+    /// Add a bidder to a private auction's allowlist (seller only)
+    pub fn add_auction_bidder(
+        env: &Env,
+        auction_id: u64,
+        seller: &Address,
+        bidder: &Address
+    ) -> Result<(), SettlementError> {
+        let mut auction = AuctionStore::get(env, auction_id)?;
+        let mut allowed = Self::seller_allowlist(&auction, seller)?;
+
+        if allowed.contains(bidder.clone()) {
+            return Err(SettlementError::AlreadyExists);
+        }
+        allowed.push_back(bidder.clone());
+
+        auction.allowed_bidders = Some(allowed);
+        Self::store_allowlist(env, &auction)
+    }
+
+    /// Remove a bidder from a private auction's allowlist (seller only)
+    pub fn remove_auction_bidder(
+        env: &Env,
+        auction_id: u64,
+        seller: &Address,
+        bidder: &Address
+    ) -> Result<(), SettlementError> {
+        let mut auction = AuctionStore::get(env, auction_id)?;
+        let mut allowed = Self::seller_allowlist(&auction, seller)?;
+
+        let index = allowed.first_index_of(bidder.clone()).ok_or(SettlementError::NotFound)?;
+        allowed.remove(index);
+
+        auction.allowed_bidders = Some(allowed);
+        Self::store_allowlist(env, &auction)
+    }
+
+    /// Withdraw escrowed bid funds that no longer back the leading bid
+    pub fn withdraw_bid_escrow(env: &Env, auction_id: u64, bidder: &Address) -> Result<i128, SettlementError> {
+        let auction = AuctionStore::get(env, auction_id)?;
+        let escrowed = BidEscrowStore::get(env, auction_id, bidder);
+
+        // The leading bid stays locked while the auction is live, paused or disputed
+        let is_live = matches!(auction.state, TransactionState::Pending | TransactionState::Paused);
+        let is_held = is_live || auction.state == TransactionState::Disputed;
+        let locked = if is_held && auction.highest_bidder.as_ref() == Some(bidder) {
+            auction.highest_bid
+        } else {
+            0
+        };
+
+        // A standing proxy keeps its full limit in escrow until the auction closes
+        let locked = match ProxyBidStore::get(env, auction_id, bidder) {
+            Some(proxy) if is_live => locked.max(proxy.max_amount),
+            _ => locked,
+        };
+
+        let withdrawable = escrowed.saturating_sub(locked);
+        if withdrawable <= 0 {
+            return Err(SettlementError::InsufficientFunds);
+        }
+
+        BidEscrowStore::release(env, auction_id, bidder, withdrawable)?;
+        asset_utils::transfer_tokens(
+            &auction.currency.contract,
+            &env.current_contract_address(),
+            bidder,
+            withdrawable,
+            env
+        )?;
+
+        Ok(withdrawable)
+    }
+
+    /// Get the escrowed bid funds for a bidder
+    pub fn get_bid_escrow(env: &Env, auction_id: u64, bidder: &Address) -> i128 {
+        BidEscrowStore::get(env, auction_id, bidder)
+    }
+
+    /// Pause a live auction (seller only)
+    pub fn pause_auction(env: &Env, auction_id: u64, caller: &Address) -> Result<(), SettlementError> {
+        let mut auction = AuctionStore::get(env, auction_id)?;
+
+        if &auction.seller != caller {
+            return Err(SettlementError::Unauthorized);
+        }
+        if !Self::is_auction_active(&auction, env)? {
+            return Err(SettlementError::InvalidState);
+        }
+
+        // A candle auction's hidden end time cannot move without revealing it
+        if CandleAuctionStore::has(env, auction_id) {
+            return Err(SettlementError::InvalidState);
+        }
+
+        let timestamp = env.ledger().timestamp();
+        auction.state = TransactionState::Paused;
+        auction.paused_at = timestamp;
+        AuctionStore::update(env, &auction)?;
+
+        let event = AuctionPausedEvent {
+            auction_id,
+            seller: caller.clone(),
+            timestamp,
+        };
+        emit_auction_paused(env, event);
+
+        Ok(())
+    }
+
+    /// Resume a paused auction, pushing its end time out by the time spent paused (seller only)
+    pub fn resume_auction(env: &Env, auction_id: u64, caller: &Address) -> Result<(), SettlementError> {
+        let mut auction = AuctionStore::get(env, auction_id)?;
+
+        if &auction.seller != caller {
+            return Err(SettlementError::Unauthorized);
+        }
+        if auction.state != TransactionState::Paused {
+            return Err(SettlementError::InvalidState);
+        }
+
+        let timestamp = env.ledger().timestamp();
+        let paused_duration = timestamp.saturating_sub(auction.paused_at);
+        let new_end_time = auction.end_time
+            .checked_add(paused_duration)
+            .ok_or(SettlementError::Overflow)?;
+
+        auction.end_time = new_end_time;
+        auction.state = TransactionState::Pending;
+        auction.paused_at = 0;
+        AuctionStore::update(env, &auction)?;
+
+        // Sealed-bid reveals shift with the bidding phase
+        if let Ok(mut vickrey_data) = VickreyAuctionStore::get(env, auction_id) {
+            vickrey_data.reveal_end = vickrey_data.reveal_end
+                .checked_add(paused_duration)
+                .ok_or(SettlementError::Overflow)?;
+            VickreyAuctionStore::update(env, auction_id, &vickrey_data)?;
+        }
+        if let Ok(mut sealed_data) = SealedAuctionStore::get(env, auction_id) {
+            sealed_data.reveal_end = sealed_data.reveal_end
+                .checked_add(paused_duration)
+                .ok_or(SettlementError::Overflow)?;
+            SealedAuctionStore::update(env, auction_id, &sealed_data)?;
+        }
+
+        let event = AuctionResumedEvent {
+            auction_id,
+            seller: caller.clone(),
+            paused_duration,
+            new_end_time,
+            timestamp,
+        };
+        emit_auction_resumed(env, event);
+
+        Ok(())
+    }
+
+    /// Open a scheduled auction for bidding once its start time is reached
+    pub fn open_scheduled_auction(env: &Env, auction_id: u64) -> Result<(), SettlementError> {
+        let mut auction = AuctionStore::get(env, auction_id)?;
+
+        if auction.state != TransactionState::Scheduled {
+            return Err(SettlementError::InvalidState);
+        }
+
+        let timestamp = env.ledger().timestamp();
+        if timestamp < auction.start_time {
+            return Err(SettlementError::AuctionNotStarted);
+        }
+
+        auction.state = TransactionState::Pending;
+        AuctionStore::update(env, &auction)?;
+        AuctionStore::unschedule(env, auction_id);
+
+        let event = AuctionOpenedEvent {
+            auction_id,
+            seller: auction.seller.clone(),
+            start_time: auction.start_time,
+            end_time: auction.end_time,
+            timestamp,
+        };
+        emit_auction_opened(env, event);
+
+        Ok(())
+    }
+
+    /// Get current price for Dutch auction
+    pub fn get_dutch_auction_price(env: &Env, auction_id: u64) -> Result<i128, SettlementError> {
+        let auction = AuctionStore::get(env, auction_id)?;
+        let dutch_data = DutchAuctionStore::get(env, auction_id)?;
+
+        let current_time = env.ledger().timestamp();
+
+        // Update price if needed
+        let updated_price = match dutch_data.decay_type {
+            DecayType::Linear => math_utils::calculate_time_weighted_price(
+                dutch_data.last_price_update,
+                auction.end_time,
+                current_time,
+                dutch_data.current_price,
+                dutch_data.ending_price,
+                env
+            )?,
+            DecayType::Exponential if current_time >= auction.end_time => dutch_data.ending_price,
+            DecayType::Exponential => math_utils::calculate_exponential_decay_price(
+                dutch_data.starting_price,
+                dutch_data.ending_price,
+                dutch_data.half_life_seconds,
+                current_time.saturating_sub(auction.start_time),
+                env
+            )?,
+        };
+
+        // Update stored price
+        let mut updated_data = dutch_data;
+        updated_data.current_price = updated_price;
+        updated_data.last_price_update = current_time;
+        DutchAuctionStore::update(env, auction_id, &updated_data)?;
+
+        Ok(updated_price)
+    }
+
+    /// Cancel an auction
+    pub fn cancel_auction(
+        env: &Env,
+        auction_id: u64,
+        canceller: &Address
+    ) -> Result<(), SettlementError> {
+        let mut auction = AuctionStore::get(env, auction_id)?;
+
+        // Only seller can cancel
+        if &auction.seller != canceller {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        // Can only cancel an open or scheduled auction with no bids placed
+        if !matches!(auction.state, TransactionState::Pending | TransactionState::Paused | TransactionState::Scheduled)
+            || auction.highest_bid > 0
+        {
+            return Err(SettlementError::InvalidState);
+        }
+
+        if auction.state == TransactionState::Scheduled {
+            AuctionStore::unschedule(env, auction_id);
+        }
+        auction.state = TransactionState::Cancelled;
+        AuctionStore::update(env, &auction)?;
+        PlatformStatsStore::record_cancelled(env);
+
+        let event = AuctionEndedEvent {
+            auction_id,
+            winner: None,
+            final_price: 0,
+            reason: Bytes::from_slice(env, b"cancelled"),
+            timestamp: env.ledger().timestamp(),
+        };
+        emit_auction_ended(env, event);
+
+        Ok(())
+    }
+
+    /// Get auction configuration
+    pub fn get_auction_config(env: &Env) -> Result<AuctionConfig, SettlementError> {
+        env.storage()
+            .instance()
+            .get(&AUCTION_CONFIG)
+            .ok_or(SettlementError::NotFound)
+    }
+
+    /// Update auction configuration
+    pub fn update_auction_config(
+        env: &Env,
+        config: &AuctionConfig,
+        _admin: &Address
+    ) -> Result<(), SettlementError> {
+        Self::validate_auction_config(config)?;
+
+        // Check admin permissions
+        env.storage().instance().set(&AUCTION_CONFIG, config);
+        Ok(())
+    }
+
+    /// Validate auction configuration
+    pub fn validate_auction_config(config: &AuctionConfig) -> Result<(), SettlementError> {
+        // Bids must rise by a positive step of at most 100%
+        if config.min_bid_increment_bps == 0 || config.min_bid_increment_bps > 10000 {
+            return Err(SettlementError::InvalidAmount);
+        }
+
+        if config.max_auction_duration == 0 {
+            return Err(SettlementError::InvalidAmount);
+        }
+
+        // Commit-reveal is a 0/1 flag and needs time to reveal in
+        if config.commit_reveal_enabled > 1 || (config.commit_reveal_enabled == 1 && config.reveal_period == 0) {
+            return Err(SettlementError::InvalidAmount);
+        }
+
+        Ok(())
+    }
+
+    /// Internal: Validate auction parameters
+    fn validate_auction_params(
+        starting_price: i128,
+        reserve_price: i128,
+        duration: u64,
+        bid_increment: i128,
+        config: &AuctionConfig
+    ) -> Result<(), SettlementError> {
+        if starting_price <= 0 {
+            return Err(SettlementError::InvalidAmount);
+        }
+
+        if reserve_price < 0 || reserve_price > starting_price {
+            return Err(SettlementError::InvalidAmount);
+        }
+
+        if duration == 0 || duration > config.max_auction_duration {
+            return Err(SettlementError::InvalidAmount);
+        }
+
+        if bid_increment <= 0 {
+            return Err(SettlementError::InvalidBidIncrement);
+        }
+
+        Ok(())
+    }
+
+    /// Internal: Reject bids on paused or inactive auctions
+    fn ensure_accepting_bids(auction: &AuctionTransaction, env: &Env) -> Result<(), SettlementError> {
+        if auction.state == TransactionState::Paused {
+            return Err(SettlementError::InvalidState);
+        }
+        if auction.state == TransactionState::Scheduled {
+            return Err(SettlementError::AuctionNotStarted);
+        }
+        if !Self::is_auction_active(auction, env)? {
+            return Err(SettlementError::AuctionAlreadyEnded);
+        }
+        Ok(())
+    }
+
+    /// Internal: Check if auction is active
+    fn is_auction_active(auction: &AuctionTransaction, env: &Env) -> Result<bool, SettlementError> {
+        let current_time = env.ledger().timestamp();
+        Ok(current_time >= auction.start_time &&
+           current_time <= auction.end_time &&
+           auction.state == TransactionState::Pending)
+    }
+
+    /// Internal: Validate bid amount
+    fn validate_bid_amount(
+        auction: &AuctionTransaction,
+        bid_amount: i128,
+        env: &Env
+    ) -> Result<(), SettlementError> {
+        // Must be higher than current highest bid
+        if bid_amount <= auction.highest_bid {
+            return Err(SettlementError::BidTooLow);
+        }
+
+        // Must meet minimum increment
+        if auction.highest_bid > 0 {
+            let min_increment = math_utils::calculate_bid_increment(
+                auction.highest_bid,
+                (auction.bid_increment.max(100) as u64).max(100), // At least 1%
+                env
+            )?;
+            if bid_amount < auction.highest_bid + min_increment {
+                return Err(SettlementError::BidTooLow);
+            }
+        } else {
+            // First bid must meet or exceed starting price
+            if bid_amount < auction.starting_price {
+                return Err(SettlementError::BidTooLow);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Internal: Let standing proxy bids respond to the current leading bid.
+    ///
+    /// The strongest proxy outside the lead is compared with what the leader is willing to
+    /// pay; whichever side has the higher limit ends up one increment above the other.
+    fn run_proxy_bidding(env: &Env, auction_id: u64) -> Result<(), SettlementError> {
+        let mut auction = AuctionStore::get(env, auction_id)?;
+
+        let mut leader_proxy: Option<ProxyBid> = None;
+        let mut challenger: Option<ProxyBid> = None;
+        for proxy in ProxyBidStore::get_all(env, auction_id).iter() {
+            if auction.highest_bidder.as_ref() == Some(&proxy.bidder) {
+                leader_proxy = Some(proxy);
+            } else if challenger.as_ref().is_none_or(|best| proxy.max_amount > best.max_amount) {
+                challenger = Some(proxy);
+            }
+        }
+
+        let challenger = match challenger {
+            Some(proxy) => proxy,
+            None => return Ok(()),
+        };
+
+        let next_min = Self::min_next_bid(&auction, auction.highest_bid, env)?;
+        if challenger.max_amount < next_min {
+            return Ok(());
+        }
+
+        let leader_limit = leader_proxy
+            .as_ref()
+            .map_or(auction.highest_bid, |proxy| proxy.max_amount.max(auction.highest_bid));
+
+        if challenger.max_amount > leader_limit {
+            // Challenger takes the lead one increment above the leader's limit
+            let target = Self::min_next_bid(&auction, leader_limit, env)?.max(next_min);
+            let amount = challenger.max_amount.min(target);
+            Self::place_proxy_bid(env, &mut auction, challenger, amount)
+        } else if let Some(leader) = leader_proxy {
+            // Leader's proxy answers one increment above the challenger's limit
+            let target = Self::min_next_bid(&auction, challenger.max_amount, env)?;
+            let amount = leader.max_amount.min(target);
+            if amount > auction.highest_bid {
+                Self::place_proxy_bid(env, &mut auction, leader, amount)
+            } else {
+                Ok(())
+            }
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Internal: Place an automatic bid on behalf of a proxy
+    fn place_proxy_bid(
+        env: &Env,
+        auction: &mut AuctionTransaction,
+        mut proxy: ProxyBid,
+        amount: i128
+    ) -> Result<(), SettlementError> {
+        let timestamp = env.ledger().timestamp();
+
+        let bid = Self::process_direct_bid(env, auction, &proxy.bidder, amount, timestamp)?;
+        AuctionStore::add_bid(env, auction.auction_id, &bid)?;
+        AuctionStore::update(env, auction)?;
+
+        proxy.current_bid = amount;
+        ProxyBidStore::put(env, auction.auction_id, &proxy);
+
+        let event = ProxyBidActivatedEvent {
+            auction_id: auction.auction_id,
+            bidder: proxy.bidder.clone(),
+            amount,
+            max_amount: proxy.max_amount,
+            timestamp,
+        };
+        emit_proxy_bid_activated(env, event);
+
+        Ok(())
+    }
+
+    /// Internal: Smallest valid bid above `amount` (the starting price if nothing has been bid)
+    fn min_next_bid(auction: &AuctionTransaction, amount: i128, env: &Env) -> Result<i128, SettlementError> {
+        if amount == 0 {
+            return Ok(auction.starting_price);
+        }
+
+        let increment = math_utils::calculate_bid_increment(
+            amount,
+            auction.bid_increment.max(100) as u64, // At least 1%
+            env
+        )?;
+        math_utils::safe_add(amount, increment, env)
+    }
+
+    /// Internal: Check seller rights and return the editable allowlist
+    fn seller_allowlist(auction: &AuctionTransaction, seller: &Address) -> Result<Vec<Address>, SettlementError> {
+        if &auction.seller != seller {
+            return Err(SettlementError::Unauthorized);
+        }
+        if auction.state != TransactionState::Pending {
+            return Err(SettlementError::InvalidState);
+        }
+
+        // Open auctions have no list to manage
+        auction.allowed_bidders.clone().ok_or(SettlementError::InvalidState)
+    }
+
+    /// Internal: Persist an allowlist change, keeping Dutch auction data in sync
+    fn store_allowlist(env: &Env, auction: &AuctionTransaction) -> Result<(), SettlementError> {
+        AuctionStore::update(env, auction)?;
+
+        if let Ok(mut dutch_data) = DutchAuctionStore::get(env, auction.auction_id) {
+            dutch_data.allowed_bidders = auction.allowed_bidders.clone();
+            DutchAuctionStore::update(env, auction.auction_id, &dutch_data)?;
+        }
+
+        Ok(())
+    }
+
+    /// Internal: Draw and commit to a candle auction's closing time.
+    ///
+    /// Soroban's PRNG is only pseudo-random: validators can predict or influence it, and
+    /// the secret sits in contract storage where anyone reading ledger state can see it.
+    /// The commitment keeps the end time out of the public getters and proves it wasn't
+    /// changed after the fact; it is a deterrent against sniping, not a security boundary.
+    fn draw_candle_end(
+        env: &Env,
+        auction_id: u64,
+        start_time: u64,
+        end_time: u64,
+        candle_window: u64
+    ) -> Result<(), SettlementError> {
+        let earliest_end = end_time.saturating_sub(candle_window).max(start_time);
+        let candle_end: u64 = env.prng().gen_range(earliest_end..=end_time);
+        let salt: BytesN<32> = env.prng().gen();
+
+        let candle_data = CandleAuctionData {
+            earliest_end,
+            latest_end: end_time,
+            end_commitment: Self::candle_commitment(env, candle_end, &salt),
+            revealed_end: 0,
+        };
+        CandleAuctionStore::put(env, auction_id, &candle_data)?;
+        CandleAuctionStore::put_secret(env, auction_id, candle_end, &salt);
+        Ok(())
+    }
+
+    /// Internal: Hash a candle end time with its salt
+    fn candle_commitment(env: &Env, candle_end: u64, salt: &BytesN<32>) -> BytesN<32> {
+        let mut preimage = Bytes::from_array(env, &candle_end.to_be_bytes());
+        preimage.append(&Bytes::from(salt.clone()));
+        env.crypto().sha256(&preimage).into()
+    }
+
+    /// Internal: Move bid funds into contract escrow
+    fn escrow_bid(
+        env: &Env,
+        auction: &AuctionTransaction,
+        bidder: &Address,
+        bid_amount: i128
+    ) -> Result<(), SettlementError> {
+        asset_utils::transfer_tokens_checked(
+            &auction.currency.contract,
+            bidder,
+            &env.current_contract_address(),
+            bid_amount,
+            env
+        )?;
+        BidEscrowStore::deposit(env, auction.auction_id, bidder, bid_amount)?;
+        Ok(())
+    }
+
+    /// Internal: Check whether an auction takes sealed bids
+    fn is_sealed_bid_auction(env: &Env, auction_id: u64) -> bool {
+        VickreyAuctionStore::has(env, auction_id) || SealedAuctionStore::has(env, auction_id)
+    }
+
+    /// Internal: Record a sealed bid commitment on a Vickrey or sealed first-price auction
+    fn place_sealed_bid(
+        env: &Env,
+        auction_id: u64,
+        bidder: &Address,
+        commitment_hash: Option<Bytes>
+    ) -> Result<(), SettlementError> {
+        let commitment = commitment_hash.ok_or(SettlementError::InvalidState)?;
+
+        // Register the bidder on whichever sealed-bid auction this is
+        let reveal_end = if let Ok(mut vickrey_data) = VickreyAuctionStore::get(env, auction_id) {
+            if vickrey_data.committed_bidders.contains(bidder.clone()) {
+                return Err(SettlementError::AlreadyExists);
+            }
+            vickrey_data.committed_bidders.push_back(bidder.clone());
+            VickreyAuctionStore::update(env, auction_id, &vickrey_data)?;
+            vickrey_data.reveal_end
+        } else {
+            let mut sealed_data = SealedAuctionStore::get(env, auction_id)?;
+            if sealed_data.committed_bidders.contains(bidder.clone()) {
+                return Err(SettlementError::AlreadyExists);
+            }
+            sealed_data.committed_bidders.push_back(bidder.clone());
+            SealedAuctionStore::update(env, auction_id, &sealed_data)?;
+            sealed_data.reveal_end
+        };
+
+        CommitRevealScheme::store_commitment(
+            env,
+            bidder,
+            auction_id,
+            &commitment,
+            reveal_end
+        )?;
+
+        // Amount stays hidden until the reveal phase
+        let timestamp = env.ledger().timestamp();
+        AuctionStore::add_bid(env, auction_id, &Bid {
+            bidder: bidder.clone(),
+            amount: 0,
+            placed_at: timestamp,
+            is_committed: true,
+            commitment_hash: Some(commitment),
+        })?;
+
+        let event = BidPlacedEvent {
+            auction_id,
+            bidder: bidder.clone(),
+            amount: 0,
+            is_committed: true,
+            timestamp,
+        };
+        emit_bid_placed(env, event);
+
+        Ok(())
+    }
 
     /// Internal: Process a direct bid
     fn process_direct_bid(
@@ -470,6 +1703,123 @@ impl AuctionEngine {
         })
     }
 
+    /// Internal: Deliver the lot's NFTs to the winner and pay out the winning bid, recording each step
+    fn settle_winning_bid(
+        env: &Env,
+        auction: &AuctionTransaction,
+        lot: &Vec<(Address, u64)>,
+        winner: &Address,
+        price: i128,
+        result: &mut ExecutionResult
+    ) -> Result<(), SettlementError> {
+        let payout = Self::price_winning_bid(env, auction, lot, winner, price)?;
+        Self::deliver_lot(env, auction, lot, winner)?;
+        result.transferred_nft = true;
+        Self::pay_winning_bid(env, auction, winner, &payout, result)
+    }
+
+    /// Internal: Work out the winning bid's royalty splits and platform fee without moving anything
+    ///
+    /// Royalties are worked out per NFT on an even share of the price, the last NFT taking
+    /// any remainder. Fails if the seller's share can't cover the platform fee.
+    fn price_winning_bid(
+        env: &Env,
+        auction: &AuctionTransaction,
+        lot: &Vec<(Address, u64)>,
+        winner: &Address,
+        price: i128
+    ) -> Result<WinningBidPayout, SettlementError> {
+        let platform_fee = FeeManager::calculate_fee(env, price, winner, Some(auction.nft_address.clone()))?;
+
+        let item_count = lot.len() as i128;
+        let share = math_utils::safe_div(price, item_count, env)?;
+        let mut items = Vec::new(env);
+        let mut seller_amount: i128 = 0;
+        for (index, (nft_address, token_id)) in lot.iter().enumerate() {
+            let item_price = if index as i128 == item_count - 1 {
+                math_utils::safe_sub(price, math_utils::safe_mul(share, item_count - 1, env)?, env)?
+            } else {
+                share
+            };
+            let royalties = Self::item_royalties(env, auction, &nft_address, token_id, item_price);
+
+            // Mirrors the split RoyaltyDistributor::distribute_royalties will pay
+            let creator_amount = math_utils::calculate_percentage(item_price, royalties.creator_percentage, env)?;
+            let platform_amount = math_utils::calculate_percentage(item_price, royalties.platform_percentage, env)?;
+            let deductions = math_utils::safe_add(creator_amount, platform_amount, env)?;
+            if deductions > item_price {
+                return Err(SettlementError::InvalidAmount);
+            }
+            let mut item_seller_amount = math_utils::safe_sub(item_price, deductions, env)?;
+            if royalties.creator_address == auction.seller {
+                item_seller_amount = math_utils::safe_add(item_seller_amount, creator_amount, env)?;
+            }
+            seller_amount = math_utils::safe_add(seller_amount, item_seller_amount, env)?;
+            items.push_back((royalties, item_price));
+        }
+
+        // The seller's share of the split has to cover the platform fee
+        if platform_fee > seller_amount {
+            return Err(SettlementError::InvalidAmount);
+        }
+
+        Ok(WinningBidPayout { platform_fee, items })
+    }
+
+    /// Internal: Move the lot's NFTs from the seller to the winner
+    fn deliver_lot(
+        env: &Env,
+        auction: &AuctionTransaction,
+        lot: &Vec<(Address, u64)>,
+        winner: &Address
+    ) -> Result<(), SettlementError> {
+        for (nft_address, token_id) in lot.iter() {
+            asset_utils::transfer_nft(&nft_address, &auction.seller, winner, token_id, env)?;
+        }
+        Ok(())
+    }
+
+    /// Internal: Pay royalties, the platform fee and the seller out of the winner's escrow
+    fn pay_winning_bid(
+        env: &Env,
+        auction: &AuctionTransaction,
+        winner: &Address,
+        payout: &WinningBidPayout,
+        result: &mut ExecutionResult
+    ) -> Result<(), SettlementError> {
+        let mut seller_amount: i128 = 0;
+        let mut distributed = true;
+        for (royalties, item_price) in payout.items.iter() {
+            let distribution_result = RoyaltyDistributor::distribute_royalties(
+                env,
+                auction.auction_id,
+                &royalties,
+                &auction.currency,
+                item_price,
+                &auction.seller
+            )?;
+            seller_amount = math_utils::safe_add(seller_amount, distribution_result.seller_amount, env)?;
+            distributed &= distribution_result.distribution_success;
+        }
+        result.distributed_royalties = distributed;
+
+        FeeManager::collect_platform_fee(env, payout.platform_fee, &auction.currency, winner, None)?;
+        result.collected_platform_fee = true;
+
+        // The seller's share of the split, less the platform fee
+        let proceeds = math_utils::safe_sub(seller_amount, payout.platform_fee, env)?;
+        asset_utils::transfer_tokens(
+            &auction.currency.contract,
+            &env.current_contract_address(),
+            &auction.seller,
+            proceeds,
+            env
+        )?;
+        result.transferred_payment = true;
+
+        Ok(())
+    }
+
     /// Internal: Check if auction can be ended
     fn can_end_auction(auction: &AuctionTransaction, env: &Env) -> Result<bool, SettlementError> {
         let current_time = env.ledger().timestamp();
@@ -494,6 +1844,8 @@ impl Default for AuctionConfig {
             dutch_price_decrement: 1000,     // 1000 units per time unit
             commit_reveal_enabled: 0,
             reveal_period: 3600,             // 1 hour
+            max_extensions: 10,
+            candle_window: 3600,             // 1 hour
         }
     }
 }
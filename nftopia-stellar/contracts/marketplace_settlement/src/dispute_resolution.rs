@@ -1,17 +1,34 @@
-use soroban_sdk::{Env, Address, Map, Vec, Symbol, symbol_short, Bytes, contracttype};
+use soroban_sdk::{Env, Address, Map, Vec, Symbol, symbol_short, Bytes, BytesN, contracttype};
 use crate::error::SettlementError;
-use crate::types::Dispute;
+use crate::types::{Asset, Dispute, TransactionState};
 use crate::error::{DISPUTE_RESOLUTION_NOT_RESOLVED, DISPUTE_RESOLUTION_REFUND_BUYER, DISPUTE_RESOLUTION_RELEASE_TO_SELLER, DISPUTE_RESOLUTION_SPLIT_FUNDS, DISPUTE_RESOLUTION_CANCEL_TRANSACTION};
 use crate::storage::dispute_store::DisputeStore;
+use crate::storage::auction_store::{AuctionStore, BidEscrowStore};
+use crate::storage::transaction_store::{SaleTransactionStore, SaleEscrowStore};
+use crate::storage::DataKey;
+use crate::utils::{asset_utils, math_utils};
 use crate::events::{
-    emit_dispute_created, emit_dispute_vote, emit_dispute_resolved,
-    DisputeCreatedEvent, DisputeVoteEvent, DisputeResolvedEvent
+    emit_dispute_created, emit_dispute_vote, emit_dispute_resolved, emit_dispute_executed,
+    emit_dispute_appealed, emit_arbitrator_slashed, DisputeCreatedEvent, DisputeVoteEvent,
+    DisputeResolvedEvent, DisputeExecutedEvent, DisputeAppealedEvent, ArbitratorSlashedEvent
 };
 
 // Storage keys
 const ARBITRATORS: Symbol = symbol_short!("arbiters");
 const DISPUTE_CONFIG: Symbol = symbol_short!("dsp_cfg");
 
+/// Share of an arbitrator's stake slashed for a failed ruling, in basis points
+const ARBITRATOR_SLASH_BPS: u64 = 500;
+
+/// Time either party has to appeal a ruling
+const APPEAL_PERIOD: u64 = 172800; // 48 hours
+
+/// Reputation an arbitrator needs to sit on an appeal panel
+const SENIOR_ARBITRATOR_REPUTATION: u64 = 800;
+
+/// Evidence items each party may submit to a dispute
+const MAX_EVIDENCE_PER_PARTY: u32 = 10;
+
 /// Dispute configuration
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -21,6 +38,9 @@ pub struct DisputeConfig {
     pub evidence_submission_period: u64, // Time allowed for evidence submission
     pub max_arbitrators_per_dispute: u64,
     pub min_arbitrator_reputation: u64,
+    pub min_arbitrator_stake: i128,   // Bond required to be selected for a panel
+    pub voting_period: u64,           // Time arbitrators have to decide a dispute
+    pub auto_resolution_default: u64, // Applied when voting times out: refund buyer or cancel transaction
 }
 
 /// Arbitrator information
@@ -33,6 +53,53 @@ pub struct Arbitrator {
     pub successful_resolutions: u64,
     pub is_active: u64, // 0 = inactive, 1 = active
     pub registered_at: u64,
+    pub disputes_assigned: u64,
+    pub votes_cast: u64,
+    pub correct_votes: u64,      // Votes on the side the dispute was resolved for
+    pub rulings_appealed: u64,   // Disputes taken from this arbitrator's panel on appeal
+    pub total_vote_time: u64,    // Seconds from panel assignment to vote, summed over all votes
+    pub last_active: u64,        // When the arbitrator last voted, 0 = never
+}
+
+/// Audit view of an arbitrator's dispute record
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArbitratorStats {
+    pub total_assigned: u64,
+    pub total_voted: u64,
+    pub total_correct: u64,
+    pub total_appealed: u64,
+    pub average_resolution_time_secs: u64,
+    pub last_active: u64,
+}
+
+/// Reputation-weighted tally of a dispute's votes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WeightedVoteResult {
+    pub votes_for: u64,        // Votes favouring the initiator
+    pub votes_against: u64,
+    pub weighted_for: u64,     // Reputation behind the votes for
+    pub weighted_against: u64,
+    pub resolved: bool,        // Enough reputation has voted to decide the dispute
+}
+
+/// Evidence whose integrity was checked against the submitter's hash
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EvidenceRecord {
+    pub submitter: Address,
+    pub hash: BytesN<32>, // sha256 of `data`
+    pub data: Bytes,
+    pub submitted_at: u64,
+}
+
+/// Funds held for a disputed transaction
+struct DisputedEscrow {
+    buyer: Address,
+    seller: Address,
+    amount: i128,
+    currency: Asset,
 }
 
 /// Dispute resolution manager
@@ -48,6 +115,12 @@ impl DisputeResolutionManager {
         reason: &Bytes,
         evidence_uri: Option<Bytes>
     ) -> Result<u64, SettlementError> {
+        // Only the buyer or seller of the transaction can open a dispute over it
+        let (buyer, seller) = Self::parties(env, transaction_id, auction_id)?;
+        if *initiator != seller && Some(initiator.clone()) != buyer {
+            return Err(SettlementError::Unauthorized);
+        }
+
         // Check if dispute already exists for this transaction
         if DisputeStore::exists_for_transaction(env, transaction_id) {
             return Err(SettlementError::AlreadyExists);
@@ -63,12 +136,22 @@ impl DisputeResolutionManager {
         let config = Self::get_dispute_config(env)?;
 
         // Select arbitrators
-        let arbitrators = Self::select_arbitrators(env, &config)?;
+        let arbitrators = Self::select_arbitrators(
+            env,
+            &config,
+            config.min_arbitrator_reputation,
+            &Vec::new(env)
+        )?;
 
         if arbitrators.is_empty() {
             return Err(SettlementError::InsufficientArbitrators);
         }
 
+        let mut initiator_evidence = Vec::new(env);
+        if let Some(uri) = evidence_uri {
+            initiator_evidence.push_back(uri);
+        }
+
         // Create dispute
         let dispute_id = DisputeStore::next_id(env);
         let dispute = Dispute {
@@ -77,16 +160,20 @@ impl DisputeResolutionManager {
             auction_id,
             initiator: initiator.clone(),
             reason: reason.clone(),
-            evidence_uri,
+            initiator_evidence,
+            respondent_evidence: Vec::new(env),
             arbitrators: arbitrators.clone(),
             votes: Map::new(env),
             required_votes: config.arbitration_quorum,
             created_at: env.ledger().timestamp(),
             resolved_at: 0,
             resolution: DISPUTE_RESOLUTION_NOT_RESOLVED,
+            appeal_deadline: 0,
+            appeal_filed_by: None,
         };
 
         DisputeStore::put(env, &dispute)?;
+        Self::record_assignment(env, &arbitrators)?;
 
         // Emit dispute created event
         let event = DisputeCreatedEvent {
@@ -131,6 +218,13 @@ impl DisputeResolutionManager {
         dispute.votes.set(arbitrator.clone(), vote);
         DisputeStore::update(env, &dispute)?;
 
+        let now = env.ledger().timestamp();
+        let mut arb = Self::get_arbitrator(env, arbitrator)?;
+        arb.votes_cast += 1;
+        arb.total_vote_time = arb.total_vote_time.saturating_add(now.saturating_sub(Self::voting_started_at(&dispute)));
+        arb.last_active = now;
+        Self::store_arbitrator(env, &arb)?;
+
         // Emit vote event
         let event = DisputeVoteEvent {
             dispute_id,
@@ -146,20 +240,81 @@ impl DisputeResolutionManager {
         Ok(())
     }
 
-    /// Submit additional evidence
+    /// Appeal a ruling to a fresh panel of senior arbitrators
+    ///
+    /// Either party may appeal once, within 48 hours of the ruling. The original panel
+    /// is replaced, so its arbitrators can't vote on the appeal.
+    pub fn file_appeal(env: &Env, dispute_id: u64, appellant: &Address) -> Result<(), SettlementError> {
+        let mut dispute = DisputeStore::get(env, dispute_id)?;
+
+        let respondent = Self::respondent(env, &dispute)?;
+        if *appellant != dispute.initiator && *appellant != respondent {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        if dispute.appeal_filed_by.is_some() {
+            return Err(SettlementError::AlreadyExists);
+        }
+        if dispute.resolved_at == 0 {
+            return Err(SettlementError::InvalidState);
+        }
+        let now = env.ledger().timestamp();
+        if now > dispute.appeal_deadline {
+            return Err(SettlementError::Expired);
+        }
+
+        let config = Self::get_dispute_config(env)?;
+        let panel = Self::select_arbitrators(env, &config, SENIOR_ARBITRATOR_REPUTATION, &dispute.arbitrators)?;
+        if panel.is_empty() {
+            return Err(SettlementError::InsufficientArbitrators);
+        }
+
+        for arbitrator in dispute.arbitrators.iter() {
+            let mut arb = Self::get_arbitrator(env, &arbitrator)?;
+            arb.rulings_appealed += 1;
+            Self::store_arbitrator(env, &arb)?;
+        }
+        Self::record_assignment(env, &panel)?;
+
+        let overturned_resolution = dispute.resolution;
+        dispute.arbitrators = panel.clone();
+        dispute.votes = Map::new(env);
+        dispute.required_votes = config.arbitration_quorum.min(panel.len() as u64);
+        dispute.resolved_at = 0;
+        dispute.resolution = DISPUTE_RESOLUTION_NOT_RESOLVED;
+        dispute.appeal_deadline = now;
+        dispute.appeal_filed_by = Some(appellant.clone());
+        DisputeStore::update(env, &dispute)?;
+
+        let event = DisputeAppealedEvent {
+            dispute_id,
+            appellant: appellant.clone(),
+            overturned_resolution,
+            arbitrators: panel,
+            timestamp: now,
+        };
+        emit_dispute_appealed(env, event);
+
+        Ok(())
+    }
+
+    /// Submit additional evidence for the initiator or, with `is_respondent`, the other party
     pub fn submit_evidence(
         env: &Env,
         dispute_id: u64,
         submitter: &Address,
-        evidence_uri: &Bytes
+        evidence_uri: &Bytes,
+        is_respondent: bool
     ) -> Result<(), SettlementError> {
         let mut dispute = DisputeStore::get(env, dispute_id)?;
 
-        // Only initiator or arbitrators can submit evidence
-        let is_authorized = dispute.initiator == *submitter ||
-                           dispute.arbitrators.contains(submitter.clone());
-
-        if !is_authorized {
+        // Each party can only add to its own side of the case
+        let party = if is_respondent {
+            Self::respondent(env, &dispute)?
+        } else {
+            dispute.initiator.clone()
+        };
+        if party != *submitter {
             return Err(SettlementError::Unauthorized);
         }
 
@@ -171,7 +326,15 @@ impl DisputeResolutionManager {
             return Err(SettlementError::Expired);
         }
 
-        dispute.evidence_uri = Some(evidence_uri.clone());
+        let evidence = if is_respondent {
+            &mut dispute.respondent_evidence
+        } else {
+            &mut dispute.initiator_evidence
+        };
+        if evidence.len() >= MAX_EVIDENCE_PER_PARTY {
+            return Err(SettlementError::InvalidAmount);
+        }
+        evidence.push_back(evidence_uri.clone());
         DisputeStore::update(env, &dispute)?;
 
         Ok(())
@@ -191,13 +354,12 @@ impl DisputeResolutionManager {
             return Err(SettlementError::DisputeAlreadyResolved);
         }
 
-        dispute.resolution = resolution;
-        dispute.resolved_at = env.ledger().timestamp();
+        Self::mark_resolved(env, &mut dispute, resolution)?;
 
         DisputeStore::update(env, &dispute)?;
 
         // Update arbitrator reputations
-        Self::update_arbitrator_reputations(env, &dispute, true)?;
+        Self::update_arbitrator_reputations(env, &dispute.arbitrators, true)?;
 
         // Emit resolution event
         let event = DisputeResolvedEvent {
@@ -212,6 +374,47 @@ impl DisputeResolutionManager {
         Ok(())
     }
 
+    /// Apply the configured default resolution to a dispute that wasn't decided in time
+    ///
+    /// Assigned arbitrators who never voted are penalized. Either default pays the escrow
+    /// back to the buyer of record, never to whoever opened the dispute.
+    pub fn finalize_stale_dispute(env: &Env, dispute_id: u64) -> Result<(), SettlementError> {
+        let mut dispute = DisputeStore::get(env, dispute_id)?;
+
+        if dispute.resolved_at != 0 {
+            return Err(SettlementError::DisputeAlreadyResolved);
+        }
+
+        let config = Self::get_dispute_config(env)?;
+
+        let deadline = Self::voting_started_at(&dispute).saturating_add(config.voting_period);
+        if env.ledger().timestamp() < deadline {
+            return Err(SettlementError::InvalidState);
+        }
+
+        Self::mark_resolved(env, &mut dispute, config.auto_resolution_default)?;
+        DisputeStore::update(env, &dispute)?;
+
+        let mut absent = Vec::new(env);
+        for arbitrator in dispute.arbitrators.iter() {
+            if !dispute.votes.contains_key(arbitrator.clone()) {
+                absent.push_back(arbitrator);
+            }
+        }
+        Self::update_arbitrator_reputations(env, &absent, false)?;
+
+        let event = DisputeResolvedEvent {
+            dispute_id,
+            resolution: dispute.resolution,
+            winning_votes: 0, // Default resolution
+            total_votes: dispute.votes.len() as u64,
+            timestamp: dispute.resolved_at,
+        };
+        emit_dispute_resolved(env, event);
+
+        Ok(())
+    }
+
     /// Execute dispute resolution
     pub fn execute_dispute_resolution(
         env: &Env,
@@ -224,6 +427,11 @@ impl DisputeResolutionManager {
             return Err(SettlementError::InvalidState);
         }
 
+        // The ruling stands only once it can no longer be appealed
+        if dispute.appeal_filed_by.is_none() && env.ledger().timestamp() <= dispute.appeal_deadline {
+            return Err(SettlementError::InvalidState);
+        }
+
         let resolution = dispute.resolution;
 
         // Execute resolution based on type
@@ -259,12 +467,95 @@ impl DisputeResolutionManager {
             successful_resolutions: 0,
             is_active: 1,
             registered_at: env.ledger().timestamp(),
+            disputes_assigned: 0,
+            votes_cast: 0,
+            correct_votes: 0,
+            rulings_appealed: 0,
+            total_vote_time: 0,
+            last_active: 0,
         };
 
         Self::store_arbitrator(env, &arbitrator_info)?;
         Ok(())
     }
 
+    /// Bond tokens as an arbitrator, returning the arbitrator's total stake
+    ///
+    /// All stakes share one asset, fixed by the first stake ever made.
+    pub fn stake_as_arbitrator(
+        env: &Env,
+        arbitrator: &Address,
+        amount: i128,
+        asset: &Asset
+    ) -> Result<i128, SettlementError> {
+        if amount <= 0 {
+            return Err(SettlementError::InvalidAmount);
+        }
+
+        match Self::get_stake_asset(env) {
+            Some(stake_asset) if stake_asset.contract != asset.contract => {
+                return Err(SettlementError::InvalidCurrency);
+            }
+            Some(_) => {}
+            None => env.storage().instance().set(&DataKey::ArbitratorStakeAsset, asset),
+        }
+
+        asset_utils::transfer_tokens_checked(
+            &asset.contract,
+            arbitrator,
+            &env.current_contract_address(),
+            amount,
+            env
+        )?;
+
+        let stake = math_utils::safe_add(Self::get_arbitrator_stake(env, arbitrator), amount, env)?;
+        env.storage().persistent().set(&DataKey::ArbitratorStake(arbitrator.clone()), &stake);
+        Ok(stake)
+    }
+
+    /// Return an arbitrator's whole stake once none of their disputes are pending
+    pub fn unstake_arbitrator(env: &Env, arbitrator: &Address) -> Result<i128, SettlementError> {
+        let stake = Self::get_arbitrator_stake(env, arbitrator);
+        if stake == 0 {
+            return Err(SettlementError::InsufficientFunds);
+        }
+
+        if DisputeStore::has_unresolved_for_arbitrator(env, arbitrator) {
+            return Err(SettlementError::InvalidState);
+        }
+
+        let asset = Self::get_stake_asset(env).ok_or(SettlementError::InvalidState)?;
+        env.storage().persistent().remove(&DataKey::ArbitratorStake(arbitrator.clone()));
+        asset_utils::transfer_tokens(&asset.contract, &env.current_contract_address(), arbitrator, stake, env)?;
+        Ok(stake)
+    }
+
+    /// Get the tokens an arbitrator has bonded
+    pub fn get_arbitrator_stake(env: &Env, arbitrator: &Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ArbitratorStake(arbitrator.clone()))
+            .unwrap_or(0)
+    }
+
+    /// Get the slashed stake waiting to be withdrawn
+    pub fn get_penalty_pool_balance(env: &Env) -> i128 {
+        env.storage().instance().get(&DataKey::PenaltyPool).unwrap_or(0)
+    }
+
+    /// Pay the penalty pool out to `recipient`, returning the amount sent
+    pub fn withdraw_penalty_pool(env: &Env, recipient: &Address) -> Result<i128, SettlementError> {
+        let balance = Self::get_penalty_pool_balance(env);
+        if balance == 0 {
+            return Err(SettlementError::InsufficientFunds);
+        }
+
+        let asset = Self::get_stake_asset(env).ok_or(SettlementError::InvalidState)?;
+        env.storage().instance().set(&DataKey::PenaltyPool, &0i128);
+        asset_utils::transfer_tokens(&asset.contract, &env.current_contract_address(), recipient, balance, env)?;
+        Ok(balance)
+    }
+
     /// Update arbitrator reputation
     pub fn update_arbitrator_reputation(
         env: &Env,
@@ -285,6 +576,42 @@ impl DisputeResolutionManager {
         Ok(())
     }
 
+    /// Get an arbitrator's dispute record
+    pub fn get_arbitrator_stats(env: &Env, arbitrator: &Address) -> Result<ArbitratorStats, SettlementError> {
+        let arbitrators: Map<Address, Arbitrator> = env
+            .storage()
+            .instance()
+            .get(&ARBITRATORS)
+            .unwrap_or(Map::new(env));
+
+        let arb = arbitrators.get(arbitrator.clone()).ok_or(SettlementError::NotFound)?;
+        Ok(Self::stats_for(&arb))
+    }
+
+    /// Get up to `n` arbitrators with the most correct votes, best first
+    pub fn get_top_arbitrators(env: &Env, n: u32) -> Result<Vec<(Address, ArbitratorStats)>, SettlementError> {
+        let mut ranked: Vec<(Address, ArbitratorStats)> = Vec::new(env);
+
+        // Insertion sort; equal records keep their storage order
+        for arb in Self::get_all_arbitrators(env)?.iter() {
+            let stats = Self::stats_for(&arb);
+            let mut index = ranked.len();
+            while index > 0 {
+                let (_, previous) = ranked.get(index - 1).ok_or(SettlementError::NotFound)?;
+                if previous.total_correct >= stats.total_correct {
+                    break;
+                }
+                index -= 1;
+            }
+            ranked.insert(index, (arb.address.clone(), stats));
+        }
+
+        while ranked.len() > n {
+            ranked.pop_back();
+        }
+        Ok(ranked)
+    }
+
     /// Get dispute configuration
     pub fn get_dispute_config(env: &Env) -> Result<DisputeConfig, SettlementError> {
         env.storage()
@@ -299,46 +626,100 @@ impl DisputeResolutionManager {
         config: &DisputeConfig,
         _admin: &Address
     ) -> Result<(), SettlementError> {
+        Self::validate_dispute_config(config)?;
+
         // Check admin permissions
         env.storage().instance().set(&DISPUTE_CONFIG, config);
         Ok(())
     }
 
-    /// Internal: Try to resolve dispute if enough votes
-    fn try_resolve_dispute(env: &Env, dispute: &mut Dispute) -> Result<(), SettlementError> {
-        let total_votes = dispute.votes.len();
-        if (total_votes as u64) < dispute.required_votes {
-            return Ok(());
+    /// Validate dispute configuration
+    pub fn validate_dispute_config(config: &DisputeConfig) -> Result<(), SettlementError> {
+        // Timed-out disputes either protect the buyer or unwind the transaction
+        if !matches!(
+            config.auto_resolution_default,
+            DISPUTE_RESOLUTION_REFUND_BUYER | DISPUTE_RESOLUTION_CANCEL_TRANSACTION
+        ) {
+            return Err(SettlementError::InvalidAmount);
+        }
+
+        // A dispute can't be decided without voters
+        if config.arbitration_quorum == 0 {
+            return Err(SettlementError::InvalidAmount);
         }
 
-        let mut votes_for_initiator = 0u64;
-        for (_, vote_value) in dispute.votes.iter() {
+        Ok(())
+    }
+
+    /// Tally a dispute's votes with each arbitrator weighted by reputation
+    ///
+    /// Every vote counts for the arbitrator's reputation score (at least one point). The
+    /// dispute is decided once the reputation that has voted reaches `required_votes`
+    /// times the average reputation of the assigned arbitrators.
+    pub fn calculate_weighted_outcome(env: &Env, dispute: &Dispute) -> Result<WeightedVoteResult, SettlementError> {
+        let mut result = WeightedVoteResult {
+            votes_for: 0,
+            votes_against: 0,
+            weighted_for: 0,
+            weighted_against: 0,
+            resolved: false,
+        };
+
+        for (arbitrator, vote_value) in dispute.votes.iter() {
+            let weight = Self::vote_weight(env, &arbitrator)?;
             if vote_value == 1 {
-                votes_for_initiator += 1;
+                result.votes_for += 1;
+                result.weighted_for = result.weighted_for.saturating_add(weight);
+            } else {
+                result.votes_against += 1;
+                result.weighted_against = result.weighted_against.saturating_add(weight);
             }
         }
 
-        // Simple majority wins
-        let resolution = if votes_for_initiator > (total_votes as u64) / 2 {
+        if dispute.arbitrators.is_empty() {
+            return Ok(result);
+        }
+
+        let mut total_reputation = 0u64;
+        for arbitrator in dispute.arbitrators.iter() {
+            total_reputation = total_reputation.saturating_add(Self::vote_weight(env, &arbitrator)?);
+        }
+        let average_reputation = total_reputation / dispute.arbitrators.len() as u64;
+        let threshold = dispute.required_votes.saturating_mul(average_reputation);
+
+        result.resolved = !dispute.votes.is_empty()
+            && result.weighted_for.saturating_add(result.weighted_against) >= threshold;
+        Ok(result)
+    }
+
+    /// Internal: Try to resolve dispute once enough reputation has voted
+    fn try_resolve_dispute(env: &Env, dispute: &mut Dispute) -> Result<(), SettlementError> {
+        let outcome = Self::calculate_weighted_outcome(env, dispute)?;
+        if !outcome.resolved {
+            return Ok(());
+        }
+
+        // Weighted majority wins; a vote for the initiator backs whichever side opened the dispute
+        let initiator_won = outcome.weighted_for > outcome.weighted_against;
+        let resolution = if initiator_won != Self::initiated_by_seller(env, dispute)? {
             DISPUTE_RESOLUTION_REFUND_BUYER
         } else {
             DISPUTE_RESOLUTION_RELEASE_TO_SELLER
         };
 
-        dispute.resolution = resolution;
-        dispute.resolved_at = env.ledger().timestamp();
+        Self::mark_resolved(env, dispute, resolution)?;
 
         DisputeStore::update(env, dispute)?;
 
         // Update arbitrator reputations
-        Self::update_arbitrator_reputations(env, dispute, true)?;
+        Self::update_arbitrator_reputations(env, &dispute.arbitrators, true)?;
 
         // Emit resolution event
         let event = DisputeResolvedEvent {
             dispute_id: dispute.dispute_id,
             resolution,
-            winning_votes: votes_for_initiator,
-            total_votes: total_votes as u64,
+            winning_votes: outcome.votes_for,
+            total_votes: outcome.votes_for + outcome.votes_against,
             timestamp: dispute.resolved_at,
         };
         emit_dispute_resolved(env, event);
@@ -346,8 +727,92 @@ impl DisputeResolutionManager {
         Ok(())
     }
 
-    /// Internal: Select arbitrators for a dispute
-    fn select_arbitrators(env: &Env, config: &DisputeConfig) -> Result<Vec<Address>, SettlementError> {
+    /// The party a dispute was raised against
+    pub fn respondent(env: &Env, dispute: &Dispute) -> Result<Address, SettlementError> {
+        let (buyer, seller) = Self::parties(env, dispute.transaction_id, dispute.auction_id)?;
+        if dispute.initiator == seller {
+            buyer.ok_or(SettlementError::InvalidState)
+        } else {
+            Ok(seller)
+        }
+    }
+
+    /// Internal: Whether the seller, rather than the buyer, opened a dispute
+    fn initiated_by_seller(env: &Env, dispute: &Dispute) -> Result<bool, SettlementError> {
+        let (_, seller) = Self::parties(env, dispute.transaction_id, dispute.auction_id)?;
+        Ok(dispute.initiator == seller)
+    }
+
+    /// Internal: The buyer, if there is one yet, and the seller of a sale or auction. An
+    /// auction's buyer is its leading bidder.
+    fn parties(
+        env: &Env,
+        transaction_id: u64,
+        auction_id: Option<u64>
+    ) -> Result<(Option<Address>, Address), SettlementError> {
+        match auction_id {
+            Some(auction_id) => {
+                let auction = AuctionStore::get(env, auction_id)?;
+                Ok((auction.highest_bidder, auction.seller))
+            }
+            None => {
+                let sale = SaleTransactionStore::get(env, transaction_id)?;
+                Ok((sale.buyer, sale.seller))
+            }
+        }
+    }
+
+    /// Internal: Record a ruling, opening the appeal window unless this ruling decides an appeal
+    fn mark_resolved(env: &Env, dispute: &mut Dispute, resolution: u64) -> Result<(), SettlementError> {
+        dispute.resolution = resolution;
+        dispute.resolved_at = env.ledger().timestamp();
+        if dispute.appeal_filed_by.is_none() {
+            dispute.appeal_deadline = dispute.resolved_at.saturating_add(APPEAL_PERIOD);
+        }
+
+        // Credit the arbitrators who voted for the side that won
+        let buyer_won = match resolution {
+            DISPUTE_RESOLUTION_REFUND_BUYER => true,
+            DISPUTE_RESOLUTION_RELEASE_TO_SELLER => false,
+            _ => return Ok(()),
+        };
+        let winning_vote = (buyer_won != Self::initiated_by_seller(env, dispute)?) as u64;
+        for (arbitrator, vote) in dispute.votes.iter() {
+            if vote == winning_vote {
+                let mut arb = Self::get_arbitrator(env, &arbitrator)?;
+                arb.correct_votes += 1;
+                Self::store_arbitrator(env, &arb)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Internal: When the current panel was assigned; an appeal panel's clock starts at the appeal
+    fn voting_started_at(dispute: &Dispute) -> u64 {
+        if dispute.appeal_filed_by.is_some() {
+            dispute.appeal_deadline
+        } else {
+            dispute.created_at
+        }
+    }
+
+    /// Internal: Count a dispute against each arbitrator put on its panel
+    fn record_assignment(env: &Env, panel: &Vec<Address>) -> Result<(), SettlementError> {
+        for arbitrator in panel.iter() {
+            let mut arb = Self::get_arbitrator(env, &arbitrator)?;
+            arb.disputes_assigned += 1;
+            Self::store_arbitrator(env, &arb)?;
+        }
+        Ok(())
+    }
+
+    /// Internal: Select arbitrators for a dispute, skipping any in `excluded`
+    fn select_arbitrators(
+        env: &Env,
+        config: &DisputeConfig,
+        min_reputation: u64,
+        excluded: &Vec<Address>
+    ) -> Result<Vec<Address>, SettlementError> {
         let all_arbitrators = Self::get_all_arbitrators(env)?;
 
         if all_arbitrators.is_empty() {
@@ -358,7 +823,11 @@ impl DisputeResolutionManager {
         let mut selected = Vec::new(env);
 
         for arb in all_arbitrators.iter() {
-            if arb.is_active == 1 && arb.reputation_score >= config.min_arbitrator_reputation {
+            if arb.is_active == 1
+                && arb.reputation_score >= min_reputation
+                && !excluded.contains(arb.address.clone())
+                && Self::get_arbitrator_stake(env, &arb.address) >= config.min_arbitrator_stake
+            {
                 selected.push_back(arb.address.clone());
                 if selected.len() as u64 >= config.max_arbitrators_per_dispute {
                     break;
@@ -372,23 +841,23 @@ impl DisputeResolutionManager {
     /// Internal: Update arbitrator reputations after dispute resolution
     fn update_arbitrator_reputations(
         env: &Env,
-        dispute: &Dispute,
+        arbitrators: &Vec<Address>,
         successful_resolution: bool
     ) -> Result<(), SettlementError> {
-        for arbitrator in dispute.arbitrators.iter() {
+        for arbitrator in arbitrators.iter() {
             let mut arb = Self::get_arbitrator(env, &arbitrator)?;
             arb.disputes_handled += 1;
 
             if successful_resolution {
                 arb.successful_resolutions += 1;
+            } else {
+                Self::slash_stake(env, &arbitrator)?;
             }
 
             // Update reputation based on participation and success rate
-            let success_rate = if arb.disputes_handled > 0 {
-                (arb.successful_resolutions * 100) / arb.disputes_handled
-            } else {
-                100
-            };
+            let success_rate = (arb.successful_resolutions * 100)
+                .checked_div(arb.disputes_handled)
+                .unwrap_or(100);
 
             arb.reputation_score = success_rate;
             Self::store_arbitrator(env, &arb)?;
@@ -397,34 +866,135 @@ impl DisputeResolutionManager {
         Ok(())
     }
 
-    /// Internal: Execute refund to buyer
-    fn execute_refund_buyer(_env: &Env, _dispute: &Dispute) -> Result<(), SettlementError> {
-        // Implementation would release escrow funds back to buyer
-        // This is a placeholder
+    /// Internal: Move a share of an arbitrator's stake into the penalty pool
+    fn slash_stake(env: &Env, arbitrator: &Address) -> Result<(), SettlementError> {
+        let stake = Self::get_arbitrator_stake(env, arbitrator);
+        let slashed = math_utils::calculate_percentage(stake, ARBITRATOR_SLASH_BPS, env)?;
+        if slashed == 0 {
+            return Ok(());
+        }
+
+        let remaining_stake = math_utils::safe_sub(stake, slashed, env)?;
+        env.storage().persistent().set(&DataKey::ArbitratorStake(arbitrator.clone()), &remaining_stake);
+        let pool = math_utils::safe_add(Self::get_penalty_pool_balance(env), slashed, env)?;
+        env.storage().instance().set(&DataKey::PenaltyPool, &pool);
+
+        let event = ArbitratorSlashedEvent {
+            arbitrator: arbitrator.clone(),
+            amount: slashed,
+            remaining_stake,
+            timestamp: env.ledger().timestamp(),
+        };
+        emit_arbitrator_slashed(env, event);
+
         Ok(())
     }
 
+    /// Internal: Asset arbitrator stakes are held in
+    fn get_stake_asset(env: &Env) -> Option<Asset> {
+        env.storage().instance().get(&DataKey::ArbitratorStakeAsset)
+    }
+
+    /// Internal: Execute refund to buyer
+    fn execute_refund_buyer(env: &Env, dispute: &Dispute) -> Result<(), SettlementError> {
+        let escrow = Self::take_disputed_escrow(env, dispute, TransactionState::Resolved)?;
+        Self::pay_out_escrow(env, dispute, &escrow, escrow.amount, 0)
+    }
+
     /// Internal: Execute release to seller
-    fn execute_release_to_seller(_env: &Env, _dispute: &Dispute) -> Result<(), SettlementError> {
-        // Implementation would release escrow funds to seller
-        // This is a placeholder
-        Ok(())
+    fn execute_release_to_seller(env: &Env, dispute: &Dispute) -> Result<(), SettlementError> {
+        let escrow = Self::take_disputed_escrow(env, dispute, TransactionState::Resolved)?;
+        Self::pay_out_escrow(env, dispute, &escrow, 0, escrow.amount)
     }
 
     /// Internal: Execute fund split
-    fn execute_split_funds(_env: &Env, _dispute: &Dispute) -> Result<(), SettlementError> {
-        // Implementation would split escrow funds between parties
-        // This is a placeholder
-        Ok(())
+    fn execute_split_funds(env: &Env, dispute: &Dispute) -> Result<(), SettlementError> {
+        let escrow = Self::take_disputed_escrow(env, dispute, TransactionState::Resolved)?;
+
+        // Any odd unit goes to the seller
+        let buyer_amount = math_utils::safe_div(escrow.amount, 2, env)?;
+        let seller_amount = math_utils::safe_sub(escrow.amount, buyer_amount, env)?;
+        Self::pay_out_escrow(env, dispute, &escrow, buyer_amount, seller_amount)
+    }
+
+    /// Internal: Close the disputed sale or auction in `final_state` and claim its escrowed payment
+    fn take_disputed_escrow(
+        env: &Env,
+        dispute: &Dispute,
+        final_state: TransactionState
+    ) -> Result<DisputedEscrow, SettlementError> {
+        if let Some(auction_id) = dispute.auction_id {
+            let mut auction = AuctionStore::get(env, auction_id)?;
+            if matches!(auction.state, TransactionState::Resolved | TransactionState::Cancelled) {
+                return Err(SettlementError::InvalidState);
+            }
+            let bidder = auction.highest_bidder.clone().ok_or(SettlementError::InvalidState)?;
+
+            // The leading bid leaves the bidder's escrow so it can't also be withdrawn
+            BidEscrowStore::release(env, auction_id, &bidder, auction.highest_bid)?;
+            auction.state = final_state;
+            AuctionStore::update(env, &auction)?;
+
+            return Ok(DisputedEscrow {
+                buyer: bidder,
+                seller: auction.seller,
+                amount: auction.highest_bid,
+                currency: auction.currency,
+            });
+        }
+
+        let mut sale = SaleTransactionStore::get(env, dispute.transaction_id)?;
+
+        // Only a funded sale still holds the buyer's payment
+        if !matches!(sale.state, TransactionState::Funded | TransactionState::Disputed) {
+            return Err(SettlementError::InvalidState);
+        }
+        let buyer = sale.buyer.clone().ok_or(SettlementError::InvalidState)?;
+        SaleEscrowStore::take(env, dispute.transaction_id);
+        sale.state = final_state;
+        SaleTransactionStore::update(env, &sale)?;
+
+        Ok(DisputedEscrow {
+            buyer,
+            seller: sale.seller,
+            amount: sale.price,
+            currency: sale.currency,
+        })
     }
 
-    /// Internal: Execute transaction cancellation
-    fn execute_cancel_transaction(_env: &Env, _dispute: &Dispute) -> Result<(), SettlementError> {
-        // Implementation would cancel the transaction and refund all parties
-        // This is a placeholder
+    /// Internal: Send the escrowed payment to the recorded buyer and the seller
+    fn pay_out_escrow(
+        env: &Env,
+        dispute: &Dispute,
+        escrow: &DisputedEscrow,
+        buyer_amount: i128,
+        seller_amount: i128
+    ) -> Result<(), SettlementError> {
+        let contract = env.current_contract_address();
+        asset_utils::transfer_tokens(&escrow.currency.contract, &contract, &escrow.buyer, buyer_amount, env)?;
+        asset_utils::transfer_tokens(&escrow.currency.contract, &contract, &escrow.seller, seller_amount, env)?;
+
+        let event = DisputeExecutedEvent {
+            dispute_id: dispute.dispute_id,
+            transaction_id: dispute.transaction_id,
+            resolution: dispute.resolution,
+            buyer: escrow.buyer.clone(),
+            buyer_amount,
+            seller: escrow.seller.clone(),
+            seller_amount,
+            timestamp: env.ledger().timestamp(),
+        };
+        emit_dispute_executed(env, event);
+
         Ok(())
     }
 
+    /// Internal: Execute transaction cancellation, returning the full payment to the buyer
+    fn execute_cancel_transaction(env: &Env, dispute: &Dispute) -> Result<(), SettlementError> {
+        let escrow = Self::take_disputed_escrow(env, dispute, TransactionState::Cancelled)?;
+        Self::pay_out_escrow(env, dispute, &escrow, escrow.amount, 0)
+    }
+
     /// Internal: Get all arbitrators
     fn get_all_arbitrators(env: &Env) -> Result<Vec<Arbitrator>, SettlementError> {
         let arbitrators: Map<Address, Arbitrator> = env
@@ -441,6 +1011,23 @@ impl DisputeResolutionManager {
         Ok(result)
     }
 
+    /// Internal: Summarize an arbitrator's record
+    fn stats_for(arb: &Arbitrator) -> ArbitratorStats {
+        ArbitratorStats {
+            total_assigned: arb.disputes_assigned,
+            total_voted: arb.votes_cast,
+            total_correct: arb.correct_votes,
+            total_appealed: arb.rulings_appealed,
+            average_resolution_time_secs: arb.total_vote_time.checked_div(arb.votes_cast).unwrap_or(0),
+            last_active: arb.last_active,
+        }
+    }
+
+    /// Internal: Voting weight of an arbitrator
+    fn vote_weight(env: &Env, address: &Address) -> Result<u64, SettlementError> {
+        Ok(Self::get_arbitrator(env, address)?.reputation_score.max(1))
+    }
+
     /// Internal: Get arbitrator by address
     fn get_arbitrator(env: &Env, address: &Address) -> Result<Arbitrator, SettlementError> {
         let arbitrators: Map<Address, Arbitrator> = env
@@ -458,6 +1045,12 @@ impl DisputeResolutionManager {
                 successful_resolutions: 0,
                 is_active: 1, // Active by default
                 registered_at: env.ledger().timestamp(),
+                disputes_assigned: 0,
+                votes_cast: 0,
+                correct_votes: 0,
+                rulings_appealed: 0,
+                total_vote_time: 0,
+                last_active: 0,
             }))
     }
 
@@ -485,6 +1078,9 @@ impl Default for DisputeConfig {
             evidence_submission_period: 604800, // 7 days
             max_arbitrators_per_dispute: 5,
             min_arbitrator_reputation: 50,
+            min_arbitrator_stake: 1,
+            voting_period: 604800, // 7 days
+            auto_resolution_default: DISPUTE_RESOLUTION_REFUND_BUYER, // Buyer protection
         }
     }
 }
@@ -504,10 +1100,71 @@ impl DisputeEvidenceManager {
         Ok(())
     }
 
-    /// Get evidence for a dispute
-    pub fn get_evidence(env: &Env, _dispute_id: u64) -> Result<Vec<Bytes>, SettlementError> {
-        // Placeholder
-        Ok(Vec::new(env))
+    /// Get the hash-verified evidence data submitted to a dispute, oldest first
+    pub fn get_evidence(env: &Env, dispute_id: u64) -> Result<Vec<Bytes>, SettlementError> {
+        let mut data = Vec::new(env);
+        for record in Self::get_evidence_records(env, dispute_id).iter() {
+            data.push_back(record.data);
+        }
+        Ok(data)
+    }
+
+    /// Store evidence from either party after checking it hashes to `claimed_hash`
+    pub fn submit_evidence_with_hash(
+        env: &Env,
+        dispute_id: u64,
+        evidence_bytes: &Bytes,
+        claimed_hash: &BytesN<32>,
+        submitter: &Address
+    ) -> Result<(), SettlementError> {
+        let dispute = DisputeStore::get(env, dispute_id)?;
+        if *submitter != dispute.initiator && *submitter != DisputeResolutionManager::respondent(env, &dispute)? {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        let config = DisputeResolutionManager::get_dispute_config(env)?;
+        if env.ledger().timestamp() > dispute.created_at + config.evidence_submission_period {
+            return Err(SettlementError::Expired);
+        }
+
+        // Basic validation - check size limits
+        if evidence_bytes.len() > 10000 { // 10KB limit
+            return Err(SettlementError::InvalidAmount);
+        }
+
+        let hash: BytesN<32> = env.crypto().sha256(evidence_bytes).into();
+        if hash != *claimed_hash {
+            return Err(SettlementError::InvalidSignature);
+        }
+
+        let mut records = Self::get_evidence_records(env, dispute_id);
+        let submitted = records.iter().filter(|record| record.submitter == *submitter).count() as u32;
+        if submitted >= MAX_EVIDENCE_PER_PARTY {
+            return Err(SettlementError::InvalidAmount);
+        }
+
+        records.push_back(EvidenceRecord {
+            submitter: submitter.clone(),
+            hash,
+            data: evidence_bytes.clone(),
+            submitted_at: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&DataKey::DisputeEvidence(dispute_id), &records);
+        Ok(())
+    }
+
+    /// Get the hash-verified evidence submitted to a dispute, oldest first
+    pub fn get_evidence_records(env: &Env, dispute_id: u64) -> Vec<EvidenceRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DisputeEvidence(dispute_id))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Get the (initiator, respondent) evidence URIs submitted to a dispute
+    pub fn get_all_evidence(env: &Env, dispute_id: u64) -> Result<(Vec<Bytes>, Vec<Bytes>), SettlementError> {
+        let dispute = DisputeStore::get(env, dispute_id)?;
+        Ok((dispute.initiator_evidence, dispute.respondent_evidence))
     }
 
     /// Validate evidence format
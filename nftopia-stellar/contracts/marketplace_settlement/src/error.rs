@@ -53,6 +53,9 @@ pub enum SettlementError {
     FrontRunningDetected = 601,
     InvalidSignature = 602,
     CooldownActive = 603,
+    AddressBanned = 604,
+    CircuitBreakerTripped = 605,
+    EmergencyPaused = 606,
 
     // Fee errors
     FeeCalculationFailed = 700,
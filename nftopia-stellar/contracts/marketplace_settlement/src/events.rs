@@ -37,6 +37,88 @@ pub struct SaleCancelledEvent {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SaleFundedEvent {
+    pub transaction_id: u64,
+    pub buyer: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+// Offer Events
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OfferCreatedEvent {
+    pub offer_id: u64,
+    pub buyer: Address,
+    pub nft_address: Address,
+    pub token_id: u64,
+    pub amount: i128,
+    pub currency: Asset,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OfferAcceptedEvent {
+    pub offer_id: u64,
+    pub seller: Address,
+    pub buyer: Address,
+    pub nft_address: Address,
+    pub token_id: u64,
+    pub amount: i128,
+    pub platform_fee: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OfferCancelledEvent {
+    pub offer_id: u64,
+    pub buyer: Address,
+    pub reason: Bytes, // "cancelled" or "expired"
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollectionOfferCreatedEvent {
+    pub offer_id: u64,
+    pub buyer: Address,
+    pub nft_address: Address,
+    pub amount: i128,
+    pub currency: Asset,
+    pub max_quantity: u32,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollectionOfferFilledEvent {
+    pub offer_id: u64,
+    pub seller: Address,
+    pub buyer: Address,
+    pub nft_address: Address,
+    pub token_id: u64,
+    pub amount: i128,
+    pub platform_fee: i128,
+    pub remaining_quantity: u32,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollectionOfferCancelledEvent {
+    pub offer_id: u64,
+    pub buyer: Address,
+    pub refunded_amount: i128,
+    pub reason: Bytes, // "cancelled", "expired" or "filled"
+    pub timestamp: u64,
+}
+
 // Auction Events
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -78,7 +160,63 @@ pub struct AuctionEndedEvent {
     pub auction_id: u64,
     pub winner: Option<Address>,
     pub final_price: i128,
-    pub reason: Bytes, // "ended", "cancelled", "reserve_not_met"
+    pub reason: Bytes, // "ended", "cancelled", "reserve_not_met", "buy_now"
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VickreyAuctionSettledEvent {
+    pub auction_id: u64,
+    pub winner: Option<Address>,
+    pub winning_bid: i128,
+    pub payment_amount: i128, // Second-highest revealed bid (or starting price)
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProxyBidActivatedEvent {
+    pub auction_id: u64,
+    pub bidder: Address,
+    pub amount: i128,
+    pub max_amount: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionPausedEvent {
+    pub auction_id: u64,
+    pub seller: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionResumedEvent {
+    pub auction_id: u64,
+    pub seller: Address,
+    pub paused_duration: u64,
+    pub new_end_time: u64,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionOpenedEvent {
+    pub auction_id: u64,
+    pub seller: Address,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevealExpiredEvent {
+    pub auction_id: u64,
+    pub bidder: Address, // Commitment forfeited for not being revealed in time
     pub timestamp: u64,
 }
 
@@ -109,6 +247,17 @@ pub struct TradeAcceptedEvent {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TradeCounterOfferEvent {
+    pub trade_id: u64,
+    pub countered_by: Address,
+    pub round: u32,
+    pub top_up_amount: i128,
+    pub counter_expires_at: u64,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TradeExecutedEvent {
@@ -116,6 +265,14 @@ pub struct TradeExecutedEvent {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MultiTradeExecutedEvent {
+    pub trade_id: u64,
+    pub parties: Vec<Address>,
+    pub timestamp: u64,
+}
+
 // Bundle Events
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -129,11 +286,24 @@ pub struct BundleCreatedEvent {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BundleAuctionEndedEvent {
+    pub auction_id: u64,
+    pub winner: Option<Address>,
+    pub final_price: i128,
+    pub item_count: u32,
+    pub reason: Bytes, // "ended", "reserve_not_met"
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BundleExecutedEvent {
     pub bundle_id: u64,
-    pub buyer: Address,
+    pub buyer: Address,              // buyer who completed the bundle
+    pub sold_individually: Vec<u64>, // token IDs bought one at a time
+    pub sold_in_bulk: Vec<u64>,      // token IDs bought together in the final purchase
     pub timestamp: u64,
 }
 
@@ -161,6 +331,16 @@ pub struct PlatformFeesCollectedEvent {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralFeeDistributedEvent {
+    pub referrer: Address,
+    pub currency: Asset,
+    pub referral_amount: i128,
+    pub platform_amount: i128,
+    pub timestamp: u64,
+}
+
 // Dispute Events
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -193,6 +373,38 @@ pub struct DisputeResolvedEvent {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeExecutedEvent {
+    pub dispute_id: u64,
+    pub transaction_id: u64,
+    pub resolution: u64,
+    pub buyer: Address,
+    pub buyer_amount: i128,
+    pub seller: Address,
+    pub seller_amount: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeAppealedEvent {
+    pub dispute_id: u64,
+    pub appellant: Address,
+    pub overturned_resolution: u64,
+    pub arbitrators: Vec<Address>,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArbitratorSlashedEvent {
+    pub arbitrator: Address,
+    pub amount: i128,
+    pub remaining_stake: i128,
+    pub timestamp: u64,
+}
+
 // Security Events
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -210,6 +422,53 @@ pub struct FrontRunningDetectedEvent {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddressBannedEvent {
+    pub address: Address,
+    pub admin: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddressUnbannedEvent {
+    pub address: Address,
+    pub admin: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CircuitBreakerTrippedEvent {
+    pub transaction_id: u64,
+    pub amount: i128,
+    pub threshold: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CircuitBreakerResetEvent {
+    pub admin: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyPauseEvent {
+    pub admin: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyUnpauseEvent {
+    pub admin: Address,
+    pub paused_at: u64,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EmergencyWithdrawalEvent {
@@ -228,6 +487,99 @@ pub struct FeeConfigUpdatedEvent {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeesAutoSwappedEvent {
+    pub asset_in: Asset,
+    pub amount_in: i128,
+    pub protocol_token: Asset,
+    pub amount_out: i128,
+    pub dex: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PromotionActivatedEvent {
+    pub discount_bps: u64,
+    pub starts_at: u64,
+    pub ends_at: u64,
+    pub eligible_collections: Option<Vec<Address>>,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserTierUpdatedEvent {
+    pub user: Address,
+    pub previous_tier: Option<u32>,
+    pub new_tier: Option<u32>, // None once the user no longer qualifies for any tier
+    pub volume: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpiredSalesCleanedEvent {
+    pub removed: u32,
+    pub cleaned_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketplaceInitializedEvent {
+    pub admin: Address,
+    pub platform_fee_bps: u64,
+    pub fee_recipient: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminConfigProposedEvent {
+    pub new_config: AdminConfig,
+    pub proposed_by: Address,
+    pub executable_at: u64,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminConfigAppliedEvent {
+    pub new_config: AdminConfig,
+    pub applied_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceProposedEvent {
+    pub proposal_id: u64,
+    pub proposer: Address,
+    pub voting_deadline: u64,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceVotedEvent {
+    pub proposal_id: u64,
+    pub voter: Address,
+    pub in_favor: bool,
+    pub voting_power: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceExecutedEvent {
+    pub proposal_id: u64,
+    pub votes_for: i128,
+    pub votes_against: i128,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AdminConfigUpdatedEvent {
@@ -252,6 +604,41 @@ pub fn emit_sale_cancelled(env: &Env, event: SaleCancelledEvent) {
     env.events().publish(("MarketplaceSettlement", symbol_short!("sale_canc")), event);
 }
 
+#[allow(deprecated)]
+pub fn emit_sale_funded(env: &Env, event: SaleFundedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("sale_fund")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_offer_created(env: &Env, event: OfferCreatedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("offr_crtd")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_offer_accepted(env: &Env, event: OfferAcceptedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("offr_acpt")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_offer_cancelled(env: &Env, event: OfferCancelledEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("offr_canc")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_collection_offer_created(env: &Env, event: CollectionOfferCreatedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("coff_crtd")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_collection_offer_filled(env: &Env, event: CollectionOfferFilledEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("coff_fill")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_collection_offer_cancelled(env: &Env, event: CollectionOfferCancelledEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("coff_canc")), event);
+}
+
 #[allow(deprecated)]
 pub fn emit_auction_created(env: &Env, event: AuctionCreatedEvent) {
     env.events().publish(("MarketplaceSettlement", symbol_short!("auc_crtd")), event);
@@ -277,6 +664,36 @@ pub fn emit_auction_extended(env: &Env, event: AuctionExtendedEvent) {
     env.events().publish(("MarketplaceSettlement", symbol_short!("auc_extd")), event);
 }
 
+#[allow(deprecated)]
+pub fn emit_auction_paused(env: &Env, event: AuctionPausedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("auc_pause")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_auction_resumed(env: &Env, event: AuctionResumedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("auc_resum")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_auction_opened(env: &Env, event: AuctionOpenedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("auc_open")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_reveal_expired(env: &Env, event: RevealExpiredEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("rvl_exp")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_proxy_bid_activated(env: &Env, event: ProxyBidActivatedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("proxy_bid")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_vickrey_auction_settled(env: &Env, event: VickreyAuctionSettledEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("vick_stld")), event);
+}
+
 #[allow(deprecated)]
 pub fn emit_trade_created(env: &Env, event: TradeCreatedEvent) {
     env.events().publish(("MarketplaceSettlement", symbol_short!("trd_crtd")), event);
@@ -287,16 +704,31 @@ pub fn emit_trade_accepted(env: &Env, event: TradeAcceptedEvent) {
     env.events().publish(("MarketplaceSettlement", symbol_short!("trd_acc")), event);
 }
 
+#[allow(deprecated)]
+pub fn emit_trade_counter_offer(env: &Env, event: TradeCounterOfferEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("trd_cntr")), event);
+}
+
 #[allow(deprecated)]
 pub fn emit_trade_executed(env: &Env, event: TradeExecutedEvent) {
     env.events().publish(("MarketplaceSettlement", symbol_short!("trd_exec")), event);
 }
 
+#[allow(deprecated)]
+pub fn emit_multi_trade_executed(env: &Env, event: MultiTradeExecutedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("mtrd_exec")), event);
+}
+
 #[allow(deprecated)]
 pub fn emit_bundle_created(env: &Env, event: BundleCreatedEvent) {
     env.events().publish(("MarketplaceSettlement", symbol_short!("bndl_crtd")), event);
 }
 
+#[allow(deprecated)]
+pub fn emit_bundle_auction_ended(env: &Env, event: BundleAuctionEndedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("bndl_end")), event);
+}
+
 #[allow(deprecated)]
 pub fn emit_bundle_executed(env: &Env, event: BundleExecutedEvent) {
     env.events().publish(("MarketplaceSettlement", symbol_short!("bndl_exec")), event);
@@ -312,6 +744,11 @@ pub fn emit_platform_fees_collected(env: &Env, event: PlatformFeesCollectedEvent
     env.events().publish(("MarketplaceSettlement", symbol_short!("fee_coll")), event);
 }
 
+#[allow(deprecated)]
+pub fn emit_referral_fee_distributed(env: &Env, event: ReferralFeeDistributedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("ref_fee")), event);
+}
+
 #[allow(deprecated)]
 pub fn emit_dispute_created(env: &Env, event: DisputeCreatedEvent) {
     env.events().publish(("MarketplaceSettlement", symbol_short!("dsp_crtd")), event);
@@ -327,6 +764,21 @@ pub fn emit_dispute_resolved(env: &Env, event: DisputeResolvedEvent) {
     env.events().publish(("MarketplaceSettlement", symbol_short!("dsp_rslv")), event);
 }
 
+#[allow(deprecated)]
+pub fn emit_dispute_executed(env: &Env, event: DisputeExecutedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("dsp_exec")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_dispute_appealed(env: &Env, event: DisputeAppealedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("dsp_appl")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_arbitrator_slashed(env: &Env, event: ArbitratorSlashedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("arb_slash")), event);
+}
+
 #[allow(deprecated)]
 pub fn emit_reentrancy_detected(env: &Env, event: ReentrancyDetectedEvent) {
     env.events().publish(("MarketplaceSettlement", symbol_short!("reentr")), event);
@@ -337,6 +789,36 @@ pub fn emit_front_running_detected(env: &Env, event: FrontRunningDetectedEvent)
     env.events().publish(("MarketplaceSettlement", symbol_short!("frontrun")), event);
 }
 
+#[allow(deprecated)]
+pub fn emit_address_banned(env: &Env, event: AddressBannedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("addr_ban")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_address_unbanned(env: &Env, event: AddressUnbannedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("addr_unbn")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_circuit_breaker_tripped(env: &Env, event: CircuitBreakerTrippedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("cb_trip")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_circuit_breaker_reset(env: &Env, event: CircuitBreakerResetEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("cb_reset")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_emergency_pause(env: &Env, event: EmergencyPauseEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("emrg_paus")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_emergency_unpause(env: &Env, event: EmergencyUnpauseEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("emrg_unps")), event);
+}
+
 #[allow(deprecated)]
 pub fn emit_emergency_withdrawal(env: &Env, event: EmergencyWithdrawalEvent) {
     env.events().publish(("MarketplaceSettlement", symbol_short!("emerg_wd")), event);
@@ -347,6 +829,56 @@ pub fn emit_fee_config_updated(env: &Env, event: FeeConfigUpdatedEvent) {
     env.events().publish(("MarketplaceSettlement", symbol_short!("fee_upd")), event);
 }
 
+#[allow(deprecated)]
+pub fn emit_fees_auto_swapped(env: &Env, event: FeesAutoSwappedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("fee_swap")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_promotion_activated(env: &Env, event: PromotionActivatedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("promo_set")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_user_tier_updated(env: &Env, event: UserTierUpdatedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("tier_upd")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_expired_sales_cleaned(env: &Env, event: ExpiredSalesCleanedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("sale_cln")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_marketplace_initialized(env: &Env, event: MarketplaceInitializedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("mkt_init")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_admin_config_proposed(env: &Env, event: AdminConfigProposedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("cfg_prop")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_admin_config_applied(env: &Env, event: AdminConfigAppliedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("cfg_appl")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_governance_proposed(env: &Env, event: GovernanceProposedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("gov_prop")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_governance_voted(env: &Env, event: GovernanceVotedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("gov_vote")), event);
+}
+
+#[allow(deprecated)]
+pub fn emit_governance_executed(env: &Env, event: GovernanceExecutedEvent) {
+    env.events().publish(("MarketplaceSettlement", symbol_short!("gov_exec")), event);
+}
+
 #[allow(deprecated)]
 pub fn emit_admin_config_updated(env: &Env, event: AdminConfigUpdatedEvent) {
     env.events().publish(("MarketplaceSettlement", symbol_short!("admin_upd")), event);
@@ -1,8 +1,15 @@
-use soroban_sdk::{Env, Address, Map, Vec, Symbol, symbol_short};
+use soroban_sdk::{Env, Address, Map, Vec, Symbol, symbol_short, contracttype, vec};
 use crate::error::SettlementError;
-use crate::types::{FeeConfig, VolumeTier, Asset};
+use crate::types::{FeeConfig, VolumeTier, VipTier, PromotionalFeeOverride, Asset};
 use crate::utils::math_utils;
-use crate::events::{emit_platform_fees_collected, PlatformFeesCollectedEvent};
+use crate::storage::DataKey;
+use crate::utils::asset_utils;
+use crate::utils::dex_client::IDexClient;
+use crate::events::{
+    emit_platform_fees_collected, emit_referral_fee_distributed, emit_user_tier_updated, emit_promotion_activated,
+    emit_fees_auto_swapped,
+    PlatformFeesCollectedEvent, ReferralFeeDistributedEvent, UserTierUpdatedEvent, PromotionActivatedEvent, FeesAutoSwappedEvent
+};
 
 // Storage keys
 const FEE_CONFIG: Symbol = symbol_short!("fee_cfg");
@@ -14,18 +21,23 @@ pub struct FeeManager;
 
 impl FeeManager {
     /// Calculate fee for a transaction
+    ///
+    /// `nft_address` is the collection traded, which decides whether a promotion limited
+    /// to certain collections applies.
     pub fn calculate_fee(
         env: &Env,
         transaction_amount: i128,
-        user: &Address
+        user: &Address,
+        nft_address: Option<Address>
     ) -> Result<i128, SettlementError> {
         let fee_config = Self::get_fee_config(env)?;
+        let promotion_bps = Self::active_promotion_discount(env, nft_address.as_ref());
 
         if !fee_config.dynamic_fee_enabled {
             // Simple fee calculation
             return math_utils::calculate_fee(
                 transaction_amount,
-                fee_config.platform_fee_bps,
+                fee_config.platform_fee_bps.saturating_sub(promotion_bps),
                 fee_config.minimum_fee,
                 fee_config.maximum_fee,
                 env
@@ -33,25 +45,28 @@ impl FeeManager {
         }
 
         // Dynamic fee calculation based on user volume
-        Self::calculate_dynamic_fee(env, transaction_amount, user, &fee_config)
+        Self::calculate_dynamic_fee(env, transaction_amount, user, &fee_config, promotion_bps)
     }
 
-    /// Calculate dynamic fee based on user trading volume
+    /// Calculate dynamic fee based on user trading volume, less any promotional discount
     fn calculate_dynamic_fee(
         env: &Env,
         transaction_amount: i128,
         user: &Address,
-        fee_config: &FeeConfig
+        fee_config: &FeeConfig,
+        promotion_bps: u64
     ) -> Result<i128, SettlementError> {
         let user_volume = Self::get_user_volume(env, user)?;
-        let discount_bps: u64 = Self::calculate_volume_discount(user_volume, &fee_config.volume_discounts)?;
+        let volume_discount_bps = Self::calculate_volume_discount(user_volume, &fee_config.volume_discounts)?;
+
+        // A VIP tier's discount applies when it beats the plain volume discount
+        let tier_discount_bps = Self::matching_vip_tier(env, user_volume)
+            .map(|(_, tier)| tier.fee_discount_bps)
+            .unwrap_or(0);
+        let discount_bps = volume_discount_bps.max(tier_discount_bps).saturating_add(promotion_bps);
 
         // Apply discount to base fee
-        let discounted_fee_bps = if fee_config.platform_fee_bps > discount_bps {
-            fee_config.platform_fee_bps - discount_bps
-        } else {
-            0
-        };
+        let discounted_fee_bps = fee_config.platform_fee_bps.saturating_sub(discount_bps);
 
         // Check for VIP exemptions
         if fee_config.vip_exemptions.contains(user.clone()) {
@@ -68,12 +83,26 @@ impl FeeManager {
     }
 
     /// Collect platform fee
+    ///
+    /// When a referrer is given, `referral_fee_bps` of the fee is credited to them
+    /// and the rest accrues to the platform.
     pub fn collect_platform_fee(
         env: &Env,
         amount: i128,
         asset: &Asset,
-        collector: &Address
+        collector: &Address,
+        referrer: Option<Address>
     ) -> Result<(), SettlementError> {
+        let fee_config = Self::get_fee_config(env)?;
+
+        // Collectors can't refer themselves to claw back part of their own fee
+        let referrer = referrer.filter(|referrer| referrer != collector);
+        let referral_amount = match referrer {
+            Some(_) => math_utils::calculate_percentage(amount, fee_config.referral_fee_bps, env)?,
+            None => 0,
+        };
+        let platform_amount = math_utils::safe_sub(amount, referral_amount, env)?;
+
         // Add to accumulated fees
         let mut accumulated_fees: Map<Asset, i128> = env
             .storage()
@@ -82,26 +111,79 @@ impl FeeManager {
             .unwrap_or(Map::new(env));
 
         let current_amount = accumulated_fees.get(asset.clone()).unwrap_or(0);
-        let new_amount = math_utils::safe_add(current_amount, amount, env)?;
+        let new_amount = math_utils::safe_add(current_amount, platform_amount, env)?;
 
         accumulated_fees.set(asset.clone(), new_amount);
         env.storage().instance().set(&ACCUMULATED_FEES, &accumulated_fees);
 
+        // Once enough of another asset has built up, swap it into the protocol token
+        if fee_config.auto_swap_enabled
+            && *asset != fee_config.protocol_token
+            && new_amount > fee_config.swap_threshold
+        {
+            Self::auto_swap_fees(env, asset, &fee_config.protocol_token, new_amount);
+        }
+
         // Update user volume for dynamic fees
         Self::update_user_volume(env, collector, amount)?;
 
         // Emit fee collection event
         let event = PlatformFeesCollectedEvent {
-            amount,
+            amount: platform_amount,
             currency: asset.clone(),
             collector: collector.clone(),
             timestamp: env.ledger().timestamp(),
         };
         emit_platform_fees_collected(env, event);
 
+        if let Some(referrer) = referrer {
+            if referral_amount > 0 {
+                Self::credit_referrer(env, &referrer, asset, referral_amount)?;
+
+                let event = ReferralFeeDistributedEvent {
+                    referrer,
+                    currency: asset.clone(),
+                    referral_amount,
+                    platform_amount,
+                    timestamp: env.ledger().timestamp(),
+                };
+                emit_referral_fee_distributed(env, event);
+            }
+        }
+
         Ok(())
     }
 
+    /// Get a referrer's unwithdrawn earnings per asset
+    pub fn get_referral_earnings(env: &Env, referrer: &Address) -> Map<Asset, i128> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReferrerEarnings(referrer.clone()))
+            .unwrap_or(Map::new(env))
+    }
+
+    /// Pay out everything a referrer has earned, returning the amounts per asset
+    pub fn withdraw_referral_earnings(env: &Env, referrer: &Address) -> Result<Map<Asset, i128>, SettlementError> {
+        let earnings = Self::get_referral_earnings(env, referrer);
+        if earnings.is_empty() {
+            return Err(SettlementError::InsufficientFunds);
+        }
+
+        env.storage().persistent().remove(&DataKey::ReferrerEarnings(referrer.clone()));
+
+        for (asset, amount) in earnings.iter() {
+            asset_utils::transfer_tokens_checked(
+                &asset.contract,
+                &env.current_contract_address(),
+                referrer,
+                amount,
+                env
+            )?;
+        }
+
+        Ok(earnings)
+    }
+
     /// Withdraw accumulated platform fees
     pub fn withdraw_platform_fees(
         env: &Env,
@@ -129,7 +211,7 @@ impl FeeManager {
         }
 
         // Transfer fees to recipient
-        crate::utils::asset_utils::transfer_tokens(
+        crate::utils::asset_utils::transfer_tokens_checked(
             &asset.contract,
             &env.current_contract_address(),
             recipient,
@@ -144,6 +226,41 @@ impl FeeManager {
         Ok(amount)
     }
 
+    /// Withdraw accumulated fees for several assets to one recipient
+    ///
+    /// Each asset is withdrawn on its own: one that fails to transfer keeps its accrued
+    /// fees and is reported in `failed`, without undoing the others. Assets with nothing
+    /// accrued are reported as withdrawing zero.
+    pub fn withdraw_platform_fees_batch(
+        env: &Env,
+        assets: &Vec<Asset>,
+        recipient: &Address,
+        admin: &Address
+    ) -> Result<FeeWithdrawalSummary, SettlementError> {
+        if Self::get_fee_config(env)?.fee_recipient != *admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        let mut summary = FeeWithdrawalSummary {
+            withdrawn: Vec::new(env),
+            failed: Vec::new(env),
+        };
+        for asset in assets.iter() {
+            match Self::withdraw_platform_fees(env, &asset, recipient, admin) {
+                Ok(amount) => summary.withdrawn.push_back((asset, amount)),
+                Err(SettlementError::InsufficientFunds) if Self::get_accumulated_fees(env, &asset) <= 0 => {
+                    summary.withdrawn.push_back((asset, 0))
+                }
+                Err(error) => summary.failed.push_back(FailedWithdrawal {
+                    amount: Self::get_accumulated_fees(env, &asset),
+                    asset,
+                    error_code: error as u32,
+                }),
+            }
+        }
+        Ok(summary)
+    }
+
     /// Update fee configuration
     pub fn update_fee_config(
         env: &Env,
@@ -215,6 +332,194 @@ impl FeeManager {
         Ok(())
     }
 
+    /// Set the DEX router that accumulated fees are swapped through
+    pub fn set_swap_dex_address(env: &Env, dex_address: &Address) {
+        env.storage().instance().set(&DataKey::SwapDex, dex_address);
+    }
+
+    /// Get the DEX router that accumulated fees are swapped through, if any
+    pub fn get_swap_dex_address(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::SwapDex)
+    }
+
+    /// Stop swapping accumulated fees into the protocol token
+    pub fn disable_auto_swap(env: &Env, admin: &Address) -> Result<(), SettlementError> {
+        let mut fee_config = Self::get_fee_config(env)?;
+        fee_config.auto_swap_enabled = false;
+        Self::update_fee_config(env, &fee_config, admin)
+    }
+
+    /// Internal: swap `amount` of accumulated `asset` fees into the protocol token
+    ///
+    /// A sale shouldn't fail because the DEX does, so any failure leaves the fees where
+    /// they are. The balances are moved by what actually left and arrived.
+    fn auto_swap_fees(env: &Env, asset: &Asset, protocol_token: &Asset, amount: i128) {
+        let Some(dex) = Self::get_swap_dex_address(env) else {
+            return;
+        };
+
+        let this = env.current_contract_address();
+        let (Ok(in_before), Ok(out_before)) = (
+            asset_utils::get_token_balance(&asset.contract, &this, env),
+            asset_utils::get_token_balance(&protocol_token.contract, &this, env),
+        ) else {
+            return;
+        };
+
+        // Placeholder: no minimum output until the fee swap is given a price source
+        let path = vec![env, asset.contract.clone(), protocol_token.contract.clone()];
+        let swapped = IDexClient::new(env, &dex)
+            .swap_exact_tokens_for_tokens(amount, 0, &path, env.ledger().timestamp());
+        if swapped.is_err() {
+            return;
+        }
+
+        let (Ok(in_after), Ok(out_after)) = (
+            asset_utils::get_token_balance(&asset.contract, &this, env),
+            asset_utils::get_token_balance(&protocol_token.contract, &this, env),
+        ) else {
+            return;
+        };
+        let amount_in = in_before.saturating_sub(in_after);
+        let amount_out = out_after.saturating_sub(out_before);
+
+        let mut accumulated_fees: Map<Asset, i128> = env
+            .storage()
+            .instance()
+            .get(&ACCUMULATED_FEES)
+            .unwrap_or(Map::new(env));
+        let remaining = accumulated_fees.get(asset.clone()).unwrap_or(0).saturating_sub(amount_in);
+        let converted = accumulated_fees.get(protocol_token.clone()).unwrap_or(0).saturating_add(amount_out);
+        accumulated_fees.set(asset.clone(), remaining);
+        accumulated_fees.set(protocol_token.clone(), converted);
+        env.storage().instance().set(&ACCUMULATED_FEES, &accumulated_fees);
+
+        emit_fees_auto_swapped(env, FeesAutoSwappedEvent {
+            asset_in: asset.clone(),
+            amount_in,
+            protocol_token: protocol_token.clone(),
+            amount_out,
+            dex,
+            timestamp: env.ledger().timestamp(),
+        });
+    }
+
+    /// Schedule a promotional fee discount, replacing any existing promotion
+    pub fn set_promotion(env: &Env, promotion: &PromotionalFeeOverride) -> Result<(), SettlementError> {
+        if promotion.discount_bps > 10000 || promotion.starts_at >= promotion.ends_at {
+            return Err(SettlementError::InvalidAmount);
+        }
+        if promotion.ends_at <= env.ledger().timestamp() {
+            return Err(SettlementError::Expired);
+        }
+
+        env.storage().instance().set(&DataKey::PromotionalOverride, promotion);
+        emit_promotion_activated(env, PromotionActivatedEvent {
+            discount_bps: promotion.discount_bps,
+            starts_at: promotion.starts_at,
+            ends_at: promotion.ends_at,
+            eligible_collections: promotion.eligible_collections.clone(),
+            timestamp: env.ledger().timestamp(),
+        });
+        Ok(())
+    }
+
+    /// End the current promotion, if any
+    pub fn clear_promotion(env: &Env) {
+        env.storage().instance().remove(&DataKey::PromotionalOverride);
+    }
+
+    /// Get the scheduled or running promotion, if any
+    pub fn get_promotion(env: &Env) -> Option<PromotionalFeeOverride> {
+        env.storage().instance().get(&DataKey::PromotionalOverride)
+    }
+
+    /// Internal: the promotional discount for a trade in `nft_address` right now
+    ///
+    /// A promotion runs from `starts_at` up to, but not including, `ends_at`.
+    fn active_promotion_discount(env: &Env, nft_address: Option<&Address>) -> u64 {
+        let Some(promotion) = Self::get_promotion(env) else {
+            return 0;
+        };
+
+        let now = env.ledger().timestamp();
+        let running = promotion.starts_at <= now && now < promotion.ends_at;
+        let eligible = match (&promotion.eligible_collections, nft_address) {
+            (None, _) => true,
+            (Some(collections), Some(nft_address)) => collections.contains(nft_address),
+            (Some(_), None) => false,
+        };
+        if running && eligible {
+            promotion.discount_bps
+        } else {
+            0
+        }
+    }
+
+    /// Replace the VIP tiers
+    ///
+    /// Tiers must be ordered by strictly increasing minimum volume.
+    pub fn set_vip_tiers(env: &Env, tiers: &Vec<VipTier>) -> Result<(), SettlementError> {
+        for (i, tier) in tiers.iter().enumerate() {
+            if tier.min_volume < 0 || tier.fee_discount_bps > 10000 {
+                return Err(SettlementError::InvalidAmount);
+            }
+            if i > 0 && tiers.get(i as u32 - 1).is_some_and(|previous| previous.min_volume >= tier.min_volume) {
+                return Err(SettlementError::InvalidAmount);
+            }
+        }
+        env.storage().instance().set(&DataKey::VipTiers, tiers);
+        Ok(())
+    }
+
+    /// Get the VIP tiers, by increasing minimum volume
+    pub fn get_vip_tiers(env: &Env) -> Vec<VipTier> {
+        env.storage().instance().get(&DataKey::VipTiers).unwrap_or(Vec::new(env))
+    }
+
+    /// Get the index of the VIP tier a user holds, if any
+    pub fn get_user_tier(env: &Env, user: &Address) -> Option<u32> {
+        env.storage().persistent().get(&DataKey::UserTier(user.clone()))
+    }
+
+    /// Move a user into the highest VIP tier their current volume qualifies for
+    ///
+    /// Users drop out of their tier once their volume period lapses. Returns the tier now held.
+    pub fn evaluate_and_promote_user(env: &Env, user: &Address) -> Result<Option<u32>, SettlementError> {
+        let volume = Self::get_user_volume(env, user)?;
+        let new_tier = Self::matching_vip_tier(env, volume).map(|(index, _)| index);
+        let previous_tier = Self::get_user_tier(env, user);
+        if new_tier == previous_tier {
+            return Ok(new_tier);
+        }
+
+        let key = DataKey::UserTier(user.clone());
+        match new_tier {
+            Some(index) => env.storage().persistent().set(&key, &index),
+            None => env.storage().persistent().remove(&key),
+        }
+        emit_user_tier_updated(env, UserTierUpdatedEvent {
+            user: user.clone(),
+            previous_tier,
+            new_tier,
+            volume,
+            timestamp: env.ledger().timestamp(),
+        });
+        Ok(new_tier)
+    }
+
+    /// Internal: the highest VIP tier `volume` qualifies for, with its index
+    fn matching_vip_tier(env: &Env, volume: i128) -> Option<(u32, VipTier)> {
+        let tiers = Self::get_vip_tiers(env);
+        let mut matched = None;
+        for (i, tier) in tiers.iter().enumerate() {
+            if volume >= tier.min_volume {
+                matched = Some((i as u32, tier));
+            }
+        }
+        matched
+    }
+
     /// Get accumulated fees for an asset
     pub fn get_accumulated_fees(env: &Env, asset: &Asset) -> i128 {
         let accumulated_fees: Map<Asset, i128> = env
@@ -226,8 +531,12 @@ impl FeeManager {
         accumulated_fees.get(asset.clone()).unwrap_or(0)
     }
 
-    /// Get user trading volume
+    /// Get user trading volume for the current period
     pub fn get_user_volume(env: &Env, user: &Address) -> Result<i128, SettlementError> {
+        if Self::volume_period_lapsed(env, user)? {
+            return Ok(0);
+        }
+
         let user_volumes: Map<Address, i128> = env
             .storage()
             .instance()
@@ -237,6 +546,16 @@ impl FeeManager {
         Ok(user_volumes.get(user.clone()).unwrap_or(0))
     }
 
+    /// Internal: Add to a referrer's earnings
+    fn credit_referrer(env: &Env, referrer: &Address, asset: &Asset, amount: i128) -> Result<(), SettlementError> {
+        let mut earnings = Self::get_referral_earnings(env, referrer);
+        let balance = math_utils::safe_add(earnings.get(asset.clone()).unwrap_or(0), amount, env)?;
+
+        earnings.set(asset.clone(), balance);
+        env.storage().persistent().set(&DataKey::ReferrerEarnings(referrer.clone()), &earnings);
+        Ok(())
+    }
+
     /// Calculate volume-based discount
     fn calculate_volume_discount(volume: i128, tiers: &Vec<VolumeTier>) -> Result<u64, SettlementError> {
         for tier in tiers.iter() {
@@ -255,19 +574,46 @@ impl FeeManager {
             .get(&USER_VOLUMES)
             .unwrap_or(Map::new(env));
 
+        // Volume from an earlier period no longer counts toward discounts
+        let window_key = DataKey::VolumeWindowStart(user.clone());
+        let has_window = env.storage().persistent().has(&window_key);
+        if !has_window || Self::volume_period_lapsed(env, user)? {
+            user_volumes.set(user.clone(), 0);
+            env.storage().persistent().set(&window_key, &env.ledger().timestamp());
+        }
+
         let current_volume = user_volumes.get(user.clone()).unwrap_or(0);
         let new_volume = math_utils::safe_add(current_volume, amount, env)?;
 
         user_volumes.set(user.clone(), new_volume);
         env.storage().instance().set(&USER_VOLUMES, &user_volumes);
 
+        Self::evaluate_and_promote_user(env, user)?;
         Ok(())
     }
 
+    /// Internal: Check whether a user's volume period has run out
+    fn volume_period_lapsed(env: &Env, user: &Address) -> Result<bool, SettlementError> {
+        let period = Self::get_fee_config(env)?.volume_period_seconds;
+        if period == 0 {
+            return Ok(false);
+        }
+
+        let window_start: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VolumeWindowStart(user.clone()));
+
+        Ok(match window_start {
+            Some(start) => env.ledger().timestamp().saturating_sub(start) > period,
+            None => false,
+        })
+    }
+
     /// Validate fee configuration
-    fn validate_fee_config(config: &FeeConfig) -> Result<(), SettlementError> {
+    pub fn validate_fee_config(config: &FeeConfig) -> Result<(), SettlementError> {
         // Validate percentages
-        if config.platform_fee_bps > 10000 {
+        if config.platform_fee_bps > 10000 || config.referral_fee_bps > 10000 {
             return Err(SettlementError::InvalidFeeConfig);
         }
 
@@ -276,6 +622,11 @@ impl FeeManager {
             return Err(SettlementError::InvalidFeeConfig);
         }
 
+        // Swapping needs a positive threshold to trigger on
+        if config.auto_swap_enabled && config.swap_threshold <= 0 {
+            return Err(SettlementError::InvalidFeeConfig);
+        }
+
         // Validate volume tiers are ordered correctly
         let mut prev_volume = 0i128;
         for tier in config.volume_discounts.iter() {
@@ -335,6 +686,9 @@ impl FeeManager {
             total_accumulated_fees: accumulated_fees,
             total_users: total_users as u64,
             total_volume,
+            reset_period: Self::get_fee_config(env)
+                .map(|config| config.volume_period_seconds)
+                .unwrap_or(0),
         }
     }
 }
@@ -361,16 +715,30 @@ impl FeeConfig {
                 discounts
             },
             vip_exemptions: Vec::new(env),
+            referral_fee_bps: 1000, // 10% of the fee goes to referrers
+            volume_period_seconds: 2592000, // Volume discounts look back 30 days
+            auto_swap_enabled: false,
+            protocol_token: placeholder_protocol_token(env),
+            swap_threshold: 0,
         }
     }
 }
 
+/// Stand-in protocol token until an admin configures one; unused while auto swap is off
+pub fn placeholder_protocol_token(env: &Env) -> Asset {
+    Asset {
+        contract: env.current_contract_address(),
+        symbol: symbol_short!("NONE"),
+    }
+}
+
 /// Fee statistics structure
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct FeeStatistics {
     pub total_accumulated_fees: Map<Asset, i128>,
     pub total_users: u64,
     pub total_volume: i128,
+    pub reset_period: u64,
 }
 
 /// Fee calculator for complex fee structures
@@ -378,12 +746,15 @@ pub struct FeeCalculator;
 
 impl FeeCalculator {
     /// Calculate tiered fees based on transaction size
+    ///
+    /// Tiers are ordered by increasing `min_amount`; the largest bracket the amount
+    /// reaches sets the rate.
     pub fn calculate_tiered_fee(
         env: &Env,
         amount: i128,
         tiers: &Vec<(i128, u64)> // (min_amount, fee_bps)
     ) -> Result<i128, SettlementError> {
-        for (min_amount, fee_bps) in tiers.iter() {
+        for (min_amount, fee_bps) in tiers.iter().rev() {
             if amount >= min_amount {
                 return math_utils::calculate_percentage(amount, fee_bps, env);
             }
@@ -398,7 +769,7 @@ impl FeeCalculator {
         current_hour: u64
     ) -> Result<i128, SettlementError> {
         // Lower fees during off-peak hours (e.g., 2-6 AM)
-        let discount = if current_hour >= 2 && current_hour <= 6 {
+        let discount = if (2..=6).contains(&current_hour) {
             25 // 25% discount
         } else {
             0
@@ -422,4 +793,85 @@ impl FeeCalculator {
         let discount = math_utils::calculate_percentage(total_fee, bundle_discount_bps, env)?;
         math_utils::safe_sub(total_fee, discount, env)
     }
-}
\ No newline at end of file
+}
+
+/// Fee rates by transaction size bracket
+/// An asset whose fees couldn't be withdrawn in a batch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FailedWithdrawal {
+    pub asset: Asset,
+    pub amount: i128,    // fees still accrued for the asset
+    pub error_code: u32, // SettlementError the withdrawal failed with
+}
+
+/// Outcome of a batch fee withdrawal
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeWithdrawalSummary {
+    pub withdrawn: Vec<(Asset, i128)>,
+    pub failed: Vec<FailedWithdrawal>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeSchedule {
+    pub tiers: Vec<(i128, u64)>, // (min_amount, fee_bps), increasing min_amount
+}
+
+impl FeeSchedule {
+    /// Start building a fee schedule
+    pub fn builder(env: &Env) -> FeeScheduleBuilder {
+        FeeScheduleBuilder {
+            tiers: Vec::new(env),
+        }
+    }
+
+    /// Calculate the fee for an amount under this schedule
+    pub fn calculate_fee(&self, env: &Env, amount: i128) -> Result<i128, SettlementError> {
+        FeeCalculator::calculate_tiered_fee(env, amount, &self.tiers)
+    }
+}
+
+/// Builder for a `FeeSchedule`
+pub struct FeeScheduleBuilder {
+    tiers: Vec<(i128, u64)>,
+}
+
+impl FeeScheduleBuilder {
+    /// Add a bracket charging `fee_bps` from `min_amount` up to the next bracket
+    pub fn add_tier(mut self, min_amount: i128, fee_bps: u64) -> Self {
+        self.tiers.push_back((min_amount, fee_bps));
+        self
+    }
+
+    /// Validate the brackets and build the schedule
+    pub fn build(self) -> Result<FeeSchedule, SettlementError> {
+        if self.tiers.is_empty() {
+            return Err(SettlementError::InvalidFeeConfig);
+        }
+
+        let mut previous: Option<i128> = None;
+        for (min_amount, fee_bps) in self.tiers.iter() {
+            if min_amount < 0 || fee_bps > 10000 {
+                return Err(SettlementError::InvalidFeeConfig);
+            }
+            if previous.is_some_and(|previous| min_amount <= previous) {
+                return Err(SettlementError::InvalidFeeConfig);
+            }
+            previous = Some(min_amount);
+        }
+
+        Ok(FeeSchedule { tiers: self.tiers })
+    }
+}
+
+/// Default marketplace fee schedule: 3% under 1,000 units, 2.5% from 1,000, 2% from 10,000
+pub fn get_default_fee_schedule(env: &Env) -> FeeSchedule {
+    FeeSchedule::builder(env)
+        .add_tier(0, 300)
+        .add_tier(1_000, 250)
+        .add_tier(10_000, 200)
+        .build()
+        .expect("default fee schedule is ordered")
+}
@@ -0,0 +1,198 @@
+use soroban_sdk::{token, Address, Env};
+use crate::error::SettlementError;
+use crate::types::{AdminConfig, GovernanceProposal};
+use crate::storage::DataKey;
+use crate::storage::admin_config_store::AdminConfigStore;
+use crate::events::{
+    emit_governance_proposed, emit_governance_voted, emit_governance_executed,
+    GovernanceProposedEvent, GovernanceVotedEvent, GovernanceExecutedEvent
+};
+
+/// Seconds a governance proposal stays open for votes (7 days)
+pub const GOVERNANCE_VOTING_PERIOD: u64 = 604_800;
+
+/// Token-weighted voting on admin configuration changes
+///
+/// Once a governance token is set, the admin configuration can only change through a
+/// proposal that reaches the quorum and wins more votes for than against. Each address
+/// votes once per proposal, and the tokens it votes with are locked in the contract until
+/// voting closes, so the same tokens can't vote twice from different addresses.
+pub struct Governance;
+
+impl Governance {
+    /// Hand control of the admin configuration to holders of `token`
+    ///
+    /// This can only happen once, so the admin can't take control back by swapping tokens.
+    /// A proposal only passes once at least `quorum` tokens have voted on it.
+    pub fn set_token(env: &Env, token: &Address, quorum: i128) -> Result<(), SettlementError> {
+        if Self::is_enabled(env) {
+            return Err(SettlementError::AlreadyExists);
+        }
+        if quorum <= 0 {
+            return Err(SettlementError::InvalidAmount);
+        }
+        env.storage().instance().set(&DataKey::GovernanceToken, token);
+        env.storage().instance().set(&DataKey::GovernanceQuorum, &quorum);
+        Ok(())
+    }
+
+    /// Get the governance token, if governance is enabled
+    pub fn get_token(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::GovernanceToken)
+    }
+
+    /// Get the number of tokens that must vote on a proposal for it to pass
+    pub fn get_quorum(env: &Env) -> i128 {
+        env.storage().instance().get(&DataKey::GovernanceQuorum).unwrap_or(0)
+    }
+
+    /// Check whether config changes go through governance
+    pub fn is_enabled(env: &Env) -> bool {
+        env.storage().instance().has(&DataKey::GovernanceToken)
+    }
+
+    /// Open a proposal to replace the admin configuration
+    ///
+    /// Only holders of the governance token may propose. `new_config` must already be valid.
+    pub fn propose(env: &Env, new_config: &AdminConfig, proposer: &Address) -> Result<u64, SettlementError> {
+        let governance_token = Self::get_token(env).ok_or(SettlementError::InvalidState)?;
+        if token::Client::new(env, &governance_token).balance(proposer) <= 0 {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        let proposal_id: u64 = env.storage().instance().get(&DataKey::GovernanceProposalCount).unwrap_or(0);
+        env.storage().instance().set(&DataKey::GovernanceProposalCount, &(proposal_id + 1));
+
+        let now = env.ledger().timestamp();
+        let proposal = GovernanceProposal {
+            proposal_id,
+            proposer: proposer.clone(),
+            proposed_config: new_config.clone(),
+            votes_for: 0,
+            votes_against: 0,
+            voting_deadline: now.saturating_add(GOVERNANCE_VOTING_PERIOD),
+            executed: false,
+        };
+        Self::put(env, &proposal);
+
+        emit_governance_proposed(env, GovernanceProposedEvent {
+            proposal_id,
+            proposer: proposer.clone(),
+            voting_deadline: proposal.voting_deadline,
+            timestamp: now,
+        });
+
+        Ok(proposal_id)
+    }
+
+    /// Cast `voting_power` for or against an open proposal
+    ///
+    /// That many governance tokens move from the voter into the contract, where they stay
+    /// until `withdraw_vote_tokens` after the voting deadline.
+    pub fn vote(
+        env: &Env,
+        proposal_id: u64,
+        voter: &Address,
+        in_favor: bool,
+        voting_power: i128
+    ) -> Result<(), SettlementError> {
+        let mut proposal = Self::get(env, proposal_id)?;
+        if env.ledger().timestamp() >= proposal.voting_deadline {
+            return Err(SettlementError::Expired);
+        }
+
+        let vote_key = DataKey::GovernanceVote(proposal_id, voter.clone());
+        if env.storage().persistent().has(&vote_key) {
+            return Err(SettlementError::AlreadyExists);
+        }
+
+        if voting_power <= 0 {
+            return Err(SettlementError::InvalidAmount);
+        }
+        let governance_token = token::Client::new(env, &Self::get_token(env).ok_or(SettlementError::InvalidState)?);
+        if voting_power > governance_token.balance(voter) {
+            return Err(SettlementError::InsufficientFunds);
+        }
+        governance_token.transfer(voter, env.current_contract_address(), &voting_power);
+
+        if in_favor {
+            proposal.votes_for = proposal.votes_for.checked_add(voting_power).ok_or(SettlementError::Overflow)?;
+        } else {
+            proposal.votes_against = proposal.votes_against.checked_add(voting_power).ok_or(SettlementError::Overflow)?;
+        }
+        Self::put(env, &proposal);
+        env.storage().persistent().set(&vote_key, &voting_power);
+
+        emit_governance_voted(env, GovernanceVotedEvent {
+            proposal_id,
+            voter: voter.clone(),
+            in_favor,
+            voting_power,
+            timestamp: env.ledger().timestamp(),
+        });
+
+        Ok(())
+    }
+
+    /// Return the tokens `voter` locked to vote on a proposal once its voting has closed
+    pub fn withdraw_vote_tokens(env: &Env, proposal_id: u64, voter: &Address) -> Result<i128, SettlementError> {
+        let proposal = Self::get(env, proposal_id)?;
+        if env.ledger().timestamp() < proposal.voting_deadline {
+            return Err(SettlementError::InvalidState);
+        }
+
+        // The vote record stays behind, emptied, so the voter still can't vote again
+        let vote_key = DataKey::GovernanceVote(proposal_id, voter.clone());
+        let locked: i128 = env.storage().persistent().get(&vote_key).ok_or(SettlementError::NotFound)?;
+        if locked == 0 {
+            return Err(SettlementError::InsufficientFunds);
+        }
+        env.storage().persistent().set(&vote_key, &0i128);
+
+        let governance_token = Self::get_token(env).ok_or(SettlementError::InvalidState)?;
+        token::Client::new(env, &governance_token).transfer(&env.current_contract_address(), voter, &locked);
+        Ok(locked)
+    }
+
+    /// Apply a proposal's config once voting has closed with the quorum reached and more
+    /// votes for than against
+    pub fn execute(env: &Env, proposal_id: u64) -> Result<(), SettlementError> {
+        let mut proposal = Self::get(env, proposal_id)?;
+        if proposal.executed || env.ledger().timestamp() < proposal.voting_deadline {
+            return Err(SettlementError::InvalidState);
+        }
+        let turnout = proposal.votes_for.checked_add(proposal.votes_against).ok_or(SettlementError::Overflow)?;
+        if turnout < Self::get_quorum(env) {
+            return Err(SettlementError::InvalidState);
+        }
+        if proposal.votes_for <= proposal.votes_against {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        proposal.executed = true;
+        Self::put(env, &proposal);
+        AdminConfigStore::put(env, &proposal.proposed_config);
+
+        emit_governance_executed(env, GovernanceExecutedEvent {
+            proposal_id,
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+            timestamp: env.ledger().timestamp(),
+        });
+
+        Ok(())
+    }
+
+    /// Get a proposal
+    pub fn get(env: &Env, proposal_id: u64) -> Result<GovernanceProposal, SettlementError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Governance(proposal_id))
+            .ok_or(SettlementError::NotFound)
+    }
+
+    /// Internal: Save a proposal
+    fn put(env: &Env, proposal: &GovernanceProposal) {
+        env.storage().persistent().set(&DataKey::Governance(proposal.proposal_id), proposal);
+    }
+}
@@ -1,4 +1,5 @@
 #![no_std]
+#![allow(clippy::too_many_arguments)]
 
 // Module declarations
 pub mod error;
@@ -10,6 +11,7 @@ pub mod auction_engine;
 pub mod royalty_distributor;
 pub mod fee_manager;
 pub mod dispute_resolution;
+pub mod governance;
 pub mod security;
 pub mod events;
 pub mod settlement_core;
@@ -24,3 +26,4 @@ pub type AuctionType = types::AuctionType;
 pub type FeeConfig = types::FeeConfig;
 pub type DisputeConfig = dispute_resolution::DisputeConfig;
 pub type AuctionConfig = auction_engine::AuctionConfig;
+pub type RateLimitConfig = security::rate_limiter::RateLimitConfig;
@@ -1,9 +1,12 @@
-use soroban_sdk::{Env, Address, Map, Vec, Symbol, symbol_short, Bytes, contracttype};
+use soroban_sdk::{Env, Address, Map, Vec, Symbol, symbol_short, Bytes, contracttype, xdr::ToXdr};
 use crate::error::SettlementError;
 use crate::types::{RoyaltyDistribution, DistributionResult, Asset};
 use crate::utils::math_utils;
 use crate::utils::asset_utils;
 use crate::events::{emit_royalties_distributed, RoyaltiesDistributedEvent};
+use crate::fee_manager::FeeManager;
+use crate::storage::admin_config_store::AdminConfigStore;
+use crate::utils::nft_client::INftClient;
 
 // Storage keys
 const ROYALTY_CONFIGS: Symbol = symbol_short!("roy_cfgs");
@@ -39,12 +42,9 @@ impl RoyaltyDistributor {
         // Calculate royalty amount
         let royalty_amount = math_utils::calculate_percentage(sale_price, royalty_info.royalty_percentage, env)?;
 
-        // For now, assume 95% goes to seller, 5% to platform (this would be configurable)
-        let seller_percentage = 9500u64; // 95%
-        let platform_percentage = 500u64; // 5%
-
-        let _seller_amount = math_utils::calculate_percentage(sale_price, seller_percentage, env)?;
-        let _platform_amount = math_utils::calculate_percentage(sale_price, platform_percentage, env)?;
+        // Platform fees are charged separately by the fee manager, so the seller keeps the rest
+        let seller_percentage = 10000 - royalty_info.royalty_percentage;
+        let platform_percentage = 0u64;
 
         // Create distribution map
         let mut amounts = Map::new(env);
@@ -62,49 +62,135 @@ impl RoyaltyDistributor {
         Ok(royalty_distribution)
     }
 
+    /// Fetch a token's royalty from its NFT contract's `get_royalty_info` entrypoint
+    ///
+    /// The `(receiver, amount)` answer is converted to basis points of `sale_price` and
+    /// capped at the admin config's `max_royalty_percentage`.
+    pub fn fetch_royalty_info_from_nft(
+        env: &Env,
+        nft_contract: &Address,
+        token_id: u64,
+        sale_price: i128
+    ) -> Result<RoyaltyDistribution, SettlementError> {
+        if sale_price <= 0 {
+            return Err(SettlementError::InvalidAmount);
+        }
+
+        let (receiver, reported_amount) = INftClient::new(env, nft_contract).get_royalty_info(token_id, sale_price)?;
+        if reported_amount < 0 || reported_amount > sale_price {
+            return Err(SettlementError::InvalidAmount);
+        }
+
+        let max_percentage = AdminConfigStore::get(env)
+            .map(|config| config.max_royalty_percentage)
+            .unwrap_or(10000)
+            .min(10000);
+        let reported_percentage = math_utils::safe_div(
+            math_utils::safe_mul(reported_amount, 10000, env)?,
+            sale_price,
+            env
+        )? as u64;
+        let creator_percentage = reported_percentage.min(max_percentage);
+        let royalty_amount = math_utils::calculate_percentage(sale_price, creator_percentage, env)?;
+
+        let mut amounts = Map::new(env);
+        amounts.set(receiver.clone(), royalty_amount);
+
+        Ok(RoyaltyDistribution {
+            creator_address: receiver,
+            creator_percentage,
+            seller_percentage: 10000 - creator_percentage,
+            platform_percentage: 0,
+            total_amount: sale_price,
+            amounts,
+        })
+    }
+
+    /// Royalties for a sale: those registered with the marketplace, otherwise the NFT contract's own
+    pub fn royalties_for_sale(
+        env: &Env,
+        nft_contract: &Address,
+        token_id: u64,
+        sale_price: i128
+    ) -> Result<RoyaltyDistribution, SettlementError> {
+        Self::calculate_royalties(env, nft_contract, token_id, sale_price)
+            .or_else(|_| Self::fetch_royalty_info_from_nft(env, nft_contract, token_id, sale_price))
+    }
+
     /// Distribute royalties for a transaction
+    ///
+    /// Pays the creator and platform shares of `total_amount` out of the contract's
+    /// escrow. The seller's share is reported but left for the caller to pay, and a
+    /// creator who is also the seller has their royalty folded into that share.
     pub fn distribute_royalties(
         env: &Env,
         transaction_id: u64,
         royalty_distribution: &RoyaltyDistribution,
-        payment_asset: &Asset
+        payment_asset: &Asset,
+        total_amount: i128,
+        seller: &Address
     ) -> Result<DistributionResult, SettlementError> {
-        let mut total_distributed = 0i128;
+        if total_amount < 0 {
+            return Err(SettlementError::InvalidAmount);
+        }
+
+        let mut creator_amount = math_utils::calculate_percentage(
+            total_amount,
+            royalty_distribution.creator_percentage,
+            env
+        )?;
+        let platform_amount = math_utils::calculate_percentage(
+            total_amount,
+            royalty_distribution.platform_percentage,
+            env
+        )?;
+
+        let deductions = math_utils::safe_add(creator_amount, platform_amount, env)?;
+        if deductions > total_amount {
+            return Err(SettlementError::InvalidAmount);
+        }
+        let mut seller_amount = math_utils::safe_sub(total_amount, deductions, env)?;
+
+        // A creator reselling their own work is paid once, as the seller
+        if royalty_distribution.creator_address == *seller {
+            seller_amount = math_utils::safe_add(seller_amount, creator_amount, env)?;
+            creator_amount = 0;
+        }
+
+        let contract = env.current_contract_address();
         let mut distribution_success = true;
 
-        // Distribute to each recipient
-        for (recipient, amount) in royalty_distribution.amounts.iter() {
-            match asset_utils::transfer_tokens(
+        if creator_amount > 0
+            && asset_utils::transfer_tokens(
+                &payment_asset.contract,
+                &contract,
+                &royalty_distribution.creator_address,
+                creator_amount,
+                env
+            ).is_err()
+        {
+            distribution_success = false;
+        }
+
+        if platform_amount > 0 {
+            let fee_recipient = FeeManager::get_fee_config(env)?.fee_recipient;
+            if asset_utils::transfer_tokens(
                 &payment_asset.contract,
-                &env.current_contract_address(),
-                &recipient,
-                amount,
+                &contract,
+                &fee_recipient,
+                platform_amount,
                 env
-            ) {
-                Ok(_) => {
-                    total_distributed = math_utils::safe_add(total_distributed, amount, env)?;
-                }
-                Err(_) => {
-                    distribution_success = false;
-                    // Log error but continue with other distributions
-                }
+            ).is_err() {
+                distribution_success = false;
             }
         }
 
         let result = DistributionResult {
             transaction_id,
-            total_amount: royalty_distribution.total_amount,
-            creator_amount: royalty_distribution.amounts.get(royalty_distribution.creator_address.clone()).unwrap_or(0),
-            seller_amount: math_utils::calculate_percentage(
-                royalty_distribution.total_amount,
-                royalty_distribution.seller_percentage,
-                env
-            )?,
-            platform_amount: math_utils::calculate_percentage(
-                royalty_distribution.total_amount,
-                royalty_distribution.platform_percentage,
-                env
-            )?,
+            total_amount,
+            creator_amount,
+            seller_amount,
+            platform_amount,
             distribution_success,
             timestamp: env.ledger().timestamp(),
         };
@@ -158,7 +244,7 @@ impl RoyaltyDistributor {
         nft_contract: &Address,
         token_id: u64
     ) -> Result<RoyaltyInfo, SettlementError> {
-        let key = Self::make_royalty_key(nft_contract, token_id);
+        let key = Self::make_royalty_key(env, nft_contract, token_id);
         let royalty_configs: Map<RoyaltyKey, RoyaltyInfo> = env
             .storage()
             .instance()
@@ -167,7 +253,7 @@ impl RoyaltyDistributor {
 
         match royalty_configs.get(key) {
             Some(info) => Ok(info),
-            None => return Err(SettlementError::NotFound),
+            None => Err(SettlementError::NotFound),
         }
     }
 
@@ -307,10 +393,10 @@ impl RoyaltyDistributor {
     }
 
     /// Internal: Create storage key for royalty info
-    fn make_royalty_key(_nft_contract: &Address, _token_id: u64) -> RoyaltyKey {
-        let key = Bytes::new(&Env::default());
-        // Convert address and token_id to bytes and append
-        // This is a simplified implementation - in practice you'd need proper serialization
+    fn make_royalty_key(env: &Env, nft_contract: &Address, token_id: u64) -> RoyaltyKey {
+        // Serialized contract address followed by the big-endian token id
+        let mut key = nft_contract.clone().to_xdr(env);
+        key.extend_from_array(&token_id.to_be_bytes());
         key
     }
 
@@ -322,7 +408,7 @@ impl RoyaltyDistributor {
             .get(&ROYALTY_CONFIGS)
             .unwrap_or(Map::new(env));
 
-        let key = Self::make_royalty_key(&royalty_info.nft_contract, royalty_info.token_id);
+        let key = Self::make_royalty_key(env, &royalty_info.nft_contract, royalty_info.token_id);
         royalty_configs.set(key, royalty_info.clone());
 
         env.storage().instance().set(&ROYALTY_CONFIGS, &royalty_configs);
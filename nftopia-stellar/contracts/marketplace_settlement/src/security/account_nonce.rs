@@ -0,0 +1,34 @@
+use soroban_sdk::{Address, Env};
+use crate::error::SettlementError;
+use crate::storage::DataKey;
+
+/// Per-address counters that retire signatures made before an account's latest activity
+///
+/// Every guarded state-changing call advances the caller's nonce. A signed message carries
+/// the signer's nonce at signing time and is rejected once the account has moved past it.
+pub struct AccountNonce;
+
+impl AccountNonce {
+    /// Get an address's current nonce
+    pub fn get(env: &Env, address: &Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AccountNonce(address.clone()))
+            .unwrap_or(0)
+    }
+
+    /// Advance an address's nonce, returning the new value
+    pub fn increment(env: &Env, address: &Address) -> u64 {
+        let nonce = Self::get(env, address).saturating_add(1);
+        env.storage().persistent().set(&DataKey::AccountNonce(address.clone()), &nonce);
+        nonce
+    }
+
+    /// Check a signed nonce hasn't been overtaken by the signer's account nonce
+    pub fn require_current(env: &Env, address: &Address, nonce: u64) -> Result<(), SettlementError> {
+        if nonce < Self::get(env, address) {
+            return Err(SettlementError::AlreadyExists);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use soroban_sdk::{Env, Address};
+use crate::error::SettlementError;
+use crate::storage::DataKey;
+use crate::events::{
+    emit_address_banned, emit_address_unbanned,
+    AddressBannedEvent, AddressUnbannedEvent
+};
+
+/// Addresses blocked from trading on the marketplace
+pub struct BanList;
+
+impl BanList {
+    /// Ban an address
+    pub fn ban(env: &Env, target: &Address, admin: &Address) -> Result<(), SettlementError> {
+        if Self::is_banned(env, target) {
+            return Err(SettlementError::AlreadyExists);
+        }
+
+        env.storage().instance().set(&DataKey::Banned(target.clone()), &true);
+
+        let event = AddressBannedEvent {
+            address: target.clone(),
+            admin: admin.clone(),
+            timestamp: env.ledger().timestamp(),
+        };
+        emit_address_banned(env, event);
+
+        Ok(())
+    }
+
+    /// Lift a ban on an address
+    pub fn unban(env: &Env, target: &Address, admin: &Address) -> Result<(), SettlementError> {
+        if !Self::is_banned(env, target) {
+            return Err(SettlementError::NotFound);
+        }
+
+        env.storage().instance().remove(&DataKey::Banned(target.clone()));
+
+        let event = AddressUnbannedEvent {
+            address: target.clone(),
+            admin: admin.clone(),
+            timestamp: env.ledger().timestamp(),
+        };
+        emit_address_unbanned(env, event);
+
+        Ok(())
+    }
+
+    /// Check whether an address is banned
+    pub fn is_banned(env: &Env, address: &Address) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Banned(address.clone()))
+            .unwrap_or(false)
+    }
+}
+
+/// Fail if `address` has been banned from the marketplace
+pub fn require_not_banned(env: &Env, address: &Address) -> Result<(), SettlementError> {
+    if BanList::is_banned(env, address) {
+        return Err(SettlementError::AddressBanned);
+    }
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use soroban_sdk::{Env, Address};
+use crate::error::SettlementError;
+use crate::types::AdminConfig;
+use crate::storage::DataKey;
+use crate::storage::admin_config_store::AdminConfigStore;
+use crate::events::{
+    emit_circuit_breaker_tripped, emit_circuit_breaker_reset,
+    CircuitBreakerTrippedEvent, CircuitBreakerResetEvent
+};
+
+/// Halts settlement when an unusually large transaction comes through
+pub struct CircuitBreaker;
+
+impl CircuitBreaker {
+    /// Check a settlement amount against the configured limits
+    ///
+    /// Returns `Ok(true)` if this amount tripped the breaker, in which case the
+    /// caller must stop without settling so the tripped flag is kept.
+    pub fn check(env: &Env, transaction_id: u64, amount: i128) -> Result<bool, SettlementError> {
+        if Self::is_tripped(env) {
+            return Err(SettlementError::CircuitBreakerTripped);
+        }
+
+        let admin_config: AdminConfig = AdminConfigStore::get(env)
+            .ok_or(SettlementError::NotFound)?;
+
+        if amount > admin_config.max_single_transaction_amount {
+            return Err(SettlementError::InvalidAmount);
+        }
+
+        if amount > admin_config.circuit_breaker_threshold {
+            env.storage().instance().set(&DataKey::CircuitBreakerTripped, &true);
+
+            let event = CircuitBreakerTrippedEvent {
+                transaction_id,
+                amount,
+                threshold: admin_config.circuit_breaker_threshold,
+                timestamp: env.ledger().timestamp(),
+            };
+            emit_circuit_breaker_tripped(env, event);
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    /// Check whether settlement is halted
+    pub fn is_tripped(env: &Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::CircuitBreakerTripped)
+            .unwrap_or(false)
+    }
+
+    /// Resume settlement after a trip
+    pub fn reset(env: &Env, admin: &Address) -> Result<(), SettlementError> {
+        if !Self::is_tripped(env) {
+            return Err(SettlementError::InvalidState);
+        }
+
+        env.storage().instance().remove(&DataKey::CircuitBreakerTripped);
+
+        let event = CircuitBreakerResetEvent {
+            admin: admin.clone(),
+            timestamp: env.ledger().timestamp(),
+        };
+        emit_circuit_breaker_reset(env, event);
+
+        Ok(())
+    }
+}
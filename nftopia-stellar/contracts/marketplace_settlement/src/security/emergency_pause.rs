@@ -0,0 +1,78 @@
+use soroban_sdk::{Env, Address};
+use crate::error::SettlementError;
+use crate::storage::DataKey;
+use crate::events::{
+    emit_emergency_pause, emit_emergency_unpause,
+    EmergencyPauseEvent, EmergencyUnpauseEvent
+};
+
+// How long the marketplace stays halted before it can be unpaused
+pub const EMERGENCY_UNPAUSE_COOLDOWN: u64 = 86400; // 24 hours
+
+/// Marketplace-wide halt for security incidents
+pub struct EmergencyPause;
+
+impl EmergencyPause {
+    /// Halt all marketplace operations
+    pub fn pause(env: &Env, admin: &Address) -> Result<(), SettlementError> {
+        if Self::is_paused(env) {
+            return Err(SettlementError::InvalidState);
+        }
+
+        let timestamp = env.ledger().timestamp();
+        env.storage().instance().set(&DataKey::EmergencyPaused, &true);
+        env.storage().instance().set(&DataKey::EmergencyPausedAt, &timestamp);
+
+        let event = EmergencyPauseEvent {
+            admin: admin.clone(),
+            timestamp,
+        };
+        emit_emergency_pause(env, event);
+
+        Ok(())
+    }
+
+    /// Resume marketplace operations once the cooldown since the pause has passed
+    pub fn unpause(env: &Env, admin: &Address) -> Result<(), SettlementError> {
+        if !Self::is_paused(env) {
+            return Err(SettlementError::InvalidState);
+        }
+
+        let paused_at: u64 = env.storage()
+            .instance()
+            .get(&DataKey::EmergencyPausedAt)
+            .unwrap_or(0);
+        let timestamp = env.ledger().timestamp();
+        if timestamp < paused_at.saturating_add(EMERGENCY_UNPAUSE_COOLDOWN) {
+            return Err(SettlementError::CooldownActive);
+        }
+
+        env.storage().instance().remove(&DataKey::EmergencyPaused);
+        env.storage().instance().remove(&DataKey::EmergencyPausedAt);
+
+        let event = EmergencyUnpauseEvent {
+            admin: admin.clone(),
+            paused_at,
+            timestamp,
+        };
+        emit_emergency_unpause(env, event);
+
+        Ok(())
+    }
+
+    /// Check whether the marketplace is halted
+    pub fn is_paused(env: &Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::EmergencyPaused)
+            .unwrap_or(false)
+    }
+}
+
+/// Fail if the marketplace has been halted
+pub fn require_not_emergency_paused(env: &Env) -> Result<(), SettlementError> {
+    if EmergencyPause::is_paused(env) {
+        return Err(SettlementError::EmergencyPaused);
+    }
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{Env, Symbol, Vec, Address, symbol_short, Bytes};
+use soroban_sdk::{Env, Symbol, Vec, Address, symbol_short, Bytes, xdr::ToXdr};
 use crate::error::SettlementError;
 use crate::events::{emit_front_running_detected, FrontRunningDetectedEvent};
 use crate::types::Bid;
@@ -6,18 +6,29 @@ use crate::types::Bid;
 // Storage keys
 const COMMITMENT_STORAGE: Symbol = symbol_short!("commits");
 
+// Size of a sha256 commitment in bytes
+const COMMITMENT_LENGTH: u32 = 32;
+
 /// Commit-reveal scheme for bid protection
 pub struct CommitRevealScheme;
 
 impl CommitRevealScheme {
     /// Create a commitment hash from bid details
+    ///
+    /// sha256 over the bidder's XDR-encoded address, the auction ID (8 bytes
+    /// big-endian), the bid amount (16 bytes big-endian) and the salt.
     pub fn create_commitment(
-        _bidder: &Address,
-        _auction_id: u64,
-        _bid_amount: i128,
+        env: &Env,
+        bidder: &Address,
+        auction_id: u64,
+        bid_amount: i128,
         salt: &Bytes
     ) -> Bytes {
-        salt.clone()
+        let mut preimage = bidder.clone().to_xdr(env);
+        preimage.append(&Bytes::from_array(env, &auction_id.to_be_bytes()));
+        preimage.append(&Bytes::from_array(env, &bid_amount.to_be_bytes()));
+        preimage.append(salt);
+        env.crypto().sha256(&preimage).to_bytes().into()
     }
 
     /// Store a commitment
@@ -28,6 +39,11 @@ impl CommitRevealScheme {
         commitment_hash: &Bytes,
         reveal_deadline: u64
     ) -> Result<(), SettlementError> {
+        // Anything other than a sha256 digest can never match a reveal
+        if commitment_hash.len() != COMMITMENT_LENGTH {
+            return Err(SettlementError::CommitmentMismatch);
+        }
+
         let mut commitments: soroban_sdk::Map<Address, soroban_sdk::Map<u64, (Bytes, u64)>> = env
             .storage()
             .instance()
@@ -65,7 +81,7 @@ impl CommitRevealScheme {
 
         let (stored_hash, reveal_deadline) = bidder_commitments
             .get(auction_id)
-            .unwrap_or((Bytes::new(&env), 0));
+            .unwrap_or((Bytes::new(env), 0));
 
         // Check if reveal deadline has passed
         let current_time = env.ledger().timestamp();
@@ -74,7 +90,7 @@ impl CommitRevealScheme {
         }
 
         // Verify the commitment
-        let computed_hash = Self::create_commitment(bidder, auction_id, bid_amount, salt);
+        let computed_hash = Self::create_commitment(env, bidder, auction_id, bid_amount, salt);
         if computed_hash != stored_hash {
             return Err(SettlementError::CommitmentMismatch);
         }
@@ -181,12 +197,10 @@ impl FrontRunningDetector {
         let time_window = 60; // 60 seconds
 
         for bid in recent_bids.iter() {
-            if bid.bidder == new_bid.bidder {
-                if new_bid.placed_at - bid.placed_at < time_window {
-                    same_bidder_count += 1;
-                    if same_bidder_count >= 3 {
-                        return true;
-                    }
+            if bid.bidder == new_bid.bidder && new_bid.placed_at - bid.placed_at < time_window {
+                same_bidder_count += 1;
+                if same_bidder_count >= 3 {
+                    return true;
                 }
             }
         }
@@ -228,11 +242,7 @@ impl FrontRunningDetector {
         // Check if new bid follows similar pattern
         if let Some(last_interval) = intervals.get(intervals.len() - 1) {
             let new_interval = new_bid.placed_at - recent_bids.get(recent_bids.len() - 1).unwrap().placed_at;
-            let diff = if new_interval > last_interval {
-                new_interval - last_interval
-            } else {
-                last_interval - new_interval
-            };
+            let diff = new_interval.abs_diff(last_interval);
 
             // If timing is too regular (within 5 seconds), flag as suspicious
             diff < 5
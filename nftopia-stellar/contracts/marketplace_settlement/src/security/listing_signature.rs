@@ -0,0 +1,66 @@
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env};
+use crate::error::SettlementError;
+use crate::security::account_nonce::AccountNonce;
+use crate::storage::DataKey;
+use crate::types::OffChainListing;
+
+/// Seller signatures over listings kept off-chain until they sell
+pub struct ListingSignature;
+
+impl ListingSignature {
+    /// Register the ed25519 key a seller signs off-chain listings with
+    pub fn register_key(env: &Env, seller: &Address, public_key: &BytesN<32>) {
+        env.storage().persistent().set(&DataKey::ListingKey(seller.clone()), public_key);
+    }
+
+    /// Get a seller's listing key, if registered
+    pub fn get_key(env: &Env, seller: &Address) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::ListingKey(seller.clone()))
+    }
+
+    /// The bytes a seller signs for a listing
+    ///
+    /// This contract's address, then the seller, NFT contract and currency contract as
+    /// XDR, interleaved with the token ID, price, expiry and nonce as big-endian integers.
+    pub fn message(env: &Env, listing: &OffChainListing) -> Bytes {
+        let mut message = env.current_contract_address().to_xdr(env);
+        message.append(&listing.seller.clone().to_xdr(env));
+        message.append(&listing.nft_address.clone().to_xdr(env));
+        message.extend_from_array(&listing.token_id.to_be_bytes());
+        message.extend_from_array(&listing.price.to_be_bytes());
+        message.append(&listing.currency.contract.clone().to_xdr(env));
+        message.extend_from_array(&listing.expiry.to_be_bytes());
+        message.extend_from_array(&listing.nonce.to_be_bytes());
+        message
+    }
+
+    /// Check the seller signed `listing` and consume its nonce
+    ///
+    /// The nonce must not be below the seller's account nonce, so a listing signed before
+    /// the seller's latest on-chain activity is stale. A signature that doesn't match the
+    /// seller's key aborts the invocation.
+    pub fn verify_and_consume(env: &Env, listing: &OffChainListing) -> Result<(), SettlementError> {
+        let public_key = Self::get_key(env, &listing.seller).ok_or(SettlementError::NotFound)?;
+
+        let nonce_key = DataKey::UsedNonce(listing.seller.clone(), listing.nonce);
+        if env.storage().persistent().has(&nonce_key) {
+            return Err(SettlementError::AlreadyExists);
+        }
+        AccountNonce::require_current(env, &listing.seller, listing.nonce)?;
+
+        let signature: BytesN<64> = listing
+            .signature
+            .clone()
+            .try_into()
+            .map_err(|_| SettlementError::InvalidSignature)?;
+        env.crypto().ed25519_verify(&public_key, &Self::message(env, listing), &signature);
+
+        env.storage().persistent().set(&nonce_key, &true);
+        Ok(())
+    }
+
+    /// Check whether a seller has already used a listing nonce
+    pub fn is_nonce_used(env: &Env, seller: &Address, nonce: u64) -> bool {
+        env.storage().persistent().has(&DataKey::UsedNonce(seller.clone(), nonce))
+    }
+}
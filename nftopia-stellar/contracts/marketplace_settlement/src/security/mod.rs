@@ -1,2 +1,8 @@
 pub mod reentrancy_guard;
-pub mod frontrun_protection;
\ No newline at end of file
+pub mod frontrun_protection;
+pub mod rate_limiter;
+pub mod ban_list;
+pub mod circuit_breaker;
+pub mod nft_whitelist;
+pub mod emergency_pause;
+pub mod listing_signature;pub mod account_nonce;
@@ -0,0 +1,52 @@
+use soroban_sdk::{Env, Address, Vec};
+use crate::error::SettlementError;
+use crate::storage::DataKey;
+use crate::storage::admin_config_store::AdminConfigStore;
+
+/// NFT contracts vetted for listing on the marketplace
+pub struct NftWhitelist;
+
+impl NftWhitelist {
+    /// Allow listings from an NFT contract
+    pub fn add(env: &Env, nft_contract: &Address) -> Result<(), SettlementError> {
+        let mut allowed = Self::get_all(env);
+        if allowed.contains(nft_contract.clone()) {
+            return Err(SettlementError::AlreadyExists);
+        }
+
+        allowed.push_back(nft_contract.clone());
+        env.storage().instance().set(&DataKey::AllowedNftContracts, &allowed);
+        Ok(())
+    }
+
+    /// Stop accepting listings from an NFT contract
+    pub fn remove(env: &Env, nft_contract: &Address) -> Result<(), SettlementError> {
+        let mut allowed = Self::get_all(env);
+        let index = allowed
+            .first_index_of(nft_contract.clone())
+            .ok_or(SettlementError::NotFound)?;
+
+        allowed.remove(index);
+        env.storage().instance().set(&DataKey::AllowedNftContracts, &allowed);
+        Ok(())
+    }
+
+    /// Get every whitelisted NFT contract
+    pub fn get_all(env: &Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::AllowedNftContracts)
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Check whether an NFT contract may be listed
+    ///
+    /// Every contract is allowed while the admin config bypasses the whitelist.
+    pub fn is_allowed(env: &Env, nft_contract: &Address) -> bool {
+        let bypass = AdminConfigStore::get(env)
+            .map(|config| config.bypass_whitelist)
+            .unwrap_or(false);
+
+        bypass || Self::get_all(env).contains(nft_contract.clone())
+    }
+}
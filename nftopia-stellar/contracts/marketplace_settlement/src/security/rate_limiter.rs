@@ -0,0 +1,85 @@
+use soroban_sdk::{Env, Symbol, Address, Map, symbol_short, contracttype};
+use crate::error::SettlementError;
+
+// Storage keys
+const RATE_LIMITS: Symbol = symbol_short!("rate_lim");
+const RATE_LIMIT_CONFIG: Symbol = symbol_short!("rate_cfg");
+
+/// Per-caller call limits for rate limited entrypoints
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateLimitConfig {
+    pub max_calls: u64,      // Calls allowed per window
+    pub window_seconds: u64, // Length of the window
+}
+
+/// Rate limiter tracking calls per caller and function
+pub struct RateLimiter;
+
+impl RateLimiter {
+    /// Record a call, failing if the caller has used up its calls for the current window
+    pub fn check_and_record(
+        env: &Env,
+        caller: &Address,
+        function_name: &str,
+        max_calls: u64,
+        window_seconds: u64
+    ) -> Result<(), SettlementError> {
+        let key = (RATE_LIMITS, Symbol::new(env, function_name));
+        let mut calls: Map<Address, (u64, u64)> = env
+            .storage()
+            .instance()
+            .get(&key)
+            .unwrap_or(Map::new(env));
+
+        let now = env.ledger().timestamp();
+        let (count, window_start) = calls.get(caller.clone()).unwrap_or((0, now));
+
+        // Start a fresh window once the previous one has passed
+        let (count, window_start) = if now >= window_start.saturating_add(window_seconds) {
+            (0, now)
+        } else {
+            (count, window_start)
+        };
+
+        if count >= max_calls {
+            return Err(SettlementError::CooldownActive);
+        }
+
+        calls.set(caller.clone(), (count + 1, window_start));
+        env.storage().instance().set(&key, &calls);
+        Ok(())
+    }
+
+    /// Get the rate limit configuration
+    pub fn get_config(env: &Env) -> RateLimitConfig {
+        env.storage()
+            .instance()
+            .get(&RATE_LIMIT_CONFIG)
+            .unwrap_or_default()
+    }
+
+    /// Update the rate limit configuration
+    pub fn update_config(
+        env: &Env,
+        config: &RateLimitConfig,
+        _admin: &Address
+    ) -> Result<(), SettlementError> {
+        if config.max_calls == 0 || config.window_seconds == 0 {
+            return Err(SettlementError::InvalidAmount);
+        }
+
+        env.storage().instance().set(&RATE_LIMIT_CONFIG, config);
+        Ok(())
+    }
+}
+
+/// Default rate limit configuration
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            max_calls: 10,
+            window_seconds: 60,
+        }
+    }
+}
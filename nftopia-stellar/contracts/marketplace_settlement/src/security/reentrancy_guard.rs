@@ -1,6 +1,7 @@
 use soroban_sdk::{Env, Symbol, Address, symbol_short, Bytes};
 use crate::error::SettlementError;
 use crate::events::{emit_reentrancy_detected, ReentrancyDetectedEvent};
+use crate::security::account_nonce::AccountNonce;
 
 // Storage keys
 const REENTRANCY_GUARD: Symbol = symbol_short!("reentrant");
@@ -11,6 +12,10 @@ pub struct ReentrancyGuard;
 
 impl ReentrancyGuard {
     /// Execute a function with reentrancy protection
+    ///
+    /// Advances `caller`'s account nonce, so entrypoints must have required the
+    /// caller's auth first; otherwise anyone could retire another account's signed
+    /// listings.
     pub fn execute<F, R>(env: &Env, caller: &Address, function_name: &str, f: F) -> Result<R, SettlementError>
     where
         F: FnOnce() -> Result<R, SettlementError>,
@@ -33,6 +38,9 @@ impl ReentrancyGuard {
         // Set the reentrancy lock
         env.storage().instance().set(&REENTRANCY_GUARD, &true);
 
+        // Each call moves the caller past anything they signed before it
+        AccountNonce::increment(env, caller);
+
         // Execute the function
         let result = f();
 
@@ -104,7 +112,7 @@ impl FunctionLock {
 macro_rules! non_reentrant {
     ($env:expr, $caller:expr, $function_name:expr, $body:block) => {
         {
-            use crate::security::reentrancy_guard::ReentrancyGuard;
+            use $crate::security::reentrancy_guard::ReentrancyGuard;
             ReentrancyGuard::execute($env, $caller, $function_name, || $body)
         }
     };
@@ -115,7 +123,7 @@ macro_rules! non_reentrant {
 macro_rules! function_lock {
     ($env:expr, $function_key:expr, $caller:expr, $body:block) => {
         {
-            use crate::security::reentrancy_guard::FunctionLock;
+            use $crate::security::reentrancy_guard::FunctionLock;
             FunctionLock::execute($env, $function_key, $caller, || $body)
         }
     };
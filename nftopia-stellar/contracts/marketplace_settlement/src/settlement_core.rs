@@ -1,21 +1,52 @@
-use soroban_sdk::{contract, contractimpl, Address, Env, Vec, symbol_short, Symbol, Bytes};
+use soroban_sdk::{contract, contractimpl, Address, Env, Map, Vec, Symbol, Bytes, BytesN};
 use crate::error::SettlementError;
 use crate::types::{
     SaleTransaction, AuctionTransaction, TradeTransaction, BundleTransaction,
-    ExecutionResult, Asset, AuctionType, AdminConfig,
-    FeeConfig, VolumeTier
+    ExecutionResult, Asset, AuctionOptions, AuctionType, AdminConfig, VickreyAuctionData,
+    CandleAuctionData, ProxyBid, SealedAuctionData, Offer, CollectionOffer, RoyaltyDistribution, DistributionResult,
+    FeeConfig, CollectionStats, PlatformStats, OffChainListing, BundleAuctionData, MultiPartyTrade, VipTier, PromotionalFeeOverride,
+    StorageTTLConfig, AuctionFilter, BidderBid, GovernanceProposal
 };
 use crate::storage::{
-    transaction_store::{SaleTransactionStore, TradeTransactionStore, BundleTransactionStore},
+    transaction_store::{
+        SaleTransactionStore, SaleEscrowStore, SaleHistoryStore, PriceHistoryStore, FloorPriceStore,
+        TradeTransactionStore, BundleTransactionStore
+    },
     auction_store::AuctionStore,
+    dispute_store::DisputeStore,
+    offer_store::{OfferStore, CollectionOfferStore},
+    stats_store::{CollectionStatsStore, PlatformStatsStore},
+    admin_config_store::{AdminConfigStore, ADMIN_CONFIG_TIMELOCK},
+    ttl_store::StorageTtlStore,
 };
+use crate::storage::DataKey;
 use crate::atomic_swap::AtomicSwapEngine;
 use crate::auction_engine::AuctionEngine;
 use crate::royalty_distributor::RoyaltyDistributor;
-use crate::fee_manager::FeeManager;
-use crate::dispute_resolution::DisputeResolutionManager;
+use crate::fee_manager::{FeeManager, FeeSchedule, FeeWithdrawalSummary};
+use crate::governance::Governance;
+use crate::dispute_resolution::{ArbitratorStats, DisputeEvidenceManager, DisputeResolutionManager, EvidenceRecord};
 use crate::security::reentrancy_guard::ReentrancyGuard;
-use crate::utils::{asset_utils, time_utils};
+use crate::security::rate_limiter::{RateLimiter, RateLimitConfig};
+use crate::security::ban_list::{BanList, require_not_banned};
+use crate::security::circuit_breaker::CircuitBreaker;
+use crate::security::nft_whitelist::NftWhitelist;
+use crate::security::emergency_pause::{EmergencyPause, require_not_emergency_paused};
+use crate::security::listing_signature::ListingSignature;
+use crate::security::account_nonce::AccountNonce;
+use crate::utils::{asset_utils, math_utils, time_utils};
+use crate::events::{
+    emit_sale_created, emit_sale_executed, emit_sale_cancelled, emit_sale_funded,
+    emit_offer_created, emit_offer_accepted, emit_offer_cancelled,
+    emit_collection_offer_created, emit_collection_offer_filled, emit_collection_offer_cancelled,
+    emit_admin_config_updated, emit_bundle_executed, emit_expired_sales_cleaned, emit_marketplace_initialized,
+    emit_admin_config_proposed, emit_admin_config_applied,
+    SaleCreatedEvent, SaleExecutedEvent, SaleCancelledEvent, SaleFundedEvent,
+    OfferCreatedEvent, OfferAcceptedEvent, OfferCancelledEvent,
+    CollectionOfferCreatedEvent, CollectionOfferFilledEvent, CollectionOfferCancelledEvent,
+    AdminConfigUpdatedEvent, BundleExecutedEvent, ExpiredSalesCleanedEvent, MarketplaceInitializedEvent,
+    AdminConfigProposedEvent, AdminConfigAppliedEvent
+};
 
 /// Marketplace Settlement Contract
 #[contract]
@@ -24,56 +55,65 @@ pub struct MarketplaceSettlement;
 /// Implementation of the Marketplace Settlement Contract
 #[contractimpl]
 impl MarketplaceSettlement {
-    /// Initialize the contract with admin configuration
-    pub fn initialize(env: Env, admin: Address) -> Result<(), SettlementError> {
-        // Set default configurations
+    /// Initialize the contract with its admin and fee, auction and dispute configuration
+    ///
+    /// Can only run once. All three configurations are validated before anything is stored.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        fee_config: FeeConfig,
+        auction_config: crate::auction_engine::AuctionConfig,
+        dispute_config: crate::dispute_resolution::DisputeConfig
+    ) -> Result<(), SettlementError> {
+        if Self::is_initialized(env.clone()) {
+            return Err(SettlementError::AlreadyExists);
+        }
+        admin.require_auth();
+        FeeManager::validate_fee_config(&fee_config)?;
+        AuctionEngine::validate_auction_config(&auction_config)?;
+        DisputeResolutionManager::validate_dispute_config(&dispute_config)?;
+
+        // Limits shared with the auction and dispute configs mirror them
         let admin_config = AdminConfig {
             admin: admin.clone(),
             emergency_withdrawal_enabled: true,
             max_transaction_duration: 2592000, // 30 days
-            max_auction_duration: 604800,      // 7 days
-            min_bid_increment_bps: 100,        // 1%
+            max_auction_duration: auction_config.max_auction_duration,
+            min_bid_increment_bps: auction_config.min_bid_increment_bps,
             max_royalty_percentage: 5000,      // 50%
-            dispute_cooling_period: 86400,     // 24 hours
-            arbitration_quorum: 3,
+            dispute_cooling_period: dispute_config.cooling_period,
+            arbitration_quorum: dispute_config.arbitration_quorum,
+            max_single_transaction_amount: 100_000_000_000_000, // 10M tokens at 7 decimals
+            circuit_breaker_threshold: 10_000_000_000_000,      // 1M tokens at 7 decimals
+            bypass_whitelist: true, // Open listing until collections have been vetted
+            max_history_per_token: 100,
+            max_price_history_per_token: 100,
         };
 
-        env.storage().instance().set(&symbol_short!("admin_cfg"), &admin_config);
-
-        // Set default fee config
-        let fee_config = FeeConfig {
-            platform_fee_bps: 250, // 2.5%
-            minimum_fee: 1000,     // Minimum 1000 units
-            maximum_fee: 1000000,  // Maximum 1M units
-            fee_recipient: Address::from_string(&soroban_sdk::String::from_str(&env, "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA")), // Fee recipient address
-            dynamic_fee_enabled: true,
-            volume_discounts: {
-                let mut discounts = Vec::new(&env);
-                discounts.push_back(VolumeTier {
-                    min_volume: 1000000,     // 1M volume
-                    fee_discount_bps: 50,    // 0.5% discount
-                });
-                discounts.push_back(VolumeTier {
-                    min_volume: 10000000,    // 10M volume
-                    fee_discount_bps: 100,   // 1% discount
-                });
-                discounts
-            },
-            vip_exemptions: Vec::new(&env),
-        };
+        AdminConfigStore::put(&env, &admin_config);
         FeeManager::update_fee_config(&env, &fee_config, &admin)?;
-
-        // Set default auction config
-        let auction_config = crate::auction_engine::AuctionConfig::default();
         AuctionEngine::update_auction_config(&env, &auction_config, &admin)?;
-
-        // Set default dispute config
-        let dispute_config = crate::dispute_resolution::DisputeConfig::default();
         DisputeResolutionManager::update_dispute_config(&env, &dispute_config, &admin)?;
 
+        // Set default rate limits
+        RateLimiter::update_config(&env, &RateLimitConfig::default(), &admin)?;
+
+        env.storage().instance().set(&DataKey::Initialized, &true);
+        emit_marketplace_initialized(&env, MarketplaceInitializedEvent {
+            admin,
+            platform_fee_bps: fee_config.platform_fee_bps,
+            fee_recipient: fee_config.fee_recipient,
+            timestamp: env.ledger().timestamp(),
+        });
+
         Ok(())
     }
 
+    /// Check whether the contract has been initialized
+    pub fn is_initialized(env: Env) -> bool {
+        env.storage().instance().has(&DataKey::Initialized)
+    }
+
     /// Create a fixed-price sale
     pub fn create_sale(
         env: Env,
@@ -84,30 +124,32 @@ impl MarketplaceSettlement {
         currency: Asset,
         duration_seconds: u64
     ) -> Result<u64, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        seller.require_auth();
+        require_not_banned(&env, &seller)?;
         ReentrancyGuard::execute(&env, &seller, "create_sale", || {
             // Validate inputs
-            asset_utils::validate_asset(&currency, &Vec::new(&env), &env)?;
+            asset_utils::validate_payment_amount(price, 1)?;
             asset_utils::validate_nft_contract(&nft_address, &env)?;
+            let now = env.ledger().timestamp();
+            let expires_at = time_utils::calculate_expiration(now, duration_seconds)?;
             time_utils::validate_transaction_timing(
-                env.ledger().timestamp(),
-                env.ledger().timestamp() + duration_seconds,
+                now,
+                expires_at,
                 2592000, // 30 days max
                 &env
             )?;
 
             // Check NFT ownership
-            asset_utils::check_nft_ownership(&nft_address, token_id, &seller, &env)?;
+            if !asset_utils::check_nft_ownership(&nft_address, token_id, &seller, &env)? {
+                return Err(SettlementError::Unauthorized);
+            }
 
             // Calculate royalties
-            let royalty_distribution = RoyaltyDistributor::calculate_royalties(
-                &env,
-                &nft_address,
-                token_id,
-                price
-            )?;
+            let royalty_distribution = royalties_or_default(&env, &nft_address, token_id, price, &seller);
 
             // Calculate platform fee
-            let platform_fee = FeeManager::calculate_fee(&env, price, &seller)?;
+            let platform_fee = FeeManager::calculate_fee(&env, price, &seller, Some(nft_address.clone()))?;
 
             let transaction_id = SaleTransactionStore::next_id(&env);
 
@@ -120,14 +162,15 @@ impl MarketplaceSettlement {
                 price,
                 currency: currency.clone(),
                 state: crate::types::TransactionState::Pending,
-                created_at: env.ledger().timestamp(),
-                expires_at: env.ledger().timestamp() + duration_seconds,
+                created_at: now,
+                expires_at,
                 escrow_address: env.current_contract_address(),
                 royalty_info: royalty_distribution,
                 platform_fee,
             };
 
             SaleTransactionStore::put(&env, &sale)?;
+            FloorPriceStore::listed(&env, &nft_address, price, expires_at);
 
             // Initialize atomic swap
             AtomicSwapEngine::initialize_swap(
@@ -141,72 +184,300 @@ impl MarketplaceSettlement {
                 price
             )?;
 
+            emit_sale_created(&env, SaleCreatedEvent {
+                transaction_id,
+                seller: seller.clone(),
+                nft_address,
+                token_id,
+                price,
+                currency,
+                expires_at,
+                timestamp: now,
+            });
+
             Ok(transaction_id)
         })
     }
 
-    /// Execute a sale
-    pub fn execute_sale(
+    /// Buy a listed NFT at its fixed price, optionally crediting a referrer with part of the fee
+    ///
+    /// Fails with `InvalidAmount` if the price is above `max_price`, the most the buyer agreed to pay.
+    pub fn buy(
         env: Env,
         transaction_id: u64,
         buyer: Address,
-        payment_amount: i128
+        max_price: i128,
+        referrer: Option<Address>
     ) -> Result<ExecutionResult, SettlementError> {
-        ReentrancyGuard::execute(&env, &buyer, "execute_sale", || {
+        require_not_emergency_paused(&env)?;
+        buyer.require_auth();
+        ReentrancyGuard::execute(&env, &buyer, "buy", || {
             let mut sale = SaleTransactionStore::get(&env, transaction_id)?;
 
-            // Validate sale state
             if sale.state != crate::types::TransactionState::Pending {
                 return Err(SettlementError::InvalidState);
             }
 
-            // Check expiration
             if time_utils::is_expired(sale.expires_at, &env) {
                 return Err(SettlementError::Expired);
             }
 
-            // Validate payment
-            if payment_amount != sale.price {
+            if sale.seller == buyer {
+                return Err(SettlementError::Unauthorized);
+            }
+
+            // Slippage protection: the listing may have changed since the buyer signed
+            if sale.price > max_price {
                 return Err(SettlementError::InvalidAmount);
             }
 
-            // Update sale with buyer
+            // An oversized sale halts settlement and stays listed
+            if CircuitBreaker::check(&env, transaction_id, sale.price)? {
+                return Ok(halted_settlement(&env, transaction_id));
+            }
+
+            sale.buyer = Some(buyer.clone());
+            sale.state = crate::types::TransactionState::Executed;
+            SaleTransactionStore::update(&env, &sale)?;
+            FloorPriceStore::delisted(&env, &sale.nft_address, sale.price);
+            record_sale_history(&env, &sale)?;
+            CollectionStatsStore::record_sale(&env, &sale, &buyer)?;
+
+            // Collect payment into escrow, then settle from it
+            let contract = env.current_contract_address();
+            asset_utils::transfer_tokens_checked(&sale.currency.contract, &buyer, &contract, sale.price, &env)?;
+            settle_sale(&env, &sale, &buyer, referrer)
+        })
+    }
+
+    /// Register the ed25519 key that signs a seller's off-chain listings
+    pub fn register_listing_key(env: Env, seller: Address, public_key: BytesN<32>) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        seller.require_auth();
+        ListingSignature::register_key(&env, &seller, &public_key);
+        Ok(())
+    }
+
+    /// Get the bytes a seller signs for an off-chain listing
+    pub fn get_listing_message(env: Env, listing: OffChainListing) -> Bytes {
+        ListingSignature::message(&env, &listing)
+    }
+
+    /// Get an address's account nonce, which off-chain listings it signs must not fall below
+    pub fn get_nonce(env: Env, address: Address) -> u64 {
+        AccountNonce::get(&env, &address)
+    }
+
+    /// Buy an NFT from a listing the seller signed off-chain
+    pub fn execute_off_chain_listing(
+        env: Env,
+        listing: OffChainListing,
+        buyer: Address
+    ) -> Result<ExecutionResult, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        buyer.require_auth();
+        require_not_banned(&env, &buyer)?;
+        require_not_banned(&env, &listing.seller)?;
+        ReentrancyGuard::execute(&env, &buyer, "execute_off_chain_listing", || {
+            if time_utils::is_expired(listing.expiry, &env) {
+                return Err(SettlementError::Expired);
+            }
+
+            if listing.seller == buyer {
+                return Err(SettlementError::Unauthorized);
+            }
+
+            asset_utils::validate_payment_amount(listing.price, 1)?;
+            asset_utils::validate_nft_contract(&listing.nft_address, &env)?;
+            ListingSignature::verify_and_consume(&env, &listing)?;
+
+            if !asset_utils::check_nft_ownership(&listing.nft_address, listing.token_id, &listing.seller, &env)? {
+                return Err(SettlementError::Unauthorized);
+            }
+
+            // Only a listing the seller signed can halt settlement, and it is spent
+            // either way; the seller re-signs once the breaker is reset
+            let transaction_id = SaleTransactionStore::next_id(&env);
+            if CircuitBreaker::check(&env, transaction_id, listing.price)? {
+                return Ok(halted_settlement(&env, transaction_id));
+            }
+
+            // Record the listing as an executed sale so it joins the token's history
+            let now = env.ledger().timestamp();
+            let sale = SaleTransaction {
+                transaction_id,
+                seller: listing.seller.clone(),
+                buyer: Some(buyer.clone()),
+                nft_address: listing.nft_address.clone(),
+                token_id: listing.token_id,
+                price: listing.price,
+                currency: listing.currency.clone(),
+                state: crate::types::TransactionState::Executed,
+                created_at: now,
+                expires_at: listing.expiry,
+                escrow_address: env.current_contract_address(),
+                royalty_info: royalties_or_default(&env, &listing.nft_address, listing.token_id, listing.price, &listing.seller),
+                platform_fee: FeeManager::calculate_fee(&env, listing.price, &listing.seller, Some(listing.nft_address.clone()))?,
+            };
+            SaleTransactionStore::put(&env, &sale)?;
+            record_sale_history(&env, &sale)?;
+            CollectionStatsStore::record_sale(&env, &sale, &buyer)?;
+
+            let contract = env.current_contract_address();
+            asset_utils::transfer_tokens_checked(&sale.currency.contract, &buyer, &contract, sale.price, &env)?;
+            settle_sale(&env, &sale, &buyer, None)
+        })
+    }
+
+    /// Escrow the buyer's payment for a fixed-price sale ahead of execution
+    pub fn fund_sale(env: Env, transaction_id: u64, buyer: Address) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        buyer.require_auth();
+        require_not_banned(&env, &buyer)?;
+        ReentrancyGuard::execute(&env, &buyer, "fund_sale", || {
+            let mut sale = SaleTransactionStore::get(&env, transaction_id)?;
+
+            if sale.state != crate::types::TransactionState::Pending {
+                return Err(SettlementError::InvalidState);
+            }
+
+            if time_utils::is_expired(sale.expires_at, &env) {
+                return Err(SettlementError::Expired);
+            }
+
+            if sale.seller == buyer {
+                return Err(SettlementError::Unauthorized);
+            }
+
+            let contract = env.current_contract_address();
+            asset_utils::transfer_tokens_checked(&sale.currency.contract, &buyer, &contract, sale.price, &env)?;
+
             sale.buyer = Some(buyer.clone());
             sale.state = crate::types::TransactionState::Funded;
             SaleTransactionStore::update(&env, &sale)?;
+            SaleEscrowStore::set(&env, transaction_id, sale.price);
+            FloorPriceStore::delisted(&env, &sale.nft_address, sale.price);
 
-            // Execute atomic swap
-            AtomicSwapEngine::execute_swap(&env, transaction_id, &buyer)?;
+            emit_sale_funded(&env, SaleFundedEvent {
+                transaction_id,
+                buyer: buyer.clone(),
+                amount: sale.price,
+                timestamp: env.ledger().timestamp(),
+            });
+            Ok(())
+        })
+    }
 
-            // Distribute royalties and fees
-            let distribution_result = RoyaltyDistributor::distribute_royalties(
-                &env,
+    /// Return a funded sale's escrowed payment to its buyer and cancel the sale
+    pub fn refund_escrow(env: Env, transaction_id: u64) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        let sale = SaleTransactionStore::get(&env, transaction_id)?;
+        let buyer = sale.buyer.clone().ok_or(SettlementError::InvalidState)?;
+        buyer.require_auth();
+        ReentrancyGuard::execute(&env, &buyer, "refund_escrow", || {
+            let mut sale = SaleTransactionStore::get(&env, transaction_id)?;
+            if sale.state != crate::types::TransactionState::Funded {
+                return Err(SettlementError::InvalidState);
+            }
+
+            refund_sale_escrow(&env, &sale)?;
+            sale.state = crate::types::TransactionState::Cancelled;
+            SaleTransactionStore::update(&env, &sale)?;
+
+            emit_sale_cancelled(&env, SaleCancelledEvent {
                 transaction_id,
-                &sale.royalty_info,
-                &sale.currency
-            )?;
+                cancelled_by: buyer.clone(),
+                reason: Bytes::from_slice(&env, b"refunded"),
+                timestamp: env.ledger().timestamp(),
+            });
+            Ok(())
+        })
+    }
 
-            // Collect platform fee
-            FeeManager::collect_platform_fee(
-                &env,
-                sale.platform_fee,
-                &sale.currency,
-                &buyer
-            )?;
+    /// Get the buyer funds escrowed for a sale
+    pub fn get_sale_escrow(env: Env, transaction_id: u64) -> i128 {
+        SaleEscrowStore::get(&env, transaction_id)
+    }
 
-            // Update final state
-            sale.state = crate::types::TransactionState::Executed;
+    /// Cancel a fixed-price sale that hasn't been bought yet, refunding any escrowed payment
+    pub fn cancel_sale(env: Env, transaction_id: u64, seller: Address) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        seller.require_auth();
+        ReentrancyGuard::execute(&env, &seller, "cancel_sale", || {
+            let mut sale = SaleTransactionStore::get(&env, transaction_id)?;
+            if sale.seller != seller {
+                return Err(SettlementError::Unauthorized);
+            }
+            match sale.state {
+                crate::types::TransactionState::Pending => {
+                    FloorPriceStore::delisted(&env, &sale.nft_address, sale.price)
+                }
+                crate::types::TransactionState::Funded => refund_sale_escrow(&env, &sale)?,
+                _ => return Err(SettlementError::InvalidState),
+            }
+
+            sale.state = crate::types::TransactionState::Cancelled;
             SaleTransactionStore::update(&env, &sale)?;
 
-            Ok(ExecutionResult {
+            emit_sale_cancelled(&env, SaleCancelledEvent {
                 transaction_id,
-                success: true,
-                transferred_nft: true,
-                transferred_payment: true,
-                distributed_royalties: distribution_result.distribution_success,
-                collected_platform_fee: true,
+                cancelled_by: seller.clone(),
+                reason: Bytes::from_slice(&env, b"cancelled"),
                 timestamp: env.ledger().timestamp(),
-            })
+            });
+            Ok(())
+        })
+    }
+
+    /// Execute a funded sale, paying out its escrow
+    pub fn execute_sale(
+        env: Env,
+        transaction_id: u64,
+        buyer: Address,
+        payment_amount: i128
+    ) -> Result<ExecutionResult, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        buyer.require_auth();
+        ReentrancyGuard::execute(&env, &buyer, "execute_sale", || {
+            let mut sale = SaleTransactionStore::get(&env, transaction_id)?;
+
+            // Only a sale whose payment is already in escrow can execute
+            if sale.state != crate::types::TransactionState::Funded {
+                return Err(SettlementError::InvalidState);
+            }
+
+            if sale.buyer != Some(buyer.clone()) {
+                return Err(SettlementError::Unauthorized);
+            }
+
+            // Check expiration
+            if time_utils::is_expired(sale.expires_at, &env) {
+                return Err(SettlementError::Expired);
+            }
+
+            // Validate payment against the escrow
+            if payment_amount != sale.price || payment_amount != SaleEscrowStore::get(&env, transaction_id) {
+                return Err(SettlementError::InvalidAmount);
+            }
+
+            // An oversized sale halts settlement instead of executing
+            if CircuitBreaker::check(&env, transaction_id, sale.price)? {
+                return Ok(halted_settlement(&env, transaction_id));
+            }
+
+            // Royalties are owed as they stand when the sale settles, not when it was listed
+            sale.royalty_info = royalties_or_default(&env, &sale.nft_address, sale.token_id, sale.price, &sale.seller);
+
+            // Release the escrow and settle
+            SaleEscrowStore::take(&env, transaction_id);
+            sale.state = crate::types::TransactionState::Executed;
+            SaleTransactionStore::update(&env, &sale)?;
+            record_sale_history(&env, &sale)?;
+            CollectionStatsStore::record_sale(&env, &sale, &buyer)?;
+
+            let result = settle_sale(&env, &sale, &buyer, None)?;
+            asset_utils::notify_nft_sold(&sale.nft_address, sale.token_id, sale.price, &env);
+            Ok(result)
         })
     }
 
@@ -221,9 +492,21 @@ impl MarketplaceSettlement {
         duration_seconds: u64,
         bid_increment: i128,
         auction_type: AuctionType,
-        currency: Asset
+        currency: Asset,
+        options: AuctionOptions
     ) -> Result<u64, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        seller.require_auth();
+        require_not_banned(&env, &seller)?;
         ReentrancyGuard::execute(&env, &seller, "create_auction", || {
+            // A bundle's items are only known to create_bundle_auction
+            if auction_type == AuctionType::Bundle {
+                return Err(SettlementError::InvalidState);
+            }
+            asset_utils::validate_nft_contract(&nft_address, &env)?;
+            if !asset_utils::check_nft_ownership(&nft_address, token_id, &seller, &env)? {
+                return Err(SettlementError::Unauthorized);
+            }
             AuctionEngine::create_auction(
                 &env,
                 auction_type,
@@ -234,11 +517,57 @@ impl MarketplaceSettlement {
                 reserve_price,
                 duration_seconds,
                 bid_increment,
+                &currency,
+                &options
+            )
+        })
+    }
+
+    /// Auction several NFTs from the same seller as a single lot
+    pub fn create_bundle_auction(
+        env: Env,
+        seller: Address,
+        items: Vec<crate::types::NFTItem>,
+        starting_price: i128,
+        reserve_price: i128,
+        duration_seconds: u64,
+        bid_increment: i128,
+        currency: Asset
+    ) -> Result<u64, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        seller.require_auth();
+        require_not_banned(&env, &seller)?;
+        ReentrancyGuard::execute(&env, &seller, "create_bundle_auction", || {
+            for item in items.iter() {
+                asset_utils::validate_nft_contract(&item.nft_address, &env)?;
+            }
+            AuctionEngine::create_bundle_auction(
+                &env,
+                &seller,
+                &items,
+                starting_price,
+                reserve_price,
+                duration_seconds,
+                bid_increment,
                 &currency
             )
         })
     }
 
+    /// Settle a bundle auction, delivering the whole lot to the winner
+    pub fn end_bundle_auction(env: Env, auction_id: u64, caller: Address) -> Result<ExecutionResult, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        caller.require_auth();
+        ReentrancyGuard::execute(&env, &caller, "end_bundle_auction", || {
+            AuctionEngine::end_bundle_auction(&env, auction_id, &caller)
+        })
+    }
+
+    /// Get the NFTs and pricing of a bundle auction
+    pub fn get_bundle_auction(env: Env, auction_id: u64) -> Result<BundleAuctionData, SettlementError> {
+        AuctionEngine::get_bundle_auction(&env, auction_id)
+    }
+
     /// Place a bid on an auction
     pub fn place_bid(
         env: Env,
@@ -247,8 +576,17 @@ impl MarketplaceSettlement {
         bid_amount: i128,
         commitment_hash: Option<Bytes>
     ) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        bidder.require_auth();
+        require_not_banned(&env, &bidder)?;
         ReentrancyGuard::execute(&env, &bidder, "place_bid", || {
-            AuctionEngine::place_bid(&env, auction_id, &bidder, bid_amount, commitment_hash)
+            let limit = RateLimiter::get_config(&env);
+            RateLimiter::check_and_record(&env, &bidder, "place_bid", limit.max_calls, limit.window_seconds)?;
+            AuctionEngine::place_bid(&env, auction_id, &bidder, bid_amount, commitment_hash)?;
+
+            let ttl = StorageTtlStore::get_config(&env);
+            AuctionStore::bump_auction_storage_ttl(&env, auction_id, ttl.auction_min_ttl);
+            Ok(())
         })
     }
 
@@ -260,75 +598,311 @@ impl MarketplaceSettlement {
         bid_amount: i128,
         salt: Bytes
     ) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        bidder.require_auth();
         ReentrancyGuard::execute(&env, &bidder, "reveal_bid", || {
             AuctionEngine::reveal_bid(&env, auction_id, &bidder, bid_amount, &salt)
         })
     }
 
-    /// End an auction
-    pub fn end_auction(env: Env, auction_id: u64, caller: Address) -> Result<(), SettlementError> {
-        ReentrancyGuard::execute(&env, &caller, "end_auction", || {
-            AuctionEngine::end_auction(&env, auction_id, &caller)
+    /// Allow an additional bidder on a private auction (seller only)
+    pub fn add_auction_bidder(
+        env: Env,
+        auction_id: u64,
+        seller: Address,
+        bidder: Address
+    ) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        seller.require_auth();
+        ReentrancyGuard::execute(&env, &seller, "add_auction_bidder", || {
+            AuctionEngine::add_auction_bidder(&env, auction_id, &seller, &bidder)
         })
     }
 
-    /// Create a trade
-    pub fn create_trade(
+    /// Remove a bidder from a private auction (seller only)
+    pub fn remove_auction_bidder(
         env: Env,
-        initiator: Address,
-        counterparty: Option<Address>,
-        initiator_nfts: Vec<crate::types::NFTItem>,
-        counterparty_nfts: Vec<crate::types::NFTItem>,
-        duration_seconds: u64
-    ) -> Result<u64, SettlementError> {
-        ReentrancyGuard::execute(&env, &initiator, "create_trade", || {
-            // Validate trade parameters
-            if initiator_nfts.is_empty() {
-                return Err(SettlementError::InvalidAmount);
-            }
-
-            let trade_id = TradeTransactionStore::next_id(&env);
-
-            let trade = TradeTransaction {
-                trade_id,
-                initiator: initiator.clone(),
-                counterparty,
-                initiator_nfts,
-                counterparty_nfts,
-                state: crate::types::TransactionState::Pending,
-                created_at: env.ledger().timestamp(),
-                expires_at: env.ledger().timestamp() + duration_seconds,
-                platform_fee: 0, // Would be calculated
-            };
-
-            TradeTransactionStore::put(&env, &trade)?;
-            Ok(trade_id)
+        auction_id: u64,
+        seller: Address,
+        bidder: Address
+    ) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        seller.require_auth();
+        ReentrancyGuard::execute(&env, &seller, "remove_auction_bidder", || {
+            AuctionEngine::remove_auction_bidder(&env, auction_id, &seller, &bidder)
         })
     }
 
-    /// Accept a trade
-    pub fn accept_trade(env: Env, trade_id: u64, acceptor: Address) -> Result<(), SettlementError> {
-        ReentrancyGuard::execute(&env, &acceptor.clone(), "accept_trade", || {
-            let mut trade = TradeTransactionStore::get(&env, trade_id)?;
+    /// Set a proxy bid that bids automatically up to `max_amount`
+    pub fn set_proxy_bid(
+        env: Env,
+        auction_id: u64,
+        bidder: Address,
+        max_amount: i128
+    ) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        bidder.require_auth();
+        ReentrancyGuard::execute(&env, &bidder, "set_proxy_bid", || {
+            AuctionEngine::set_proxy_bid(&env, auction_id, &bidder, max_amount)
+        })
+    }
 
-            if trade.state != crate::types::TransactionState::Pending {
-                return Err(SettlementError::InvalidState);
-            }
+    /// Get a bidder's proxy bid
+    pub fn get_proxy_bid(env: Env, auction_id: u64, bidder: Address) -> Option<ProxyBid> {
+        AuctionEngine::get_proxy_bid(&env, auction_id, &bidder)
+    }
 
-            if time_utils::is_expired(trade.expires_at, &env) {
-                return Err(SettlementError::Expired);
-            }
+    /// Pause a live auction (seller only)
+    pub fn pause_auction(env: Env, auction_id: u64, caller: Address) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        caller.require_auth();
+        ReentrancyGuard::execute(&env, &caller, "pause_auction", || {
+            AuctionEngine::pause_auction(&env, auction_id, &caller)
+        })
+    }
 
-            trade.counterparty = Some(acceptor);
-            trade.state = crate::types::TransactionState::Funded;
-            TradeTransactionStore::update(&env, &trade)?;
+    /// Resume a paused auction (seller only)
+    pub fn resume_auction(env: Env, auction_id: u64, caller: Address) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        caller.require_auth();
+        ReentrancyGuard::execute(&env, &caller, "resume_auction", || {
+            AuctionEngine::resume_auction(&env, auction_id, &caller)
+        })
+    }
 
-            Ok(())
+    /// Withdraw bid funds held in escrow after being outbid
+    pub fn withdraw_bid_escrow(env: Env, auction_id: u64, bidder: Address) -> Result<i128, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        bidder.require_auth();
+        ReentrancyGuard::execute(&env, &bidder, "withdraw_bid_escrow", || {
+            AuctionEngine::withdraw_bid_escrow(&env, auction_id, &bidder)
         })
     }
 
-    /// Execute a trade
-    pub fn execute_trade(env: Env, trade_id: u64, executor: Address) -> Result<(), SettlementError> {
+    /// Get bid funds held in escrow for a bidder
+    pub fn get_bid_escrow(env: Env, auction_id: u64, bidder: Address) -> i128 {
+        AuctionEngine::get_bid_escrow(&env, auction_id, &bidder)
+    }
+
+    /// Buy an auctioned NFT immediately at its buyout price
+    pub fn buy_now(
+        env: Env,
+        auction_id: u64,
+        buyer: Address
+    ) -> Result<ExecutionResult, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        buyer.require_auth();
+        ReentrancyGuard::execute(&env, &buyer, "buy_now", || {
+            let Some(price) = AuctionEngine::buy_now(&env, auction_id, &buyer)? else {
+                return Ok(halted_settlement(&env, auction_id));
+            };
+            let auction = AuctionStore::get(&env, auction_id)?;
+
+            // Collect payment into escrow and hand the NFT to the buyer
+            let contract = env.current_contract_address();
+            asset_utils::transfer_tokens_checked(&auction.currency.contract, &buyer, &contract, price, &env)?;
+            asset_utils::transfer_nft(&auction.nft_address, &auction.seller, &buyer, auction.token_id, &env)?;
+
+            // Use configured royalties when available, otherwise the auction's defaults
+            let royalty_distribution = AuctionEngine::royalties_for(&env, &auction, price);
+            let distribution_result = RoyaltyDistributor::distribute_royalties(
+                &env,
+                auction_id,
+                &royalty_distribution,
+                &auction.currency,
+                price,
+                &auction.seller
+            )?;
+
+            let platform_fee = FeeManager::calculate_fee(&env, price, &buyer, Some(auction.nft_address.clone()))?;
+            FeeManager::collect_platform_fee(&env, platform_fee, &auction.currency, &buyer, None)?;
+
+            Ok(ExecutionResult {
+                transaction_id: auction_id,
+                success: true,
+                transferred_nft: true,
+                transferred_payment: true,
+                distributed_royalties: distribution_result.distribution_success,
+                collected_platform_fee: true,
+                timestamp: env.ledger().timestamp(),
+            })
+        })
+    }
+
+    /// Reveal a sealed bid on a Vickrey auction
+    pub fn reveal_vickrey_bid(
+        env: Env,
+        auction_id: u64,
+        bidder: Address,
+        bid_amount: i128,
+        salt: Bytes
+    ) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        bidder.require_auth();
+        ReentrancyGuard::execute(&env, &bidder, "reveal_vickrey_bid", || {
+            AuctionEngine::reveal_vickrey_bid(&env, auction_id, &bidder, bid_amount, &salt)
+        })
+    }
+
+    /// Settle a Vickrey auction after its reveal period
+    pub fn end_vickrey_auction(env: Env, auction_id: u64, caller: Address) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        caller.require_auth();
+        ReentrancyGuard::execute(&env, &caller, "end_vickrey_auction", || {
+            AuctionEngine::end_vickrey_auction(&env, auction_id, &caller)
+        })
+    }
+
+    /// Reveal a bid on a sealed first-price auction
+    pub fn reveal_sealed_bid(
+        env: Env,
+        auction_id: u64,
+        bidder: Address,
+        bid_amount: i128,
+        salt: Bytes
+    ) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        bidder.require_auth();
+        ReentrancyGuard::execute(&env, &bidder, "reveal_sealed_bid", || {
+            AuctionEngine::reveal_sealed_bid(&env, auction_id, &bidder, bid_amount, &salt)
+        })
+    }
+
+    /// Settle a sealed first-price auction after its reveal period
+    pub fn end_sealed_auction(env: Env, auction_id: u64, caller: Address) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        caller.require_auth();
+        ReentrancyGuard::execute(&env, &caller, "end_sealed_auction", || {
+            AuctionEngine::end_sealed_auction(&env, auction_id, &caller)
+        })
+    }
+
+    /// Settle a candle auction after its window closes
+    pub fn end_candle_auction(env: Env, auction_id: u64, caller: Address) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        caller.require_auth();
+        ReentrancyGuard::execute(&env, &caller, "end_candle_auction", || {
+            AuctionEngine::end_candle_auction(&env, auction_id, &caller)
+        })
+    }
+
+    /// End an auction, settling the winning bid
+    ///
+    /// Anyone can settle an auction that has ended. The seller can also pass a nonzero
+    /// `min_acceptable_price`, signed by them, and settlement then fails with
+    /// `InvalidAmount` if the winning bid is below it.
+    pub fn end_auction(
+        env: Env,
+        auction_id: u64,
+        caller: Address,
+        min_acceptable_price: i128
+    ) -> Result<ExecutionResult, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        caller.require_auth();
+        ReentrancyGuard::execute(&env, &caller, "end_auction", || {
+            AuctionEngine::end_auction(&env, auction_id, &caller, min_acceptable_price)
+        })
+    }
+
+    /// Open a scheduled auction for bidding once its start time is reached
+    pub fn open_scheduled_auction(env: Env, auction_id: u64) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        AuctionEngine::open_scheduled_auction(&env, auction_id)
+    }
+
+    /// Get the auctions waiting to open as (start_time, auction_id), soonest first
+    pub fn get_scheduled_auctions(env: Env) -> Vec<(u64, u64)> {
+        AuctionStore::get_scheduled(&env)
+    }
+
+    /// Cancel an auction that hasn't received any bids (seller only)
+    pub fn cancel_auction(env: Env, auction_id: u64, seller: Address) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        seller.require_auth();
+        ReentrancyGuard::execute(&env, &seller, "cancel_auction", || {
+            AuctionEngine::cancel_auction(&env, auction_id, &seller)
+        })
+    }
+
+    /// Create a trade
+    pub fn create_trade(
+        env: Env,
+        initiator: Address,
+        counterparty: Option<Address>,
+        initiator_nfts: Vec<crate::types::NFTItem>,
+        counterparty_nfts: Vec<crate::types::NFTItem>,
+        top_up_amount: i128,
+        top_up_currency: Option<Address>,
+        duration_seconds: u64
+    ) -> Result<u64, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        initiator.require_auth();
+        ReentrancyGuard::execute(&env, &initiator, "create_trade", || {
+            // Validate trade parameters
+            if initiator_nfts.is_empty() {
+                return Err(SettlementError::InvalidAmount);
+            }
+
+            // A top-up needs a currency to be paid in
+            if top_up_amount != 0 && top_up_currency.is_none() {
+                return Err(SettlementError::InvalidCurrency);
+            }
+
+            let trade_id = TradeTransactionStore::next_id(&env);
+
+            let trade = TradeTransaction {
+                trade_id,
+                initiator: initiator.clone(),
+                counterparty,
+                initiator_nfts,
+                counterparty_nfts,
+                top_up_amount,
+                top_up_currency,
+                state: crate::types::TransactionState::Pending,
+                created_at: env.ledger().timestamp(),
+                expires_at: env.ledger().timestamp() + duration_seconds,
+                counter_expires_at: 0,
+                counter_rounds: 0,
+                last_countered_by: None,
+                platform_fee: 0, // Would be calculated
+            };
+
+            TradeTransactionStore::put(&env, &trade)?;
+            Ok(trade_id)
+        })
+    }
+
+    /// Accept a trade
+    pub fn accept_trade(env: Env, trade_id: u64, acceptor: Address) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        acceptor.require_auth();
+        ReentrancyGuard::execute(&env, &acceptor.clone(), "accept_trade", || {
+            let mut trade = TradeTransactionStore::get(&env, trade_id)?;
+
+            if trade.state != crate::types::TransactionState::Pending {
+                return Err(SettlementError::InvalidState);
+            }
+
+            AtomicSwapEngine::check_acceptor(&trade, &acceptor)?;
+
+            if time_utils::is_expired(AtomicSwapEngine::trade_deadline(&trade), &env) {
+                return Err(SettlementError::Expired);
+            }
+
+            if trade.counterparty.is_none() {
+                trade.counterparty = Some(acceptor);
+            }
+            trade.state = crate::types::TransactionState::Funded;
+            TradeTransactionStore::update(&env, &trade)?;
+
+            Ok(())
+        })
+    }
+
+    /// Execute a trade
+    pub fn execute_trade(env: Env, trade_id: u64, executor: Address) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        executor.require_auth();
         ReentrancyGuard::execute(&env, &executor, "execute_trade", || {
             let mut trade = TradeTransactionStore::get(&env, trade_id)?;
 
@@ -336,158 +910,1259 @@ impl MarketplaceSettlement {
                 return Err(SettlementError::InvalidState);
             }
 
-            // Execute NFT swaps
-            // This is a simplified implementation
-            trade.state = crate::types::TransactionState::Executed;
-            TradeTransactionStore::update(&env, &trade)?;
+            if executor != trade.initiator && trade.counterparty != Some(executor.clone()) {
+                return Err(SettlementError::Unauthorized);
+            }
+
+            if time_utils::is_expired(AtomicSwapEngine::trade_deadline(&trade), &env) {
+                return Err(SettlementError::Expired);
+            }
+
+            trade.state = crate::types::TransactionState::Executed;
+            TradeTransactionStore::update(&env, &trade)?;
+
+            AtomicSwapEngine::execute_trade(&env, &trade)
+        })
+    }
+
+    /// Counter a trade with different counterparty NFTs and top-up
+    ///
+    /// Either party may counter; the other then has to accept the new terms.
+    pub fn counter_offer(
+        env: Env,
+        trade_id: u64,
+        counterparty: Address,
+        modified_nfts: Vec<crate::types::NFTItem>,
+        modified_top_up: i128
+    ) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        counterparty.require_auth();
+        ReentrancyGuard::execute(&env, &counterparty, "counter_offer", || {
+            let mut trade = TradeTransactionStore::get(&env, trade_id)?;
+            AtomicSwapEngine::counter_offer(&env, &mut trade, &counterparty, modified_nfts, modified_top_up)?;
+            TradeTransactionStore::update(&env, &trade)
+        })
+    }
+
+    /// Get a trade
+    pub fn get_trade(env: Env, trade_id: u64) -> Result<TradeTransaction, SettlementError> {
+        TradeTransactionStore::get(&env, trade_id)
+    }
+
+    /// Propose a trade between three or more parties
+    ///
+    /// `recipient_index[i]` is the party receiving `offered_nfts[i]`. Proposing counts as
+    /// the proposer's signature, and escrows the proposer's top-up if they owe one.
+    pub fn propose_multi_trade(
+        env: Env,
+        proposer: Address,
+        parties: Vec<Address>,
+        offered_nfts: Vec<crate::types::NFTItem>,
+        recipient_index: Vec<u32>,
+        top_up_amounts: Vec<i128>,
+        top_up_currency: Option<Address>,
+        duration_seconds: u64
+    ) -> Result<u64, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        proposer.require_auth();
+        ReentrancyGuard::execute(&env, &proposer, "propose_multi_trade", || {
+            AtomicSwapEngine::propose_multi_trade(
+                &env,
+                &proposer,
+                parties,
+                offered_nfts,
+                recipient_index,
+                top_up_amounts,
+                top_up_currency,
+                duration_seconds
+            )
+        })
+    }
+
+    /// Sign a multi-party trade, which executes once every party has signed
+    pub fn sign_multi_trade(env: Env, trade_id: u64, party: Address) -> Result<bool, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        party.require_auth();
+        ReentrancyGuard::execute(&env, &party, "sign_multi_trade", || {
+            AtomicSwapEngine::sign_multi_trade(&env, trade_id, &party)
+        })
+    }
+
+    /// Cancel a pending multi-party trade, refunding any escrowed top-ups
+    pub fn cancel_multi_trade(env: Env, trade_id: u64, party: Address) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        party.require_auth();
+        ReentrancyGuard::execute(&env, &party, "cancel_multi_trade", || {
+            AtomicSwapEngine::cancel_multi_trade(&env, trade_id, &party)
+        })
+    }
+
+    /// Get a multi-party trade
+    pub fn get_multi_trade(env: Env, trade_id: u64) -> Result<MultiPartyTrade, SettlementError> {
+        AtomicSwapEngine::get_multi_trade(&env, trade_id)
+    }
+
+    /// Create a bundle sale
+    pub fn create_bundle(
+        env: Env,
+        seller: Address,
+        items: Vec<crate::types::NFTItem>,
+        total_price: i128,
+        currency: Asset,
+        duration_seconds: u64
+    ) -> Result<u64, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        seller.require_auth();
+        require_not_banned(&env, &seller)?;
+        ReentrancyGuard::execute(&env, &seller, "create_bundle", || {
+            if items.is_empty() {
+                return Err(SettlementError::InvalidAmount);
+            }
+
+            // Sold items are tracked by token ID, so each must be unique within the bundle
+            for (i, item) in items.iter().enumerate() {
+                if let Some(price) = item.individual_price {
+                    if price <= 0 {
+                        return Err(SettlementError::InvalidAmount);
+                    }
+                }
+                if items.iter().skip(i + 1).any(|other| other.token_id == item.token_id) {
+                    return Err(SettlementError::AlreadyExists);
+                }
+                asset_utils::validate_nft_contract(&item.nft_address, &env)?;
+                if !asset_utils::check_nft_ownership(&item.nft_address, item.token_id, &seller, &env)? {
+                    return Err(SettlementError::Unauthorized);
+                }
+            }
+
+            let bundle_id = BundleTransactionStore::next_id(&env);
+
+            let bundle = BundleTransaction {
+                bundle_id,
+                seller: seller.clone(),
+                buyer: None,
+                items,
+                total_price,
+                currency,
+                state: crate::types::TransactionState::Pending,
+                created_at: env.ledger().timestamp(),
+                expires_at: env.ledger().timestamp() + duration_seconds,
+                platform_fee: 0, // Would be calculated
+            };
+
+            BundleTransactionStore::put(&env, &bundle)?;
+            Ok(bundle_id)
+        })
+    }
+
+    /// Get a bundle sale
+    pub fn get_bundle(env: Env, bundle_id: u64) -> Result<BundleTransaction, SettlementError> {
+        BundleTransactionStore::get(&env, bundle_id)
+    }
+
+    /// Buy a single individually priced item out of an active bundle
+    ///
+    /// The bundle stays open for the remaining items and executes once the last one sells.
+    pub fn purchase_bundle_item(
+        env: Env,
+        bundle_id: u64,
+        token_id: u64,
+        buyer: Address
+    ) -> Result<ExecutionResult, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        buyer.require_auth();
+        ReentrancyGuard::execute(&env, &buyer, "purchase_bundle_item", || {
+            let mut bundle = BundleTransactionStore::get(&env, bundle_id)?;
+            require_bundle_open(&env, &bundle, &buyer)?;
+
+            let item = bundle.items
+                .iter()
+                .find(|item| item.token_id == token_id)
+                .ok_or(SettlementError::NotFound)?;
+            let price = item.individual_price.ok_or(SettlementError::InvalidState)?;
+            if BundleTransactionStore::is_item_sold(&env, bundle_id, token_id) {
+                return Err(SettlementError::AlreadyExists);
+            }
+            BundleTransactionStore::mark_item_sold(&env, bundle_id, token_id);
+
+            let all_sold = bundle.items
+                .iter()
+                .all(|item| BundleTransactionStore::is_item_sold(&env, bundle_id, item.token_id));
+            if all_sold {
+                bundle.buyer = Some(buyer.clone());
+                bundle.state = crate::types::TransactionState::Executed;
+                BundleTransactionStore::update(&env, &bundle)?;
+            }
+
+            let result = settle_bundle_items(&env, &bundle, &Vec::from_array(&env, [item]), price, &buyer)?;
+
+            if all_sold {
+                let mut sold_individually = Vec::new(&env);
+                for item in bundle.items.iter() {
+                    sold_individually.push_back(item.token_id);
+                }
+                emit_bundle_executed(&env, BundleExecutedEvent {
+                    bundle_id,
+                    buyer: buyer.clone(),
+                    sold_individually,
+                    sold_in_bulk: Vec::new(&env),
+                    timestamp: result.timestamp,
+                });
+            }
+            Ok(result)
+        })
+    }
+
+    /// Buy every item still left in a bundle
+    ///
+    /// The price is the bundle's total less what its individually sold items fetched.
+    pub fn purchase_bundle(
+        env: Env,
+        bundle_id: u64,
+        buyer: Address
+    ) -> Result<ExecutionResult, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        buyer.require_auth();
+        ReentrancyGuard::execute(&env, &buyer, "purchase_bundle", || {
+            let mut bundle = BundleTransactionStore::get(&env, bundle_id)?;
+            require_bundle_open(&env, &bundle, &buyer)?;
+
+            let mut price = bundle.total_price;
+            let mut remaining = Vec::new(&env);
+            let mut sold_individually = Vec::new(&env);
+            let mut sold_in_bulk = Vec::new(&env);
+            for item in bundle.items.iter() {
+                if BundleTransactionStore::is_item_sold(&env, bundle_id, item.token_id) {
+                    price = math_utils::safe_sub(price, item.individual_price.unwrap_or(0), &env)?;
+                    sold_individually.push_back(item.token_id);
+                } else {
+                    BundleTransactionStore::mark_item_sold(&env, bundle_id, item.token_id);
+                    sold_in_bulk.push_back(item.token_id);
+                    remaining.push_back(item);
+                }
+            }
+            if price <= 0 {
+                return Err(SettlementError::InvalidAmount);
+            }
+
+            bundle.buyer = Some(buyer.clone());
+            bundle.state = crate::types::TransactionState::Executed;
+            BundleTransactionStore::update(&env, &bundle)?;
+
+            let result = settle_bundle_items(&env, &bundle, &remaining, price, &buyer)?;
+            emit_bundle_executed(&env, BundleExecutedEvent {
+                bundle_id,
+                buyer: buyer.clone(),
+                sold_individually,
+                sold_in_bulk,
+                timestamp: result.timestamp,
+            });
+            Ok(result)
+        })
+    }
+
+    /// Check whether an item has already been sold out of a bundle
+    pub fn is_bundle_item_sold(env: Env, bundle_id: u64, token_id: u64) -> bool {
+        BundleTransactionStore::is_item_sold(&env, bundle_id, token_id)
+    }
+
+    /// Make an offer on an NFT, escrowing the offered amount
+    pub fn make_offer(
+        env: Env,
+        buyer: Address,
+        nft_address: Address,
+        token_id: u64,
+        amount: i128,
+        currency: Asset,
+        duration_seconds: u64
+    ) -> Result<u64, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        buyer.require_auth();
+        require_not_banned(&env, &buyer)?;
+        ReentrancyGuard::execute(&env, &buyer, "make_offer", || {
+            asset_utils::validate_payment_amount(amount, 1)?;
+            asset_utils::validate_nft_contract(&nft_address, &env)?;
+
+            let now = env.ledger().timestamp();
+            let expires_at = time_utils::calculate_expiration(now, duration_seconds)?;
+            time_utils::validate_transaction_timing(
+                now,
+                expires_at,
+                2592000, // 30 days max
+                &env
+            )?;
+
+            // Hold the offered funds until the offer is accepted, cancelled or expires
+            asset_utils::transfer_tokens_checked(
+                &currency.contract,
+                &buyer,
+                &env.current_contract_address(),
+                amount,
+                &env
+            )?;
+
+            let offer_id = OfferStore::next_id(&env);
+            let offer = Offer {
+                offer_id,
+                buyer: buyer.clone(),
+                nft_address: nft_address.clone(),
+                token_id,
+                amount,
+                currency: currency.clone(),
+                expires_at,
+            };
+            OfferStore::put(&env, &offer)?;
+
+            emit_offer_created(&env, OfferCreatedEvent {
+                offer_id,
+                buyer: buyer.clone(),
+                nft_address,
+                token_id,
+                amount,
+                currency,
+                expires_at,
+                timestamp: now,
+            });
+
+            Ok(offer_id)
+        })
+    }
+
+    /// Accept an offer on an NFT the seller owns
+    pub fn accept_offer(
+        env: Env,
+        offer_id: u64,
+        seller: Address
+    ) -> Result<ExecutionResult, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        seller.require_auth();
+        ReentrancyGuard::execute(&env, &seller, "accept_offer", || {
+            let offer = OfferStore::get(&env, offer_id)?;
+
+            if time_utils::is_expired(offer.expires_at, &env) {
+                return Err(SettlementError::Expired);
+            }
+
+            // An oversized offer halts settlement and stays open
+            if CircuitBreaker::check(&env, offer_id, offer.amount)? {
+                return Ok(halted_settlement(&env, offer_id));
+            }
+
+            OfferStore::remove(&env, offer_id)?;
+            let (distribution_result, platform_fee) = settle_offer(&env, &offer, &seller)?;
+
+            let timestamp = env.ledger().timestamp();
+            emit_offer_accepted(&env, OfferAcceptedEvent {
+                offer_id,
+                seller: seller.clone(),
+                buyer: offer.buyer.clone(),
+                nft_address: offer.nft_address.clone(),
+                token_id: offer.token_id,
+                amount: offer.amount,
+                platform_fee,
+                timestamp,
+            });
+
+            Ok(ExecutionResult {
+                transaction_id: offer_id,
+                success: true,
+                transferred_nft: true,
+                transferred_payment: true,
+                distributed_royalties: distribution_result.distribution_success,
+                collected_platform_fee: true,
+                timestamp,
+            })
+        })
+    }
+
+    /// Cancel an offer and refund the escrowed amount to the buyer
+    pub fn cancel_offer(env: Env, offer_id: u64, buyer: Address) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        buyer.require_auth();
+        ReentrancyGuard::execute(&env, &buyer, "cancel_offer", || {
+            let offer = OfferStore::get(&env, offer_id)?;
+            if offer.buyer != buyer {
+                return Err(SettlementError::Unauthorized);
+            }
+
+            OfferStore::remove(&env, offer_id)?;
+            refund_offer(&env, &offer, "cancelled")
+        })
+    }
+
+    /// Make a floor offer for up to `max_quantity` tokens from a collection
+    pub fn make_collection_offer(
+        env: Env,
+        buyer: Address,
+        nft_address: Address,
+        amount: i128,
+        currency: Asset,
+        duration_seconds: u64,
+        max_quantity: u32
+    ) -> Result<u64, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        buyer.require_auth();
+        ReentrancyGuard::execute(&env, &buyer, "make_collection_offer", || {
+            asset_utils::validate_payment_amount(amount, 1)?;
+            asset_utils::validate_nft_contract(&nft_address, &env)?;
+            if max_quantity == 0 {
+                return Err(SettlementError::InvalidAmount);
+            }
+
+            let now = env.ledger().timestamp();
+            let expires_at = time_utils::calculate_expiration(now, duration_seconds)?;
+            time_utils::validate_transaction_timing(
+                now,
+                expires_at,
+                2592000, // 30 days max
+                &env
+            )?;
+
+            // Escrow enough to fill every requested token
+            let escrow = math_utils::safe_mul(amount, max_quantity as i128, &env)?;
+            asset_utils::transfer_tokens_checked(
+                &currency.contract,
+                &buyer,
+                &env.current_contract_address(),
+                escrow,
+                &env
+            )?;
+
+            let offer_id = CollectionOfferStore::next_id(&env);
+            let offer = CollectionOffer {
+                offer_id,
+                buyer: buyer.clone(),
+                nft_address: nft_address.clone(),
+                amount,
+                currency: currency.clone(),
+                expires_at,
+                max_quantity,
+            };
+            CollectionOfferStore::put(&env, &offer)?;
+
+            emit_collection_offer_created(&env, CollectionOfferCreatedEvent {
+                offer_id,
+                buyer: buyer.clone(),
+                nft_address,
+                amount,
+                currency,
+                max_quantity,
+                expires_at,
+                timestamp: now,
+            });
+
+            Ok(offer_id)
+        })
+    }
+
+    /// Sell a token from the offer's collection into a collection offer
+    pub fn fill_collection_offer(
+        env: Env,
+        offer_id: u64,
+        seller: Address,
+        token_id: u64
+    ) -> Result<ExecutionResult, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        seller.require_auth();
+        ReentrancyGuard::execute(&env, &seller, "fill_collection_offer", || {
+            let mut offer = CollectionOfferStore::get(&env, offer_id)?;
+
+            if time_utils::is_expired(offer.expires_at, &env) {
+                return Err(SettlementError::Expired);
+            }
+
+            if CircuitBreaker::check(&env, offer_id, offer.amount)? {
+                return Ok(halted_settlement(&env, offer_id));
+            }
+
+            let fill = Offer {
+                offer_id,
+                buyer: offer.buyer.clone(),
+                nft_address: offer.nft_address.clone(),
+                token_id,
+                amount: offer.amount,
+                currency: offer.currency.clone(),
+                expires_at: offer.expires_at,
+            };
+            let (distribution_result, platform_fee) = settle_offer(&env, &fill, &seller)?;
+
+            offer.max_quantity -= 1;
+            if offer.max_quantity == 0 {
+                CollectionOfferStore::remove(&env, offer_id)?;
+            } else {
+                CollectionOfferStore::update(&env, &offer)?;
+            }
+
+            let timestamp = env.ledger().timestamp();
+            emit_collection_offer_filled(&env, CollectionOfferFilledEvent {
+                offer_id,
+                seller: seller.clone(),
+                buyer: offer.buyer.clone(),
+                nft_address: offer.nft_address.clone(),
+                token_id,
+                amount: offer.amount,
+                platform_fee,
+                remaining_quantity: offer.max_quantity,
+                timestamp,
+            });
+
+            // Fully filled offers close themselves
+            if offer.max_quantity == 0 {
+                emit_collection_offer_cancelled(&env, CollectionOfferCancelledEvent {
+                    offer_id,
+                    buyer: offer.buyer.clone(),
+                    refunded_amount: 0,
+                    reason: Bytes::from_slice(&env, b"filled"),
+                    timestamp,
+                });
+            }
+
+            Ok(ExecutionResult {
+                transaction_id: offer_id,
+                success: true,
+                transferred_nft: true,
+                transferred_payment: true,
+                distributed_royalties: distribution_result.distribution_success,
+                collected_platform_fee: true,
+                timestamp,
+            })
+        })
+    }
+
+    /// Cancel a collection offer and refund the unfilled remainder to the buyer
+    pub fn cancel_collection_offer(env: Env, offer_id: u64, buyer: Address) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        buyer.require_auth();
+        ReentrancyGuard::execute(&env, &buyer, "cancel_collection_offer", || {
+            let offer = CollectionOfferStore::get(&env, offer_id)?;
+            if offer.buyer != buyer {
+                return Err(SettlementError::Unauthorized);
+            }
+
+            CollectionOfferStore::remove(&env, offer_id)?;
+            refund_collection_offer(&env, &offer, "cancelled")
+        })
+    }
+
+    /// Remove expired offers and refund their buyers; returns how many were cleared
+    pub fn cleanup_expired_offers(env: Env) -> Result<u32, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        let expired = OfferStore::take_expired(&env);
+        for offer in expired.iter() {
+            refund_offer(&env, &offer, "expired")?;
+        }
+
+        let expired_collection = CollectionOfferStore::take_expired(&env);
+        for offer in expired_collection.iter() {
+            refund_collection_offer(&env, &offer, "expired")?;
+        }
+        Ok(expired.len() + expired_collection.len())
+    }
+
+    /// Cancel a transaction
+    pub fn cancel_transaction(
+        env: Env,
+        transaction_id: u64,
+        transaction_type: Symbol, // "sale", "auction", "trade", "bundle"
+        canceller: Address
+    ) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        canceller.require_auth();
+        ReentrancyGuard::execute(&env, &canceller, "cancel_transaction", || {
+        if transaction_type == Symbol::new(&env, "sale") {
+            let mut sale = SaleTransactionStore::get(&env, transaction_id)?;
+            if sale.seller != canceller {
+                return Err(SettlementError::Unauthorized);
+            }
+            match sale.state {
+                crate::types::TransactionState::Pending => {
+                    FloorPriceStore::delisted(&env, &sale.nft_address, sale.price)
+                }
+                crate::types::TransactionState::Funded => refund_sale_escrow(&env, &sale)?,
+                _ => return Err(SettlementError::InvalidState),
+            }
+            sale.state = crate::types::TransactionState::Cancelled;
+            SaleTransactionStore::update(&env, &sale)?;
+        } else {
+            return Err(SettlementError::InvalidAmount);
+        }
+            Ok(())
+        })
+    }
+
+    /// Open a dispute over a sale as its buyer or seller
+    pub fn initiate_dispute(
+        env: Env,
+        transaction_id: u64,
+        reason: Bytes,
+        evidence_uri: Option<Bytes>,
+        initiator: Address
+    ) -> Result<u64, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        initiator.require_auth();
+        ReentrancyGuard::execute(&env, &initiator, "initiate_dispute", || {
+            let dispute_id = DisputeResolutionManager::initiate_dispute(
+                &env,
+                transaction_id,
+                None, // No auction ID for now
+                &initiator,
+                &reason,
+                evidence_uri
+            )?;
+
+            let ttl = StorageTtlStore::get_config(&env);
+            DisputeStore::bump_dispute_storage_ttl(&env, dispute_id, ttl.dispute_min_ttl);
+            Ok(dispute_id)
+        })
+    }
+
+    /// Vote on a dispute
+    pub fn vote_on_dispute(
+        env: Env,
+        dispute_id: u64,
+        arbitrator: Address,
+        vote: u64
+    ) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        arbitrator.require_auth();
+        ReentrancyGuard::execute(&env, &arbitrator, "vote_on_dispute", || {
+            DisputeResolutionManager::vote_on_dispute(&env, dispute_id, &arbitrator, vote)
+        })
+    }
+
+    /// Add an evidence URI to the initiator's or, with `is_respondent`, the other party's case
+    pub fn submit_evidence(
+        env: Env,
+        dispute_id: u64,
+        submitter: Address,
+        evidence_uri: Bytes,
+        is_respondent: bool
+    ) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        submitter.require_auth();
+        DisputeResolutionManager::submit_evidence(&env, dispute_id, &submitter, &evidence_uri, is_respondent)
+    }
+
+    /// Get the (initiator, respondent) evidence URIs submitted to a dispute
+    pub fn get_all_evidence(env: Env, dispute_id: u64) -> Result<(Vec<Bytes>, Vec<Bytes>), SettlementError> {
+        DisputeEvidenceManager::get_all_evidence(&env, dispute_id)
+    }
+
+    /// Submit evidence bytes along with their sha256, which the contract verifies
+    pub fn submit_evidence_with_hash(
+        env: Env,
+        dispute_id: u64,
+        evidence_bytes: Bytes,
+        claimed_hash: BytesN<32>,
+        submitter: Address
+    ) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        submitter.require_auth();
+        DisputeEvidenceManager::submit_evidence_with_hash(&env, dispute_id, &evidence_bytes, &claimed_hash, &submitter)
+    }
+
+    /// Get the hash-verified evidence submitted to a dispute, oldest first
+    pub fn get_evidence_records(env: Env, dispute_id: u64) -> Vec<EvidenceRecord> {
+        DisputeEvidenceManager::get_evidence_records(&env, dispute_id)
+    }
+
+    /// Apply the default resolution to a dispute whose voting period has passed
+    pub fn finalize_stale_dispute(env: Env, dispute_id: u64) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        DisputeResolutionManager::finalize_stale_dispute(&env, dispute_id)
+    }
+
+    /// Appeal a dispute ruling to a panel of senior arbitrators
+    pub fn file_appeal(env: Env, dispute_id: u64, appellant: Address) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        appellant.require_auth();
+        ReentrancyGuard::execute(&env, &appellant, "file_appeal", || {
+            DisputeResolutionManager::file_appeal(&env, dispute_id, &appellant)
+        })
+    }
+
+    /// Execute dispute resolution
+    pub fn execute_dispute_resolution(
+        env: Env,
+        dispute_id: u64,
+        executor: Address
+    ) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
+        executor.require_auth();
+        ReentrancyGuard::execute(&env, &executor, "execute_dispute_resolution", || {
+            DisputeResolutionManager::execute_dispute_resolution(&env, dispute_id, &executor)
+        })
+    }
+
+    /// Bond tokens to become eligible for dispute panels, returning the total stake
+    pub fn stake_as_arbitrator(
+        env: Env,
+        arbitrator: Address,
+        amount: i128,
+        asset: Asset
+    ) -> Result<i128, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        arbitrator.require_auth();
+        ReentrancyGuard::execute(&env, &arbitrator, "stake_as_arbitrator", || {
+            DisputeResolutionManager::stake_as_arbitrator(&env, &arbitrator, amount, &asset)
+        })
+    }
+
+    /// Withdraw an arbitrator's stake once they sit on no undecided disputes
+    pub fn unstake_arbitrator(env: Env, arbitrator: Address) -> Result<i128, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        arbitrator.require_auth();
+        ReentrancyGuard::execute(&env, &arbitrator, "unstake_arbitrator", || {
+            DisputeResolutionManager::unstake_arbitrator(&env, &arbitrator)
+        })
+    }
+
+    /// Get an arbitrator's dispute record
+    pub fn get_arbitrator_stats(env: Env, arbitrator: Address) -> Result<ArbitratorStats, SettlementError> {
+        DisputeResolutionManager::get_arbitrator_stats(&env, &arbitrator)
+    }
+
+    /// Get up to `n` arbitrators ranked by correct votes
+    pub fn get_top_arbitrators(env: Env, n: u32) -> Result<Vec<(Address, ArbitratorStats)>, SettlementError> {
+        DisputeResolutionManager::get_top_arbitrators(&env, n)
+    }
+
+    /// Get the tokens an arbitrator has bonded
+    pub fn get_arbitrator_stake(env: Env, arbitrator: Address) -> i128 {
+        DisputeResolutionManager::get_arbitrator_stake(&env, &arbitrator)
+    }
+
+    /// Get the stake slashed from arbitrators that hasn't been withdrawn
+    pub fn get_penalty_pool_balance(env: Env) -> i128 {
+        DisputeResolutionManager::get_penalty_pool_balance(&env)
+    }
+
+    /// Withdraw the slashed arbitrator stake to the admin (admin only)
+    pub fn withdraw_penalty_pool(env: Env, admin: Address) -> Result<i128, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        admin.require_auth();
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        DisputeResolutionManager::withdraw_penalty_pool(&env, &admin)
+    }
+
+    /// Emergency withdrawal (admin only)
+    pub fn emergency_withdraw(
+        env: Env,
+        transaction_id: u64,
+        reason: Bytes,
+        admin: Address
+    ) -> Result<(), SettlementError> {
+        admin.require_auth();
+        // Check admin permissions
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        if !admin_config.emergency_withdrawal_enabled {
+            return Err(SettlementError::InvalidState);
+        }
+
+        AtomicSwapEngine::emergency_withdraw(&env, transaction_id, &admin, &reason)
+    }
+
+    /// Update fee configuration (admin only)
+    pub fn update_fee_config(
+        env: Env,
+        new_config: FeeConfig,
+        admin: Address
+    ) -> Result<(), SettlementError> {
+        admin.require_auth();
+        // Check admin permissions
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        FeeManager::update_fee_config(&env, &new_config, &admin)
+    }
+
+    /// Set the DEX router accumulated fees are swapped through (admin only)
+    pub fn set_swap_dex_address(env: Env, dex_address: Address, admin: Address) -> Result<(), SettlementError> {
+        admin.require_auth();
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        FeeManager::set_swap_dex_address(&env, &dex_address);
+        Ok(())
+    }
+
+    /// Stop swapping accumulated fees into the protocol token (admin only)
+    pub fn disable_auto_swap(env: Env, admin: Address) -> Result<(), SettlementError> {
+        admin.require_auth();
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        FeeManager::disable_auto_swap(&env, &admin)
+    }
+
+    /// Schedule a promotional fee discount, replacing any current one (admin only)
+    pub fn set_promotion(env: Env, promotion: PromotionalFeeOverride, admin: Address) -> Result<(), SettlementError> {
+        admin.require_auth();
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        FeeManager::set_promotion(&env, &promotion)
+    }
+
+    /// End the current promotional fee discount (admin only)
+    pub fn clear_promotion(env: Env, admin: Address) -> Result<(), SettlementError> {
+        admin.require_auth();
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        FeeManager::clear_promotion(&env);
+        Ok(())
+    }
+
+    /// Get the scheduled or running promotional fee discount, if any
+    pub fn get_promotion(env: Env) -> Option<PromotionalFeeOverride> {
+        FeeManager::get_promotion(&env)
+    }
+
+    /// Replace the VIP tiers users are promoted into by trading volume (admin only)
+    pub fn set_vip_tiers(env: Env, tiers: Vec<VipTier>, admin: Address) -> Result<(), SettlementError> {
+        admin.require_auth();
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        FeeManager::set_vip_tiers(&env, &tiers)
+    }
+
+    /// Get the VIP tiers, by increasing minimum volume
+    pub fn get_vip_tiers(env: Env) -> Vec<VipTier> {
+        FeeManager::get_vip_tiers(&env)
+    }
+
+    /// Get the index of the VIP tier a user holds, if any
+    pub fn get_user_tier(env: Env, user: Address) -> Option<u32> {
+        FeeManager::get_user_tier(&env, &user)
+    }
+
+    /// Re-evaluate a user's VIP tier against their current volume
+    pub fn evaluate_and_promote_user(env: Env, user: Address) -> Result<Option<u32>, SettlementError> {
+        FeeManager::evaluate_and_promote_user(&env, &user)
+    }
+
+    /// Update auction configuration (admin only)
+    pub fn update_auction_config(
+        env: Env,
+        new_config: crate::auction_engine::AuctionConfig,
+        admin: Address
+    ) -> Result<(), SettlementError> {
+        admin.require_auth();
+        // Check admin permissions
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        AuctionEngine::update_auction_config(&env, &new_config, &admin)
+    }
+
+    /// Get auction configuration
+    pub fn get_auction_config(env: Env) -> Result<crate::auction_engine::AuctionConfig, SettlementError> {
+        AuctionEngine::get_auction_config(&env)
+    }
+
+    /// Update rate limit configuration (admin only)
+    pub fn update_rate_limit_config(
+        env: Env,
+        new_config: RateLimitConfig,
+        admin: Address
+    ) -> Result<(), SettlementError> {
+        admin.require_auth();
+        // Check admin permissions
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        RateLimiter::update_config(&env, &new_config, &admin)
+    }
+
+    /// Get rate limit configuration
+    pub fn get_rate_limit_config(env: Env) -> RateLimitConfig {
+        RateLimiter::get_config(&env)
+    }
+
+    /// Ban an address from trading on the marketplace (admin only)
+    pub fn ban_address(env: Env, target: Address, admin: Address) -> Result<(), SettlementError> {
+        admin.require_auth();
+        // Check admin permissions
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        BanList::ban(&env, &target, &admin)
+    }
+
+    /// Lift a ban on an address (admin only)
+    pub fn unban_address(env: Env, target: Address, admin: Address) -> Result<(), SettlementError> {
+        admin.require_auth();
+        // Check admin permissions
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        BanList::unban(&env, &target, &admin)
+    }
+
+    /// Check whether an address is banned
+    pub fn is_banned(env: Env, address: Address) -> bool {
+        BanList::is_banned(&env, &address)
+    }
+
+    /// Halt all marketplace operations (admin only)
+    pub fn emergency_pause(env: Env, admin: Address) -> Result<(), SettlementError> {
+        admin.require_auth();
+        // Check admin permissions
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        EmergencyPause::pause(&env, &admin)
+    }
+
+    /// Resume marketplace operations, no sooner than 24 hours after the pause (admin only)
+    pub fn emergency_unpause(env: Env, admin: Address) -> Result<(), SettlementError> {
+        admin.require_auth();
+        // Check admin permissions
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        EmergencyPause::unpause(&env, &admin)
+    }
+
+    /// Check whether the marketplace is halted
+    pub fn is_emergency_paused(env: Env) -> bool {
+        EmergencyPause::is_paused(&env)
+    }
+
+    /// Propose a new admin configuration (admin only)
+    ///
+    /// The change only takes effect through `execute_admin_config_update` once the 48-hour
+    /// timelock has passed. A new proposal replaces any pending one and restarts the clock.
+    /// Unavailable once governance is enabled.
+    pub fn propose_admin_config_update(
+        env: Env,
+        new_config: AdminConfig,
+        admin: Address
+    ) -> Result<(), SettlementError> {
+        admin.require_auth();
+        // Check admin permissions
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin || Governance::is_enabled(&env) {
+            return Err(SettlementError::Unauthorized);
+        }
+        validate_admin_config(&new_config)?;
+
+        let now = env.ledger().timestamp();
+        AdminConfigStore::put_pending(&env, &new_config, now);
+
+        emit_admin_config_proposed(&env, AdminConfigProposedEvent {
+            new_config,
+            proposed_by: admin,
+            executable_at: now.saturating_add(ADMIN_CONFIG_TIMELOCK),
+            timestamp: now,
+        });
 
-            Ok(())
-        })
+        Ok(())
     }
 
-    /// Create a bundle sale
-    pub fn create_bundle(
-        env: Env,
-        seller: Address,
-        items: Vec<crate::types::NFTItem>,
-        total_price: i128,
-        currency: Asset,
-        duration_seconds: u64
-    ) -> Result<u64, SettlementError> {
-        ReentrancyGuard::execute(&env, &seller, "create_bundle", || {
-            if items.is_empty() {
-                return Err(SettlementError::InvalidAmount);
-            }
+    /// Apply the pending admin configuration once its timelock has passed (admin only)
+    pub fn execute_admin_config_update(env: Env, admin: Address) -> Result<(), SettlementError> {
+        admin.require_auth();
+        // Check admin permissions
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
 
-            let bundle_id = BundleTransactionStore::next_id(&env);
+        if admin_config.admin != admin || Governance::is_enabled(&env) {
+            return Err(SettlementError::Unauthorized);
+        }
 
-            let bundle = BundleTransaction {
-                bundle_id,
-                seller: seller.clone(),
-                buyer: None,
-                items,
-                total_price,
-                currency,
-                state: crate::types::TransactionState::Pending,
-                created_at: env.ledger().timestamp(),
-                expires_at: env.ledger().timestamp() + duration_seconds,
-                platform_fee: 0, // Would be calculated
-            };
+        let (new_config, proposed_at) = AdminConfigStore::get_pending(&env)
+            .ok_or(SettlementError::NotFound)?;
+        if env.ledger().timestamp() < proposed_at.saturating_add(ADMIN_CONFIG_TIMELOCK) {
+            return Err(SettlementError::CooldownActive);
+        }
 
-            BundleTransactionStore::put(&env, &bundle)?;
-            Ok(bundle_id)
-        })
+        AdminConfigStore::take_pending(&env);
+        AdminConfigStore::put(&env, &new_config);
+
+        emit_admin_config_applied(&env, AdminConfigAppliedEvent {
+            new_config,
+            applied_by: admin,
+            timestamp: env.ledger().timestamp(),
+        });
+
+        Ok(())
     }
 
-    /// Cancel a transaction
-    pub fn cancel_transaction(
-        env: Env,
-        transaction_id: u64,
-        transaction_type: Symbol, // "sale", "auction", "trade", "bundle"
-        canceller: Address
-    ) -> Result<(), SettlementError> {
-        ReentrancyGuard::execute(&env, &canceller, "cancel_transaction", || {
-        if transaction_type == Symbol::new(&env, "sale") {
-            let mut sale = SaleTransactionStore::get(&env, transaction_id)?;
-            if sale.seller != canceller {
-                return Err(SettlementError::Unauthorized);
-            }
-            if sale.state != crate::types::TransactionState::Pending {
-                return Err(SettlementError::InvalidState);
-            }
-            sale.state = crate::types::TransactionState::Cancelled;
-            SaleTransactionStore::update(&env, &sale)?;
-        } else {
-            return Err(SettlementError::InvalidAmount);
+    /// Get the proposed admin configuration and when it was proposed, if one is pending
+    pub fn get_pending_admin_config(env: Env) -> Option<(AdminConfig, u64)> {
+        AdminConfigStore::get_pending(&env)
+    }
+
+    /// Hand admin configuration changes over to holders of `token` (admin only)
+    ///
+    /// One-way: afterwards the config only changes through governance proposals that at
+    /// least `quorum` tokens vote on, and any pending admin proposal is dropped.
+    pub fn set_governance_token(env: Env, token: Address, quorum: i128, admin: Address) -> Result<(), SettlementError> {
+        admin.require_auth();
+        // Check admin permissions
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
         }
-            Ok(())
-        })
+
+        Governance::set_token(&env, &token, quorum)?;
+        AdminConfigStore::take_pending(&env);
+        Ok(())
     }
 
-    /// Initiate a dispute
-    pub fn initiate_dispute(
+    /// Get the governance token, if config changes are governed by token holders
+    pub fn get_governance_token(env: Env) -> Option<Address> {
+        Governance::get_token(&env)
+    }
+
+    /// Propose a new admin configuration for token holders to vote on
+    pub fn propose_config_change(
         env: Env,
-        transaction_id: u64,
-        reason: Bytes,
-        evidence_uri: Option<Bytes>,
-        initiator: Address
+        new_config: AdminConfig,
+        proposer: Address
     ) -> Result<u64, SettlementError> {
-        ReentrancyGuard::execute(&env, &initiator, "initiate_dispute", || {
-            DisputeResolutionManager::initiate_dispute(
-                &env,
-                transaction_id,
-                None, // No auction ID for now
-                &initiator,
-                &reason,
-                evidence_uri
-            )
-        })
+        proposer.require_auth();
+        validate_admin_config(&new_config)?;
+        Governance::propose(&env, &new_config, &proposer)
     }
 
-    /// Vote on a dispute
-    pub fn vote_on_dispute(
+    /// Vote on a governance proposal, locking `voting_power` governance tokens until voting closes
+    pub fn vote(
         env: Env,
-        dispute_id: u64,
-        arbitrator: Address,
-        vote: u64
+        proposal_id: u64,
+        voter: Address,
+        in_favor: bool,
+        voting_power: i128
     ) -> Result<(), SettlementError> {
-        ReentrancyGuard::execute(&env, &arbitrator, "vote_on_dispute", || {
-            DisputeResolutionManager::vote_on_dispute(&env, dispute_id, &arbitrator, vote)
-        })
+        voter.require_auth();
+        Governance::vote(&env, proposal_id, &voter, in_favor, voting_power)
     }
 
-    /// Execute dispute resolution
-    pub fn execute_dispute_resolution(
-        env: Env,
-        dispute_id: u64,
-        executor: Address
-    ) -> Result<(), SettlementError> {
-        ReentrancyGuard::execute(&env, &executor, "execute_dispute_resolution", || {
-            DisputeResolutionManager::execute_dispute_resolution(&env, dispute_id, &executor)
-        })
+    /// Take back the governance tokens locked to vote on a proposal once its voting has closed
+    pub fn withdraw_vote_tokens(env: Env, proposal_id: u64, voter: Address) -> Result<i128, SettlementError> {
+        voter.require_auth();
+        Governance::withdraw_vote_tokens(&env, proposal_id, &voter)
     }
 
-    /// Emergency withdrawal (admin only)
-    pub fn emergency_withdraw(
-        env: Env,
-        transaction_id: u64,
-        reason: Bytes,
-        admin: Address
-    ) -> Result<(), SettlementError> {
+    /// Apply a governance proposal that passed once its voting deadline has passed
+    pub fn execute_proposal(env: Env, proposal_id: u64) -> Result<(), SettlementError> {
+        Governance::execute(&env, proposal_id)
+    }
+
+    /// Get a governance proposal
+    pub fn get_governance_proposal(env: Env, proposal_id: u64) -> Result<GovernanceProposal, SettlementError> {
+        Governance::get(&env, proposal_id)
+    }
+
+    /// Set how long live auction and dispute data is kept from eviction (admin only)
+    pub fn set_storage_ttl_config(env: Env, config: StorageTTLConfig, admin: Address) -> Result<(), SettlementError> {
+        admin.require_auth();
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        StorageTtlStore::put_config(&env, &config)
+    }
+
+    /// Get how long live auction and dispute data is kept from eviction
+    pub fn get_storage_ttl_config(env: Env) -> StorageTTLConfig {
+        StorageTtlStore::get_config(&env)
+    }
+
+    /// Move the admin configuration from instance to persistent storage (admin only, once)
+    pub fn migrate_admin_cfg_to_persistent(env: Env, admin: Address) -> Result<(), SettlementError> {
+        admin.require_auth();
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        AdminConfigStore::migrate_to_persistent(&env)?;
+
+        let event = AdminConfigUpdatedEvent {
+            updated_fields: Bytes::from_slice(&env, b"storage_migrated"),
+            updated_by: admin,
+            timestamp: env.ledger().timestamp(),
+        };
+        emit_admin_config_updated(&env, event);
+
+        Ok(())
+    }
+
+    /// Move the admin configuration back to instance storage (admin only)
+    pub fn rollback_admin_config_migration(env: Env, admin: Address) -> Result<(), SettlementError> {
+        admin.require_auth();
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        AdminConfigStore::rollback_migration(&env)?;
+
+        let event = AdminConfigUpdatedEvent {
+            updated_fields: Bytes::from_slice(&env, b"storage_rolled_back"),
+            updated_by: admin,
+            timestamp: env.ledger().timestamp(),
+        };
+        emit_admin_config_updated(&env, event);
+
+        Ok(())
+    }
+
+    /// Check whether the admin configuration has moved to persistent storage
+    pub fn is_admin_config_migrated(env: Env) -> bool {
+        AdminConfigStore::is_migrated(&env)
+    }
+
+    /// Get admin configuration
+    pub fn get_admin_config(env: Env) -> Result<AdminConfig, SettlementError> {
+        AdminConfigStore::get(&env)
+            .ok_or(SettlementError::NotFound)
+    }
+
+    /// Resume settlement after the circuit breaker tripped (admin only)
+    pub fn reset_circuit_breaker(env: Env, admin: Address) -> Result<(), SettlementError> {
+        admin.require_auth();
         // Check admin permissions
-        let admin_config: AdminConfig = env.storage()
-            .instance()
-            .get(&symbol_short!("admin_cfg"))
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
             .ok_or(SettlementError::Unauthorized)?;
 
         if admin_config.admin != admin {
             return Err(SettlementError::Unauthorized);
         }
 
-        if !admin_config.emergency_withdrawal_enabled {
-            return Err(SettlementError::InvalidState);
+        CircuitBreaker::reset(&env, &admin)
+    }
+
+    /// Check whether the circuit breaker has halted settlement
+    pub fn is_circuit_breaker_tripped(env: Env) -> bool {
+        CircuitBreaker::is_tripped(&env)
+    }
+
+    /// Whitelist an NFT contract for listing (admin only)
+    pub fn add_allowed_nft_contract(env: Env, nft_contract: Address, admin: Address) -> Result<(), SettlementError> {
+        admin.require_auth();
+        // Check admin permissions
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
         }
 
-        AtomicSwapEngine::emergency_withdraw(&env, transaction_id, &admin, &reason)
+        NftWhitelist::add(&env, &nft_contract)
     }
 
-    /// Update fee configuration (admin only)
-    pub fn update_fee_config(
-        env: Env,
-        new_config: FeeConfig,
-        admin: Address
-    ) -> Result<(), SettlementError> {
+    /// Remove an NFT contract from the listing whitelist (admin only)
+    pub fn remove_allowed_nft_contract(env: Env, nft_contract: Address, admin: Address) -> Result<(), SettlementError> {
+        admin.require_auth();
         // Check admin permissions
-        let admin_config: AdminConfig = env.storage()
-            .instance()
-            .get(&symbol_short!("admin_cfg"))
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
             .ok_or(SettlementError::Unauthorized)?;
 
         if admin_config.admin != admin {
             return Err(SettlementError::Unauthorized);
         }
 
-        FeeManager::update_fee_config(&env, &new_config, &admin)
+        NftWhitelist::remove(&env, &nft_contract)
+    }
+
+    /// Get the whitelisted NFT contracts
+    pub fn get_allowed_nft_contracts(env: Env) -> Vec<Address> {
+        NftWhitelist::get_all(&env)
+    }
+
+    /// Get the default fee schedule by transaction size
+    pub fn get_default_fee_schedule(env: Env) -> FeeSchedule {
+        crate::fee_manager::get_default_fee_schedule(&env)
+    }
+
+    /// Withdraw referral fees earned by a referrer
+    pub fn withdraw_referral_earnings(env: Env, referrer: Address) -> Result<Map<Asset, i128>, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        referrer.require_auth();
+        ReentrancyGuard::execute(&env, &referrer, "withdraw_referral_earnings", || {
+            FeeManager::withdraw_referral_earnings(&env, &referrer)
+        })
+    }
+
+    /// Get a referrer's unwithdrawn earnings per asset
+    pub fn get_referral_earnings(env: Env, referrer: Address) -> Map<Asset, i128> {
+        FeeManager::get_referral_earnings(&env, &referrer)
     }
 
     /// Withdraw platform fees (admin only)
@@ -497,10 +2172,10 @@ impl MarketplaceSettlement {
         recipient: Address,
         admin: Address
     ) -> Result<i128, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        admin.require_auth();
         // Check admin permissions
-        let admin_config: AdminConfig = env.storage()
-            .instance()
-            .get(&symbol_short!("admin_cfg"))
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
             .ok_or(SettlementError::Unauthorized)?;
 
         if admin_config.admin != admin {
@@ -510,11 +2185,92 @@ impl MarketplaceSettlement {
         FeeManager::withdraw_platform_fees(&env, &asset, &recipient, &admin)
     }
 
+    /// Withdraw platform fees for several assets at once (admin only)
+    ///
+    /// An asset that fails to withdraw is reported rather than reverting the batch.
+    pub fn withdraw_platform_fees_batch(
+        env: Env,
+        assets: Vec<Asset>,
+        recipient: Address,
+        admin: Address
+    ) -> Result<FeeWithdrawalSummary, SettlementError> {
+        require_not_emergency_paused(&env)?;
+        admin.require_auth();
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        FeeManager::withdraw_platform_fees_batch(&env, &assets, &recipient, &admin)
+    }
+
     /// Get transaction details
     pub fn get_sale(env: Env, transaction_id: u64) -> Result<SaleTransaction, SettlementError> {
         SaleTransactionStore::get(&env, transaction_id)
     }
 
+    /// Get a token's executed sales, oldest first
+    pub fn get_sale_history(
+        env: Env,
+        nft_address: Address,
+        token_id: u64,
+        offset: u32,
+        limit: u32
+    ) -> Vec<SaleTransaction> {
+        let history = SaleHistoryStore::get(&env, &nft_address, token_id);
+        let end = offset.saturating_add(limit).min(history.len());
+
+        let mut sales = Vec::new(&env);
+        for index in offset..end {
+            if let Some(sale) = history.get(index).and_then(|id| SaleTransactionStore::get(&env, id).ok()) {
+                sales.push_back(sale);
+            }
+        }
+        sales
+    }
+
+    /// Get a token's (price, timestamp) sale points, oldest first
+    pub fn get_price_history(env: Env, nft_address: Address, token_id: u64) -> Vec<(i128, u64)> {
+        PriceHistoryStore::get(&env, &nft_address, token_id)
+    }
+
+    /// Get the price a token last sold for
+    pub fn get_last_sale_price(env: Env, nft_address: Address, token_id: u64) -> Option<i128> {
+        PriceHistoryStore::last_price(&env, &nft_address, token_id)
+    }
+
+    /// Get the lowest active listing price for an NFT collection
+    pub fn get_floor_price(env: Env, nft_address: Address) -> Option<i128> {
+        FloorPriceStore::get(&env, &nft_address)
+    }
+
+    /// Get marketplace-wide auction statistics
+    pub fn get_platform_wide_stats(env: Env) -> PlatformStats {
+        PlatformStatsStore::get(&env)
+    }
+
+    /// Get trading statistics for an NFT collection
+    pub fn get_collection_stats(env: Env, nft_address: Address) -> CollectionStats {
+        CollectionStatsStore::get(&env, &nft_address)
+    }
+
+    /// Get the moving average sale price of an NFT collection, if it has sold
+    pub fn get_collection_ema_price(env: Env, nft_address: Address) -> Option<i128> {
+        CollectionStatsStore::get_ema_price(&env, &nft_address)
+    }
+
+    /// Get offer details
+    pub fn get_offer(env: Env, offer_id: u64) -> Result<Offer, SettlementError> {
+        OfferStore::get(&env, offer_id)
+    }
+
+    /// Get collection offer details
+    pub fn get_collection_offer(env: Env, offer_id: u64) -> Result<CollectionOffer, SettlementError> {
+        CollectionOfferStore::get(&env, offer_id)
+    }
+
     /// Get auction details
     pub fn get_auction(env: Env, auction_id: u64) -> Result<AuctionTransaction, SettlementError> {
         AuctionStore::get(&env, auction_id)
@@ -525,18 +2281,355 @@ impl MarketplaceSettlement {
         AuctionEngine::get_dutch_auction_price(&env, auction_id)
     }
 
+    /// Get Vickrey auction reveal state
+    pub fn get_vickrey_auction(env: Env, auction_id: u64) -> Result<VickreyAuctionData, SettlementError> {
+        AuctionEngine::get_vickrey_auction(&env, auction_id)
+    }
+
+    /// Get sealed first-price auction reveal state
+    pub fn get_sealed_auction(env: Env, auction_id: u64) -> Result<SealedAuctionData, SettlementError> {
+        AuctionEngine::get_sealed_auction(&env, auction_id)
+    }
+
+    /// Get candle auction window and, once settled, the revealed end time
+    pub fn get_candle_auction(env: Env, auction_id: u64) -> Result<CandleAuctionData, SettlementError> {
+        AuctionEngine::get_candle_auction(&env, auction_id)
+    }
+
     /// Get accumulated fees
     pub fn get_accumulated_fees(env: Env, asset: Asset) -> i128 {
         FeeManager::get_accumulated_fees(&env, &asset)
     }
 
+    /// Get every auction a seller has created, oldest first
+    pub fn get_seller_auctions(env: Env, seller: Address) -> Vec<AuctionTransaction> {
+        AuctionStore::get_by_seller(&env, &seller)
+    }
+
+    /// Get a page of the bids `bidder` has placed across all auctions
+    pub fn get_bids_by_bidder(env: Env, bidder: Address, offset: u64, limit: u64) -> Vec<BidderBid> {
+        AuctionStore::get_bids_by_bidder(&env, &bidder, offset, limit)
+    }
+
+    /// Get a page of the auctions matching `filter`, oldest first
+    pub fn get_auctions_paginated(env: Env, offset: u64, limit: u64, filter: AuctionFilter) -> Vec<AuctionTransaction> {
+        AuctionStore::get_auctions_paginated(&env, offset, limit, &filter)
+    }
+
     /// Get user volume
     pub fn get_user_volume(env: Env, user: Address) -> Result<i128, SettlementError> {
         FeeManager::get_user_volume(&env, &user)
     }
 
+    /// Remove up to `batch_size` cancelled or expired sales, returning how many went (admin only)
+    pub fn cleanup_expired_sales(env: Env, batch_size: u32, admin: Address) -> Result<u32, SettlementError> {
+        admin.require_auth();
+        let admin_config: AdminConfig = AdminConfigStore::get(&env)
+            .ok_or(SettlementError::Unauthorized)?;
+
+        if admin_config.admin != admin {
+            return Err(SettlementError::Unauthorized);
+        }
+
+        let removed = SaleTransactionStore::cleanup_expired(&env, batch_size);
+        emit_expired_sales_cleaned(&env, ExpiredSalesCleanedEvent {
+            removed,
+            cleaned_by: admin,
+            timestamp: env.ledger().timestamp(),
+        });
+        Ok(removed)
+    }
+
     /// Cleanup expired commitments
     pub fn cleanup_expired_commitments(env: Env) -> Result<(), SettlementError> {
+        require_not_emergency_paused(&env)?;
         AuctionEngine::cleanup_expired_commitments(&env)
     }
-}
\ No newline at end of file
+}
+
+/// Internal: royalties from the marketplace or the NFT contract, or a distribution where the seller keeps everything
+fn royalties_or_default(
+    env: &Env,
+    nft_address: &Address,
+    token_id: u64,
+    price: i128,
+    seller: &Address
+) -> RoyaltyDistribution {
+    RoyaltyDistributor::royalties_for_sale(env, nft_address, token_id, price).unwrap_or(RoyaltyDistribution {
+        creator_address: seller.clone(),
+        creator_percentage: 0,
+        seller_percentage: 10000,
+        platform_percentage: 0,
+        total_amount: price,
+        amounts: Map::new(env),
+    })
+}
+
+/// Internal: hand a sale's NFT to the buyer and pay out the price held by the contract
+fn settle_sale(
+    env: &Env,
+    sale: &SaleTransaction,
+    buyer: &Address,
+    referrer: Option<Address>
+) -> Result<ExecutionResult, SettlementError> {
+    asset_utils::transfer_nft(&sale.nft_address, &sale.seller, buyer, sale.token_id, env)?;
+
+    let distribution_result = RoyaltyDistributor::distribute_royalties(
+        env,
+        sale.transaction_id,
+        &sale.royalty_info,
+        &sale.currency,
+        sale.price,
+        &sale.seller
+    )?;
+    FeeManager::collect_platform_fee(env, sale.platform_fee, &sale.currency, buyer, referrer)?;
+
+    // The seller's share of the split, less the platform fee
+    if sale.platform_fee > distribution_result.seller_amount {
+        return Err(SettlementError::InvalidAmount);
+    }
+    let proceeds = math_utils::safe_sub(distribution_result.seller_amount, sale.platform_fee, env)?;
+    asset_utils::transfer_tokens(
+        &sale.currency.contract,
+        &env.current_contract_address(),
+        &sale.seller,
+        proceeds,
+        env
+    )?;
+
+    let timestamp = env.ledger().timestamp();
+    emit_sale_executed(env, SaleExecutedEvent {
+        transaction_id: sale.transaction_id,
+        seller: sale.seller.clone(),
+        buyer: buyer.clone(),
+        nft_address: sale.nft_address.clone(),
+        token_id: sale.token_id,
+        price: sale.price,
+        platform_fee: sale.platform_fee,
+        timestamp,
+    });
+
+    Ok(ExecutionResult {
+        transaction_id: sale.transaction_id,
+        success: true,
+        transferred_nft: true,
+        transferred_payment: true,
+        distributed_royalties: distribution_result.distribution_success,
+        collected_platform_fee: true,
+        timestamp,
+    })
+}
+
+/// Internal: check the limits in an admin configuration are consistent
+fn validate_admin_config(config: &AdminConfig) -> Result<(), SettlementError> {
+    if config.circuit_breaker_threshold <= 0
+        || config.max_single_transaction_amount < config.circuit_breaker_threshold
+        || config.max_history_per_token == 0
+        || config.max_price_history_per_token == 0
+    {
+        return Err(SettlementError::InvalidAmount);
+    }
+    Ok(())
+}
+
+/// Internal: check a bundle can still be bought from, and not by its own seller
+fn require_bundle_open(env: &Env, bundle: &BundleTransaction, buyer: &Address) -> Result<(), SettlementError> {
+    if bundle.state != crate::types::TransactionState::Pending {
+        return Err(SettlementError::InvalidState);
+    }
+    if time_utils::is_expired(bundle.expires_at, env) {
+        return Err(SettlementError::Expired);
+    }
+    if bundle.seller == *buyer {
+        return Err(SettlementError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Internal: collect `price` for some of a bundle's items and settle them with the seller
+///
+/// The price is split evenly across the items for royalties, with any remainder on the last.
+fn settle_bundle_items(
+    env: &Env,
+    bundle: &BundleTransaction,
+    items: &Vec<crate::types::NFTItem>,
+    price: i128,
+    buyer: &Address
+) -> Result<ExecutionResult, SettlementError> {
+    let contract = env.current_contract_address();
+    asset_utils::transfer_tokens_checked(&bundle.currency.contract, buyer, &contract, price, env)?;
+
+    let share = price / items.len() as i128;
+    let mut seller_amount = 0i128;
+    let mut distributed_royalties = true;
+    for (i, item) in items.iter().enumerate() {
+        let item_price = if i as u32 + 1 == items.len() {
+            math_utils::safe_sub(price, share * (items.len() as i128 - 1), env)?
+        } else {
+            share
+        };
+        asset_utils::transfer_nft(&item.nft_address, &bundle.seller, buyer, item.token_id, env)?;
+        let royalties = royalties_or_default(env, &item.nft_address, item.token_id, item_price, &bundle.seller);
+        let distribution = RoyaltyDistributor::distribute_royalties(
+            env,
+            bundle.bundle_id,
+            &royalties,
+            &bundle.currency,
+            item_price,
+            &bundle.seller
+        )?;
+        seller_amount = math_utils::safe_add(seller_amount, distribution.seller_amount, env)?;
+        distributed_royalties = distributed_royalties && distribution.distribution_success;
+    }
+
+    let platform_fee = FeeManager::calculate_fee(env, price, &bundle.seller, None)?;
+    FeeManager::collect_platform_fee(env, platform_fee, &bundle.currency, buyer, None)?;
+    if platform_fee > seller_amount {
+        return Err(SettlementError::InvalidAmount);
+    }
+    let proceeds = math_utils::safe_sub(seller_amount, platform_fee, env)?;
+    asset_utils::transfer_tokens(&bundle.currency.contract, &contract, &bundle.seller, proceeds, env)?;
+
+    Ok(ExecutionResult {
+        transaction_id: bundle.bundle_id,
+        success: true,
+        transferred_nft: true,
+        transferred_payment: true,
+        distributed_royalties,
+        collected_platform_fee: true,
+        timestamp: env.ledger().timestamp(),
+    })
+}
+
+/// Internal: add an executed sale to its token's provenance and price histories
+fn record_sale_history(env: &Env, sale: &SaleTransaction) -> Result<(), SettlementError> {
+    let admin_config: AdminConfig = AdminConfigStore::get(env)
+        .ok_or(SettlementError::NotFound)?;
+
+    SaleHistoryStore::append(
+        env,
+        &sale.nft_address,
+        sale.token_id,
+        sale.transaction_id,
+        admin_config.max_history_per_token
+    );
+    PriceHistoryStore::push(
+        env,
+        &sale.nft_address,
+        sale.token_id,
+        sale.price,
+        admin_config.max_price_history_per_token
+    );
+    Ok(())
+}
+
+/// Internal: return a funded sale's escrowed payment to its buyer
+fn refund_sale_escrow(env: &Env, sale: &SaleTransaction) -> Result<(), SettlementError> {
+    let buyer = sale.buyer.clone().ok_or(SettlementError::InvalidState)?;
+    let amount = SaleEscrowStore::take(env, sale.transaction_id);
+    if amount > 0 {
+        asset_utils::transfer_tokens(
+            &sale.currency.contract,
+            &env.current_contract_address(),
+            &buyer,
+            amount,
+            env
+        )?;
+    }
+    Ok(())
+}
+
+/// Internal: hand the NFT to the offer's buyer and pay out its escrowed amount.
+/// Returns the royalty distribution result and the platform fee taken.
+fn settle_offer(
+    env: &Env,
+    offer: &Offer,
+    seller: &Address
+) -> Result<(DistributionResult, i128), SettlementError> {
+    if !asset_utils::check_nft_ownership(&offer.nft_address, offer.token_id, seller, env)? {
+        return Err(SettlementError::Unauthorized);
+    }
+
+    asset_utils::transfer_nft(&offer.nft_address, seller, &offer.buyer, offer.token_id, env)?;
+
+    let royalty_distribution = royalties_or_default(env, &offer.nft_address, offer.token_id, offer.amount, seller);
+    let distribution_result = RoyaltyDistributor::distribute_royalties(
+        env,
+        offer.offer_id,
+        &royalty_distribution,
+        &offer.currency,
+        offer.amount,
+        seller
+    )?;
+
+    let platform_fee = FeeManager::calculate_fee(env, offer.amount, &offer.buyer, Some(offer.nft_address.clone()))?;
+    FeeManager::collect_platform_fee(env, platform_fee, &offer.currency, &offer.buyer, None)?;
+
+    // The seller's share of the split, less the platform fee
+    if platform_fee > distribution_result.seller_amount {
+        return Err(SettlementError::InvalidAmount);
+    }
+    let proceeds = math_utils::safe_sub(distribution_result.seller_amount, platform_fee, env)?;
+    asset_utils::transfer_tokens(
+        &offer.currency.contract,
+        &env.current_contract_address(),
+        seller,
+        proceeds,
+        env
+    )?;
+
+    Ok((distribution_result, platform_fee))
+}
+
+/// Internal: the result of a settlement the circuit breaker halted before anything moved
+fn halted_settlement(env: &Env, transaction_id: u64) -> ExecutionResult {
+    ExecutionResult {
+        transaction_id,
+        success: false,
+        transferred_nft: false,
+        transferred_payment: false,
+        distributed_royalties: false,
+        collected_platform_fee: false,
+        timestamp: env.ledger().timestamp(),
+    }
+}
+
+/// Internal: return an offer's escrowed funds to its buyer
+fn refund_offer(env: &Env, offer: &Offer, reason: &str) -> Result<(), SettlementError> {
+    asset_utils::transfer_tokens(
+        &offer.currency.contract,
+        &env.current_contract_address(),
+        &offer.buyer,
+        offer.amount,
+        env
+    )?;
+
+    emit_offer_cancelled(env, OfferCancelledEvent {
+        offer_id: offer.offer_id,
+        buyer: offer.buyer.clone(),
+        reason: Bytes::from_slice(env, reason.as_bytes()),
+        timestamp: env.ledger().timestamp(),
+    });
+    Ok(())
+}
+
+/// Internal: return the unfilled part of a collection offer's escrow to its buyer
+fn refund_collection_offer(env: &Env, offer: &CollectionOffer, reason: &str) -> Result<(), SettlementError> {
+    let refund = math_utils::safe_mul(offer.amount, offer.max_quantity as i128, env)?;
+    asset_utils::transfer_tokens(
+        &offer.currency.contract,
+        &env.current_contract_address(),
+        &offer.buyer,
+        refund,
+        env
+    )?;
+
+    emit_collection_offer_cancelled(env, CollectionOfferCancelledEvent {
+        offer_id: offer.offer_id,
+        buyer: offer.buyer.clone(),
+        refunded_amount: refund,
+        reason: Bytes::from_slice(env, reason.as_bytes()),
+        timestamp: env.ledger().timestamp(),
+    });
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use soroban_sdk::{Env, Symbol, symbol_short};
+use crate::types::AdminConfig;
+use crate::error::SettlementError;
+use crate::storage::DataKey;
+
+// Where the admin config lived before moving to persistent storage
+const LEGACY_ADMIN_CONFIG: Symbol = symbol_short!("admin_cfg");
+
+/// Seconds a proposed admin config must wait before it can be applied (48 hours)
+pub const ADMIN_CONFIG_TIMELOCK: u64 = 172_800;
+
+/// Storage manager for the admin configuration
+///
+/// The config started out in instance storage. Once migrated it lives under
+/// `DataKey::AdminConfigV2` in persistent storage and `DataKey::MigrationDone` routes
+/// reads and writes there.
+pub struct AdminConfigStore;
+
+impl AdminConfigStore {
+    /// Get the admin configuration, wherever it currently lives
+    pub fn get(env: &Env) -> Option<AdminConfig> {
+        if Self::is_migrated(env) {
+            env.storage().persistent().get(&DataKey::AdminConfigV2)
+        } else {
+            env.storage().instance().get(&LEGACY_ADMIN_CONFIG)
+        }
+    }
+
+    /// Save the admin configuration, wherever it currently lives
+    pub fn put(env: &Env, config: &AdminConfig) {
+        if Self::is_migrated(env) {
+            env.storage().persistent().set(&DataKey::AdminConfigV2, config);
+        } else {
+            env.storage().instance().set(&LEGACY_ADMIN_CONFIG, config);
+        }
+    }
+
+    /// Get the proposed config and when it was proposed, if a change is pending
+    pub fn get_pending(env: &Env) -> Option<(AdminConfig, u64)> {
+        env.storage().instance().get(&DataKey::PendingAdminConfig)
+    }
+
+    /// Record a proposed config, replacing any earlier proposal and restarting the timelock
+    pub fn put_pending(env: &Env, config: &AdminConfig, proposed_at: u64) {
+        env.storage().instance().set(&DataKey::PendingAdminConfig, &(config.clone(), proposed_at));
+    }
+
+    /// Remove and return the pending proposal
+    pub fn take_pending(env: &Env) -> Option<(AdminConfig, u64)> {
+        let pending = Self::get_pending(env);
+        env.storage().instance().remove(&DataKey::PendingAdminConfig);
+        pending
+    }
+
+    /// Check whether the config has moved to persistent storage
+    pub fn is_migrated(env: &Env) -> bool {
+        env.storage().instance().get(&DataKey::MigrationDone).unwrap_or(false)
+    }
+
+    /// Move the config from instance to persistent storage
+    pub fn migrate_to_persistent(env: &Env) -> Result<(), SettlementError> {
+        if Self::is_migrated(env) {
+            return Err(SettlementError::AlreadyExists);
+        }
+        let config: AdminConfig = env.storage()
+            .instance()
+            .get(&LEGACY_ADMIN_CONFIG)
+            .ok_or(SettlementError::NotFound)?;
+
+        env.storage().persistent().set(&DataKey::AdminConfigV2, &config);
+        env.storage().instance().set(&DataKey::MigrationDone, &true);
+        env.storage().instance().remove(&LEGACY_ADMIN_CONFIG);
+        Ok(())
+    }
+
+    /// Move the config back to instance storage, undoing `migrate_to_persistent`
+    pub fn rollback_migration(env: &Env) -> Result<(), SettlementError> {
+        if !Self::is_migrated(env) {
+            return Err(SettlementError::InvalidState);
+        }
+        let config: AdminConfig = env.storage()
+            .persistent()
+            .get(&DataKey::AdminConfigV2)
+            .ok_or(SettlementError::NotFound)?;
+
+        env.storage().instance().set(&LEGACY_ADMIN_CONFIG, &config);
+        env.storage().instance().remove(&DataKey::MigrationDone);
+        env.storage().persistent().remove(&DataKey::AdminConfigV2);
+        Ok(())
+    }
+}
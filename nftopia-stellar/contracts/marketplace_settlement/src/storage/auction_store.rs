@@ -1,12 +1,25 @@
-use soroban_sdk::{Env, Map, Vec, Symbol, Address, symbol_short};
-use crate::types::{AuctionTransaction, Bid, DutchAuctionData};
+use soroban_sdk::{Env, Map, Vec, Symbol, Address, BytesN, symbol_short};
+use crate::types::{
+    AuctionFilter, AuctionTransaction, AuctionType, Bid, BidderBid, BundleAuctionData, CandleAuctionData, DutchAuctionData, ProxyBid,
+    SealedAuctionData, TransactionState, VickreyAuctionData
+};
 use crate::error::SettlementError;
+use crate::storage::DataKey;
+use crate::storage::ttl_store::StorageTtlStore;
 
 // Storage keys
 pub const AUCTIONS: Symbol = symbol_short!("auctions");
 pub const AUCTION_BIDS: Symbol = symbol_short!("auc_bids");
 pub const DUTCH_AUCTIONS: Symbol = symbol_short!("dutch_auc");
 pub const NEXT_AUCTION_ID: Symbol = symbol_short!("next_auc");
+pub const VICKREY_AUCTIONS: Symbol = symbol_short!("vick_auc");
+pub const CANDLE_AUCTIONS: Symbol = symbol_short!("cndl_auc");
+pub const SEALED_AUCTIONS: Symbol = symbol_short!("seal_auc");
+pub const BUNDLE_AUCTIONS: Symbol = symbol_short!("bndl_auc");
+
+// Most auctions returned by one page of get_auctions_paginated, and most bids by one page
+// of get_bids_by_bidder
+pub const MAX_AUCTION_PAGE_SIZE: u64 = 100;
 
 /// Storage manager for auction transactions
 pub struct AuctionStore;
@@ -28,6 +41,17 @@ impl AuctionStore {
             .get(&AUCTIONS)
             .unwrap_or(Map::new(env));
 
+        // New auctions join the index pages are read from and their seller's index
+        if !auctions.contains_key(auction.auction_id) {
+            let mut index = Self::get_index(env);
+            index.push_back(auction.auction_id);
+            env.storage().persistent().set(&DataKey::AuctionIndex, &index);
+
+            let mut seller_auctions = Self::get_seller_index(env, &auction.seller);
+            seller_auctions.push_back(auction.auction_id);
+            env.storage().persistent().set(&DataKey::SellerAuctions(auction.seller.clone()), &seller_auctions);
+        }
+
         auctions.set(auction.auction_id, auction.clone());
         env.storage().instance().set(&AUCTIONS, &auctions);
         Ok(())
@@ -51,39 +75,142 @@ impl AuctionStore {
         Self::put(env, auction)
     }
 
-    /// Remove an auction
+    /// Remove an auction, dropping it from the auction and seller indexes
     pub fn remove(env: &Env, auction_id: u64) -> Result<(), SettlementError> {
         let mut auctions: Map<u64, AuctionTransaction> = env
             .storage()
             .instance()
             .get(&AUCTIONS)
             .ok_or(SettlementError::AuctionNotFound)?;
+        let auction = auctions.get(auction_id).ok_or(SettlementError::AuctionNotFound)?;
 
         auctions.remove(auction_id);
         env.storage().instance().set(&AUCTIONS, &auctions);
+
+        let mut index = Self::get_index(env);
+        if let Some(position) = index.first_index_of(auction_id) {
+            index.remove(position);
+            env.storage().persistent().set(&DataKey::AuctionIndex, &index);
+        }
+
+        let seller_key = DataKey::SellerAuctions(auction.seller.clone());
+        let mut seller_auctions = Self::get_seller_index(env, &auction.seller);
+        if let Some(position) = seller_auctions.first_index_of(auction_id) {
+            seller_auctions.remove(position);
+        }
+        if seller_auctions.is_empty() {
+            env.storage().persistent().remove(&seller_key);
+        } else {
+            env.storage().persistent().set(&seller_key, &seller_auctions);
+        }
         Ok(())
     }
 
-    /// Get all active auctions
-    pub fn get_active(env: &Env) -> Vec<AuctionTransaction> {
+    /// Get a page of the auctions matching `filter`, oldest first
+    ///
+    /// `offset` is how many matching auctions to skip; at most `MAX_AUCTION_PAGE_SIZE`
+    /// are returned however large `limit` is.
+    pub fn get_auctions_paginated(env: &Env, offset: u64, limit: u64, filter: &AuctionFilter) -> Vec<AuctionTransaction> {
         let auctions: Map<u64, AuctionTransaction> = env
             .storage()
             .instance()
             .get(&AUCTIONS)
             .unwrap_or(Map::new(env));
+        let limit = limit.min(MAX_AUCTION_PAGE_SIZE);
 
-        let mut result = Vec::new(env);
-        let current_time = env.ledger().timestamp();
-
-        for (_, auction) in auctions.iter() {
-            if auction.end_time > current_time && auction.state == crate::types::TransactionState::Pending {
-                result.push_back(auction);
+        let mut page = Vec::new(env);
+        let mut skipped = 0u64;
+        for auction_id in Self::get_index(env).iter() {
+            if page.len() as u64 >= limit {
+                break;
+            }
+            let Some(auction) = auctions.get(auction_id) else {
+                continue;
+            };
+            if !Self::matches(env, &auction, filter) {
+                continue;
+            }
+            if skipped < offset {
+                skipped += 1;
+                continue;
             }
+            page.push_back(auction);
         }
-        result
+        page
+    }
+
+    /// Get the type of an auction from the type-specific data stored alongside it
+    pub fn auction_type(env: &Env, auction_id: u64) -> AuctionType {
+        if DutchAuctionStore::get(env, auction_id).is_ok() {
+            AuctionType::Dutch
+        } else if VickreyAuctionStore::has(env, auction_id) {
+            AuctionType::Vickrey
+        } else if CandleAuctionStore::has(env, auction_id) {
+            AuctionType::Candle
+        } else if SealedAuctionStore::has(env, auction_id) {
+            AuctionType::SealedFirstPrice
+        } else if BundleAuctionStore::has(env, auction_id) {
+            AuctionType::Bundle
+        } else {
+            AuctionType::English
+        }
+    }
+
+    /// Internal: Auction IDs in creation order
+    fn get_index(env: &Env) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AuctionIndex)
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Get the auctions waiting to open as (start_time, auction_id), soonest first
+    pub fn get_scheduled(env: &Env) -> Vec<(u64, u64)> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ScheduledAuctions)
+            .unwrap_or(Vec::new(env))
     }
 
-    /// Get auctions by seller
+    /// Add an auction to the scheduled index, keeping it ordered by start time
+    pub fn schedule(env: &Env, start_time: u64, auction_id: u64) {
+        let mut scheduled = Self::get_scheduled(env);
+        let position = scheduled
+            .iter()
+            .position(|(other_start, _)| other_start > start_time)
+            .unwrap_or(scheduled.len() as usize);
+        scheduled.insert(position as u32, (start_time, auction_id));
+        env.storage().persistent().set(&DataKey::ScheduledAuctions, &scheduled);
+    }
+
+    /// Drop an auction from the scheduled index
+    pub fn unschedule(env: &Env, auction_id: u64) {
+        let mut scheduled = Self::get_scheduled(env);
+        if let Some(position) = scheduled.iter().position(|(_, id)| id == auction_id) {
+            scheduled.remove(position as u32);
+            env.storage().persistent().set(&DataKey::ScheduledAuctions, &scheduled);
+        }
+    }
+
+    /// Internal: A seller's auction IDs in creation order
+    fn get_seller_index(env: &Env, seller: &Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SellerAuctions(seller.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Internal: Check an auction against every criterion the filter sets
+    fn matches(env: &Env, auction: &AuctionTransaction, filter: &AuctionFilter) -> bool {
+        (filter.state.is_empty() || filter.state.contains(auction.state.clone()))
+            && filter.seller.as_ref().is_none_or(|seller| auction.seller == *seller)
+            && filter.nft_address.as_ref().is_none_or(|nft_address| auction.nft_address == *nft_address)
+            // Checked last as it reads the type-specific stores
+            && (filter.auction_type.is_empty()
+                || filter.auction_type.contains(Self::auction_type(env, auction.auction_id)))
+    }
+
+    /// Get auctions by seller, oldest first
     pub fn get_by_seller(env: &Env, seller: &Address) -> Vec<AuctionTransaction> {
         let auctions: Map<u64, AuctionTransaction> = env
             .storage()
@@ -92,14 +219,32 @@ impl AuctionStore {
             .unwrap_or(Map::new(env));
 
         let mut result = Vec::new(env);
-        for (_, auction) in auctions.iter() {
-            if &auction.seller == seller {
+        for auction_id in Self::get_seller_index(env, seller).iter() {
+            if let Some(auction) = auctions.get(auction_id) {
                 result.push_back(auction);
             }
         }
         result
     }
 
+    /// Keep an auction's storage alive for at least `additional_ledgers` more ledgers
+    ///
+    /// The auction, its bids and any Dutch auction data live in the contract instance, so
+    /// the instance TTL is extended along with the auction's persistent escrow, proxy bid
+    /// and candle entries.
+    pub fn bump_auction_storage_ttl(env: &Env, auction_id: u64, additional_ledgers: u32) {
+        env.storage().instance().extend_ttl(additional_ledgers, additional_ledgers);
+
+        for bid in Self::get_bids(env, auction_id).iter() {
+            StorageTtlStore::extend_persistent(env, &DataKey::BidEscrow(auction_id, bid.bidder), additional_ledgers);
+        }
+        StorageTtlStore::extend_persistent(env, &DataKey::ProxyBidders(auction_id), additional_ledgers);
+        for proxy in ProxyBidStore::get_all(env, auction_id).iter() {
+            StorageTtlStore::extend_persistent(env, &DataKey::ProxyBid(auction_id, proxy.bidder), additional_ledgers);
+        }
+        StorageTtlStore::extend_persistent(env, &DataKey::CandleSecret(auction_id), additional_ledgers);
+    }
+
     /// Add a bid to an auction
     pub fn add_bid(env: &Env, auction_id: u64, bid: &Bid) -> Result<(), SettlementError> {
         let mut all_bids: Map<u64, Vec<Bid>> = env
@@ -113,6 +258,7 @@ impl AuctionStore {
 
         all_bids.set(auction_id, auction_bids);
         env.storage().instance().set(&AUCTION_BIDS, &all_bids);
+        Self::index_bidder(env, auction_id, &bid.bidder);
         Ok(())
     }
 
@@ -155,8 +301,67 @@ impl AuctionStore {
 
         all_bids.set(auction_id, auction_bids);
         env.storage().instance().set(&AUCTION_BIDS, &all_bids);
+        Self::index_bidder(env, auction_id, &new_bid.bidder);
         Ok(())
     }
+
+    /// Get a page of the bids `bidder` has placed, by auction in first bid order
+    ///
+    /// `offset` is how many bids to skip; at most `MAX_AUCTION_PAGE_SIZE` are returned
+    /// however large `limit` is. Bids on cancelled auctions are kept and marked stale.
+    pub fn get_bids_by_bidder(env: &Env, bidder: &Address, offset: u64, limit: u64) -> Vec<BidderBid> {
+        let auctions: Map<u64, AuctionTransaction> = env
+            .storage()
+            .instance()
+            .get(&AUCTIONS)
+            .unwrap_or(Map::new(env));
+        let limit = limit.min(MAX_AUCTION_PAGE_SIZE);
+
+        let mut page = Vec::new(env);
+        let mut skipped = 0u64;
+        for auction_id in Self::get_bidder_index(env, bidder).iter() {
+            if page.len() as u64 >= limit {
+                break;
+            }
+            let stale = auctions
+                .get(auction_id)
+                .is_none_or(|auction| auction.state == TransactionState::Cancelled);
+            for bid in Self::get_bids(env, auction_id).iter() {
+                if page.len() as u64 >= limit {
+                    break;
+                }
+                if bid.bidder != *bidder {
+                    continue;
+                }
+                if skipped < offset {
+                    skipped += 1;
+                    continue;
+                }
+                page.push_back(BidderBid { auction_id, bid, stale });
+            }
+        }
+        page
+    }
+
+    /// Internal: IDs of the auctions a bidder has bid in, in first bid order
+    fn get_bidder_index(env: &Env, bidder: &Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BidderAuctions(bidder.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Internal: Record that a bidder has bid in an auction, once
+    fn index_bidder(env: &Env, auction_id: u64, bidder: &Address) {
+        let mut bidder_auctions = Self::get_bidder_index(env, bidder);
+        if bidder_auctions.contains(auction_id) {
+            return;
+        }
+        bidder_auctions.push_back(auction_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::BidderAuctions(bidder.clone()), &bidder_auctions);
+    }
 }
 
 /// Storage manager for Dutch auction data
@@ -206,4 +411,265 @@ impl DutchAuctionStore {
         env.storage().instance().set(&DUTCH_AUCTIONS, &dutch_auctions);
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Storage manager for Vickrey auction data
+pub struct VickreyAuctionStore;
+
+impl VickreyAuctionStore {
+    /// Store Vickrey auction data
+    pub fn put(env: &Env, auction_id: u64, data: &VickreyAuctionData) -> Result<(), SettlementError> {
+        let mut vickrey_auctions: Map<u64, VickreyAuctionData> = env
+            .storage()
+            .instance()
+            .get(&VICKREY_AUCTIONS)
+            .unwrap_or(Map::new(env));
+
+        vickrey_auctions.set(auction_id, data.clone());
+        env.storage().instance().set(&VICKREY_AUCTIONS, &vickrey_auctions);
+        Ok(())
+    }
+
+    /// Get Vickrey auction data
+    pub fn get(env: &Env, auction_id: u64) -> Result<VickreyAuctionData, SettlementError> {
+        let vickrey_auctions: Map<u64, VickreyAuctionData> = env
+            .storage()
+            .instance()
+            .get(&VICKREY_AUCTIONS)
+            .ok_or(SettlementError::AuctionNotFound)?;
+
+        vickrey_auctions
+            .get(auction_id)
+            .ok_or(SettlementError::AuctionNotFound)
+    }
+
+    /// Check whether an auction is a Vickrey auction
+    pub fn has(env: &Env, auction_id: u64) -> bool {
+        Self::get(env, auction_id).is_ok()
+    }
+
+    /// Update Vickrey auction data
+    pub fn update(env: &Env, auction_id: u64, data: &VickreyAuctionData) -> Result<(), SettlementError> {
+        Self::put(env, auction_id, data)
+    }
+}
+
+/// Storage manager for bid funds held in escrow per auction and bidder
+pub struct BidEscrowStore;
+
+impl BidEscrowStore {
+    /// Get the escrowed amount for a bidder
+    pub fn get(env: &Env, auction_id: u64, bidder: &Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BidEscrow(auction_id, bidder.clone()))
+            .unwrap_or(0)
+    }
+
+    /// Add funds to a bidder's escrow
+    pub fn deposit(env: &Env, auction_id: u64, bidder: &Address, amount: i128) -> Result<i128, SettlementError> {
+        let balance = Self::get(env, auction_id, bidder)
+            .checked_add(amount)
+            .ok_or(SettlementError::Overflow)?;
+        Self::set(env, auction_id, bidder, balance);
+        Ok(balance)
+    }
+
+    /// Remove funds from a bidder's escrow
+    pub fn release(env: &Env, auction_id: u64, bidder: &Address, amount: i128) -> Result<i128, SettlementError> {
+        let current = Self::get(env, auction_id, bidder);
+        if amount > current {
+            return Err(SettlementError::InsufficientFunds);
+        }
+        let balance = current - amount;
+        Self::set(env, auction_id, bidder, balance);
+        Ok(balance)
+    }
+
+    /// Internal: Write or clear the escrow entry
+    fn set(env: &Env, auction_id: u64, bidder: &Address, amount: i128) {
+        let key = DataKey::BidEscrow(auction_id, bidder.clone());
+        if amount == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &amount);
+        }
+    }
+}
+
+/// Storage manager for candle auction data
+pub struct CandleAuctionStore;
+
+impl CandleAuctionStore {
+    /// Store candle auction data
+    pub fn put(env: &Env, auction_id: u64, data: &CandleAuctionData) -> Result<(), SettlementError> {
+        let mut candle_auctions: Map<u64, CandleAuctionData> = env
+            .storage()
+            .instance()
+            .get(&CANDLE_AUCTIONS)
+            .unwrap_or(Map::new(env));
+
+        candle_auctions.set(auction_id, data.clone());
+        env.storage().instance().set(&CANDLE_AUCTIONS, &candle_auctions);
+        Ok(())
+    }
+
+    /// Get candle auction data
+    pub fn get(env: &Env, auction_id: u64) -> Result<CandleAuctionData, SettlementError> {
+        let candle_auctions: Map<u64, CandleAuctionData> = env
+            .storage()
+            .instance()
+            .get(&CANDLE_AUCTIONS)
+            .ok_or(SettlementError::AuctionNotFound)?;
+
+        candle_auctions
+            .get(auction_id)
+            .ok_or(SettlementError::AuctionNotFound)
+    }
+
+    /// Check whether an auction is a candle auction
+    pub fn has(env: &Env, auction_id: u64) -> bool {
+        Self::get(env, auction_id).is_ok()
+    }
+
+    /// Update candle auction data
+    pub fn update(env: &Env, auction_id: u64, data: &CandleAuctionData) -> Result<(), SettlementError> {
+        Self::put(env, auction_id, data)
+    }
+
+    /// Store the drawn end time and salt behind the commitment
+    pub fn put_secret(env: &Env, auction_id: u64, end_time: u64, salt: &BytesN<32>) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::CandleSecret(auction_id), &(end_time, salt.clone()));
+    }
+
+    /// Take the drawn end time and salt, removing them from storage
+    pub fn take_secret(env: &Env, auction_id: u64) -> Result<(u64, BytesN<32>), SettlementError> {
+        let key = DataKey::CandleSecret(auction_id);
+        let secret = env.storage().persistent().get(&key).ok_or(SettlementError::NotFound)?;
+        env.storage().persistent().remove(&key);
+        Ok(secret)
+    }
+}
+
+/// Storage manager for proxy bids
+pub struct ProxyBidStore;
+
+impl ProxyBidStore {
+    /// Store a proxy bid, registering the bidder on first use
+    pub fn put(env: &Env, auction_id: u64, proxy: &ProxyBid) {
+        let mut bidders = Self::bidders(env, auction_id);
+        if !bidders.contains(proxy.bidder.clone()) {
+            bidders.push_back(proxy.bidder.clone());
+            env.storage().persistent().set(&DataKey::ProxyBidders(auction_id), &bidders);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::ProxyBid(auction_id, proxy.bidder.clone()), proxy);
+    }
+
+    /// Get a bidder's proxy bid
+    pub fn get(env: &Env, auction_id: u64, bidder: &Address) -> Option<ProxyBid> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ProxyBid(auction_id, bidder.clone()))
+    }
+
+    /// Get every proxy bid on an auction
+    pub fn get_all(env: &Env, auction_id: u64) -> Vec<ProxyBid> {
+        let mut proxies = Vec::new(env);
+        for bidder in Self::bidders(env, auction_id).iter() {
+            if let Some(proxy) = Self::get(env, auction_id, &bidder) {
+                proxies.push_back(proxy);
+            }
+        }
+        proxies
+    }
+
+    /// Internal: Bidders holding a proxy bid on an auction
+    fn bidders(env: &Env, auction_id: u64) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ProxyBidders(auction_id))
+            .unwrap_or(Vec::new(env))
+    }
+}
+
+/// Storage manager for sealed first-price auction data
+pub struct SealedAuctionStore;
+
+impl SealedAuctionStore {
+    /// Store sealed auction data
+    pub fn put(env: &Env, auction_id: u64, data: &SealedAuctionData) -> Result<(), SettlementError> {
+        let mut sealed_auctions: Map<u64, SealedAuctionData> = env
+            .storage()
+            .instance()
+            .get(&SEALED_AUCTIONS)
+            .unwrap_or(Map::new(env));
+
+        sealed_auctions.set(auction_id, data.clone());
+        env.storage().instance().set(&SEALED_AUCTIONS, &sealed_auctions);
+        Ok(())
+    }
+
+    /// Get sealed auction data
+    pub fn get(env: &Env, auction_id: u64) -> Result<SealedAuctionData, SettlementError> {
+        let sealed_auctions: Map<u64, SealedAuctionData> = env
+            .storage()
+            .instance()
+            .get(&SEALED_AUCTIONS)
+            .ok_or(SettlementError::AuctionNotFound)?;
+
+        sealed_auctions
+            .get(auction_id)
+            .ok_or(SettlementError::AuctionNotFound)
+    }
+
+    /// Check whether an auction is a sealed first-price auction
+    pub fn has(env: &Env, auction_id: u64) -> bool {
+        Self::get(env, auction_id).is_ok()
+    }
+
+    /// Update sealed auction data
+    pub fn update(env: &Env, auction_id: u64, data: &SealedAuctionData) -> Result<(), SettlementError> {
+        Self::put(env, auction_id, data)
+    }
+}
+
+/// Storage manager for bundle auction data
+pub struct BundleAuctionStore;
+
+impl BundleAuctionStore {
+    /// Store bundle auction data
+    pub fn put(env: &Env, auction_id: u64, data: &BundleAuctionData) -> Result<(), SettlementError> {
+        let mut bundle_auctions: Map<u64, BundleAuctionData> = env
+            .storage()
+            .instance()
+            .get(&BUNDLE_AUCTIONS)
+            .unwrap_or(Map::new(env));
+
+        bundle_auctions.set(auction_id, data.clone());
+        env.storage().instance().set(&BUNDLE_AUCTIONS, &bundle_auctions);
+        Ok(())
+    }
+
+    /// Get bundle auction data
+    pub fn get(env: &Env, auction_id: u64) -> Result<BundleAuctionData, SettlementError> {
+        let bundle_auctions: Map<u64, BundleAuctionData> = env
+            .storage()
+            .instance()
+            .get(&BUNDLE_AUCTIONS)
+            .ok_or(SettlementError::AuctionNotFound)?;
+
+        bundle_auctions
+            .get(auction_id)
+            .ok_or(SettlementError::AuctionNotFound)
+    }
+
+    /// Check whether an auction is a bundle auction
+    pub fn has(env: &Env, auction_id: u64) -> bool {
+        Self::get(env, auction_id).is_ok()
+    }
+}
@@ -1,6 +1,8 @@
 use soroban_sdk::{Env, Map, Vec, Symbol, Address, symbol_short};
 use crate::types::Dispute;
 use crate::error::SettlementError;
+use crate::storage::DataKey;
+use crate::storage::ttl_store::StorageTtlStore;
 
 // Storage keys
 pub const DISPUTES: Symbol = symbol_short!("disputes");
@@ -44,6 +46,14 @@ impl DisputeStore {
             .ok_or(SettlementError::DisputeNotFound)
     }
 
+    /// Keep a dispute's storage alive for at least `additional_ledgers` more ledgers
+    ///
+    /// Disputes live in the contract instance; their evidence is a persistent entry.
+    pub fn bump_dispute_storage_ttl(env: &Env, dispute_id: u64, additional_ledgers: u32) {
+        env.storage().instance().extend_ttl(additional_ledgers, additional_ledgers);
+        StorageTtlStore::extend_persistent(env, &DataKey::DisputeEvidence(dispute_id), additional_ledgers);
+    }
+
     /// Update a dispute
     pub fn update(env: &Env, dispute: &Dispute) -> Result<(), SettlementError> {
         Self::put(env, dispute)
@@ -62,6 +72,20 @@ impl DisputeStore {
         Ok(())
     }
 
+    /// Check whether an arbitrator sits on a dispute that hasn't been decided yet
+    pub fn has_unresolved_for_arbitrator(env: &Env, arbitrator: &Address) -> bool {
+        let disputes: Map<u64, Dispute> = env
+            .storage()
+            .instance()
+            .get(&DISPUTES)
+            .unwrap_or(Map::new(env));
+
+        disputes
+            .values()
+            .iter()
+            .any(|dispute| dispute.resolved_at == 0 && dispute.arbitrators.contains(arbitrator.clone()))
+    }
+
     /// Get disputes by transaction ID
     pub fn get_by_transaction(env: &Env, transaction_id: u64) -> Vec<Dispute> {
         let disputes: Map<u64, Dispute> = env
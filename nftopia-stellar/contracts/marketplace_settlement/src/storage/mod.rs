@@ -1,3 +1,64 @@
+use soroban_sdk::{contracttype, Address};
+
 pub mod transaction_store;
 pub mod auction_store;
-pub mod dispute_store;
\ No newline at end of file
+pub mod dispute_store;
+pub mod offer_store;
+pub mod stats_store;
+pub mod admin_config_store;
+pub mod ttl_store;
+
+/// Keys for per-participant records
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    AuctionIndex,            // every auction ID in creation order
+    SellerAuctions(Address), // seller -> their auction IDs in creation order
+    BidderAuctions(Address), // bidder -> IDs of the auctions they've bid in, first bid order
+    BidEscrow(u64, Address), // (auction_id, bidder) -> escrowed bid funds
+    CandleSecret(u64),       // auction_id -> (secret end time, salt)
+    ProxyBid(u64, Address),  // (auction_id, bidder) -> standing proxy bid
+    ProxyBidders(u64),       // auction_id -> bidders holding a proxy bid
+    Banned(Address),         // address -> banned from trading (instance storage)
+    CircuitBreakerTripped,   // settlement halted after an oversized transaction (instance storage)
+    AllowedNftContracts,     // NFT contracts vetted for listing (instance storage)
+    AdminConfigV2,           // admin configuration once migrated out of instance storage
+    MigrationDone,           // admin configuration has moved to AdminConfigV2 (instance storage)
+    StorageTTLConfig,        // ledgers auction and dispute data is kept alive for (instance storage)
+    ReferrerEarnings(Address), // referrer -> unwithdrawn referral fees per asset
+    VolumeWindowStart(Address), // user -> start of the current fee volume period
+    VipTiers,                // VIP tiers by increasing min_volume (instance storage)
+    UserTier(Address),       // user -> index of the VIP tier they currently hold
+    PromotionalOverride,     // the scheduled or running promotional fee discount (instance storage)
+    SwapDex,                 // DEX router accumulated fees are swapped through (instance storage)
+    EmergencyPaused,         // all marketplace operations halted (instance storage)
+    EmergencyPausedAt,       // when the emergency pause began (instance storage)
+    Escrow(u64),             // sale transaction_id -> buyer funds held by the contract
+    TokenSaleHistory(Address, u64), // (nft_address, token_id) -> executed sale IDs, oldest first
+    TokenPriceHistory(Address, u64), // (nft_address, token_id) -> (sale price, timestamp), oldest first
+    FloorPrice(Address),     // nft_address -> lowest active listing price
+    CollectionStats(Address), // nft_address -> trading statistics
+    CollectionEMA(Address),  // nft_address -> moving average sale price
+    CollectionBuyers(Address), // nft_address -> buyers counted as unique (capped)
+    CollectionSellers(Address), // nft_address -> sellers counted as unique (capped)
+    PlatformStats,           // marketplace-wide auction statistics (instance storage)
+    PlatformBuyers,          // auction winners counted as unique (capped)
+    PlatformSellers,         // auction sellers counted as unique (capped)
+    ArbitratorStake(Address), // arbitrator -> tokens bonded to be eligible for disputes
+    ArbitratorStakeAsset,    // asset every arbitrator stake is held in (instance storage)
+    PenaltyPool,             // stake slashed from arbitrators, awaiting withdrawal (instance storage)
+    DisputeEvidence(u64),    // dispute_id -> hash-verified evidence, oldest first
+    ListingKey(Address),     // seller -> ed25519 key that signs their off-chain listings
+    UsedNonce(Address, u64), // (seller, nonce) -> off-chain listing already executed
+    AccountNonce(Address),   // address -> count of its state-changing calls, stamped into what it signs
+    BundleItemSold(u64, u64), // (bundle_id, token_id) -> item already sold out of the bundle
+    MultiTrade(u64),         // trade_id -> multi-party trade
+    Initialized,             // initialize has run (instance storage)
+    PendingAdminConfig,      // (proposed admin config, proposal time) awaiting the timelock (instance storage)
+    GovernanceToken,         // token whose balances weigh config votes; set once (instance storage)
+    GovernanceProposalCount, // number of governance proposals created (instance storage)
+    Governance(u64),         // proposal_id -> governance proposal
+    GovernanceVote(u64, Address), // (proposal_id, voter) -> tokens the voter still has locked on it
+    GovernanceQuorum,        // tokens that must vote for a proposal to pass (instance storage)
+    ScheduledAuctions,       // (start_time, auction_id) of auctions waiting to open, soonest first
+}
@@ -0,0 +1,188 @@
+use soroban_sdk::{Env, Map, Vec, Symbol, Address, symbol_short};
+use crate::types::{CollectionOffer, Offer};
+use crate::error::SettlementError;
+use crate::utils::time_utils;
+
+// Storage keys
+pub const OFFERS: Symbol = symbol_short!("offers");
+pub const NEXT_OFFER_ID: Symbol = symbol_short!("next_offr");
+pub const COLLECTION_OFFERS: Symbol = symbol_short!("col_offrs");
+pub const NEXT_COLLECTION_OFFER_ID: Symbol = symbol_short!("next_coff");
+
+/// Storage manager for buyer offers
+pub struct OfferStore;
+
+impl OfferStore {
+    /// Get the next available offer ID
+    pub fn next_id(env: &Env) -> u64 {
+        let current_id: u64 = env.storage().instance().get(&NEXT_OFFER_ID).unwrap_or(1);
+        let next_id = current_id + 1;
+        env.storage().instance().set(&NEXT_OFFER_ID, &next_id);
+        current_id
+    }
+
+    /// Store an offer
+    pub fn put(env: &Env, offer: &Offer) -> Result<(), SettlementError> {
+        let mut offers: Map<u64, Offer> = env
+            .storage()
+            .instance()
+            .get(&OFFERS)
+            .unwrap_or(Map::new(env));
+
+        offers.set(offer.offer_id, offer.clone());
+        env.storage().instance().set(&OFFERS, &offers);
+        Ok(())
+    }
+
+    /// Get an offer by ID
+    pub fn get(env: &Env, offer_id: u64) -> Result<Offer, SettlementError> {
+        let offers: Map<u64, Offer> = env
+            .storage()
+            .instance()
+            .get(&OFFERS)
+            .ok_or(SettlementError::NotFound)?;
+
+        offers
+            .get(offer_id)
+            .ok_or(SettlementError::NotFound)
+    }
+
+    /// Remove an offer
+    pub fn remove(env: &Env, offer_id: u64) -> Result<(), SettlementError> {
+        let mut offers: Map<u64, Offer> = env
+            .storage()
+            .instance()
+            .get(&OFFERS)
+            .ok_or(SettlementError::NotFound)?;
+
+        if !offers.contains_key(offer_id) {
+            return Err(SettlementError::NotFound);
+        }
+
+        offers.remove(offer_id);
+        env.storage().instance().set(&OFFERS, &offers);
+        Ok(())
+    }
+
+    /// Get all open offers on a token
+    pub fn get_by_token(env: &Env, nft_address: &Address, token_id: u64) -> Vec<Offer> {
+        let offers: Map<u64, Offer> = env
+            .storage()
+            .instance()
+            .get(&OFFERS)
+            .unwrap_or(Map::new(env));
+
+        let mut result = Vec::new(env);
+        for (_, offer) in offers.iter() {
+            if offer.nft_address == *nft_address && offer.token_id == token_id {
+                result.push_back(offer);
+            }
+        }
+        result
+    }
+
+    /// Remove and return every offer whose expiry has passed
+    pub fn take_expired(env: &Env) -> Vec<Offer> {
+        let mut offers: Map<u64, Offer> = env
+            .storage()
+            .instance()
+            .get(&OFFERS)
+            .unwrap_or(Map::new(env));
+
+        let mut expired = Vec::new(env);
+        for (offer_id, offer) in offers.clone().iter() {
+            if time_utils::is_expired(offer.expires_at, env) {
+                expired.push_back(offer);
+                offers.remove(offer_id);
+            }
+        }
+
+        if !expired.is_empty() {
+            env.storage().instance().set(&OFFERS, &offers);
+        }
+        expired
+    }
+}
+
+/// Storage manager for collection-wide floor offers
+pub struct CollectionOfferStore;
+
+impl CollectionOfferStore {
+    /// Get the next available collection offer ID
+    pub fn next_id(env: &Env) -> u64 {
+        let current_id: u64 = env.storage().instance().get(&NEXT_COLLECTION_OFFER_ID).unwrap_or(1);
+        let next_id = current_id + 1;
+        env.storage().instance().set(&NEXT_COLLECTION_OFFER_ID, &next_id);
+        current_id
+    }
+
+    /// Store a collection offer
+    pub fn put(env: &Env, offer: &CollectionOffer) -> Result<(), SettlementError> {
+        let mut offers: Map<u64, CollectionOffer> = env
+            .storage()
+            .instance()
+            .get(&COLLECTION_OFFERS)
+            .unwrap_or(Map::new(env));
+
+        offers.set(offer.offer_id, offer.clone());
+        env.storage().instance().set(&COLLECTION_OFFERS, &offers);
+        Ok(())
+    }
+
+    /// Get a collection offer by ID
+    pub fn get(env: &Env, offer_id: u64) -> Result<CollectionOffer, SettlementError> {
+        let offers: Map<u64, CollectionOffer> = env
+            .storage()
+            .instance()
+            .get(&COLLECTION_OFFERS)
+            .ok_or(SettlementError::NotFound)?;
+
+        offers
+            .get(offer_id)
+            .ok_or(SettlementError::NotFound)
+    }
+
+    /// Update a collection offer
+    pub fn update(env: &Env, offer: &CollectionOffer) -> Result<(), SettlementError> {
+        Self::put(env, offer)
+    }
+
+    /// Remove a collection offer
+    pub fn remove(env: &Env, offer_id: u64) -> Result<(), SettlementError> {
+        let mut offers: Map<u64, CollectionOffer> = env
+            .storage()
+            .instance()
+            .get(&COLLECTION_OFFERS)
+            .ok_or(SettlementError::NotFound)?;
+
+        if !offers.contains_key(offer_id) {
+            return Err(SettlementError::NotFound);
+        }
+
+        offers.remove(offer_id);
+        env.storage().instance().set(&COLLECTION_OFFERS, &offers);
+        Ok(())
+    }
+
+    /// Remove and return every collection offer whose expiry has passed
+    pub fn take_expired(env: &Env) -> Vec<CollectionOffer> {
+        let mut offers: Map<u64, CollectionOffer> = env
+            .storage()
+            .instance()
+            .get(&COLLECTION_OFFERS)
+            .unwrap_or(Map::new(env));
+
+        let mut expired = Vec::new(env);
+        for (offer_id, offer) in offers.clone().iter() {
+            if time_utils::is_expired(offer.expires_at, env) {
+                expired.push_back(offer);
+                offers.remove(offer_id);
+            }
+        }
+
+        if !expired.is_empty() {
+            env.storage().instance().set(&COLLECTION_OFFERS, &offers);
+        }
+        expired
+    }
+}
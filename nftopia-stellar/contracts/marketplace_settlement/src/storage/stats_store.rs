@@ -0,0 +1,152 @@
+use soroban_sdk::{Env, Map, Address};
+use crate::types::{CollectionStats, PlatformStats, SaleTransaction};
+use crate::error::SettlementError;
+use crate::storage::DataKey;
+use crate::utils::math_utils;
+
+// Addresses remembered per set for the unique buyer and seller counts
+pub const MAX_TRACKED_ADDRESSES: u32 = 500;
+
+// Sales the collection price moving average is smoothed over
+pub const EMA_WINDOW_SIZE: u32 = 10;
+
+/// Storage manager for per-collection trading statistics
+pub struct CollectionStatsStore;
+
+impl CollectionStatsStore {
+    /// Get a collection's statistics
+    pub fn get(env: &Env, nft_address: &Address) -> CollectionStats {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CollectionStats(nft_address.clone()))
+            .unwrap_or(CollectionStats {
+                total_volume: 0,
+                total_sales: 0,
+                unique_buyers: 0,
+                unique_sellers: 0,
+            })
+    }
+
+    /// Count an executed sale towards its collection's statistics
+    pub fn record_sale(env: &Env, sale: &SaleTransaction, buyer: &Address) -> Result<(), SettlementError> {
+        let mut stats = Self::get(env, &sale.nft_address);
+
+        stats.total_volume = stats.total_volume
+            .checked_add(sale.price)
+            .ok_or(SettlementError::Overflow)?;
+        stats.total_sales += 1;
+
+        if track_unique(env, DataKey::CollectionBuyers(sale.nft_address.clone()), buyer) {
+            stats.unique_buyers += 1;
+        }
+        if track_unique(env, DataKey::CollectionSellers(sale.nft_address.clone()), &sale.seller) {
+            stats.unique_sellers += 1;
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::CollectionStats(sale.nft_address.clone()), &stats);
+
+        // The first sale seeds the moving average
+        let ema = match Self::get_ema_price(env, &sale.nft_address) {
+            Some(current) => math_utils::update_moving_average(current, sale.price, EMA_WINDOW_SIZE, env)?,
+            None => sale.price,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::CollectionEMA(sale.nft_address.clone()), &ema);
+        Ok(())
+    }
+
+    /// Get a collection's moving average sale price, once it has sold
+    pub fn get_ema_price(env: &Env, nft_address: &Address) -> Option<i128> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CollectionEMA(nft_address.clone()))
+    }
+}
+
+/// Storage manager for marketplace-wide auction statistics
+///
+/// Every update is made in the same call as the auction state change it counts.
+pub struct PlatformStatsStore;
+
+impl PlatformStatsStore {
+    /// Get the platform statistics
+    pub fn get(env: &Env) -> PlatformStats {
+        env.storage()
+            .instance()
+            .get(&DataKey::PlatformStats)
+            .unwrap_or(PlatformStats {
+                total_auctions_created: 0,
+                total_auctions_completed: 0,
+                total_auctions_cancelled: 0,
+                total_volume: 0,
+                total_unique_sellers: 0,
+                total_unique_buyers: 0,
+                average_final_price: 0,
+            })
+    }
+
+    /// Count a newly created auction
+    pub fn record_created(env: &Env) {
+        let mut stats = Self::get(env);
+        stats.total_auctions_created += 1;
+        Self::put(env, &stats);
+    }
+
+    /// Count a cancelled auction
+    pub fn record_cancelled(env: &Env) {
+        let mut stats = Self::get(env);
+        stats.total_auctions_cancelled += 1;
+        Self::put(env, &stats);
+    }
+
+    /// Count an auction that closed with a winner
+    pub fn record_completed(
+        env: &Env,
+        seller: &Address,
+        winner: &Address,
+        final_price: i128
+    ) -> Result<(), SettlementError> {
+        let mut stats = Self::get(env);
+
+        stats.total_auctions_completed += 1;
+        stats.total_volume = stats.total_volume
+            .checked_add(final_price)
+            .ok_or(SettlementError::Overflow)?;
+        stats.average_final_price = stats.total_volume / stats.total_auctions_completed as i128;
+
+        if track_unique(env, DataKey::PlatformSellers, seller) {
+            stats.total_unique_sellers += 1;
+        }
+        if track_unique(env, DataKey::PlatformBuyers, winner) {
+            stats.total_unique_buyers += 1;
+        }
+
+        Self::put(env, &stats);
+        Ok(())
+    }
+
+    /// Internal: Write the platform statistics
+    fn put(env: &Env, stats: &PlatformStats) {
+        env.storage().instance().set(&DataKey::PlatformStats, stats);
+    }
+}
+
+/// Internal: Remember an address in a capped set, returning whether it counts as new
+///
+/// Once the set is full, addresses outside it are always counted as new, so the
+/// unique counts become an upper bound rather than growing storage without limit.
+fn track_unique(env: &Env, key: DataKey, address: &Address) -> bool {
+    let mut seen: Map<Address, bool> = env.storage().persistent().get(&key).unwrap_or(Map::new(env));
+    if seen.contains_key(address.clone()) {
+        return false;
+    }
+
+    if seen.len() < MAX_TRACKED_ADDRESSES {
+        seen.set(address.clone(), true);
+        env.storage().persistent().set(&key, &seen);
+    }
+    true
+}
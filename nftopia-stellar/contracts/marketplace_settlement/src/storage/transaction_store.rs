@@ -1,6 +1,7 @@
-use soroban_sdk::{Env, Map, Vec, Symbol, symbol_short};
-use crate::types::{SaleTransaction, TradeTransaction, BundleTransaction};
+use soroban_sdk::{Env, Address, Map, Vec, Symbol, symbol_short};
+use crate::types::{SaleTransaction, TradeTransaction, BundleTransaction, CollectionFloor, TransactionState};
 use crate::error::SettlementError;
+use crate::storage::DataKey;
 
 // Storage keys
 pub const SALE_TRANSACTIONS: Symbol = symbol_short!("sale_tx");
@@ -66,6 +67,38 @@ impl SaleTransactionStore {
         Ok(())
     }
 
+    /// Remove up to `batch_size` cancelled sales and pending sales past their expiry
+    ///
+    /// Returns the number removed. The floor price index needs no update: cancelled sales
+    /// left it when cancelled, and a floor held by an expired listing lapses with it.
+    pub fn cleanup_expired(env: &Env, batch_size: u32) -> u32 {
+        let mut transactions: Map<u64, SaleTransaction> = env
+            .storage()
+            .instance()
+            .get(&SALE_TRANSACTIONS)
+            .unwrap_or(Map::new(env));
+
+        let now = env.ledger().timestamp();
+        let mut removed = 0u32;
+        for (transaction_id, sale) in transactions.clone().iter() {
+            if removed >= batch_size {
+                break;
+            }
+            let expired = sale.state == TransactionState::Pending && sale.expires_at < now;
+            if !expired && sale.state != TransactionState::Cancelled {
+                continue;
+            }
+
+            transactions.remove(transaction_id);
+            removed += 1;
+        }
+
+        if removed > 0 {
+            env.storage().instance().set(&SALE_TRANSACTIONS, &transactions);
+        }
+        removed
+    }
+
     /// Get all sale transactions (paginated)
     pub fn get_all(env: &Env, offset: u64, limit: u64) -> Vec<SaleTransaction> {
         let transactions: Map<u64, SaleTransaction> = env
@@ -128,6 +161,188 @@ impl SaleTransactionStore {
     }
 }
 
+/// Storage manager for buyer funds escrowed against a sale
+pub struct SaleEscrowStore;
+
+impl SaleEscrowStore {
+    /// Get the amount escrowed for a sale
+    pub fn get(env: &Env, transaction_id: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Escrow(transaction_id))
+            .unwrap_or(0)
+    }
+
+    /// Record the amount escrowed for a sale
+    pub fn set(env: &Env, transaction_id: u64, amount: i128) {
+        env.storage().persistent().set(&DataKey::Escrow(transaction_id), &amount);
+    }
+
+    /// Clear a sale's escrow, returning the amount that was held
+    pub fn take(env: &Env, transaction_id: u64) -> i128 {
+        let amount = Self::get(env, transaction_id);
+        env.storage().persistent().remove(&DataKey::Escrow(transaction_id));
+        amount
+    }
+}
+
+/// Storage manager for each token's executed sales
+pub struct SaleHistoryStore;
+
+impl SaleHistoryStore {
+    /// Get the IDs of a token's executed sales, oldest first
+    pub fn get(env: &Env, nft_address: &Address, token_id: u64) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TokenSaleHistory(nft_address.clone(), token_id))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Record a sale, dropping the oldest entries beyond `max_len`
+    pub fn append(env: &Env, nft_address: &Address, token_id: u64, transaction_id: u64, max_len: u32) {
+        let mut history = Self::get(env, nft_address, token_id);
+        history.push_back(transaction_id);
+        while history.len() > max_len {
+            history.pop_front();
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::TokenSaleHistory(nft_address.clone(), token_id), &history);
+    }
+}
+
+/// Storage manager for each token's sale prices over time
+pub struct PriceHistoryStore;
+
+impl PriceHistoryStore {
+    /// Get a token's (price, timestamp) points, oldest first
+    pub fn get(env: &Env, nft_address: &Address, token_id: u64) -> Vec<(i128, u64)> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TokenPriceHistory(nft_address.clone(), token_id))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Record a sale price, evicting the oldest points beyond `max_len`
+    pub fn push(env: &Env, nft_address: &Address, token_id: u64, price: i128, max_len: u32) {
+        let mut history = Self::get(env, nft_address, token_id);
+        history.push_back((price, env.ledger().timestamp()));
+        while history.len() > max_len {
+            history.pop_front();
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::TokenPriceHistory(nft_address.clone(), token_id), &history);
+    }
+
+    /// Get the price a token last sold for
+    pub fn last_price(env: &Env, nft_address: &Address, token_id: u64) -> Option<i128> {
+        Self::get(env, nft_address, token_id).last().map(|(price, _)| price)
+    }
+}
+
+/// Storage manager for each collection's floor price
+///
+/// A cheaper listing lowers the floor immediately. When the listing holding the floor
+/// is bought, cancelled or expires the floor is only marked stale, and the remaining
+/// listings are re-scanned on the first read after that ledger closes, so any number of
+/// delistings within one ledger cost a single scan.
+pub struct FloorPriceStore;
+
+impl FloorPriceStore {
+    /// Get a collection's floor price, re-scanning active listings if it has gone stale
+    pub fn get(env: &Env, nft_address: &Address) -> Option<i128> {
+        let floor = Self::load(env, nft_address)?;
+        let now = env.ledger().timestamp();
+
+        let stale_since = match floor.stale_since {
+            Some(since) => Some(since),
+            None if now >= floor.expires_at => Some(floor.expires_at),
+            None => None,
+        };
+
+        match stale_since {
+            Some(since) if now > since => Self::rescan(env, nft_address),
+            _ => Some(floor.price),
+        }
+    }
+
+    /// Lower the floor if a new listing undercuts it
+    pub fn listed(env: &Env, nft_address: &Address, price: i128, expires_at: u64) {
+        // A fresh floor is the lowest active listing, so anything at or below it is the new floor
+        let lowers_floor = match Self::load(env, nft_address) {
+            Some(floor) => price <= floor.price,
+            None => true,
+        };
+
+        if lowers_floor {
+            Self::store(env, nft_address, &CollectionFloor {
+                price,
+                expires_at,
+                stale_since: None,
+            });
+        }
+    }
+
+    /// Mark the floor stale if the listing leaving the market was holding it
+    pub fn delisted(env: &Env, nft_address: &Address, price: i128) {
+        if let Some(mut floor) = Self::load(env, nft_address) {
+            if floor.stale_since.is_none() && price <= floor.price {
+                floor.stale_since = Some(env.ledger().timestamp());
+                Self::store(env, nft_address, &floor);
+            }
+        }
+    }
+
+    /// Internal: Recompute the floor from the collection's active listings
+    fn rescan(env: &Env, nft_address: &Address) -> Option<i128> {
+        let transactions: Map<u64, SaleTransaction> = env
+            .storage()
+            .instance()
+            .get(&SALE_TRANSACTIONS)
+            .unwrap_or(Map::new(env));
+
+        let now = env.ledger().timestamp();
+        let mut lowest: Option<CollectionFloor> = None;
+        for (_, sale) in transactions.iter() {
+            if &sale.nft_address != nft_address
+                || sale.state != TransactionState::Pending
+                || now >= sale.expires_at
+            {
+                continue;
+            }
+            if lowest.as_ref().is_none_or(|floor| sale.price < floor.price) {
+                lowest = Some(CollectionFloor {
+                    price: sale.price,
+                    expires_at: sale.expires_at,
+                    stale_since: None,
+                });
+            }
+        }
+
+        match lowest {
+            Some(floor) => {
+                Self::store(env, nft_address, &floor);
+                Some(floor.price)
+            }
+            None => {
+                env.storage().persistent().remove(&DataKey::FloorPrice(nft_address.clone()));
+                None
+            }
+        }
+    }
+
+    /// Internal: Read the stored floor
+    fn load(env: &Env, nft_address: &Address) -> Option<CollectionFloor> {
+        env.storage().persistent().get(&DataKey::FloorPrice(nft_address.clone()))
+    }
+
+    /// Internal: Write the stored floor
+    fn store(env: &Env, nft_address: &Address, floor: &CollectionFloor) {
+        env.storage().persistent().set(&DataKey::FloorPrice(nft_address.clone()), floor);
+    }
+}
+
 /// Storage manager for trade transactions
 pub struct TradeTransactionStore;
 
@@ -214,4 +429,14 @@ impl BundleTransactionStore {
     pub fn update(env: &Env, transaction: &BundleTransaction) -> Result<(), SettlementError> {
         Self::put(env, transaction)
     }
+
+    /// Mark an item as sold out of a bundle
+    pub fn mark_item_sold(env: &Env, bundle_id: u64, token_id: u64) {
+        env.storage().persistent().set(&DataKey::BundleItemSold(bundle_id, token_id), &true);
+    }
+
+    /// Check whether an item has already been sold out of a bundle
+    pub fn is_item_sold(env: &Env, bundle_id: u64, token_id: u64) -> bool {
+        env.storage().persistent().has(&DataKey::BundleItemSold(bundle_id, token_id))
+    }
 }
\ No newline at end of file
@@ -0,0 +1,45 @@
+use soroban_sdk::Env;
+use crate::types::StorageTTLConfig;
+use crate::error::SettlementError;
+use crate::storage::DataKey;
+
+// Roughly a week and a month of 5 second ledgers
+pub const DEFAULT_AUCTION_MIN_TTL: u32 = 120_960;
+pub const DEFAULT_DISPUTE_MIN_TTL: u32 = 518_400;
+
+/// Storage manager for how long live auction and dispute data is kept from eviction
+pub struct StorageTtlStore;
+
+impl StorageTtlStore {
+    /// Get the TTL configuration, or the defaults if none was set
+    pub fn get_config(env: &Env) -> StorageTTLConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::StorageTTLConfig)
+            .unwrap_or(StorageTTLConfig {
+                auction_min_ttl: DEFAULT_AUCTION_MIN_TTL,
+                dispute_min_ttl: DEFAULT_DISPUTE_MIN_TTL,
+            })
+    }
+
+    /// Replace the TTL configuration
+    ///
+    /// Each TTL must be positive and within the network's maximum entry TTL.
+    pub fn put_config(env: &Env, config: &StorageTTLConfig) -> Result<(), SettlementError> {
+        let max_ttl = env.storage().max_ttl();
+        for ttl in [config.auction_min_ttl, config.dispute_min_ttl] {
+            if ttl == 0 || ttl > max_ttl {
+                return Err(SettlementError::InvalidAmount);
+            }
+        }
+        env.storage().instance().set(&DataKey::StorageTTLConfig, config);
+        Ok(())
+    }
+
+    /// Extend a persistent entry to live at least `ledgers` more ledgers, if it exists
+    pub fn extend_persistent(env: &Env, key: &DataKey, ledgers: u32) {
+        if env.storage().persistent().has(key) {
+            env.storage().persistent().extend_ttl(key, ledgers, ledgers);
+        }
+    }
+}
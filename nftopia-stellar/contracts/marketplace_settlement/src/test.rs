@@ -0,0 +1,4542 @@
+#![cfg(test)]
+
+use crate::dispute_resolution::{Arbitrator, ArbitratorStats, DisputeConfig, DisputeResolutionManager, WeightedVoteResult};
+use crate::auction_engine::AuctionConfig;
+use crate::error::{
+    SettlementError, DISPUTE_RESOLUTION_CANCEL_TRANSACTION, DISPUTE_RESOLUTION_NOT_RESOLVED,
+    DISPUTE_RESOLUTION_RELEASE_TO_SELLER, DISPUTE_RESOLUTION_SPLIT_FUNDS,
+};
+use crate::fee_manager::{get_default_fee_schedule, FeeManager, FeeSchedule};
+use crate::royalty_distributor::RoyaltyDistributor;
+use crate::security::frontrun_protection::CommitRevealScheme;
+use crate::security::rate_limiter::RateLimitConfig;
+use crate::storage::DataKey;
+use crate::storage::admin_config_store::ADMIN_CONFIG_TIMELOCK;
+use crate::storage::auction_store::AuctionStore;
+use crate::storage::dispute_store::DisputeStore;
+use crate::storage::transaction_store::SaleTransactionStore;
+use crate::settlement_core::{MarketplaceSettlement, MarketplaceSettlementClient};
+use crate::types::{
+    AdminConfig, Asset, AuctionFilter, AuctionOptions, AuctionType, FeeConfig, NFTItem, OffChainListing, RoyaltyDistribution, PromotionalFeeOverride, StorageTTLConfig, TransactionState, VipTier,
+};
+use crate::utils::fixed_point::FixedPoint128;
+use crate::utils::math_utils::{calculate_exponential_decay_price, calculate_percentage, update_moving_average};
+use ed25519_dalek::{Signer, SigningKey};
+use nft_contract::types::{CollectionConfig, Role, RoyaltyInfo, TokenAttribute};
+use nft_contract::{NftContract, NftContractClient};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{storage::{Instance as _, Persistent as _}, Address as _, Events, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, xdr::ToXdr, Address, Bytes, BytesN, Env, Map, String, Symbol, TryFromVal, Vec,
+};
+
+// Slippage limit for purchases that aren't testing it
+const ANY_PRICE: i128 = i128::MAX;
+
+fn setup<'a>(env: &Env) -> (MarketplaceSettlementClient<'a>, Address) {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let admin = Address::generate(env);
+    let contract_id = env.register(MarketplaceSettlement, ());
+    let client = MarketplaceSettlementClient::new(env, &contract_id);
+    client.initialize(&admin, &default_fee_config(&client, &admin), &AuctionConfig::default(), &DisputeConfig::default());
+    (client, admin)
+}
+
+/// Default fees paid to `recipient`, built where the marketplace's own address is known
+fn default_fee_config(client: &MarketplaceSettlementClient, recipient: &Address) -> FeeConfig {
+    client.env.as_contract(&client.address, || FeeConfig::new(recipient.clone(), &client.env))
+}
+
+fn test_asset(env: &Env) -> Asset {
+    let token = env.register_stellar_asset_contract_v2(Address::generate(env));
+    Asset {
+        contract: token.address(),
+        symbol: symbol_short!("USDC"),
+    }
+}
+
+fn fund(env: &Env, asset: &Asset, account: &Address, amount: i128) {
+    StellarAssetClient::new(env, &asset.contract).mint(account, &amount);
+}
+
+fn balance(env: &Env, asset: &Asset, account: &Address) -> i128 {
+    TokenClient::new(env, &asset.contract).balance(account)
+}
+
+/// A deployed NFT collection whose tokens carry no royalty
+fn nft_collection<'a>(env: &Env) -> NftContractClient<'a> {
+    let admin = Address::generate(env);
+    let nft = NftContractClient::new(env, &env.register(NftContract, ()));
+    nft.initialize(&admin, &CollectionConfig {
+        name: String::from_str(env, "Test NFT"),
+        symbol: String::from_str(env, "TNFT"),
+        description: String::from_str(env, "Marketplace test collection"),
+        base_uri: String::from_str(env, "https://test.com/"),
+        max_supply: None,
+        mint_price: None,
+        royalty_default: RoyaltyInfo {
+            recipient: admin.clone(),
+            percentage: 0,
+        },
+        metadata_is_frozen: false,
+        provenance_hash: None,
+    });
+    nft
+}
+
+/// Mint a token to `owner` and let the marketplace move it
+fn mint_for_sale(env: &Env, nft: &NftContractClient, owner: &Address, marketplace: &Address) -> u64 {
+    let token_id = nft.mint(
+        owner,
+        &String::from_str(env, "ipfs://token"),
+        &Vec::new(env),
+        &None,
+        &None,
+        &None,
+        &nft.get_admin(),
+    );
+    nft.set_approval_for_all(marketplace, &true, owner);
+    token_id
+}
+
+/// A fresh token owned by `seller` that the marketplace may move
+fn owned_token(env: &Env, client: &MarketplaceSettlementClient, seller: &Address) -> (Address, u64) {
+    let nft = nft_collection(env);
+    let token_id = mint_for_sale(env, &nft, seller, &client.address);
+    (nft.address, token_id)
+}
+
+fn holds(nft: &NftContractClient, owner: &Address, token_id: u64) -> bool {
+    nft.get_tokens_of_owner(owner, &0, &100).contains(token_id)
+}
+
+/// A fresh account holding plenty of the auction's currency
+fn funded_bidder(env: &Env, client: &MarketplaceSettlementClient, auction_id: u64) -> Address {
+    let bidder = Address::generate(env);
+    fund(env, &client.get_auction(&auction_id).currency, &bidder, 1_000_000);
+    bidder
+}
+
+fn set_time(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|li| li.timestamp = timestamp);
+}
+
+/// Propose `config`, wait out the timelock and apply it
+fn apply_admin_config(env: &Env, client: &MarketplaceSettlementClient, config: &AdminConfig, admin: &Address) {
+    client.propose_admin_config_update(config, admin);
+    set_time(env, env.ledger().timestamp() + ADMIN_CONFIG_TIMELOCK);
+    client.execute_admin_config_update(admin);
+}
+
+/// Move past the 48 hours either party has to appeal a dispute ruling
+fn close_appeal_window(env: &Env) {
+    env.ledger().with_mut(|li| li.timestamp += 172_801);
+}
+
+fn create_vickrey_auction(env: &Env, client: &MarketplaceSettlementClient) -> u64 {
+    let seller = Address::generate(env);
+    let (nft, token_id) = owned_token(env, client, &seller);
+    client.create_auction(
+        &seller,
+        &nft,
+        &token_id,
+        &1_000,
+        &500,
+        &3_600,
+        &100,
+        &AuctionType::Vickrey,
+        &test_asset(env),
+        &AuctionOptions {
+            buyout_price: None,
+            allowed_bidders: None,
+            dutch_half_life: None,
+            start_time: None,
+        },
+    )
+}
+
+fn create_english_auction(env: &Env, client: &MarketplaceSettlementClient, buyout_price: Option<i128>) -> u64 {
+    let seller = Address::generate(env);
+    let nft = nft_collection(env);
+    let token_id = mint_for_sale(env, &nft, &seller, &client.address);
+    client.create_auction(
+        &seller,
+        &nft.address,
+        &token_id,
+        &2_000,
+        &1_500,
+        &3_600,
+        &100,
+        &AuctionType::English,
+        &test_asset(env),
+        &AuctionOptions {
+            buyout_price,
+            allowed_bidders: None,
+            dutch_half_life: None,
+            start_time: None,
+        },
+    )
+}
+
+fn seal(client: &MarketplaceSettlementClient, auction_id: u64, bidder: &Address, amount: i128, salt: &Bytes) {
+    let commitment = CommitRevealScheme::create_commitment(&client.env, bidder, auction_id, amount, salt);
+    client.place_bid(&auction_id, bidder, &0, &Some(commitment));
+}
+
+#[test]
+fn test_initialize() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    assert_eq!(count_events(&env, symbol_short!("mkt_init")), 1);
+    assert!(client.is_initialized());
+    assert_eq!(client.get_accumulated_fees(&test_asset(&env)), 0);
+
+    // A second initialization can't take over the contract
+    let attacker = Address::generate(&env);
+    let result = client.try_initialize(
+        &attacker,
+        &default_fee_config(&client, &attacker),
+        &AuctionConfig::default(),
+        &DisputeConfig::default()
+    );
+    assert_eq!(result, Err(Ok(SettlementError::AlreadyExists)));
+    assert_eq!(client.get_admin_config().admin, admin);
+}
+
+#[test]
+fn test_initialize_rejects_invalid_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let client = MarketplaceSettlementClient::new(&env, &env.register(MarketplaceSettlement, ()));
+    assert!(!client.is_initialized());
+
+    // A platform fee above 100%
+    let mut fee_config = default_fee_config(&client, &admin);
+    fee_config.platform_fee_bps = 10_001;
+    let result = client.try_initialize(&admin, &fee_config, &AuctionConfig::default(), &DisputeConfig::default());
+    assert_eq!(result, Err(Ok(SettlementError::InvalidFeeConfig)));
+
+    let auction_config = AuctionConfig { min_bid_increment_bps: 0, ..AuctionConfig::default() };
+    let result = client.try_initialize(
+        &admin,
+        &default_fee_config(&client, &admin),
+        &auction_config,
+        &DisputeConfig::default()
+    );
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+
+    let dispute_config = DisputeConfig { arbitration_quorum: 0, ..DisputeConfig::default() };
+    let result = client.try_initialize(
+        &admin,
+        &default_fee_config(&client, &admin),
+        &AuctionConfig::default(),
+        &dispute_config
+    );
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+
+    // The admin has to sign for the contract they are handed
+    env.set_auths(&[]);
+    let result = client.try_initialize(
+        &admin,
+        &default_fee_config(&client, &admin),
+        &AuctionConfig::default(),
+        &DisputeConfig::default()
+    );
+    assert!(matches!(result, Err(Err(_))));
+    env.mock_all_auths();
+
+    // Nothing was stored, so a valid configuration can still initialize
+    assert!(!client.is_initialized());
+    client.initialize(&admin, &default_fee_config(&client, &admin), &AuctionConfig::default(), &DisputeConfig::default());
+    assert!(client.is_initialized());
+}
+
+#[test]
+fn test_vickrey_winner_pays_second_highest_bid() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let auction_id = create_vickrey_auction(&env, &client);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let alice_salt = Bytes::from_slice(&env, b"alice-salt");
+    let bob_salt = Bytes::from_slice(&env, b"bob-salt");
+    let carol_salt = Bytes::from_slice(&env, b"carol-salt");
+
+    set_time(&env, 1_120);
+    seal(&client, auction_id, &alice, 5_000, &alice_salt);
+    set_time(&env, 1_907);
+    seal(&client, auction_id, &bob, 3_250, &bob_salt);
+    set_time(&env, 2_731);
+    seal(&client, auction_id, &carol, 1_400, &carol_salt);
+
+    // Reveals are rejected while bidding is still open
+    let result = client.try_reveal_vickrey_bid(&auction_id, &alice, &5_000, &alice_salt);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+
+    set_time(&env, 4_700);
+    client.reveal_vickrey_bid(&auction_id, &bob, &3_250, &bob_salt);
+    client.reveal_vickrey_bid(&auction_id, &alice, &5_000, &alice_salt);
+    client.reveal_vickrey_bid(&auction_id, &carol, &1_400, &carol_salt);
+
+    let data = client.get_vickrey_auction(&auction_id);
+    assert_eq!(data.highest_bidder, Some(alice.clone()));
+    assert_eq!(data.highest_bid, 5_000);
+    assert_eq!(data.second_highest_bid, 3_250);
+
+    // Settlement waits for the reveal period to close
+    let result = client.try_end_vickrey_auction(&auction_id, &alice);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+
+    set_time(&env, 8_300);
+    client.end_vickrey_auction(&auction_id, &alice);
+
+    let auction = client.get_auction(&auction_id);
+    assert_eq!(auction.state, TransactionState::Executed);
+    assert_eq!(auction.highest_bidder, Some(alice));
+    assert_eq!(auction.highest_bid, 3_250);
+}
+
+#[test]
+fn test_vickrey_single_bidder_pays_starting_price() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let auction_id = create_vickrey_auction(&env, &client);
+
+    let bidder = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"only-bid");
+    set_time(&env, 1_450);
+    seal(&client, auction_id, &bidder, 2_600, &salt);
+
+    set_time(&env, 5_000);
+    client.reveal_vickrey_bid(&auction_id, &bidder, &2_600, &salt);
+
+    set_time(&env, 8_400);
+    client.end_vickrey_auction(&auction_id, &bidder);
+
+    let auction = client.get_auction(&auction_id);
+    assert_eq!(auction.highest_bidder, Some(bidder));
+    assert_eq!(auction.highest_bid, 1_000);
+}
+
+#[test]
+fn test_vickrey_rejects_unsealed_and_duplicate_bids() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let auction_id = create_vickrey_auction(&env, &client);
+
+    let bidder = Address::generate(&env);
+    set_time(&env, 1_300);
+    let result = client.try_place_bid(&auction_id, &bidder, &2_000, &None);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+
+    let salt = Bytes::from_slice(&env, b"first");
+    seal(&client, auction_id, &bidder, 2_000, &salt);
+
+    set_time(&env, 2_050);
+    let commitment = CommitRevealScheme::create_commitment(&env, &bidder, auction_id, 2_500, &salt);
+    let result = client.try_place_bid(&auction_id, &bidder, &0, &Some(commitment));
+    assert_eq!(result, Err(Ok(SettlementError::AlreadyExists)));
+
+    // The generic end path cannot settle a sealed-bid auction
+    set_time(&env, 9_000);
+    let result = client.try_end_auction(&auction_id, &bidder, &0);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+}
+
+#[test]
+fn test_commitment_binds_bid_details() {
+    let env = Env::default();
+    let bidder = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"salt");
+    let commitment = CommitRevealScheme::create_commitment(&env, &bidder, 42, 2_500, &salt);
+
+    // sha256(bidder XDR || auction_id as u64 BE || bid_amount as i128 BE || salt)
+    let mut preimage = bidder.clone().to_xdr(&env);
+    preimage.append(&Bytes::from_array(&env, &[0, 0, 0, 0, 0, 0, 0, 42]));
+    preimage.append(&Bytes::from_array(&env, &[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x09, 0xc4]));
+    preimage.append(&salt);
+    let expected: Bytes = env.crypto().sha256(&preimage).to_bytes().into();
+    assert_eq!(commitment, expected);
+    assert_eq!(commitment.len(), 32);
+    assert_eq!(commitment, CommitRevealScheme::create_commitment(&env, &bidder, 42, 2_500, &salt));
+
+    // Changing any input changes the commitment
+    let other_salt = Bytes::from_slice(&env, b"tlas");
+    assert_ne!(commitment, CommitRevealScheme::create_commitment(&env, &bidder, 42, 2_500, &other_salt));
+    assert_ne!(commitment, CommitRevealScheme::create_commitment(&env, &bidder, 42, 2_501, &salt));
+    assert_ne!(commitment, CommitRevealScheme::create_commitment(&env, &bidder, 43, 2_500, &salt));
+    let other_bidder = Address::generate(&env);
+    assert_ne!(commitment, CommitRevealScheme::create_commitment(&env, &other_bidder, 42, 2_500, &salt));
+}
+
+#[test]
+fn test_commitment_rejects_raw_salt() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let auction_id = create_vickrey_auction(&env, &client);
+    let bidder = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"first");
+
+    // The salt alone is no longer a valid commitment
+    set_time(&env, 1_300);
+    let result = client.try_place_bid(&auction_id, &bidder, &0, &Some(salt.clone()));
+    assert_eq!(result, Err(Ok(SettlementError::CommitmentMismatch)));
+
+    // A commitment only reveals with the amount it was made for
+    seal(&client, auction_id, &bidder, 2_000, &salt);
+    set_time(&env, 4_800);
+    let result = client.try_reveal_vickrey_bid(&auction_id, &bidder, &2_500, &salt);
+    assert_eq!(result, Err(Ok(SettlementError::CommitmentMismatch)));
+    client.reveal_vickrey_bid(&auction_id, &bidder, &2_000, &salt);
+}
+
+#[test]
+fn test_vickrey_reveal_checks() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let auction_id = create_vickrey_auction(&env, &client);
+
+    let bidder = Address::generate(&env);
+    let lowballer = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"secret");
+    let low_salt = Bytes::from_slice(&env, b"lowball");
+
+    set_time(&env, 1_600);
+    seal(&client, auction_id, &bidder, 3_000, &salt);
+    set_time(&env, 2_240);
+    seal(&client, auction_id, &lowballer, 400, &low_salt);
+
+    set_time(&env, 4_800);
+    let result = client.try_reveal_vickrey_bid(&auction_id, &bidder, &3_000, &Bytes::from_slice(&env, b"wrong"));
+    assert_eq!(result, Err(Ok(SettlementError::CommitmentMismatch)));
+
+    client.reveal_vickrey_bid(&auction_id, &bidder, &3_000, &salt);
+    let result = client.try_reveal_vickrey_bid(&auction_id, &bidder, &3_000, &salt);
+    assert_eq!(result, Err(Ok(SettlementError::AlreadyExists)));
+
+    let result = client.try_reveal_vickrey_bid(&auction_id, &lowballer, &400, &low_salt);
+    assert_eq!(result, Err(Ok(SettlementError::BidTooLow)));
+
+    // Reveals close with the reveal period
+    set_time(&env, 8_300);
+    let result = client.try_reveal_vickrey_bid(&auction_id, &lowballer, &400, &low_salt);
+    assert_eq!(result, Err(Ok(SettlementError::Expired)));
+}
+
+#[test]
+fn test_buy_now_ends_auction_at_buyout_price() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let nft = nft_collection(&env);
+    let seller = Address::generate(&env);
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let auction_id = client.create_auction(
+        &seller,
+        &nft.address,
+        &token_id,
+        &2_000,
+        &1_500,
+        &3_600,
+        &100,
+        &AuctionType::English,
+        &test_asset(&env),
+        &AuctionOptions {
+            buyout_price: Some(9_000),
+            allowed_bidders: None,
+            dutch_half_life: None,
+            start_time: None,
+        },
+    );
+
+    let buyer = funded_bidder(&env, &client, auction_id);
+    set_time(&env, 1_730);
+    let result = client.buy_now(&auction_id, &buyer);
+    assert!(result.success);
+    assert!(holds(&nft, &buyer, token_id));
+
+    let auction = client.get_auction(&auction_id);
+    assert_eq!(auction.state, TransactionState::Executed);
+    assert_eq!(auction.highest_bidder, Some(buyer.clone()));
+    assert_eq!(auction.highest_bid, 9_000);
+    assert_eq!(client.get_accumulated_fees(&auction.currency), 1_000); // Minimum platform fee
+
+    // The auction is closed to further purchases and bids
+    let result = client.try_buy_now(&auction_id, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::AuctionAlreadyEnded)));
+}
+
+#[test]
+fn test_end_auction_settles_winning_bid() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let auction_id = create_english_auction(&env, &client, None);
+    let auction = client.get_auction(&auction_id);
+    let nft = NftContractClient::new(&env, &auction.nft_address);
+    let winner = funded_bidder(&env, &client, auction_id);
+    set_time(&env, 1_340);
+    client.place_bid(&auction_id, &winner, &50_000, &None);
+
+    set_time(&env, 4_700);
+    let result = client.end_auction(&auction_id, &admin, &0);
+    assert_eq!(count_events(&env, symbol_short!("auc_ended")), 1);
+    assert!(result.success);
+    assert!(result.transferred_nft);
+    assert!(result.transferred_payment);
+    assert!(result.distributed_royalties);
+    assert!(result.collected_platform_fee);
+    assert!(holds(&nft, &winner, auction.token_id));
+
+    // The seller is paid from the winner's escrow less the 2.5% platform fee
+    assert_eq!(balance(&env, &auction.currency, &auction.seller), 48_750);
+    assert_eq!(balance(&env, &auction.currency, &client.address), 1_250);
+    assert_eq!(client.get_accumulated_fees(&auction.currency), 1_250);
+    assert_eq!(client.get_bid_escrow(&auction_id, &winner), 0);
+    assert_eq!(client.get_auction(&auction_id).state, TransactionState::Executed);
+}
+
+/// NFT contract that only answers royalty queries, owing `bps` of every sale to one receiver
+#[contract]
+struct MockRoyaltyNft;
+
+#[contractimpl]
+impl MockRoyaltyNft {
+    pub fn __constructor(env: Env, receiver: Address, bps: i128) {
+        env.storage().instance().set(&symbol_short!("receiver"), &receiver);
+        env.storage().instance().set(&symbol_short!("bps"), &bps);
+    }
+
+    pub fn get_royalty_info(env: Env, _token_id: u64, sale_price: i128) -> (Address, i128) {
+        let receiver: Address = env.storage().instance().get(&symbol_short!("receiver")).unwrap();
+        let bps: i128 = env.storage().instance().get(&symbol_short!("bps")).unwrap();
+        (receiver, sale_price * bps / 10_000)
+    }
+}
+
+#[test]
+fn test_fetch_royalty_info_from_nft() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let creator = Address::generate(&env);
+    let fair = env.register(MockRoyaltyNft, (creator.clone(), 1_000_i128));
+    let greedy = env.register(MockRoyaltyNft, (creator.clone(), 8_000_i128));
+    let broken = env.register(MockRoyaltyNft, (creator.clone(), 12_000_i128));
+    let no_royalties = env.register(MockDex, ());
+
+    env.as_contract(&client.address, || {
+        let distribution = RoyaltyDistributor::fetch_royalty_info_from_nft(&env, &fair, 1, 50_000).unwrap();
+        assert_eq!(distribution.creator_address, creator);
+        assert_eq!(distribution.creator_percentage, 1_000);
+        assert_eq!(distribution.seller_percentage, 9_000);
+        assert_eq!(distribution.amounts.get(creator.clone()), Some(5_000));
+
+        // Capped at the admin config's 50% maximum
+        let distribution = RoyaltyDistributor::fetch_royalty_info_from_nft(&env, &greedy, 1, 50_000).unwrap();
+        assert_eq!(distribution.creator_percentage, 5_000);
+        assert_eq!(distribution.amounts.get(creator.clone()), Some(25_000));
+
+        // A royalty above the price, a free sale or a contract without royalties is rejected
+        let result = RoyaltyDistributor::fetch_royalty_info_from_nft(&env, &broken, 1, 50_000);
+        assert_eq!(result, Err(SettlementError::InvalidAmount));
+        let result = RoyaltyDistributor::fetch_royalty_info_from_nft(&env, &fair, 1, 0);
+        assert_eq!(result, Err(SettlementError::InvalidAmount));
+        let result = RoyaltyDistributor::fetch_royalty_info_from_nft(&env, &no_royalties, 1, 50_000);
+        assert_eq!(result, Err(SettlementError::InvalidState));
+    });
+}
+
+#[test]
+fn test_settlement_pays_nft_contract_royalties() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let creator = Address::generate(&env);
+
+    // An auction pays the royalty the collection reports when it ends
+    let auction_id = create_english_auction(&env, &client, None);
+    let auction = client.get_auction(&auction_id);
+    let nft = NftContractClient::new(&env, &auction.nft_address);
+    nft.set_token_royalty(&auction.token_id, &creator, &1_000, &nft.get_admin());
+    let winner = funded_bidder(&env, &client, auction_id);
+    set_time(&env, 1_340);
+    client.place_bid(&auction_id, &winner, &50_000, &None);
+    set_time(&env, 4_700);
+    assert!(client.end_auction(&auction_id, &admin, &0).distributed_royalties);
+    assert_eq!(balance(&env, &auction.currency, &creator), 5_000);
+    assert_eq!(balance(&env, &auction.currency, &auction.seller), 43_750);
+
+    // A sale pays the royalty in force when it executes, not when it was listed
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let currency = test_asset(&env);
+    fund(&env, &currency, &buyer, 100_000);
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &100_000, &currency, &86_400);
+    nft.set_token_royalty(&token_id, &creator, &500, &nft.get_admin());
+    client.fund_sale(&sale_id, &buyer);
+    client.execute_sale(&sale_id, &buyer, &100_000);
+    assert_eq!(balance(&env, &currency, &creator), 5_000);
+    assert_eq!(balance(&env, &currency, &seller), 92_500);
+    assert_eq!(client.get_sale(&sale_id).royalty_info.creator_percentage, 500);
+}
+
+#[test]
+fn test_end_auction_disputes_failed_settlement() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let auction_id = create_english_auction(&env, &client, None);
+    let auction = client.get_auction(&auction_id);
+    let nft = NftContractClient::new(&env, &auction.nft_address);
+    let winner = funded_bidder(&env, &client, auction_id);
+    set_time(&env, 1_340);
+    client.place_bid(&auction_id, &winner, &50_000, &None);
+
+    // The seller revokes the marketplace's approval before the auction closes
+    nft.set_approval_for_all(&client.address, &false, &auction.seller);
+    set_time(&env, 4_700);
+    let result = client.end_auction(&auction_id, &admin, &0);
+    assert_eq!(count_events(&env, symbol_short!("emerg_wd")), 1);
+    assert!(!result.success);
+    assert!(!result.transferred_nft);
+    assert!(!result.transferred_payment);
+    assert!(holds(&nft, &auction.seller, auction.token_id));
+
+    // The winning bid stays escrowed for the dispute
+    assert_eq!(client.get_auction(&auction_id).state, TransactionState::Disputed);
+    assert_eq!(client.get_bid_escrow(&auction_id, &winner), 50_000);
+    assert_eq!(balance(&env, &auction.currency, &client.address), 50_000);
+    assert_eq!(balance(&env, &auction.currency, &auction.seller), 0);
+    let result = client.try_withdraw_bid_escrow(&auction_id, &winner);
+    assert_eq!(result, Err(Ok(SettlementError::InsufficientFunds)));
+}
+
+#[test]
+fn test_platform_wide_stats_lifecycle() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let sold = create_english_auction(&env, &client, None);
+    let disputed = create_english_auction(&env, &client, None);
+    let cancelled = create_english_auction(&env, &client, None);
+    let bought_out = create_english_auction(&env, &client, Some(9_000));
+    let stats = client.get_platform_wide_stats();
+    assert_eq!(stats.total_auctions_created, 4);
+    assert_eq!(stats.total_auctions_completed, 0);
+
+    // Only the seller can cancel, and only once
+    let seller = client.get_auction(&cancelled).seller;
+    let result = client.try_cancel_auction(&cancelled, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    client.cancel_auction(&cancelled, &seller);
+    assert_eq!(count_events(&env, symbol_short!("auc_ended")), 1);
+    assert_eq!(client.get_auction(&cancelled).state, TransactionState::Cancelled);
+    assert_eq!(client.try_cancel_auction(&cancelled, &seller), Err(Ok(SettlementError::InvalidState)));
+    assert_eq!(client.get_platform_wide_stats().total_auctions_cancelled, 1);
+
+    let buyer = funded_bidder(&env, &client, sold);
+    fund(&env, &client.get_auction(&bought_out).currency, &buyer, 1_000_000);
+    let other_buyer = funded_bidder(&env, &client, disputed);
+    set_time(&env, 1_340);
+    client.place_bid(&sold, &buyer, &50_000, &None);
+    set_time(&env, 1_517);
+    client.place_bid(&disputed, &other_buyer, &30_000, &None);
+    let result = client.try_cancel_auction(&sold, &client.get_auction(&sold).seller);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+    set_time(&env, 1_730);
+    client.buy_now(&bought_out, &buyer);
+
+    // A settlement that fails doesn't count as completed
+    let auction = client.get_auction(&disputed);
+    NftContractClient::new(&env, &auction.nft_address).set_approval_for_all(&client.address, &false, &auction.seller);
+    set_time(&env, 4_700);
+    client.end_auction(&sold, &admin, &0);
+    client.end_auction(&disputed, &admin, &0);
+    assert_eq!(client.get_auction(&disputed).state, TransactionState::Disputed);
+
+    let stats = client.get_platform_wide_stats();
+    assert_eq!(stats.total_auctions_created, 4);
+    assert_eq!(stats.total_auctions_completed, 2);
+    assert_eq!(stats.total_auctions_cancelled, 1);
+    assert_eq!(stats.total_volume, 59_000);
+    assert_eq!(stats.average_final_price, 29_500);
+    assert_eq!(stats.total_unique_sellers, 2);
+    assert_eq!(stats.total_unique_buyers, 1);
+}
+
+#[test]
+fn test_buy_now_requires_buyout_price() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let auction_id = create_english_auction(&env, &client, None);
+
+    set_time(&env, 1_240);
+    let result = client.try_buy_now(&auction_id, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+}
+
+#[test]
+fn test_create_auction_requires_token_owner() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let owner = Address::generate(&env);
+    let (nft, token_id) = owned_token(&env, &client, &owner);
+
+    // An approved token can't be auctioned off by anyone but its holder
+    let result = client.try_create_auction(
+        &Address::generate(&env),
+        &nft,
+        &token_id,
+        &2_000,
+        &1,
+        &3_600,
+        &100,
+        &AuctionType::English,
+        &test_asset(&env),
+        &AuctionOptions {
+            buyout_price: None,
+            allowed_bidders: None,
+            dutch_half_life: None,
+            start_time: None,
+        },
+    );
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+}
+
+#[test]
+fn test_buyout_price_validation() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let (nft, token_id) = owned_token(&env, &client, &seller);
+
+    // Buyout below the reserve is rejected
+    let result = client.try_create_auction(
+        &seller,
+        &nft,
+        &token_id,
+        &2_000,
+        &1_500,
+        &3_600,
+        &100,
+        &AuctionType::English,
+        &test_asset(&env),
+        &AuctionOptions {
+            buyout_price: Some(1_200),
+            allowed_bidders: None,
+            dutch_half_life: None,
+            start_time: None,
+        },
+    );
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+
+    // Only English auctions offer a buyout
+    let result = client.try_create_auction(
+        &seller,
+        &nft,
+        &token_id,
+        &2_000,
+        &1_500,
+        &3_600,
+        &100,
+        &AuctionType::Dutch,
+        &test_asset(&env),
+        &AuctionOptions {
+            buyout_price: Some(5_000),
+            allowed_bidders: None,
+            dutch_half_life: None,
+            start_time: None,
+        },
+    );
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+}
+
+#[test]
+fn test_auctions_paginated_with_filters() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let sellers: [Address; 2] = core::array::from_fn(|_| Address::generate(&env));
+    let collections: [NftContractClient; 2] = core::array::from_fn(|_| nft_collection(&env));
+    let currency = test_asset(&env);
+
+    // 200 auctions alternating sellers, every other pair in the second collection,
+    // every fifth one Dutch and every tenth one cancelled
+    for i in 0..200u64 {
+        let seller = &sellers[(i % 2) as usize];
+        let nft = &collections[((i / 2) % 2) as usize];
+        let token_id = mint_for_sale(&env, nft, seller, &client.address);
+        let auction_type = if i % 5 == 0 { AuctionType::Dutch } else { AuctionType::English };
+        let auction_id = client.create_auction(
+            seller,
+            &nft.address,
+            &token_id,
+            &2_000,
+            &1_500,
+            &3_600,
+            &100,
+            &auction_type,
+            &currency,
+            &AuctionOptions { buyout_price: None, allowed_bidders: None, dutch_half_life: None, start_time: None },
+        );
+        if i % 10 == 9 {
+            client.cancel_auction(&auction_id, seller);
+        }
+    }
+
+    let everything = AuctionFilter {
+        state: Vec::new(&env),
+        seller: None,
+        nft_address: None,
+        auction_type: Vec::new(&env),
+    };
+    let page = client.get_auctions_paginated(&0, &50, &everything);
+    assert_eq!(page.len(), 50);
+    assert_eq!(page.get(0).unwrap().auction_id, 1);
+    let page = client.get_auctions_paginated(&190, &50, &everything);
+    assert_eq!(page.len(), 10);
+    assert_eq!(page.get(9).unwrap().auction_id, 200);
+    assert_eq!(client.get_auctions_paginated(&200, &50, &everything).len(), 0);
+    // Pages are capped however many are asked for
+    assert_eq!(client.get_auctions_paginated(&0, &1_000, &everything).len(), 100);
+
+    let by_seller = AuctionFilter { seller: Some(sellers[1].clone()), ..everything.clone() };
+    let page = client.get_auctions_paginated(&0, &100, &by_seller);
+    assert_eq!(page.len(), 100);
+    assert!(page.iter().all(|auction| auction.seller == sellers[1]));
+
+    let dutch = AuctionFilter { auction_type: vec![&env, AuctionType::Dutch], ..everything.clone() };
+    let page = client.get_auctions_paginated(&0, &100, &dutch);
+    assert_eq!(page.len(), 40);
+    assert_eq!(page.get(1).unwrap().auction_id, 6);
+
+    let cancelled = AuctionFilter { state: vec![&env, TransactionState::Cancelled], ..everything.clone() };
+    assert_eq!(client.get_auctions_paginated(&0, &100, &cancelled).len(), 20);
+
+    // Criteria combine: the first seller's open English auctions in the second collection
+    let combined = AuctionFilter {
+        state: vec![&env, TransactionState::Pending],
+        seller: Some(sellers[0].clone()),
+        nft_address: Some(collections[1].address.clone()),
+        auction_type: vec![&env, AuctionType::English],
+    };
+    let first = client.get_auctions_paginated(&0, &20, &combined);
+    let rest = client.get_auctions_paginated(&20, &100, &combined);
+    assert_eq!(first.len() + rest.len(), 40);
+    assert_eq!(rest.get(0).unwrap().auction_id, first.get(19).unwrap().auction_id + 4);
+    for auction in first.iter().chain(rest.iter()) {
+        let i = auction.auction_id - 1;
+        assert!(i % 2 == 0 && (i / 2) % 2 == 1 && i % 5 != 0 && i % 10 != 9);
+    }
+}
+
+#[test]
+fn test_seller_auction_index_stays_consistent() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let other = Address::generate(&env);
+    let nft = nft_collection(&env);
+    let currency = test_asset(&env);
+    let options = AuctionOptions { buyout_price: None, allowed_bidders: None, dutch_half_life: None, start_time: None };
+    let create = |seller: &Address| {
+        let token_id = mint_for_sale(&env, &nft, seller, &client.address);
+        client.create_auction(
+            seller, &nft.address, &token_id, &2_000, &1_500, &3_600, &100, &AuctionType::English, &currency, &options,
+        )
+    };
+
+    let mut ids = Vec::new(&env);
+    for i in 0..50 {
+        ids.push_back(create(&seller));
+        if i % 10 == 0 {
+            create(&other);
+        }
+    }
+    for auction_id in ids.iter().step_by(5) {
+        client.cancel_auction(&auction_id, &seller);
+    }
+
+    // Cancelled auctions stay listed under their seller
+    let listed = client.get_seller_auctions(&seller);
+    assert_eq!(listed.len(), 50);
+    assert!(listed.iter().zip(ids.iter()).all(|(auction, id)| auction.auction_id == id));
+    let cancelled = listed.iter().filter(|auction| auction.state == TransactionState::Cancelled).count();
+    assert_eq!(cancelled, 10);
+    assert_eq!(client.get_seller_auctions(&other).len(), 5);
+    assert_eq!(client.get_seller_auctions(&Address::generate(&env)).len(), 0);
+
+    // Removing an auction drops it from its seller's index and the global one
+    let removed = ids.get(3).unwrap();
+    env.as_contract(&client.address, || {
+        AuctionStore::remove(&env, removed).unwrap();
+        assert_eq!(AuctionStore::remove(&env, removed), Err(SettlementError::AuctionNotFound));
+    });
+    let listed = client.get_seller_auctions(&seller);
+    assert_eq!(listed.len(), 49);
+    assert!(listed.iter().all(|auction| auction.auction_id != removed));
+    let everything = AuctionFilter { state: Vec::new(&env), seller: None, nft_address: None, auction_type: Vec::new(&env) };
+    assert_eq!(client.get_auctions_paginated(&0, &100, &everything).len(), 54);
+
+    for auction in client.get_seller_auctions(&other).iter() {
+        env.as_contract(&client.address, || AuctionStore::remove(&env, auction.auction_id).unwrap());
+    }
+    assert_eq!(client.get_seller_auctions(&other).len(), 0);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&DataKey::SellerAuctions(other.clone())));
+    });
+}
+
+#[test]
+fn test_bids_by_bidder_pages_across_auctions() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let first = create_english_auction(&env, &client, None);
+    let sealed = create_vickrey_auction(&env, &client);
+    let second = create_english_auction(&env, &client, None);
+    let alice = funded_bidder(&env, &client, first);
+    let bob = funded_bidder(&env, &client, first);
+    fund(&env, &client.get_auction(&second).currency, &alice, 1_000_000);
+
+    set_time(&env, 1_130);
+    client.place_bid(&first, &alice, &2_000, &None);
+    set_time(&env, 1_420);
+    client.place_bid(&first, &bob, &2_350, &None);
+    seal(&client, sealed, &alice, 2_000, &Bytes::from_slice(&env, b"salt"));
+    set_time(&env, 1_987);
+    client.place_bid(&second, &alice, &2_000, &None);
+    set_time(&env, 2_510);
+    client.place_bid(&first, &alice, &2_800, &None);
+
+    // Bids come grouped by auction, in the order the bidder first bid in each
+    let page = client.get_bids_by_bidder(&alice, &0, &3);
+    let listed: Vec<(u64, i128)> = Vec::from_iter(&env, page.iter().map(|entry| (entry.auction_id, entry.bid.amount)));
+    assert_eq!(listed, vec![&env, (first, 2_000), (first, 2_800), (sealed, 0)]);
+    let page = client.get_bids_by_bidder(&alice, &3, &3);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().auction_id, second);
+    let page = client.get_bids_by_bidder(&alice, &1, &2);
+    assert_eq!(page.get(0).unwrap().bid.amount, 2_800);
+    assert_eq!(page.get(1).unwrap().auction_id, sealed);
+
+    // Empty pages past the end, for a zero limit and for an address that never bid
+    assert_eq!(client.get_bids_by_bidder(&alice, &4, &10).len(), 0);
+    assert_eq!(client.get_bids_by_bidder(&alice, &u64::MAX, &10).len(), 0);
+    assert_eq!(client.get_bids_by_bidder(&alice, &0, &0).len(), 0);
+    assert_eq!(client.get_bids_by_bidder(&alice, &0, &u64::MAX).len(), 4);
+    assert_eq!(client.get_bids_by_bidder(&bob, &0, &10).len(), 1);
+    assert_eq!(client.get_bids_by_bidder(&Address::generate(&env), &0, &10).len(), 0);
+
+    // Cancelling an auction keeps its bids listed but marks them stale
+    assert!(client.get_bids_by_bidder(&alice, &0, &10).iter().all(|entry| !entry.stale));
+    client.cancel_auction(&sealed, &client.get_auction(&sealed).seller);
+    let page = client.get_bids_by_bidder(&alice, &0, &10);
+    assert_eq!(page.len(), 4);
+    assert!(page.iter().all(|entry| entry.stale == (entry.auction_id == sealed)));
+}
+
+#[test]
+fn test_outbid_escrow_refund_cycle() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let auction_id = create_english_auction(&env, &client, None);
+
+    let alice = funded_bidder(&env, &client, auction_id);
+    let bob = funded_bidder(&env, &client, auction_id);
+    let carol = funded_bidder(&env, &client, auction_id);
+
+    set_time(&env, 1_130);
+    client.place_bid(&auction_id, &alice, &2_000, &None);
+    set_time(&env, 1_420);
+    client.place_bid(&auction_id, &bob, &2_350, &None);
+    set_time(&env, 1_987);
+    client.place_bid(&auction_id, &carol, &2_800, &None);
+    set_time(&env, 2_510);
+    client.place_bid(&auction_id, &alice, &3_120, &None);
+
+    let auction = client.get_auction(&auction_id);
+    assert_eq!(auction.highest_bidder, Some(alice.clone()));
+    assert_eq!(auction.highest_bid, 3_120);
+    assert_eq!(client.get_bid_escrow(&auction_id, &alice), 5_120);
+    assert_eq!(client.get_bid_escrow(&auction_id, &bob), 2_350);
+    assert_eq!(client.get_bid_escrow(&auction_id, &carol), 2_800);
+
+    // Outbid bidders get their full escrow back
+    assert_eq!(client.withdraw_bid_escrow(&auction_id, &bob), 2_350);
+    assert_eq!(client.get_bid_escrow(&auction_id, &bob), 0);
+    let result = client.try_withdraw_bid_escrow(&auction_id, &bob);
+    assert_eq!(result, Err(Ok(SettlementError::InsufficientFunds)));
+
+    // The leader can only recover funds above the leading bid
+    assert_eq!(client.withdraw_bid_escrow(&auction_id, &alice), 2_000);
+    assert_eq!(client.get_bid_escrow(&auction_id, &alice), 3_120);
+    let result = client.try_withdraw_bid_escrow(&auction_id, &alice);
+    assert_eq!(result, Err(Ok(SettlementError::InsufficientFunds)));
+
+    // Ending the auction consumes the winner's escrow
+    set_time(&env, 4_700);
+    client.end_auction(&auction_id, &alice, &0);
+    assert_eq!(client.get_bid_escrow(&auction_id, &alice), 0);
+
+    // Late withdrawals still work for bidders who left funds behind
+    assert_eq!(client.withdraw_bid_escrow(&auction_id, &carol), 2_800);
+}
+
+#[test]
+fn test_bid_escrow_follows_lead_changes() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let auction_id = create_english_auction(&env, &client, None);
+
+    let alice = funded_bidder(&env, &client, auction_id);
+    let bob = funded_bidder(&env, &client, auction_id);
+
+    set_time(&env, 1_200);
+    client.place_bid(&auction_id, &alice, &2_100, &None);
+
+    // Leading bid cannot be withdrawn
+    let result = client.try_withdraw_bid_escrow(&auction_id, &alice);
+    assert_eq!(result, Err(Ok(SettlementError::InsufficientFunds)));
+
+    set_time(&env, 1_873);
+    client.place_bid(&auction_id, &bob, &2_640, &None);
+
+    // Once outbid, the same funds become refundable
+    assert_eq!(client.withdraw_bid_escrow(&auction_id, &alice), 2_100);
+    let result = client.try_withdraw_bid_escrow(&auction_id, &bob);
+    assert_eq!(result, Err(Ok(SettlementError::InsufficientFunds)));
+}
+
+#[test]
+fn test_anti_sniping_extensions_are_capped() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let mut config = client.get_auction_config();
+    config.max_extensions = 3;
+    client.update_auction_config(&config, &admin);
+    assert!(env.auths().iter().any(|(address, _)| *address == admin));
+
+    let auction_id = create_english_auction(&env, &client, None);
+    assert_eq!(client.get_auction(&auction_id).end_time, 4_600);
+
+    // Five last-minute bids; only the first three push the end time out
+    let schedule: [(u64, i128, u64, u32); 5] = [
+        (4_320, 2_000, 4_620, 1),
+        (4_401, 2_210, 4_701, 2),
+        (4_530, 2_480, 4_830, 3),
+        (4_610, 2_750, 4_830, 3),
+        (4_790, 3_090, 4_830, 3),
+    ];
+    for (timestamp, amount, expected_end, expected_count) in schedule {
+        set_time(&env, timestamp);
+        client.place_bid(&auction_id, &funded_bidder(&env, &client, auction_id), &amount, &None);
+
+        let auction = client.get_auction(&auction_id);
+        assert_eq!(auction.end_time, expected_end);
+        assert_eq!(auction.extension_count, expected_count);
+    }
+
+    let auction = client.get_auction(&auction_id);
+    assert_eq!(auction.last_extended_at, 4_530);
+
+    set_time(&env, 4_831);
+    client.end_auction(&auction_id, &admin, &0);
+    assert_eq!(client.get_auction(&auction_id).highest_bid, 3_090);
+}
+
+#[test]
+fn test_auction_extends_once_per_ledger() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let auction_id = create_english_auction(&env, &client, None);
+
+    set_time(&env, 4_350);
+    client.place_bid(&auction_id, &funded_bidder(&env, &client, auction_id), &2_050, &None);
+    client.place_bid(&auction_id, &funded_bidder(&env, &client, auction_id), &2_400, &None);
+
+    let auction = client.get_auction(&auction_id);
+    assert_eq!(auction.end_time, 4_650);
+    assert_eq!(auction.extension_count, 1);
+}
+
+fn create_private_auction(
+    env: &Env,
+    client: &MarketplaceSettlementClient,
+    seller: &Address,
+    auction_type: AuctionType,
+    allowed_bidders: Vec<Address>,
+) -> u64 {
+    let (nft, token_id) = owned_token(env, client, seller);
+    client.create_auction(
+        seller,
+        &nft,
+        &token_id,
+        &2_000,
+        &1_500,
+        &3_600,
+        &100,
+        &auction_type,
+        &test_asset(env),
+        &AuctionOptions {
+            buyout_price: None,
+            allowed_bidders: Some(allowed_bidders),
+            dutch_half_life: None,
+            start_time: None,
+        },
+    )
+}
+
+#[test]
+fn test_open_auction_accepts_any_bidder() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let auction_id = create_english_auction(&env, &client, None);
+
+    set_time(&env, 1_310);
+    let bidder = funded_bidder(&env, &client, auction_id);
+    client.place_bid(&auction_id, &bidder, &2_000, &None);
+    assert_eq!(client.get_auction(&auction_id).highest_bidder, Some(bidder));
+    assert_eq!(client.get_auction(&auction_id).allowed_bidders, None);
+
+    // There is no allowlist to manage on an open auction
+    let seller = client.get_auction(&auction_id).seller;
+    let result = client.try_add_auction_bidder(&auction_id, &seller, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+}
+
+#[test]
+fn test_private_auction_restricts_bidders() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let insider = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let auction_id = create_private_auction(&env, &client, &seller, AuctionType::English, vec![&env, insider.clone()]);
+    let currency = client.get_auction(&auction_id).currency;
+    fund(&env, &currency, &insider, 1_000_000);
+    fund(&env, &currency, &outsider, 1_000_000);
+
+    set_time(&env, 1_260);
+    let result = client.try_place_bid(&auction_id, &outsider, &2_000, &None);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    client.place_bid(&auction_id, &insider, &2_000, &None);
+
+    // Only the seller can manage the list
+    let result = client.try_add_auction_bidder(&auction_id, &outsider, &outsider);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    client.add_auction_bidder(&auction_id, &seller, &outsider);
+    let result = client.try_add_auction_bidder(&auction_id, &seller, &outsider);
+    assert_eq!(result, Err(Ok(SettlementError::AlreadyExists)));
+
+    set_time(&env, 1_905);
+    client.place_bid(&auction_id, &outsider, &2_370, &None);
+    assert_eq!(client.get_auction(&auction_id).highest_bidder, Some(outsider.clone()));
+
+    client.remove_auction_bidder(&auction_id, &seller, &insider);
+    set_time(&env, 2_488);
+    let result = client.try_place_bid(&auction_id, &insider, &2_900, &None);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    let result = client.try_remove_auction_bidder(&auction_id, &seller, &insider);
+    assert_eq!(result, Err(Ok(SettlementError::NotFound)));
+}
+
+#[test]
+fn test_private_dutch_auction_allowlist() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let insider = Address::generate(&env);
+    let auction_id = create_private_auction(&env, &client, &seller, AuctionType::Dutch, vec![&env, insider.clone()]);
+    fund(&env, &client.get_auction(&auction_id).currency, &insider, 1_000_000);
+
+    set_time(&env, 1_530);
+    let result = client.try_place_bid(&auction_id, &Address::generate(&env), &2_000, &None);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    client.place_bid(&auction_id, &insider, &2_000, &None);
+
+    client.remove_auction_bidder(&auction_id, &seller, &insider);
+    assert_eq!(client.get_auction(&auction_id).allowed_bidders, Some(Vec::new(&env)));
+}
+
+fn create_candle_auction(env: &Env, client: &MarketplaceSettlementClient) -> u64 {
+    let seller = Address::generate(env);
+    let (nft, token_id) = owned_token(env, client, &seller);
+    client.create_auction(
+        &seller,
+        &nft,
+        &token_id,
+        &2_000,
+        &1_500,
+        &7_200,
+        &100,
+        &AuctionType::Candle,
+        &test_asset(env),
+        &AuctionOptions {
+            buyout_price: None,
+            allowed_bidders: None,
+            dutch_half_life: None,
+            start_time: None,
+        },
+    )
+}
+
+fn settle_candle_with_seed(seed: [u8; 32]) -> u64 {
+    let env = Env::default();
+    env.host().set_base_prng_seed(seed).unwrap();
+    let (client, admin) = setup(&env);
+    let auction_id = create_candle_auction(&env, &client);
+
+    set_time(&env, 8_300);
+    client.end_candle_auction(&auction_id, &admin);
+    client.get_candle_auction(&auction_id).revealed_end
+}
+
+#[test]
+fn test_candle_end_is_hidden_until_settlement() {
+    let env = Env::default();
+    env.host().set_base_prng_seed([7; 32]).unwrap();
+    let (client, admin) = setup(&env);
+    let auction_id = create_candle_auction(&env, &client);
+
+    let data = client.get_candle_auction(&auction_id);
+    assert_eq!(data.earliest_end, 4_600);
+    assert_eq!(data.latest_end, 8_200);
+    assert_eq!(data.revealed_end, 0);
+
+    // Cannot settle while the candle may still be burning
+    set_time(&env, 8_200);
+    let result = client.try_end_candle_auction(&auction_id, &admin);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+    let result = client.try_end_auction(&auction_id, &admin, &0);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+
+    set_time(&env, 8_201);
+    client.end_candle_auction(&auction_id, &admin);
+    let revealed_end = client.get_candle_auction(&auction_id).revealed_end;
+    assert!((4_600..=8_200).contains(&revealed_end));
+}
+
+#[test]
+fn test_candle_end_is_deterministic_for_fixed_seed() {
+    assert_eq!(settle_candle_with_seed([3; 32]), settle_candle_with_seed([3; 32]));
+    assert_eq!(settle_candle_with_seed([42; 32]), settle_candle_with_seed([42; 32]));
+}
+
+#[test]
+fn test_candle_ignores_bids_after_drawn_end() {
+    let env = Env::default();
+    env.host().set_base_prng_seed([9; 32]).unwrap();
+    let (client, admin) = setup(&env);
+    let auction_id = create_candle_auction(&env, &client);
+
+    let early = funded_bidder(&env, &client, auction_id);
+    let middle = funded_bidder(&env, &client, auction_id);
+    let sniper = funded_bidder(&env, &client, auction_id);
+
+    set_time(&env, 2_140);
+    client.place_bid(&auction_id, &early, &2_000, &None);
+    set_time(&env, 6_400);
+    client.place_bid(&auction_id, &middle, &2_450, &None);
+
+    // A bid at the very last second can never beat the candle
+    set_time(&env, 8_200);
+    client.place_bid(&auction_id, &sniper, &3_700, &None);
+    assert_eq!(client.get_auction(&auction_id).end_time, 8_200);
+
+    set_time(&env, 8_350);
+    client.end_candle_auction(&auction_id, &admin);
+    let revealed_end = client.get_candle_auction(&auction_id).revealed_end;
+
+    let auction = client.get_auction(&auction_id);
+    let (expected_winner, expected_price) = if revealed_end > 6_400 {
+        (middle.clone(), 2_450)
+    } else {
+        (early.clone(), 2_000)
+    };
+    assert_eq!(auction.highest_bidder, Some(expected_winner.clone()));
+    assert_eq!(auction.highest_bid, expected_price);
+    assert_eq!(client.get_bid_escrow(&auction_id, &expected_winner), 0);
+
+    // The sniper's funds are fully refundable
+    assert_eq!(client.withdraw_bid_escrow(&auction_id, &sniper), 3_700);
+}
+
+#[test]
+fn test_proxy_bid_outbids_direct_bids_up_to_limit() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let auction_id = create_english_auction(&env, &client, None);
+
+    let alice = funded_bidder(&env, &client, auction_id);
+    let bob = funded_bidder(&env, &client, auction_id);
+
+    // With no bids yet, the proxy opens at the starting price
+    set_time(&env, 1_200);
+    client.set_proxy_bid(&auction_id, &alice, &3_000);
+    assert_eq!(client.get_auction(&auction_id).highest_bid, 2_000);
+
+    set_time(&env, 1_650);
+    client.place_bid(&auction_id, &bob, &2_300, &None);
+    let auction = client.get_auction(&auction_id);
+    assert_eq!(auction.highest_bidder, Some(alice.clone()));
+    assert_eq!(auction.highest_bid, 2_323);
+
+    set_time(&env, 2_480);
+    client.place_bid(&auction_id, &bob, &2_900, &None);
+    let auction = client.get_auction(&auction_id);
+    assert_eq!(auction.highest_bidder, Some(alice.clone()));
+    assert_eq!(auction.highest_bid, 2_929);
+    assert_eq!(client.get_proxy_bid(&auction_id, &alice).unwrap().current_bid, 2_929);
+
+    // Past the proxy's limit the direct bid keeps the lead
+    set_time(&env, 3_111);
+    client.place_bid(&auction_id, &bob, &3_100, &None);
+    let auction = client.get_auction(&auction_id);
+    assert_eq!(auction.highest_bidder, Some(bob));
+    assert_eq!(auction.highest_bid, 3_100);
+}
+
+#[test]
+fn test_competing_proxy_bids() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let auction_id = create_english_auction(&env, &client, None);
+
+    let alice = funded_bidder(&env, &client, auction_id);
+    let bob = funded_bidder(&env, &client, auction_id);
+
+    set_time(&env, 1_200);
+    client.set_proxy_bid(&auction_id, &alice, &3_000);
+
+    // The higher limit wins, one increment above the weaker proxy's limit
+    set_time(&env, 1_530);
+    client.set_proxy_bid(&auction_id, &bob, &2_600);
+    let auction = client.get_auction(&auction_id);
+    assert_eq!(auction.highest_bidder, Some(alice.clone()));
+    assert_eq!(auction.highest_bid, 2_626);
+
+    set_time(&env, 1_940);
+    client.set_proxy_bid(&auction_id, &bob, &4_000);
+    let auction = client.get_auction(&auction_id);
+    assert_eq!(auction.highest_bidder, Some(bob.clone()));
+    assert_eq!(auction.highest_bid, 3_030);
+    assert_eq!(client.get_proxy_bid(&auction_id, &bob).unwrap().max_amount, 4_000);
+}
+
+#[test]
+fn test_proxy_bid_validation() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let auction_id = create_english_auction(&env, &client, None);
+    let bidder = Address::generate(&env);
+
+    set_time(&env, 1_420);
+    let result = client.try_set_proxy_bid(&auction_id, &bidder, &1_900);
+    assert_eq!(result, Err(Ok(SettlementError::BidTooLow)));
+
+    let vickrey_id = create_vickrey_auction(&env, &client);
+    let result = client.try_set_proxy_bid(&vickrey_id, &bidder, &5_000);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+}
+
+#[test]
+fn test_pause_and_resume_extends_end_time() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let auction_id = create_english_auction(&env, &client, None);
+    let seller = client.get_auction(&auction_id).seller;
+    let bidder = funded_bidder(&env, &client, auction_id);
+
+    set_time(&env, 1_480);
+    client.place_bid(&auction_id, &bidder, &2_000, &None);
+
+    set_time(&env, 2_000);
+    client.pause_auction(&auction_id, &seller);
+    let auction = client.get_auction(&auction_id);
+    assert_eq!(auction.state, TransactionState::Paused);
+    assert_eq!(auction.paused_at, 2_000);
+
+    // No bidding and no escrow release for the leader while paused
+    set_time(&env, 2_340);
+    let result = client.try_place_bid(&auction_id, &Address::generate(&env), &2_500, &None);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+    let result = client.try_withdraw_bid_escrow(&auction_id, &bidder);
+    assert_eq!(result, Err(Ok(SettlementError::InsufficientFunds)));
+
+    set_time(&env, 2_750);
+    client.resume_auction(&auction_id, &seller);
+    let auction = client.get_auction(&auction_id);
+    assert_eq!(auction.state, TransactionState::Pending);
+    assert_eq!(auction.end_time, 4_600 + 750);
+    assert_eq!(auction.paused_at, 0);
+
+    // A second pause adds on top of the first
+    set_time(&env, 5_000);
+    client.pause_auction(&auction_id, &seller);
+    set_time(&env, 5_121);
+    client.resume_auction(&auction_id, &seller);
+    assert_eq!(client.get_auction(&auction_id).end_time, 5_350 + 121);
+
+    // The auction is still open in what would have been its final minutes
+    set_time(&env, 5_400);
+    client.place_bid(&auction_id, &funded_bidder(&env, &client, auction_id), &2_480, &None);
+}
+
+#[test]
+fn test_pause_auction_permissions_and_state() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let auction_id = create_english_auction(&env, &client, None);
+    let seller = client.get_auction(&auction_id).seller;
+
+    set_time(&env, 1_700);
+    let stranger = Address::generate(&env);
+    let result = client.try_pause_auction(&auction_id, &stranger);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    let result = client.try_resume_auction(&auction_id, &seller);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+
+    client.pause_auction(&auction_id, &seller);
+    let result = client.try_pause_auction(&auction_id, &seller);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+    let result = client.try_resume_auction(&auction_id, &stranger);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    // A paused auction cannot be ended
+    set_time(&env, 4_900);
+    let result = client.try_end_auction(&auction_id, &seller, &0);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+}
+
+#[test]
+fn test_open_scheduled_auction() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let nft = nft_collection(&env);
+    let currency = test_asset(&env);
+    let schedule = |start_time: u64| {
+        let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+        let options = AuctionOptions { buyout_price: None, allowed_bidders: None, dutch_half_life: None, start_time: Some(start_time) };
+        client.try_create_auction(
+            &seller, &nft.address, &token_id, &2_000, &1_500, &3_600, &100, &AuctionType::English, &currency, &options,
+        )
+    };
+
+    // Starting in the past is rejected; later starts are indexed soonest first
+    set_time(&env, 2_000);
+    assert_eq!(schedule(1_999), Err(Ok(SettlementError::InvalidAmount)));
+    let later = schedule(9_000).unwrap().unwrap();
+    let sooner = schedule(5_000).unwrap().unwrap();
+    assert_eq!(client.get_scheduled_auctions(), Vec::from_array(&env, [(5_000, sooner), (9_000, later)]));
+    let auction = client.get_auction(&sooner);
+    assert_eq!(auction.state, TransactionState::Scheduled);
+    assert_eq!(auction.end_time, 8_600);
+
+    // No bids, and no opening, before the start time
+    let bidder = funded_bidder(&env, &client, sooner);
+    let result = client.try_place_bid(&sooner, &bidder, &2_000, &None);
+    assert_eq!(result, Err(Ok(SettlementError::AuctionNotStarted)));
+    let result = client.try_open_scheduled_auction(&sooner);
+    assert_eq!(result, Err(Ok(SettlementError::AuctionNotStarted)));
+
+    set_time(&env, 5_000);
+    client.open_scheduled_auction(&sooner);
+    assert_eq!(count_events(&env, symbol_short!("auc_open")), 1);
+    assert_eq!(client.get_auction(&sooner).state, TransactionState::Pending);
+    assert_eq!(client.get_scheduled_auctions(), Vec::from_array(&env, [(9_000, later)]));
+    client.place_bid(&sooner, &bidder, &2_000, &None);
+    let result = client.try_open_scheduled_auction(&sooner);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+
+    // The seller can call off an auction that hasn't opened yet
+    client.cancel_auction(&later, &seller);
+    assert_eq!(client.get_auction(&later).state, TransactionState::Cancelled);
+    assert_eq!(client.get_scheduled_auctions(), Vec::new(&env));
+}
+
+#[test]
+fn test_resume_shifts_vickrey_reveal_window() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let auction_id = create_vickrey_auction(&env, &client);
+    let seller = client.get_auction(&auction_id).seller;
+    assert_eq!(client.get_vickrey_auction(&auction_id).reveal_end, 8_200);
+
+    set_time(&env, 1_900);
+    client.pause_auction(&auction_id, &seller);
+    set_time(&env, 2_433);
+    client.resume_auction(&auction_id, &seller);
+
+    assert_eq!(client.get_auction(&auction_id).end_time, 4_600 + 533);
+    assert_eq!(client.get_vickrey_auction(&auction_id).reveal_end, 8_200 + 533);
+}
+
+#[test]
+fn test_exponential_decay_price_curve() {
+    let env = Env::default();
+    let price = |elapsed: u64| calculate_exponential_decay_price(10_000, 0, 1_000, elapsed, &env).unwrap();
+
+    assert_eq!(price(0), 10_000);
+    assert_eq!(price(1_000), 5_000);
+    assert_eq!(price(2_000), 2_500);
+    assert_eq!(price(500), 7_071); // 10_000 / sqrt(2)
+    assert_eq!(price(1_500), 3_535);
+    assert_eq!(price(100_000), 0);
+
+    // Decay approaches the floor rather than zero
+    assert_eq!(calculate_exponential_decay_price(9_000, 1_000, 600, 600, &env).unwrap(), 5_000);
+    assert_eq!(
+        calculate_exponential_decay_price(9_000, 1_000, 0, 600, &env),
+        Err(SettlementError::DivisionByZero)
+    );
+}
+
+/// Deterministic xorshift stream of fixed-point numbers sized like token amounts
+///
+/// Values stay within ±10^12 at up to 7 decimal places, the precision of Stellar assets.
+fn fixed_point_samples<const N: usize>(seed: u64) -> [FixedPoint128; N] {
+    let mut state = seed;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    core::array::from_fn(|_| {
+        let value = (next() % 2_000_000_000_001) as i128 - 1_000_000_000_000;
+        FixedPoint128::new(value, (next() % 8) as u32).unwrap()
+    })
+}
+
+#[test]
+fn test_fixed_point_algebraic_laws() {
+    let samples = fixed_point_samples::<600>(0x9E37_79B9_7F4A_7C15);
+    for abc in samples.chunks(3) {
+        let (a, b, c) = (abc[0], abc[1], abc[2]);
+
+        assert_eq!(a.add(b).unwrap().add(c).unwrap(), a.add(b.add(c).unwrap()).unwrap());
+        assert_eq!(a.mul(b).unwrap().mul(c).unwrap(), a.mul(b.mul(c).unwrap()).unwrap());
+        assert_eq!(a.mul(b.add(c).unwrap()).unwrap(), a.mul(b).unwrap().add(a.mul(c).unwrap()).unwrap());
+
+        // Subtraction and division undo addition and multiplication exactly
+        let scale = a.scale.max(b.scale);
+        assert_eq!(a.add(b).unwrap().sub(b).unwrap(), a.rescale(scale).unwrap());
+        if b.value != 0 {
+            let product = a.mul(b).unwrap();
+            assert_eq!(product.div(b).unwrap(), a.rescale(product.scale).unwrap());
+        }
+    }
+}
+
+#[test]
+fn test_fixed_point_rounding_and_limits() {
+    let fp = |value: i128, scale: u32| FixedPoint128::new(value, scale).unwrap();
+    assert_eq!(fp(25, 1).to_i128_rounded(), Ok(3));
+    assert_eq!(fp(-25, 1).to_i128_rounded(), Ok(-3));
+    assert_eq!(fp(249, 2).to_i128_rounded(), Ok(2));
+    assert_eq!(fp(-249, 2).to_i128_truncated(), Ok(-2));
+    assert_eq!(fp(1, 0).div(fp(3, 0)), Ok(fp(0, 0)));
+    assert_eq!(fp(1, 0).div(fp(30, 1)), Ok(fp(3, 1)));
+
+    assert_eq!(FixedPoint128::new(1, 39), Err(SettlementError::Overflow));
+    assert_eq!(fp(i128::MAX, 0).add(fp(1, 0)), Err(SettlementError::Overflow));
+    assert_eq!(fp(i128::MIN, 0).sub(fp(1, 0)), Err(SettlementError::Underflow));
+    assert_eq!(fp(i128::MAX / 2, 0).mul(fp(3, 0)), Err(SettlementError::Overflow));
+    assert_eq!(fp(i128::MAX, 0).add(fp(1, 1)), Err(SettlementError::Overflow));
+    assert_eq!(fp(1, 0).div(fp(0, 4)), Err(SettlementError::DivisionByZero));
+
+    // Percentages still truncate the way basis-point maths always has
+    let env = Env::default();
+    for amount in fixed_point_samples::<200>(42).iter().map(|sample| sample.value) {
+        for bps in [0, 1, 250, 3_333, 10_000] {
+            assert_eq!(calculate_percentage(amount, bps, &env), Ok(amount * bps as i128 / 10_000));
+        }
+    }
+    assert_eq!(calculate_percentage(i128::MAX, 2, &env), Err(SettlementError::Overflow));
+}
+
+#[test]
+fn test_moving_average_update() {
+    let env = Env::default();
+    assert_eq!(update_moving_average(10_000, 20_000, 10, &env), Ok(11_000));
+    assert_eq!(update_moving_average(10_000, 20_000, 1, &env), Ok(20_000));
+    assert_eq!(update_moving_average(10_000, 0, 4, &env), Ok(7_500));
+    assert_eq!(update_moving_average(10_000, 20_000, 0, &env), Err(SettlementError::InvalidAmount));
+    assert_eq!(update_moving_average(i128::MAX, 1, 3, &env), Err(SettlementError::Overflow));
+}
+
+#[test]
+fn test_dutch_auction_exponential_decay() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let (nft, token_id) = owned_token(&env, &client, &seller);
+    let auction_id = client.create_auction(
+        &seller,
+        &nft,
+        &token_id,
+        &2_000,
+        &500,
+        &3_600,
+        &100,
+        &AuctionType::Dutch,
+        &test_asset(&env),
+        &AuctionOptions {
+            buyout_price: None,
+            allowed_bidders: None,
+            dutch_half_life: Some(900),
+            start_time: None,
+        },
+    );
+
+    set_time(&env, 1_900);
+    assert_eq!(client.get_dutch_auction_price(&auction_id), 1_250);
+    set_time(&env, 2_800);
+    assert_eq!(client.get_dutch_auction_price(&auction_id), 875);
+    set_time(&env, 4_600);
+    assert_eq!(client.get_dutch_auction_price(&auction_id), 500);
+
+    // Half-lives are meaningless outside Dutch auctions
+    let seller = Address::generate(&env);
+    let (nft, token_id) = owned_token(&env, &client, &seller);
+    let result = client.try_create_auction(
+        &seller,
+        &nft,
+        &token_id,
+        &2_000,
+        &500,
+        &3_600,
+        &100,
+        &AuctionType::English,
+        &test_asset(&env),
+        &AuctionOptions {
+            buyout_price: None,
+            allowed_bidders: None,
+            dutch_half_life: Some(900),
+            start_time: None,
+        },
+    );
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+}
+
+#[test]
+fn test_dutch_auction_linear_decay_by_default() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let (nft, token_id) = owned_token(&env, &client, &seller);
+    let auction_id = client.create_auction(
+        &seller,
+        &nft,
+        &token_id,
+        &2_000,
+        &500,
+        &3_600,
+        &100,
+        &AuctionType::Dutch,
+        &test_asset(&env),
+        &AuctionOptions {
+            buyout_price: None,
+            allowed_bidders: None,
+            dutch_half_life: None,
+            start_time: None,
+        },
+    );
+
+    set_time(&env, 2_800);
+    assert_eq!(client.get_dutch_auction_price(&auction_id), 1_250);
+}
+
+fn create_sealed_auction(env: &Env, client: &MarketplaceSettlementClient) -> u64 {
+    let seller = Address::generate(env);
+    let (nft, token_id) = owned_token(env, client, &seller);
+    client.create_auction(
+        &seller,
+        &nft,
+        &token_id,
+        &1_000,
+        &500,
+        &3_600,
+        &100,
+        &AuctionType::SealedFirstPrice,
+        &test_asset(env),
+        &AuctionOptions {
+            buyout_price: None,
+            allowed_bidders: None,
+            dutch_half_life: None,
+            start_time: None,
+        },
+    )
+}
+
+fn count_events(env: &Env, name: Symbol) -> u32 {
+    let mut count = 0;
+    for (_, topics, _) in env.events().all().iter() {
+        let matches = topics
+            .get(1)
+            .and_then(|topic| Symbol::try_from_val(env, &topic).ok())
+            .is_some_and(|topic| topic == name);
+        if matches {
+            count += 1;
+        }
+    }
+    count
+}
+
+#[test]
+fn test_sealed_first_price_partial_reveal() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let auction_id = create_sealed_auction(&env, &client);
+
+    let alice = funded_bidder(&env, &client, auction_id);
+    let bob = funded_bidder(&env, &client, auction_id);
+    let carol = funded_bidder(&env, &client, auction_id);
+    let alice_salt = Bytes::from_slice(&env, b"alice");
+    let bob_salt = Bytes::from_slice(&env, b"bob");
+    let carol_salt = Bytes::from_slice(&env, b"carol");
+
+    set_time(&env, 1_260);
+    seal(&client, auction_id, &alice, 4_000, &alice_salt);
+    set_time(&env, 2_045);
+    seal(&client, auction_id, &bob, 3_200, &bob_salt);
+    set_time(&env, 3_380);
+    seal(&client, auction_id, &carol, 5_500, &carol_salt);
+
+    // Reveals only open after bidding closes
+    let result = client.try_reveal_sealed_bid(&auction_id, &alice, &4_000, &alice_salt);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+
+    set_time(&env, 5_100);
+    client.reveal_sealed_bid(&auction_id, &bob, &3_200, &bob_salt);
+    client.reveal_sealed_bid(&auction_id, &alice, &4_000, &alice_salt);
+    assert_eq!(client.get_bid_escrow(&auction_id, &alice), 4_000);
+
+    // Carol never reveals and misses the window
+    set_time(&env, 8_201);
+    let result = client.try_reveal_sealed_bid(&auction_id, &carol, &5_500, &carol_salt);
+    assert_eq!(result, Err(Ok(SettlementError::Expired)));
+
+    client.end_sealed_auction(&auction_id, &admin);
+    assert_eq!(count_events(&env, symbol_short!("rvl_exp")), 1);
+
+    // Winner pays their own bid in full
+    let auction = client.get_auction(&auction_id);
+    assert_eq!(auction.state, TransactionState::Executed);
+    assert_eq!(auction.highest_bidder, Some(alice.clone()));
+    assert_eq!(auction.highest_bid, 4_000);
+    assert_eq!(client.get_bid_escrow(&auction_id, &alice), 0);
+    assert_eq!(client.withdraw_bid_escrow(&auction_id, &bob), 3_200);
+}
+
+#[test]
+fn test_sealed_first_price_without_reveals() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let auction_id = create_sealed_auction(&env, &client);
+
+    set_time(&env, 1_730);
+    seal(&client, auction_id, &Address::generate(&env), 2_000, &Bytes::from_slice(&env, b"one"));
+    set_time(&env, 2_911);
+    seal(&client, auction_id, &Address::generate(&env), 2_600, &Bytes::from_slice(&env, b"two"));
+
+    // Settlement waits for the reveal period, and the generic path is closed
+    set_time(&env, 8_200);
+    let result = client.try_end_sealed_auction(&auction_id, &admin);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+    let result = client.try_end_auction(&auction_id, &admin, &0);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+
+    set_time(&env, 8_260);
+    client.end_sealed_auction(&auction_id, &admin);
+    assert_eq!(count_events(&env, symbol_short!("rvl_exp")), 2);
+
+    let auction = client.get_auction(&auction_id);
+    assert_eq!(auction.highest_bidder, None);
+    assert_eq!(auction.highest_bid, 0);
+}
+
+#[test]
+fn test_sealed_first_price_requires_commitment() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let auction_id = create_sealed_auction(&env, &client);
+    let bidder = Address::generate(&env);
+
+    set_time(&env, 1_555);
+    let result = client.try_place_bid(&auction_id, &bidder, &3_000, &None);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+
+    let salt = Bytes::from_slice(&env, b"sealed");
+    seal(&client, auction_id, &bidder, 3_000, &salt);
+    assert_eq!(client.get_sealed_auction(&auction_id).committed_bidders, vec![&env, bidder.clone()]);
+
+    set_time(&env, 4_700);
+    let result = client.try_reveal_sealed_bid(&auction_id, &bidder, &3_100, &Bytes::from_slice(&env, b"other"));
+    assert_eq!(result, Err(Ok(SettlementError::CommitmentMismatch)));
+}
+
+fn make_offer(env: &Env, client: &MarketplaceSettlementClient, buyer: &Address, amount: i128, duration: u64) -> u64 {
+    let currency = test_asset(env);
+    fund(env, &currency, buyer, amount);
+    client.make_offer(buyer, &Address::generate(env), &7, &amount, &currency, &duration)
+}
+
+#[test]
+fn test_accept_offer() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let nft = nft_collection(&env);
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let currency = test_asset(&env);
+    fund(&env, &currency, &buyer, 50_000);
+
+    let offer_id = client.make_offer(&buyer, &nft.address, &token_id, &50_000, &currency, &86_400);
+    let offer = client.get_offer(&offer_id);
+    assert_eq!(offer.buyer, buyer);
+    assert_eq!(offer.expires_at, 1_000 + 86_400);
+
+    set_time(&env, 5_000);
+    let result = client.accept_offer(&offer_id, &seller);
+    assert!(result.success);
+    assert_eq!(count_events(&env, symbol_short!("offr_acpt")), 1);
+
+    // Default platform fee is 2.5%; the seller receives the rest
+    assert_eq!(client.get_accumulated_fees(&offer.currency), 1_250);
+    assert_eq!(balance(&env, &offer.currency, &seller), 48_750);
+    assert_eq!(balance(&env, &offer.currency, &buyer), 0);
+    assert_eq!(balance(&env, &offer.currency, &client.address), 1_250);
+    assert!(holds(&nft, &buyer, token_id));
+    assert_eq!(client.try_get_offer(&offer_id), Err(Ok(SettlementError::NotFound)));
+    assert_eq!(client.try_accept_offer(&offer_id, &seller), Err(Ok(SettlementError::NotFound)));
+}
+
+#[test]
+fn test_offer_validation_and_expiry() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let buyer = Address::generate(&env);
+    let nft = Address::generate(&env);
+
+    let result = client.try_make_offer(&buyer, &nft, &7, &0, &test_asset(&env), &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+    let result = client.try_make_offer(&buyer, &nft, &7, &5_000, &test_asset(&env), &(31 * 86_400));
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+    let result = client.try_make_offer(&buyer, &nft, &7, &5_000, &test_asset(&env), &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::InsufficientFunds)));
+
+    let offer_id = make_offer(&env, &client, &buyer, 5_000, 3_600);
+    set_time(&env, 1_000 + 3_600);
+    let result = client.try_accept_offer(&offer_id, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Expired)));
+}
+
+#[test]
+fn test_cancel_offer() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let buyer = Address::generate(&env);
+    let offer_id = make_offer(&env, &client, &buyer, 5_000, 3_600);
+    let currency = client.get_offer(&offer_id).currency;
+    assert_eq!(balance(&env, &currency, &buyer), 0);
+
+    let result = client.try_cancel_offer(&offer_id, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    client.cancel_offer(&offer_id, &buyer);
+    assert_eq!(count_events(&env, symbol_short!("offr_canc")), 1);
+    assert_eq!(balance(&env, &currency, &buyer), 5_000);
+    assert_eq!(client.try_get_offer(&offer_id), Err(Ok(SettlementError::NotFound)));
+}
+
+#[test]
+fn test_cleanup_expired_offers() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let buyer = Address::generate(&env);
+
+    let short_offer = make_offer(&env, &client, &buyer, 5_000, 600);
+    let long_offer = make_offer(&env, &client, &buyer, 6_000, 7_200);
+
+    set_time(&env, 1_000 + 1_200);
+    assert_eq!(client.cleanup_expired_offers(), 1);
+    assert_eq!(count_events(&env, symbol_short!("offr_canc")), 1);
+    assert_eq!(client.try_get_offer(&short_offer), Err(Ok(SettlementError::NotFound)));
+    assert_eq!(client.get_offer(&long_offer).amount, 6_000);
+    assert_eq!(client.cleanup_expired_offers(), 0);
+}
+
+#[test]
+fn test_collection_offer_fills_until_exhausted() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let buyer = Address::generate(&env);
+    let nft = nft_collection(&env);
+    let currency = test_asset(&env);
+    fund(&env, &currency, &buyer, 80_000);
+
+    let offer_id = client.make_collection_offer(&buyer, &nft.address, &40_000, &currency, &86_400, &2);
+    assert_eq!(client.get_collection_offer(&offer_id).max_quantity, 2);
+    assert_eq!(balance(&env, &currency, &client.address), 80_000);
+
+    let first_seller = Address::generate(&env);
+    let second_seller = Address::generate(&env);
+    let first_token = mint_for_sale(&env, &nft, &first_seller, &client.address);
+    let second_token = mint_for_sale(&env, &nft, &second_seller, &client.address);
+    client.fill_collection_offer(&offer_id, &first_seller, &first_token);
+    assert_eq!(count_events(&env, symbol_short!("coff_fill")), 1);
+    assert_eq!(count_events(&env, symbol_short!("coff_canc")), 0);
+    assert_eq!(balance(&env, &currency, &first_seller), 39_000);
+    assert_eq!(client.get_collection_offer(&offer_id).max_quantity, 1);
+
+    // The last fill closes the offer
+    client.fill_collection_offer(&offer_id, &second_seller, &second_token);
+    assert_eq!(count_events(&env, symbol_short!("coff_canc")), 1);
+    assert!(holds(&nft, &buyer, first_token));
+    assert!(holds(&nft, &buyer, second_token));
+    assert_eq!(client.try_get_collection_offer(&offer_id), Err(Ok(SettlementError::NotFound)));
+    assert_eq!(client.get_accumulated_fees(&currency), 2_000);
+}
+
+#[test]
+fn test_collection_offer_validation() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let buyer = Address::generate(&env);
+    let nft = Address::generate(&env);
+    let currency = test_asset(&env);
+    fund(&env, &currency, &buyer, 100_000);
+
+    let result = client.try_make_collection_offer(&buyer, &nft, &40_000, &currency, &3_600, &0);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+
+    // The whole quantity has to be covered up front
+    let result = client.try_make_collection_offer(&buyer, &nft, &40_000, &currency, &3_600, &3);
+    assert_eq!(result, Err(Ok(SettlementError::InsufficientFunds)));
+
+    let offer_id = client.make_collection_offer(&buyer, &nft, &40_000, &currency, &3_600, &2);
+    let result = client.try_cancel_collection_offer(&offer_id, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    set_time(&env, 1_000 + 3_600);
+    let result = client.try_fill_collection_offer(&offer_id, &Address::generate(&env), &5);
+    assert_eq!(result, Err(Ok(SettlementError::Expired)));
+
+    assert_eq!(client.cleanup_expired_offers(), 1);
+    assert_eq!(client.try_get_collection_offer(&offer_id), Err(Ok(SettlementError::NotFound)));
+    assert_eq!(balance(&env, &currency, &buyer), 100_000);
+}
+
+#[test]
+fn test_cancel_collection_offer() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let nft = nft_collection(&env);
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let currency = test_asset(&env);
+    fund(&env, &currency, &buyer, 120_000);
+
+    let offer_id = client.make_collection_offer(&buyer, &nft.address, &40_000, &currency, &3_600, &3);
+    client.fill_collection_offer(&offer_id, &seller, &token_id);
+    client.cancel_collection_offer(&offer_id, &buyer);
+    assert_eq!(count_events(&env, symbol_short!("coff_canc")), 1);
+    assert_eq!(balance(&env, &currency, &buyer), 80_000);
+    assert_eq!(client.try_get_collection_offer(&offer_id), Err(Ok(SettlementError::NotFound)));
+}
+
+#[test]
+fn test_fixed_price_sale() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let currency = test_asset(&env);
+    fund(&env, &currency, &buyer, 150_000);
+    let nft = nft_collection(&env);
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &100_000, &currency, &86_400);
+    assert_eq!(count_events(&env, symbol_short!("sale_crtd")), 1);
+    assert_eq!(client.get_sale(&sale_id).platform_fee, 2_500);
+
+    // Sellers can't buy their own listing
+    assert_eq!(client.try_buy(&sale_id, &seller, &ANY_PRICE, &None), Err(Ok(SettlementError::Unauthorized)));
+
+    client.buy(&sale_id, &buyer, &100_000, &None);
+    assert_eq!(count_events(&env, symbol_short!("sale_exec")), 1);
+    let sale = client.get_sale(&sale_id);
+    assert_eq!(sale.state, TransactionState::Executed);
+    assert_eq!(sale.buyer, Some(buyer.clone()));
+    assert_eq!(client.get_accumulated_fees(&currency), 2_500);
+    assert_eq!(balance(&env, &currency, &buyer), 50_000);
+    assert_eq!(balance(&env, &currency, &seller), 97_500);
+    assert!(holds(&nft, &buyer, token_id));
+
+    // Accumulated fees are paid out of the contract's balance
+    let treasury = Address::generate(&env);
+    assert_eq!(client.withdraw_platform_fees(&currency, &treasury, &admin), 2_500);
+    assert!(env.auths().iter().any(|(address, _)| *address == admin));
+    assert_eq!(balance(&env, &currency, &treasury), 2_500);
+    assert_eq!(balance(&env, &currency, &client.address), 0);
+
+    assert_eq!(client.try_buy(&sale_id, &buyer, &ANY_PRICE, &None), Err(Ok(SettlementError::InvalidState)));
+    assert_eq!(client.try_cancel_sale(&sale_id, &seller), Err(Ok(SettlementError::InvalidState)));
+}
+
+#[test]
+fn test_buy_slippage_protection() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let currency = test_asset(&env);
+    fund(&env, &currency, &buyer, 150_000);
+    let nft = nft_collection(&env);
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &100_000, &currency, &86_400);
+
+    // The price moves after the buyer signed for 100_000
+    env.as_contract(&client.address, || {
+        let mut sale = SaleTransactionStore::get(&env, sale_id).unwrap();
+        sale.price = 120_000;
+        SaleTransactionStore::update(&env, &sale).unwrap();
+    });
+    let result = client.try_buy(&sale_id, &buyer, &100_000, &None);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+    assert!(holds(&nft, &seller, token_id));
+    assert_eq!(balance(&env, &currency, &buyer), 150_000);
+
+    client.buy(&sale_id, &buyer, &120_000, &None);
+    assert!(holds(&nft, &buyer, token_id));
+}
+
+#[test]
+fn test_end_auction_slippage_protection() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let auction_id = create_english_auction(&env, &client, None);
+    let seller = client.get_auction(&auction_id).seller;
+    let bidder = funded_bidder(&env, &client, auction_id);
+    set_time(&env, 1_340);
+    client.place_bid(&auction_id, &bidder, &2_000, &None);
+
+    // The seller expected bidding to reach 5_000
+    set_time(&env, 4_700);
+    let result = client.try_end_auction(&auction_id, &seller, &5_000);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+    assert_eq!(client.get_auction(&auction_id).state, TransactionState::Pending);
+
+    // Only the seller can set a floor, so no one else can hold settlement hostage with one
+    let result = client.try_end_auction(&auction_id, &bidder, &5_000);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    assert!(client.end_auction(&auction_id, &seller, &2_000).success);
+    assert!(env.auths().iter().any(|(address, _)| *address == seller));
+    assert_eq!(client.get_auction(&auction_id).state, TransactionState::Executed);
+}
+
+#[test]
+fn test_batch_fee_withdrawal() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let usdc = test_asset(&env);
+    fund(&env, &usdc, &buyer, 100_000);
+    let nft = nft_collection(&env);
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &100_000, &usdc, &86_400);
+    client.buy(&sale_id, &buyer, &100_000, &None);
+
+    // Fees booked against a second asset the contract doesn't actually hold, and a third with none
+    let eurc = Asset { symbol: symbol_short!("EURC"), ..test_asset(&env) };
+    let xlm = Asset { symbol: symbol_short!("XLM"), ..test_asset(&env) };
+    env.as_contract(&client.address, || {
+        FeeManager::collect_platform_fee(&env, 4_000, &eurc, &buyer, None).unwrap();
+    });
+
+    let treasury = Address::generate(&env);
+    let assets = vec![&env, usdc.clone(), eurc.clone(), xlm.clone()];
+    let result = client.try_withdraw_platform_fees_batch(&assets, &treasury, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    let summary = client.withdraw_platform_fees_batch(&assets, &treasury, &admin);
+    assert_eq!(summary.withdrawn, vec![&env, (usdc.clone(), 2_500), (xlm.clone(), 0)]);
+    assert_eq!(summary.failed.len(), 1);
+    let failed = summary.failed.get(0).unwrap();
+    assert_eq!((failed.asset, failed.amount), (eurc.clone(), 4_000));
+    assert_eq!(failed.error_code, SettlementError::InsufficientFunds as u32);
+
+    // The failed asset keeps its fees for a later attempt
+    assert_eq!(balance(&env, &usdc, &treasury), 2_500);
+    assert_eq!(client.get_accumulated_fees(&usdc), 0);
+    assert_eq!(client.get_accumulated_fees(&eurc), 4_000);
+    fund(&env, &eurc, &client.address, 4_000);
+    let summary = client.withdraw_platform_fees_batch(&vec![&env, eurc.clone()], &treasury, &admin);
+    assert_eq!(summary.withdrawn, vec![&env, (eurc.clone(), 4_000)]);
+    assert_eq!(balance(&env, &eurc, &treasury), 4_000);
+}
+
+#[test]
+fn test_referral_fee_split() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    let currency = test_asset(&env);
+    fund(&env, &currency, &buyer, 200_000);
+    let nft = nft_collection(&env);
+
+    // 10% of the 2,500 fee goes to the referrer, the rest to the platform
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &100_000, &currency, &86_400);
+    client.buy(&sale_id, &buyer, &ANY_PRICE, &Some(referrer.clone()));
+    assert_eq!(count_events(&env, symbol_short!("ref_fee")), 1);
+    assert_eq!(client.get_accumulated_fees(&currency), 2_250);
+    assert_eq!(client.get_referral_earnings(&referrer).get(currency.clone()), Some(250));
+    assert_eq!(balance(&env, &currency, &seller), 97_500);
+
+    // Buyers can't refer themselves
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &100_000, &currency, &86_400);
+    client.buy(&sale_id, &buyer, &ANY_PRICE, &Some(buyer.clone()));
+    assert_eq!(count_events(&env, symbol_short!("ref_fee")), 0);
+    assert_eq!(client.get_accumulated_fees(&currency), 4_750);
+    assert!(client.get_referral_earnings(&buyer).is_empty());
+
+    let withdrawn = client.withdraw_referral_earnings(&referrer);
+    assert_eq!(withdrawn.get(currency.clone()), Some(250));
+    assert_eq!(balance(&env, &currency, &referrer), 250);
+    assert!(client.get_referral_earnings(&referrer).is_empty());
+    let result = client.try_withdraw_referral_earnings(&referrer);
+    assert_eq!(result, Err(Ok(SettlementError::InsufficientFunds)));
+
+    // Referral earnings are kept apart from the platform's share
+    assert_eq!(client.withdraw_platform_fees(&currency, &admin, &admin), 4_750);
+    assert_eq!(balance(&env, &currency, &client.address), 0);
+}
+
+#[test]
+fn test_volume_discount_lapses_with_period() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let trader = Address::generate(&env);
+    let currency = test_asset(&env);
+
+    // 1M of volume earns the 0.5% discount on the 2.5% fee
+    env.as_contract(&client.address, || {
+        FeeManager::collect_platform_fee(&env, 1_000_000, &currency, &trader, None).unwrap();
+        assert_eq!(FeeManager::calculate_fee(&env, 100_000, &trader, None).unwrap(), 2_000);
+        assert_eq!(FeeManager::get_fee_statistics(&env).reset_period, 2_592_000);
+    });
+
+    // Still discounted at the end of the 30-day period
+    set_time(&env, 1_000 + 2_592_000);
+    env.as_contract(&client.address, || {
+        assert_eq!(FeeManager::get_user_volume(&env, &trader).unwrap(), 1_000_000);
+        assert_eq!(FeeManager::calculate_fee(&env, 100_000, &trader, None).unwrap(), 2_000);
+    });
+
+    // Once it lapses the discount is gone and new volume starts a fresh period
+    set_time(&env, 1_000 + 2_592_001);
+    env.as_contract(&client.address, || {
+        assert_eq!(FeeManager::get_user_volume(&env, &trader).unwrap(), 0);
+        assert_eq!(FeeManager::calculate_fee(&env, 100_000, &trader, None).unwrap(), 2_500);
+
+        FeeManager::collect_platform_fee(&env, 2_500, &currency, &trader, None).unwrap();
+        assert_eq!(FeeManager::get_user_volume(&env, &trader).unwrap(), 2_500);
+    });
+}
+
+/// Silver, gold and platinum VIP tiers at 1_000, 10_000 and 100_000 of volume
+fn vip_tiers(env: &Env) -> Vec<VipTier> {
+    let tier = |name: &str, min_volume: i128, fee_discount_bps: u64| VipTier {
+        name: Bytes::from_slice(env, name.as_bytes()),
+        min_volume,
+        fee_discount_bps,
+    };
+    vec![env, tier("silver", 1_000, 25), tier("gold", 10_000, 50), tier("platinum", 100_000, 100)]
+}
+
+#[test]
+fn test_vip_tier_promotion() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let tiers = vip_tiers(&env);
+    let result = client.try_set_vip_tiers(&tiers, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    let mut unordered = tiers.clone();
+    unordered.set(0, tiers.get(1).unwrap());
+    unordered.set(1, tiers.get(0).unwrap());
+    assert_eq!(client.try_set_vip_tiers(&unordered, &admin), Err(Ok(SettlementError::InvalidAmount)));
+    client.set_vip_tiers(&tiers, &admin);
+
+    // A sale's 2_500 fee counts toward the buyer's volume, reaching silver
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let currency = test_asset(&env);
+    fund(&env, &currency, &buyer, 100_000);
+    let nft = nft_collection(&env);
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &100_000, &currency, &86_400);
+    assert_eq!(client.get_user_tier(&buyer), None);
+    client.buy(&sale_id, &buyer, &100_000, &None);
+    assert_eq!(count_events(&env, symbol_short!("tier_upd")), 1);
+    assert_eq!(client.get_user_tier(&buyer), Some(0));
+
+    env.as_contract(&client.address, || {
+        FeeManager::collect_platform_fee(&env, 10_000, &currency, &buyer, None).unwrap();
+        assert_eq!(FeeManager::get_user_tier(&env, &buyer), Some(1));
+        // Gold's 0.5% discount on the 2.5% fee
+        assert_eq!(FeeManager::calculate_fee(&env, 400_000, &buyer, None).unwrap(), 8_000);
+
+        // Enough volume at once skips straight past any tier in between
+        let whale = Address::generate(&env);
+        FeeManager::collect_platform_fee(&env, 250_000, &currency, &whale, None).unwrap();
+        assert_eq!(FeeManager::get_user_tier(&env, &whale), Some(2));
+        assert_eq!(FeeManager::calculate_fee(&env, 400_000, &whale, None).unwrap(), 6_000);
+    });
+
+    // The tier goes with the volume period
+    set_time(&env, 1_000 + 2_592_001);
+    env.as_contract(&client.address, || {
+        FeeManager::collect_platform_fee(&env, 500, &currency, &buyer, None).unwrap();
+        assert_eq!(FeeManager::get_user_tier(&env, &buyer), None);
+    });
+}
+
+/// Mutation hook for a game that marks tokens sold at 50,000 or more as rare
+#[contract]
+struct MockMutationHook;
+
+#[contractimpl]
+impl MockMutationHook {
+    pub fn __constructor(env: Env, nft: Address) {
+        env.storage().instance().set(&symbol_short!("nft"), &nft);
+    }
+
+    pub fn on_sold(env: Env, token_id: u64, final_price: i128) {
+        if final_price < 50_000 {
+            return;
+        }
+        let nft: Address = env.storage().instance().get(&symbol_short!("nft")).unwrap();
+        let attributes = vec![&env, TokenAttribute {
+            trait_type: String::from_str(&env, "Tier"),
+            value: String::from_str(&env, "Rare"),
+        }];
+        NftContractClient::new(&env, &nft).update_token_attributes(&token_id, &attributes, &env.current_contract_address());
+    }
+}
+
+#[test]
+fn test_execute_sale_calls_mutation_hook() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let currency = test_asset(&env);
+    fund(&env, &currency, &buyer, 300_000);
+    let nft = nft_collection(&env);
+    let hook = env.register(MockMutationHook, (nft.address.clone(),));
+    let rare = vec![&env, TokenAttribute {
+        trait_type: String::from_str(&env, "Tier"),
+        value: String::from_str(&env, "Rare"),
+    }];
+
+    // A hook that fails, here for lack of the updater role, does not block the sale
+    let first = mint_for_sale(&env, &nft, &seller, &client.address);
+    nft.register_mutation_hook(&first, &hook, &seller);
+    let sale_id = client.create_sale(&seller, &nft.address, &first, &100_000, &currency, &86_400);
+    client.fund_sale(&sale_id, &buyer);
+    assert!(client.execute_sale(&sale_id, &buyer, &100_000).success);
+    assert!(holds(&nft, &buyer, first));
+    assert!(nft.get_token_attributes(&first).is_empty());
+
+    nft.grant_role(&(Role::MetadataUpdater as u32), &hook);
+
+    // Below the game's threshold the hook leaves the token alone
+    let cheap = mint_for_sale(&env, &nft, &seller, &client.address);
+    nft.register_mutation_hook(&cheap, &hook, &seller);
+    let sale_id = client.create_sale(&seller, &nft.address, &cheap, &20_000, &currency, &86_400);
+    client.fund_sale(&sale_id, &buyer);
+    client.execute_sale(&sale_id, &buyer, &20_000);
+    assert!(nft.get_token_attributes(&cheap).is_empty());
+
+    let pricey = mint_for_sale(&env, &nft, &seller, &client.address);
+    nft.register_mutation_hook(&pricey, &hook, &seller);
+    let sale_id = client.create_sale(&seller, &nft.address, &pricey, &150_000, &currency, &86_400);
+    client.fund_sale(&sale_id, &buyer);
+    client.execute_sale(&sale_id, &buyer, &150_000);
+    assert_eq!(nft.get_token_attributes(&pricey), rare);
+    assert!(holds(&nft, &buyer, pricey));
+}
+
+/// DEX router that pays out two protocol tokens for every fee token swapped
+#[contract]
+struct MockDex;
+
+#[contractimpl]
+impl MockDex {
+    pub fn swap_exact_tokens_for_tokens(
+        env: Env,
+        amount_in: i128,
+        _amount_out_min: i128,
+        path: Vec<Address>,
+        to: Address,
+        _deadline: u64,
+    ) -> Vec<i128> {
+        let amount_out = amount_in * 2;
+        let dex = env.current_contract_address();
+        TokenClient::new(&env, &path.get(0).unwrap()).transfer(&to, &dex, &amount_in);
+        TokenClient::new(&env, &path.get(1).unwrap()).transfer(&dex, &to, &amount_out);
+        vec![&env, amount_in, amount_out]
+    }
+}
+
+#[test]
+fn test_admin_entrypoints_require_admin_auth() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let fee_config = env.as_contract(&client.address, || FeeManager::get_fee_config(&env).unwrap());
+    let reason = Bytes::from_slice(&env, b"incident");
+
+    // Naming the admin isn't enough without the admin's signature
+    env.set_auths(&[]);
+    assert!(matches!(client.try_update_fee_config(&fee_config, &admin), Err(Err(_))));
+    assert!(matches!(client.try_emergency_withdraw(&1, &reason, &admin), Err(Err(_))));
+
+    env.mock_all_auths();
+    client.update_fee_config(&fee_config, &admin);
+    assert!(env.auths().iter().any(|(address, _)| *address == admin));
+}
+
+#[test]
+fn test_fee_auto_swap_through_dex() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let usdc = test_asset(&env);
+    let protocol_token = test_asset(&env);
+    let dex = env.register(MockDex, ());
+    fund(&env, &protocol_token, &dex, 1_000_000);
+    fund(&env, &usdc, &client.address, 100_000);
+
+    let mut fee_config = env.as_contract(&client.address, || FeeManager::get_fee_config(&env).unwrap());
+    fee_config.auto_swap_enabled = true;
+    fee_config.protocol_token = protocol_token.clone();
+    assert_eq!(client.try_update_fee_config(&fee_config, &admin), Err(Ok(SettlementError::InvalidFeeConfig)));
+    fee_config.swap_threshold = 3_000;
+    client.update_fee_config(&fee_config, &admin);
+
+    let result = client.try_set_swap_dex_address(&dex, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    client.set_swap_dex_address(&dex, &admin);
+
+    let collector = Address::generate(&env);
+    let collect = |amount: i128| {
+        env.as_contract(&client.address, || {
+            FeeManager::collect_platform_fee(&env, amount, &usdc, &collector, None).unwrap();
+        });
+    };
+
+    // At the threshold nothing moves; past it the whole balance is swapped
+    collect(3_000);
+    assert_eq!(client.get_accumulated_fees(&usdc), 3_000);
+    collect(1_000);
+    assert_eq!(count_events(&env, symbol_short!("fee_swap")), 1);
+    assert_eq!(client.get_accumulated_fees(&usdc), 0);
+    assert_eq!(client.get_accumulated_fees(&protocol_token), 8_000);
+    assert_eq!(balance(&env, &protocol_token, &client.address), 8_000);
+    assert_eq!(balance(&env, &usdc, &dex), 4_000);
+
+    // A router that can't be called leaves the fees in place rather than failing the sale
+    client.set_swap_dex_address(&Address::generate(&env), &admin);
+    collect(5_000);
+    assert_eq!(client.get_accumulated_fees(&usdc), 5_000);
+
+    client.set_swap_dex_address(&dex, &admin);
+    assert_eq!(client.try_disable_auto_swap(&Address::generate(&env)), Err(Ok(SettlementError::Unauthorized)));
+    client.disable_auto_swap(&admin);
+    collect(5_000);
+    assert_eq!(client.get_accumulated_fees(&usdc), 10_000);
+    assert_eq!(client.get_accumulated_fees(&protocol_token), 8_000);
+}
+
+#[test]
+fn test_promotional_fee_override_window() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let user = Address::generate(&env);
+    let promoted = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    // 1% off the 2.5% fee for one collection, from 5_000 up to 9_000
+    let mut collections = Vec::new(&env);
+    collections.push_back(promoted.clone());
+    let promotion = PromotionalFeeOverride {
+        discount_bps: 100,
+        starts_at: 5_000,
+        ends_at: 9_000,
+        eligible_collections: Some(collections),
+    };
+    assert_eq!(client.try_set_promotion(&promotion, &user), Err(Ok(SettlementError::Unauthorized)));
+    let backwards = PromotionalFeeOverride { starts_at: 9_000, ends_at: 5_000, ..promotion.clone() };
+    assert_eq!(client.try_set_promotion(&backwards, &admin), Err(Ok(SettlementError::InvalidAmount)));
+    client.set_promotion(&promotion, &admin);
+    assert_eq!(count_events(&env, symbol_short!("promo_set")), 1);
+    assert_eq!(client.get_promotion(), Some(promotion.clone()));
+
+    let fee_at = |timestamp: u64, nft_address: &Address| {
+        set_time(&env, timestamp);
+        env.as_contract(&client.address, || {
+            FeeManager::calculate_fee(&env, 400_000, &user, Some(nft_address.clone())).unwrap()
+        })
+    };
+    assert_eq!(fee_at(4_999, &promoted), 10_000);
+    assert_eq!(fee_at(5_000, &promoted), 6_000);
+    assert_eq!(fee_at(8_999, &promoted), 6_000);
+    assert_eq!(fee_at(9_000, &promoted), 10_000);
+    assert_eq!(fee_at(5_000, &other), 10_000);
+
+    // Without a collection list the promotion covers everything
+    set_time(&env, 1_000);
+    client.set_promotion(&PromotionalFeeOverride { eligible_collections: None, ..promotion }, &admin);
+    assert_eq!(fee_at(6_000, &other), 6_000);
+
+    assert_eq!(client.try_clear_promotion(&user), Err(Ok(SettlementError::Unauthorized)));
+    client.clear_promotion(&admin);
+    assert_eq!(client.get_promotion(), None);
+    assert_eq!(fee_at(6_000, &other), 10_000);
+}
+
+#[test]
+fn test_default_fee_schedule_brackets() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let schedule = client.get_default_fee_schedule();
+    assert_eq!(schedule, get_default_fee_schedule(&env));
+
+    // 3% below 1,000
+    assert_eq!(schedule.calculate_fee(&env, 0).unwrap(), 0);
+    assert_eq!(schedule.calculate_fee(&env, 999).unwrap(), 29);
+    // 2.5% from 1,000 up to 10,000
+    assert_eq!(schedule.calculate_fee(&env, 1_000).unwrap(), 25);
+    assert_eq!(schedule.calculate_fee(&env, 9_999).unwrap(), 249);
+    // 2% from 10,000
+    assert_eq!(schedule.calculate_fee(&env, 10_000).unwrap(), 200);
+    assert_eq!(schedule.calculate_fee(&env, 1_000_000).unwrap(), 20_000);
+    // Nothing is charged below the first bracket
+    assert_eq!(schedule.calculate_fee(&env, -5).unwrap(), 0);
+}
+
+#[test]
+fn test_fee_schedule_builder_validation() {
+    let env = Env::default();
+    assert_eq!(FeeSchedule::builder(&env).build(), Err(SettlementError::InvalidFeeConfig));
+
+    let result = FeeSchedule::builder(&env).add_tier(1_000, 250).add_tier(0, 300).build();
+    assert_eq!(result, Err(SettlementError::InvalidFeeConfig));
+    let result = FeeSchedule::builder(&env).add_tier(0, 300).add_tier(0, 250).build();
+    assert_eq!(result, Err(SettlementError::InvalidFeeConfig));
+    let result = FeeSchedule::builder(&env).add_tier(0, 10_001).build();
+    assert_eq!(result, Err(SettlementError::InvalidFeeConfig));
+
+    let schedule = FeeSchedule::builder(&env).add_tier(500, 100).add_tier(5_000, 50).build().unwrap();
+    assert_eq!(schedule.calculate_fee(&env, 499).unwrap(), 0);
+    assert_eq!(schedule.calculate_fee(&env, 4_000).unwrap(), 40);
+    assert_eq!(schedule.calculate_fee(&env, 8_000).unwrap(), 40);
+}
+
+#[test]
+fn test_cancel_and_expire_sale() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let nft = nft_collection(&env);
+    let first_token = mint_for_sale(&env, &nft, &seller, &client.address);
+    let second_token = mint_for_sale(&env, &nft, &seller, &client.address);
+
+    let sale_id = client.create_sale(&seller, &nft.address, &first_token, &100_000, &test_asset(&env), &3_600);
+    let result = client.try_cancel_sale(&sale_id, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    client.cancel_sale(&sale_id, &seller);
+    assert_eq!(count_events(&env, symbol_short!("sale_canc")), 1);
+    assert_eq!(client.get_sale(&sale_id).state, TransactionState::Cancelled);
+    let result = client.try_buy(&sale_id, &Address::generate(&env), &ANY_PRICE, &None);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+
+    let sale_id = client.create_sale(&seller, &nft.address, &second_token, &100_000, &test_asset(&env), &3_600);
+    set_time(&env, 1_000 + 3_600);
+    let result = client.try_buy(&sale_id, &Address::generate(&env), &ANY_PRICE, &None);
+    assert_eq!(result, Err(Ok(SettlementError::Expired)));
+}
+
+#[test]
+fn test_distribute_royalties_splits_shares() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let currency = test_asset(&env);
+    fund(&env, &currency, &client.address, 100_000);
+    let creator = Address::generate(&env);
+    let seller = Address::generate(&env);
+
+    // 10% creator royalty and a 2% platform cut on a 50,000 sale
+    let distribution = RoyaltyDistribution {
+        creator_address: creator.clone(),
+        creator_percentage: 1_000,
+        seller_percentage: 8_800,
+        platform_percentage: 200,
+        total_amount: 50_000,
+        amounts: Map::new(&env),
+    };
+    let result = env.as_contract(&client.address, || {
+        RoyaltyDistributor::distribute_royalties(&env, 1, &distribution, &currency, 50_000, &seller).unwrap()
+    });
+    assert_eq!(count_events(&env, symbol_short!("roy_dist")), 1);
+    assert!(result.distribution_success);
+    assert_eq!(result.creator_amount, 5_000);
+    assert_eq!(result.platform_amount, 1_000);
+    assert_eq!(result.seller_amount, 44_000);
+    assert_eq!(balance(&env, &currency, &creator), 5_000);
+    assert_eq!(balance(&env, &currency, &admin), 1_000);
+    // The seller's share stays in escrow for the caller to pay out
+    assert_eq!(balance(&env, &currency, &seller), 0);
+    assert_eq!(balance(&env, &currency, &client.address), 94_000);
+
+    // A creator selling their own token is paid once, as the seller
+    let result = env.as_contract(&client.address, || {
+        RoyaltyDistributor::distribute_royalties(&env, 2, &distribution, &currency, 30_000, &creator).unwrap()
+    });
+    assert_eq!(result.creator_amount, 0);
+    assert_eq!(result.platform_amount, 600);
+    assert_eq!(result.seller_amount, 29_400);
+    assert_eq!(balance(&env, &currency, &creator), 5_000);
+    assert_eq!(balance(&env, &currency, &admin), 1_600);
+}
+
+#[test]
+fn test_sale_pays_creator_royalty() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let currency = test_asset(&env);
+    fund(&env, &currency, &buyer, 100_000);
+    let nft = nft_collection(&env);
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+
+    env.as_contract(&client.address, || {
+        RoyaltyDistributor::set_royalty_info(&env, &nft.address, token_id, &creator, 1_000, &creator).unwrap();
+    });
+
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &100_000, &currency, &86_400);
+    client.buy(&sale_id, &buyer, &ANY_PRICE, &None);
+    assert_eq!(count_events(&env, symbol_short!("roy_dist")), 1);
+
+    // 10% to the creator, 2.5% platform fee, the rest to the seller
+    assert_eq!(balance(&env, &currency, &creator), 10_000);
+    assert_eq!(balance(&env, &currency, &seller), 87_500);
+    assert_eq!(balance(&env, &currency, &client.address), 2_500);
+    assert_eq!(client.get_accumulated_fees(&currency), 2_500);
+}
+
+#[test]
+fn test_funded_sale_executes_from_escrow() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let currency = test_asset(&env);
+    fund(&env, &currency, &buyer, 150_000);
+    let nft = nft_collection(&env);
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &100_000, &currency, &86_400);
+
+    // Unfunded sales cannot execute
+    let result = client.try_execute_sale(&sale_id, &buyer, &100_000);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+
+    client.fund_sale(&sale_id, &buyer);
+    assert_eq!(count_events(&env, symbol_short!("sale_fund")), 1);
+    assert_eq!(client.get_sale(&sale_id).state, TransactionState::Funded);
+    assert_eq!(client.get_sale_escrow(&sale_id), 100_000);
+    assert_eq!(balance(&env, &currency, &buyer), 50_000);
+    assert_eq!(balance(&env, &currency, &client.address), 100_000);
+    assert_eq!(client.try_fund_sale(&sale_id, &buyer), Err(Ok(SettlementError::InvalidState)));
+
+    // Only the funding buyer can execute, for the escrowed amount
+    let result = client.try_execute_sale(&sale_id, &Address::generate(&env), &100_000);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    let result = client.try_execute_sale(&sale_id, &buyer, &90_000);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+
+    let result = client.execute_sale(&sale_id, &buyer, &100_000);
+    assert!(result.success);
+    assert_eq!(client.get_sale(&sale_id).state, TransactionState::Executed);
+    assert_eq!(client.get_sale_escrow(&sale_id), 0);
+    assert!(holds(&nft, &buyer, token_id));
+
+    // The seller is paid from escrow less the 2.5% platform fee
+    assert_eq!(balance(&env, &currency, &buyer), 50_000);
+    assert_eq!(balance(&env, &currency, &seller), 97_500);
+    assert_eq!(balance(&env, &currency, &client.address), 2_500);
+}
+
+#[test]
+fn test_refund_escrow() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let currency = test_asset(&env);
+    fund(&env, &currency, &buyer, 100_000);
+    let nft = nft_collection(&env);
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &40_000, &currency, &86_400);
+
+    // Nothing to refund before the sale is funded
+    assert_eq!(client.try_refund_escrow(&sale_id), Err(Ok(SettlementError::InvalidState)));
+
+    client.fund_sale(&sale_id, &buyer);
+    assert_eq!(balance(&env, &currency, &buyer), 60_000);
+    client.refund_escrow(&sale_id);
+    assert_eq!(count_events(&env, symbol_short!("sale_canc")), 1);
+    assert_eq!(client.get_sale(&sale_id).state, TransactionState::Cancelled);
+    assert_eq!(client.get_sale_escrow(&sale_id), 0);
+    assert_eq!(balance(&env, &currency, &buyer), 100_000);
+    assert_eq!(balance(&env, &currency, &client.address), 0);
+    assert_eq!(client.try_refund_escrow(&sale_id), Err(Ok(SettlementError::InvalidState)));
+
+    // Cancelling a funded sale returns the escrow to the buyer as well
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &40_000, &currency, &86_400);
+    client.fund_sale(&sale_id, &buyer);
+    assert_eq!(balance(&env, &currency, &client.address), 40_000);
+    client.cancel_sale(&sale_id, &seller);
+    assert_eq!(client.get_sale(&sale_id).state, TransactionState::Cancelled);
+    assert_eq!(balance(&env, &currency, &buyer), 100_000);
+    assert_eq!(balance(&env, &currency, &client.address), 0);
+    assert!(holds(&nft, &seller, token_id));
+
+    // So does cancelling it as a generic transaction, which the seller has to sign
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &40_000, &currency, &86_400);
+    client.fund_sale(&sale_id, &buyer);
+    client.cancel_transaction(&sale_id, &Symbol::new(&env, "sale"), &seller);
+    assert!(env.auths().iter().any(|(address, _)| *address == seller));
+    assert_eq!(balance(&env, &currency, &buyer), 100_000);
+    assert_eq!(balance(&env, &currency, &client.address), 0);
+}
+
+#[test]
+fn test_sale_history() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    let currency = test_asset(&env);
+    let nft = nft_collection(&env);
+    let token_id = mint_for_sale(&env, &nft, &first, &client.address);
+    for owner in [&second, &third] {
+        fund(&env, &currency, owner, 100_000);
+        nft.set_approval_for_all(&client.address, &true, owner);
+    }
+    fund(&env, &currency, &first, 100_000);
+    assert_eq!(client.get_sale_history(&nft.address, &token_id, &0, &10).len(), 0);
+
+    // Sold once through buy and once through a funded sale
+    let sale_one = client.create_sale(&first, &nft.address, &token_id, &10_000, &currency, &86_400);
+    client.buy(&sale_one, &second, &ANY_PRICE, &None);
+    let sale_two = client.create_sale(&second, &nft.address, &token_id, &20_000, &currency, &86_400);
+    client.fund_sale(&sale_two, &third);
+    client.execute_sale(&sale_two, &third, &20_000);
+
+    let history = client.get_sale_history(&nft.address, &token_id, &0, &10);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap().transaction_id, sale_one);
+    assert_eq!(history.get(0).unwrap().buyer, Some(second.clone()));
+    assert_eq!(history.get(1).unwrap().transaction_id, sale_two);
+    assert_eq!(history.get(1).unwrap().price, 20_000);
+
+    // Paging
+    let page = client.get_sale_history(&nft.address, &token_id, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().transaction_id, sale_two);
+    assert_eq!(client.get_sale_history(&nft.address, &token_id, &5, &10).len(), 0);
+
+    // Cancelled sales are not recorded
+    let cancelled = client.create_sale(&third, &nft.address, &token_id, &30_000, &currency, &86_400);
+    client.cancel_sale(&cancelled, &third);
+    assert_eq!(client.get_sale_history(&nft.address, &token_id, &0, &10).len(), 2);
+
+    // The history is capped, dropping the oldest sales
+    let mut config = client.get_admin_config();
+    config.max_history_per_token = 0;
+    let result = client.try_propose_admin_config_update(&config, &admin);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+    config.max_history_per_token = 2;
+    apply_admin_config(&env, &client, &config, &admin);
+
+    let sale_three = client.create_sale(&third, &nft.address, &token_id, &30_000, &currency, &86_400);
+    client.buy(&sale_three, &first, &ANY_PRICE, &None);
+    let history = client.get_sale_history(&nft.address, &token_id, &0, &10);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap().transaction_id, sale_two);
+    assert_eq!(history.get(1).unwrap().transaction_id, sale_three);
+}
+
+#[test]
+fn test_admin_config_changes_wait_out_timelock() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let original = client.get_admin_config();
+    let mut config = original.clone();
+    config.max_royalty_percentage = 1_000;
+
+    let result = client.try_execute_admin_config_update(&admin);
+    assert_eq!(result, Err(Ok(SettlementError::NotFound)));
+    let result = client.try_propose_admin_config_update(&config, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    // Proposing stores the change without applying it
+    client.propose_admin_config_update(&config, &admin);
+    assert_eq!(count_events(&env, symbol_short!("cfg_prop")), 1);
+    assert_eq!(client.get_pending_admin_config(), Some((config.clone(), 1_000)));
+    assert_eq!(client.get_admin_config(), original);
+
+    // One second short of 48 hours is still too early
+    set_time(&env, 1_000 + ADMIN_CONFIG_TIMELOCK - 1);
+    let result = client.try_execute_admin_config_update(&admin);
+    assert_eq!(result, Err(Ok(SettlementError::CooldownActive)));
+    assert_eq!(client.get_admin_config(), original);
+
+    set_time(&env, 1_000 + ADMIN_CONFIG_TIMELOCK);
+    let result = client.try_execute_admin_config_update(&Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    client.execute_admin_config_update(&admin);
+    assert_eq!(count_events(&env, symbol_short!("cfg_appl")), 1);
+    assert_eq!(client.get_admin_config(), config);
+    assert_eq!(client.get_pending_admin_config(), None);
+    let result = client.try_execute_admin_config_update(&admin);
+    assert_eq!(result, Err(Ok(SettlementError::NotFound)));
+
+    // A new proposal restarts the clock from when it was made
+    let mut later = config.clone();
+    later.max_royalty_percentage = 2_000;
+    client.propose_admin_config_update(&config, &admin);
+    set_time(&env, 1_000 + ADMIN_CONFIG_TIMELOCK + 100);
+    client.propose_admin_config_update(&later, &admin);
+    set_time(&env, 1_000 + 2 * ADMIN_CONFIG_TIMELOCK);
+    let result = client.try_execute_admin_config_update(&admin);
+    assert_eq!(result, Err(Ok(SettlementError::CooldownActive)));
+    set_time(&env, 1_000 + 2 * ADMIN_CONFIG_TIMELOCK + 100);
+    client.execute_admin_config_update(&admin);
+    assert_eq!(client.get_admin_config(), later);
+}
+
+#[test]
+fn test_governance_vote_changes_config() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let governance_token = test_asset(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    fund(&env, &governance_token, &alice, 600);
+    fund(&env, &governance_token, &bob, 300);
+    fund(&env, &governance_token, &carol, 200);
+    let original = client.get_admin_config();
+    let mut config = original.clone();
+    config.max_royalty_percentage = 1_000;
+
+    // Proposals need governance to be enabled and a token balance
+    let result = client.try_propose_config_change(&config, &alice);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+    let result = client.try_set_governance_token(&governance_token.contract, &0, &admin);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+    client.set_governance_token(&governance_token.contract, &500, &admin);
+    assert_eq!(client.get_governance_token(), Some(governance_token.contract.clone()));
+    let result = client.try_set_governance_token(&test_asset(&env).contract, &500, &admin);
+    assert_eq!(result, Err(Ok(SettlementError::AlreadyExists)));
+    let result = client.try_propose_config_change(&config, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    // The admin can no longer change the config alone
+    let result = client.try_propose_admin_config_update(&config, &admin);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    let proposal_id = client.propose_config_change(&config, &alice);
+    assert_eq!(count_events(&env, symbol_short!("gov_prop")), 1);
+
+    // Voting power is capped by the voter's balance, one vote each, and the tokens voted
+    // with are locked so they can't be passed on to vote again
+    client.vote(&proposal_id, &alice, &true, &600);
+    assert_eq!(balance(&env, &governance_token, &alice), 0);
+    assert_eq!(client.try_vote(&proposal_id, &alice, &true, &600), Err(Ok(SettlementError::AlreadyExists)));
+    assert_eq!(client.try_vote(&proposal_id, &bob, &false, &400), Err(Ok(SettlementError::InsufficientFunds)));
+    client.vote(&proposal_id, &bob, &false, &300);
+    client.vote(&proposal_id, &carol, &false, &200);
+    let proposal = client.get_governance_proposal(&proposal_id);
+    assert_eq!((proposal.votes_for, proposal.votes_against), (600, 500));
+
+    // Nothing executes while voting is open, and no votes count after it closes
+    assert_eq!(client.try_execute_proposal(&proposal_id), Err(Ok(SettlementError::InvalidState)));
+    set_time(&env, proposal.voting_deadline);
+    let late = Address::generate(&env);
+    fund(&env, &governance_token, &late, 1_000);
+    assert_eq!(client.try_vote(&proposal_id, &late, &false, &1_000), Err(Ok(SettlementError::Expired)));
+
+    // Locked tokens come back once voting has closed
+    for (voter, locked) in [(&alice, 600), (&bob, 300), (&carol, 200)] {
+        assert_eq!(client.withdraw_vote_tokens(&proposal_id, voter), locked);
+        assert_eq!(balance(&env, &governance_token, voter), locked);
+    }
+    let result = client.try_withdraw_vote_tokens(&proposal_id, &alice);
+    assert_eq!(result, Err(Ok(SettlementError::InsufficientFunds)));
+    assert_eq!(client.try_vote(&proposal_id, &alice, &true, &600), Err(Ok(SettlementError::Expired)));
+
+    client.execute_proposal(&proposal_id);
+    assert_eq!(count_events(&env, symbol_short!("gov_exec")), 1);
+    assert_eq!(client.get_admin_config(), config);
+    assert!(client.get_governance_proposal(&proposal_id).executed);
+    assert_eq!(client.try_execute_proposal(&proposal_id), Err(Ok(SettlementError::InvalidState)));
+
+    // A tied vote does not pass
+    let rejected = client.propose_config_change(&original, &bob);
+    client.vote(&rejected, &bob, &true, &300);
+    client.vote(&rejected, &carol, &false, &200);
+    client.vote(&rejected, &alice, &false, &100);
+    set_time(&env, client.get_governance_proposal(&rejected).voting_deadline);
+    assert_eq!(client.try_execute_proposal(&rejected), Err(Ok(SettlementError::Unauthorized)));
+    assert_eq!(client.get_admin_config(), config);
+
+    // A single small holder can't pass a proposal nobody else turned out for
+    let unattended = client.propose_config_change(&original, &alice);
+    client.vote(&unattended, &alice, &true, &1);
+    let result = client.try_withdraw_vote_tokens(&unattended, &alice);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+    set_time(&env, client.get_governance_proposal(&unattended).voting_deadline);
+    assert_eq!(client.try_execute_proposal(&unattended), Err(Ok(SettlementError::InvalidState)));
+    assert_eq!(client.get_admin_config(), config);
+    assert_eq!(client.try_get_governance_proposal(&99), Err(Ok(SettlementError::NotFound)));
+}
+
+#[test]
+fn test_admin_config_survives_storage_migration() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let mut config = client.get_admin_config();
+    config.max_history_per_token = 7;
+    apply_admin_config(&env, &client, &config, &admin);
+    assert!(!client.is_admin_config_migrated());
+
+    let result = client.try_migrate_admin_cfg_to_persistent(&Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    client.migrate_admin_cfg_to_persistent(&admin);
+    assert!(client.is_admin_config_migrated());
+    assert_eq!(client.get_admin_config(), config);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().instance().has(&symbol_short!("admin_cfg")));
+        assert!(env.storage().persistent().has(&DataKey::AdminConfigV2));
+    });
+    let result = client.try_migrate_admin_cfg_to_persistent(&admin);
+    assert_eq!(result, Err(Ok(SettlementError::AlreadyExists)));
+
+    // Admin checks and config updates carry on against the migrated copy
+    config.max_price_history_per_token = 9;
+    apply_admin_config(&env, &client, &config, &admin);
+    assert_eq!(client.get_admin_config(), config);
+    let result = client.try_propose_admin_config_update(&config, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    // Rolling back restores the latest config to instance storage
+    client.rollback_admin_config_migration(&admin);
+    assert!(!client.is_admin_config_migrated());
+    assert_eq!(client.get_admin_config(), config);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&DataKey::AdminConfigV2));
+    });
+    let result = client.try_rollback_admin_config_migration(&admin);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+}
+
+#[test]
+fn test_cleanup_expired_sales() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let nft = nft_collection(&env);
+    let currency = test_asset(&env);
+
+    // 100 listings that lapse at 1_100 and 50 that run for a day, the active ones cheaper
+    let list = |price: i128, duration: u64| {
+        let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+        client.create_sale(&seller, &nft.address, &token_id, &price, &currency, &duration)
+    };
+    let expired: [u64; 100] = core::array::from_fn(|_| list(5_000, 100));
+    let active: [u64; 50] = core::array::from_fn(|_| list(8_000, 86_400));
+
+    let result = client.try_cleanup_expired_sales(&10, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    // Nothing has lapsed yet
+    assert_eq!(client.cleanup_expired_sales(&40, &admin), 0);
+
+    set_time(&env, 1_101);
+    assert_eq!(client.cleanup_expired_sales(&40, &admin), 40);
+    assert_eq!(count_events(&env, symbol_short!("sale_cln")), 1);
+    assert_eq!(client.cleanup_expired_sales(&40, &admin), 40);
+    assert_eq!(client.cleanup_expired_sales(&40, &admin), 20);
+    assert_eq!(client.cleanup_expired_sales(&40, &admin), 0);
+
+    for transaction_id in expired {
+        assert_eq!(client.try_get_sale(&transaction_id), Err(Ok(SettlementError::TransactionNotFound)));
+    }
+    for transaction_id in active {
+        assert_eq!(client.get_sale(&transaction_id).state, TransactionState::Pending);
+    }
+    assert_eq!(client.get_floor_price(&nft.address), Some(8_000));
+
+    // Cancelled listings go too, however long they had left
+    client.cancel_sale(&active[0], &seller);
+    assert_eq!(client.cleanup_expired_sales(&40, &admin), 1);
+    assert_eq!(client.try_get_sale(&active[0]), Err(Ok(SettlementError::TransactionNotFound)));
+    assert_eq!(client.get_sale(&active[1]).state, TransactionState::Pending);
+}
+
+#[test]
+fn test_floor_price_tracks_concurrent_listings() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let currency = test_asset(&env);
+    fund(&env, &currency, &buyer, 100_000);
+    let nft = nft_collection(&env);
+    let tokens: Vec<u64> = vec![&env,
+        mint_for_sale(&env, &nft, &seller, &client.address),
+        mint_for_sale(&env, &nft, &seller, &client.address),
+        mint_for_sale(&env, &nft, &seller, &client.address),
+        mint_for_sale(&env, &nft, &seller, &client.address),
+    ];
+    assert_eq!(client.get_floor_price(&nft.address), None);
+
+    set_time(&env, 100);
+    let high = client.create_sale(&seller, &nft.address, &tokens.get(0).unwrap(), &5_000, &currency, &86_400);
+    let low = client.create_sale(&seller, &nft.address, &tokens.get(1).unwrap(), &3_000, &currency, &86_400);
+    let mid = client.create_sale(&seller, &nft.address, &tokens.get(2).unwrap(), &4_000, &currency, &86_400);
+    assert_eq!(client.get_floor_price(&nft.address), Some(3_000));
+
+    // Delisting above the floor leaves it untouched
+    client.cancel_sale(&high, &seller);
+    assert_eq!(client.get_floor_price(&nft.address), Some(3_000));
+
+    // Cancelling the floor listing is picked up once the ledger closes
+    client.cancel_sale(&low, &seller);
+    assert_eq!(client.get_floor_price(&nft.address), Some(3_000));
+    set_time(&env, 105);
+    assert_eq!(client.get_floor_price(&nft.address), Some(4_000));
+
+    // A cheaper listing lowers the floor straight away
+    let cheap = client.create_sale(&seller, &nft.address, &tokens.get(3).unwrap(), &2_000, &currency, &3_600);
+    assert_eq!(client.get_floor_price(&nft.address), Some(2_000));
+
+    // Several delistings in one ledger share a single re-scan
+    set_time(&env, 110);
+    client.buy(&cheap, &buyer, &ANY_PRICE, &None);
+    client.cancel_sale(&mid, &seller);
+    assert_eq!(client.get_floor_price(&nft.address), Some(2_000));
+    set_time(&env, 115);
+    assert_eq!(client.get_floor_price(&nft.address), None);
+
+    // An expired floor listing drops out on its own
+    let short = client.create_sale(&seller, &nft.address, &tokens.get(0).unwrap(), &1_500, &currency, &3_600);
+    client.create_sale(&seller, &nft.address, &tokens.get(1).unwrap(), &6_000, &currency, &86_400);
+    assert_eq!(client.get_sale(&short).expires_at, 3_715);
+    assert_eq!(client.get_floor_price(&nft.address), Some(1_500));
+    set_time(&env, 3_716);
+    assert_eq!(client.get_floor_price(&nft.address), Some(6_000));
+
+    // Other collections are tracked separately
+    assert_eq!(client.get_floor_price(&nft_collection(&env).address), None);
+}
+
+#[test]
+fn test_collection_stats() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let other_buyer = Address::generate(&env);
+    let currency = test_asset(&env);
+    fund(&env, &currency, &buyer, 100_000);
+    fund(&env, &currency, &other_buyer, 100_000);
+    let nft = nft_collection(&env);
+    let stats = client.get_collection_stats(&nft.address);
+    assert_eq!((stats.total_volume, stats.total_sales, stats.unique_buyers, stats.unique_sellers), (0, 0, 0, 0));
+    assert_eq!(client.get_collection_ema_price(&nft.address), None);
+
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &10_000, &currency, &86_400);
+    client.buy(&sale_id, &buyer, &ANY_PRICE, &None);
+    let stats = client.get_collection_stats(&nft.address);
+    assert_eq!((stats.total_volume, stats.total_sales, stats.unique_buyers, stats.unique_sellers), (10_000, 1, 1, 1));
+    assert_eq!(client.get_collection_ema_price(&nft.address), Some(10_000));
+
+    // A repeat buyer from the same seller only adds volume
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &20_000, &currency, &86_400);
+    client.fund_sale(&sale_id, &buyer);
+    client.execute_sale(&sale_id, &buyer, &20_000);
+    let stats = client.get_collection_stats(&nft.address);
+    assert_eq!((stats.total_volume, stats.total_sales, stats.unique_buyers, stats.unique_sellers), (30_000, 2, 1, 1));
+
+    // A resale brings in a new buyer and a new seller
+    nft.set_approval_for_all(&client.address, &true, &buyer);
+    let sale_id = client.create_sale(&buyer, &nft.address, &token_id, &15_000, &currency, &86_400);
+    client.buy(&sale_id, &other_buyer, &ANY_PRICE, &None);
+    let stats = client.get_collection_stats(&nft.address);
+    assert_eq!((stats.total_volume, stats.total_sales, stats.unique_buyers, stats.unique_sellers), (45_000, 3, 2, 2));
+    assert_eq!(client.get_collection_ema_price(&nft.address), Some(11_400));
+
+    // Cancelled listings don't count
+    let sale_id = client.create_sale(&other_buyer, &nft.address, &token_id, &15_000, &currency, &86_400);
+    client.cancel_sale(&sale_id, &other_buyer);
+    assert_eq!(client.get_collection_stats(&nft.address).total_sales, 3);
+    assert_eq!(client.get_collection_stats(&nft_collection(&env).address).total_sales, 0);
+}
+
+#[test]
+fn test_price_history_evicts_oldest_sales() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let currency = test_asset(&env);
+    let nft = nft_collection(&env);
+    let token_id = mint_for_sale(&env, &nft, &first, &client.address);
+    nft.set_approval_for_all(&client.address, &true, &second);
+    fund(&env, &currency, &first, 10_000_000);
+    fund(&env, &currency, &second, 10_000_000);
+    assert_eq!(client.get_last_sale_price(&nft.address, &token_id), None);
+
+    // Pass the token back and forth 101 times at a rising price
+    let (mut seller, mut buyer) = (first, second);
+    for sale in 0..101u64 {
+        set_time(&env, 1_000 + sale * 10);
+        let price = 10_000 + sale as i128;
+        let sale_id = client.create_sale(&seller, &nft.address, &token_id, &price, &currency, &86_400);
+        client.buy(&sale_id, &buyer, &ANY_PRICE, &None);
+        assert_eq!(client.get_last_sale_price(&nft.address, &token_id), Some(price));
+        core::mem::swap(&mut seller, &mut buyer);
+    }
+
+    // The first sale has been evicted
+    let history = client.get_price_history(&nft.address, &token_id);
+    assert_eq!(history.len(), 100);
+    assert_eq!(history.first(), Some((10_001, 1_010)));
+    assert_eq!(history.last(), Some((10_100, 2_000)));
+    assert_eq!(client.get_price_history(&nft.address, &(token_id + 1)).len(), 0);
+}
+
+fn register_arbitrators(env: &Env, client: &MarketplaceSettlementClient) -> Vec<Address> {
+    register_weighted_arbitrators(env, client, &[100, 100, 100])
+}
+
+fn register_weighted_arbitrators(env: &Env, client: &MarketplaceSettlementClient, reputations: &[u64]) -> Vec<Address> {
+    let stake = test_asset(env);
+    let mut arbitrators = Vec::new(env);
+    for reputation in reputations {
+        arbitrators.push_back(staked_arbitrator(env, client, &stake, *reputation));
+    }
+    arbitrators
+}
+
+/// Register an arbitrator bonded with 10_000 of `stake`
+fn staked_arbitrator(env: &Env, client: &MarketplaceSettlementClient, stake: &Asset, reputation: u64) -> Address {
+    let arbitrator = Address::generate(env);
+    env.as_contract(&client.address, || {
+        DisputeResolutionManager::register_arbitrator(env, &arbitrator, reputation).unwrap();
+    });
+    fund(env, stake, &arbitrator, 10_000);
+    client.stake_as_arbitrator(&arbitrator, &10_000, stake);
+    arbitrator
+}
+
+/// Open a dispute on a funded sale whose payment is held by the contract
+fn disputed_sale(env: &Env, client: &MarketplaceSettlementClient) -> (u64, u64, Address, Address, Asset) {
+    let seller = Address::generate(env);
+    let buyer = Address::generate(env);
+    let currency = test_asset(env);
+    let nft = nft_collection(env);
+    let token_id = mint_for_sale(env, &nft, &seller, &client.address);
+
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &10_001, &currency, &86_400);
+    fund(env, &currency, &buyer, 10_001);
+    client.fund_sale(&sale_id, &buyer);
+
+    let dispute_id = client.initiate_dispute(&sale_id, &Bytes::from_slice(env, b"not delivered"), &None, &buyer);
+    (sale_id, dispute_id, seller, buyer, currency)
+}
+
+#[test]
+fn test_bids_and_disputes_bump_storage_ttl() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let config = StorageTTLConfig { auction_min_ttl: 200_000, dispute_min_ttl: 600_000 };
+    let result = client.try_set_storage_ttl_config(&config, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    let result = client.try_set_storage_ttl_config(&StorageTTLConfig { auction_min_ttl: 0, ..config.clone() }, &admin);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+    client.set_storage_ttl_config(&config, &admin);
+    assert_eq!(client.get_storage_ttl_config(), config);
+
+    let auction_id = create_english_auction(&env, &client, None);
+    let bidder = funded_bidder(&env, &client, auction_id);
+    client.place_bid(&auction_id, &bidder, &2_000, &None);
+    env.as_contract(&client.address, || {
+        assert_eq!(env.storage().instance().get_ttl(), 200_000);
+        assert_eq!(env.storage().persistent().get_ttl(&DataKey::BidEscrow(auction_id, bidder.clone())), 200_000);
+    });
+
+    register_arbitrators(&env, &client);
+    disputed_sale(&env, &client);
+    env.as_contract(&client.address, || {
+        assert_eq!(env.storage().instance().get_ttl(), 600_000);
+    });
+}
+
+#[test]
+fn test_dispute_refunds_buyer() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let arbitrators = register_arbitrators(&env, &client);
+    let (sale_id, dispute_id, seller, buyer, currency) = disputed_sale(&env, &client);
+
+    // Resolution can't be executed before the arbitrators have voted
+    let result = client.try_execute_dispute_resolution(&dispute_id, &buyer);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+
+    for arbitrator in arbitrators.iter() {
+        client.vote_on_dispute(&dispute_id, &arbitrator, &1);
+    }
+    close_appeal_window(&env);
+    client.execute_dispute_resolution(&dispute_id, &buyer);
+    assert_eq!(count_events(&env, symbol_short!("dsp_exec")), 1);
+    assert_eq!(client.get_sale(&sale_id).state, TransactionState::Resolved);
+    assert_eq!(balance(&env, &currency, &buyer), 10_001);
+    assert_eq!(balance(&env, &currency, &seller), 0);
+    assert_eq!(balance(&env, &currency, &client.address), 0);
+
+    // Escrow is only paid out once
+    let result = client.try_execute_dispute_resolution(&dispute_id, &buyer);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+}
+
+#[test]
+fn test_weighted_dispute_vote() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let arbitrators = register_weighted_arbitrators(&env, &client, &[300, 60, 60]);
+    let (sale_id, dispute_id, seller, buyer, currency) = disputed_sale(&env, &client);
+
+    // 360 of the 420 reputation needed (quorum of 3 at an average of 140) isn't enough
+    client.vote_on_dispute(&dispute_id, &arbitrators.get(0).unwrap(), &1);
+    client.vote_on_dispute(&dispute_id, &arbitrators.get(1).unwrap(), &0);
+    let result = client.try_execute_dispute_resolution(&dispute_id, &buyer);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+
+    // The high-reputation arbitrator outweighs the two votes against
+    client.vote_on_dispute(&dispute_id, &arbitrators.get(2).unwrap(), &0);
+    assert_eq!(count_events(&env, symbol_short!("dsp_rslv")), 1);
+    close_appeal_window(&env);
+    client.execute_dispute_resolution(&dispute_id, &buyer);
+    assert_eq!(client.get_sale(&sale_id).state, TransactionState::Resolved);
+    assert_eq!(balance(&env, &currency, &buyer), 10_001);
+    assert_eq!(balance(&env, &currency, &seller), 0);
+}
+
+#[test]
+fn test_weighted_dispute_threshold() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let arbitrators = register_weighted_arbitrators(&env, &client, &[300, 60, 60]);
+    env.as_contract(&client.address, || {
+        let config = DisputeConfig { arbitration_quorum: 2, ..DisputeConfig::default() };
+        DisputeResolutionManager::update_dispute_config(&env, &config, &admin).unwrap();
+    });
+    let (_sale_id, dispute_id, seller, buyer, currency) = disputed_sale(&env, &client);
+
+    // Two low-reputation votes fall short of the 280 needed
+    client.vote_on_dispute(&dispute_id, &arbitrators.get(1).unwrap(), &0);
+    client.vote_on_dispute(&dispute_id, &arbitrators.get(2).unwrap(), &0);
+    let outcome = env.as_contract(&client.address, || {
+        let dispute = DisputeStore::get(&env, dispute_id).unwrap();
+        DisputeResolutionManager::calculate_weighted_outcome(&env, &dispute).unwrap()
+    });
+    assert_eq!(outcome, WeightedVoteResult {
+        votes_for: 0,
+        votes_against: 2,
+        weighted_for: 0,
+        weighted_against: 120,
+        resolved: false,
+    });
+
+    client.vote_on_dispute(&dispute_id, &arbitrators.get(0).unwrap(), &1);
+    close_appeal_window(&env);
+    client.execute_dispute_resolution(&dispute_id, &buyer);
+    assert_eq!(balance(&env, &currency, &buyer), 10_001);
+    assert_eq!(balance(&env, &currency, &seller), 0);
+}
+
+fn arbitrator_reputation(env: &Env, client: &MarketplaceSettlementClient, arbitrator: &Address) -> u64 {
+    env.as_contract(&client.address, || {
+        let arbitrators: Map<Address, Arbitrator> = env.storage().instance().get(&symbol_short!("arbiters")).unwrap();
+        arbitrators.get(arbitrator.clone()).unwrap().reputation_score
+    })
+}
+
+#[test]
+fn test_stale_dispute_defaults_to_refund() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let arbitrators = register_arbitrators(&env, &client);
+    let (sale_id, dispute_id, seller, buyer, currency) = disputed_sale(&env, &client);
+    let voter = arbitrators.get(0).unwrap();
+    client.vote_on_dispute(&dispute_id, &voter, &0);
+
+    // Arbitrators have seven days to decide
+    set_time(&env, 605_799);
+    let result = client.try_finalize_stale_dispute(&dispute_id);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+
+    set_time(&env, 605_800);
+    client.finalize_stale_dispute(&dispute_id);
+    assert_eq!(count_events(&env, symbol_short!("dsp_rslv")), 1);
+    let result = client.try_finalize_stale_dispute(&dispute_id);
+    assert_eq!(result, Err(Ok(SettlementError::DisputeAlreadyResolved)));
+    let result = client.try_vote_on_dispute(&dispute_id, &arbitrators.get(1).unwrap(), &0);
+    assert_eq!(result, Err(Ok(SettlementError::DisputeAlreadyResolved)));
+
+    // Only the arbitrators who never voted are penalized
+    assert_eq!(arbitrator_reputation(&env, &client, &voter), 100);
+    assert_eq!(arbitrator_reputation(&env, &client, &arbitrators.get(1).unwrap()), 0);
+    assert_eq!(arbitrator_reputation(&env, &client, &arbitrators.get(2).unwrap()), 0);
+
+    // The buyer is protected by default
+    close_appeal_window(&env);
+    client.execute_dispute_resolution(&dispute_id, &buyer);
+    assert_eq!(client.get_sale(&sale_id).state, TransactionState::Resolved);
+    assert_eq!(balance(&env, &currency, &buyer), 10_001);
+    assert_eq!(balance(&env, &currency, &seller), 0);
+}
+
+#[test]
+fn test_unvoted_seller_dispute_refunds_recorded_buyer() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    register_arbitrators(&env, &client);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let currency = test_asset(&env);
+    let nft = nft_collection(&env);
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &10_001, &currency, &86_400);
+    fund(&env, &currency, &buyer, 10_001);
+    client.fund_sale(&sale_id, &buyer);
+
+    // Nobody outside the sale can open a dispute and wait out the vote
+    let reason = Bytes::from_slice(&env, b"no response");
+    let result = client.try_initiate_dispute(&sale_id, &reason, &None, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    // The default ruling pays the buyer of record, whoever opened the dispute
+    let dispute_id = client.initiate_dispute(&sale_id, &reason, &None, &seller);
+    set_time(&env, 605_800);
+    client.finalize_stale_dispute(&dispute_id);
+    close_appeal_window(&env);
+    client.execute_dispute_resolution(&dispute_id, &seller);
+    assert_eq!(balance(&env, &currency, &buyer), 10_001);
+    assert_eq!(balance(&env, &currency, &seller), 0);
+}
+
+#[test]
+fn test_stale_dispute_cancels_when_configured() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    register_arbitrators(&env, &client);
+    env.as_contract(&client.address, || {
+        let mut config = DisputeConfig { voting_period: 3_600, ..DisputeConfig::default() };
+        config.auto_resolution_default = DISPUTE_RESOLUTION_RELEASE_TO_SELLER;
+        let result = DisputeResolutionManager::update_dispute_config(&env, &config, &admin);
+        assert_eq!(result, Err(SettlementError::InvalidAmount));
+        config.auto_resolution_default = DISPUTE_RESOLUTION_CANCEL_TRANSACTION;
+        DisputeResolutionManager::update_dispute_config(&env, &config, &admin).unwrap();
+    });
+    let (sale_id, dispute_id, seller, buyer, currency) = disputed_sale(&env, &client);
+
+    set_time(&env, 4_600);
+    client.finalize_stale_dispute(&dispute_id);
+    close_appeal_window(&env);
+    client.execute_dispute_resolution(&dispute_id, &buyer);
+    assert_eq!(count_events(&env, symbol_short!("dsp_exec")), 1);
+    assert_eq!(client.get_sale(&sale_id).state, TransactionState::Cancelled);
+    assert_eq!(balance(&env, &currency, &buyer), 10_001);
+    assert_eq!(balance(&env, &currency, &seller), 0);
+    assert_eq!(balance(&env, &currency, &client.address), 0);
+
+    let result = client.try_execute_dispute_resolution(&dispute_id, &buyer);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+}
+
+#[test]
+fn test_arbitrator_stake_slashing() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let stake = test_asset(&env);
+    let voter = staked_arbitrator(&env, &client, &stake, 100);
+    let absent = staked_arbitrator(&env, &client, &stake, 100);
+
+    // Stakes are all held in the first staked asset
+    let result = client.try_stake_as_arbitrator(&voter, &100, &test_asset(&env));
+    assert_eq!(result, Err(Ok(SettlementError::InvalidCurrency)));
+
+    // Arbitrators without a bond are never put on a panel
+    let unbonded = Address::generate(&env);
+    env.as_contract(&client.address, || {
+        DisputeResolutionManager::register_arbitrator(&env, &unbonded, 100).unwrap();
+    });
+    let (_sale_id, dispute_id, _seller, _buyer, _currency) = disputed_sale(&env, &client);
+    let panel = env.as_contract(&client.address, || DisputeStore::get(&env, dispute_id).unwrap().arbitrators);
+    assert_eq!(panel, vec![&env, voter.clone(), absent.clone()]);
+
+    client.vote_on_dispute(&dispute_id, &voter, &1);
+    let result = client.try_unstake_arbitrator(&voter);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+
+    // Timing out slashes 5% of the stake of whoever didn't vote
+    set_time(&env, 605_800);
+    client.finalize_stale_dispute(&dispute_id);
+    assert_eq!(count_events(&env, symbol_short!("arb_slash")), 1);
+    assert_eq!(client.get_arbitrator_stake(&voter), 10_000);
+    assert_eq!(client.get_arbitrator_stake(&absent), 9_500);
+    assert_eq!(client.get_penalty_pool_balance(), 500);
+
+    // Both arbitrators can recover what is left of their stake
+    assert_eq!(client.unstake_arbitrator(&absent), 9_500);
+    assert_eq!(client.unstake_arbitrator(&voter), 10_000);
+    assert_eq!(balance(&env, &stake, &absent), 9_500);
+    assert_eq!(balance(&env, &stake, &voter), 10_000);
+    assert_eq!(client.get_arbitrator_stake(&absent), 0);
+    let result = client.try_unstake_arbitrator(&absent);
+    assert_eq!(result, Err(Ok(SettlementError::InsufficientFunds)));
+
+    let result = client.try_withdraw_penalty_pool(&voter);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    assert_eq!(client.withdraw_penalty_pool(&admin), 500);
+    assert_eq!(balance(&env, &stake, &admin), 500);
+    assert_eq!(client.get_penalty_pool_balance(), 0);
+    let result = client.try_withdraw_penalty_pool(&admin);
+    assert_eq!(result, Err(Ok(SettlementError::InsufficientFunds)));
+}
+
+#[test]
+fn test_dispute_appeal_to_senior_panel() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let stake = test_asset(&env);
+    let mut arbitrators = Vec::new(&env);
+    for _ in 0..3 {
+        arbitrators.push_back(staked_arbitrator(&env, &client, &stake, 100));
+    }
+    let (_sale_id, dispute_id, seller, buyer, currency) = disputed_sale(&env, &client);
+
+    // Only a ruling can be appealed
+    let result = client.try_file_appeal(&dispute_id, &seller);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+    for arbitrator in arbitrators.iter() {
+        client.vote_on_dispute(&dispute_id, &arbitrator, &1);
+    }
+
+    let result = client.try_file_appeal(&dispute_id, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    let result = client.try_file_appeal(&dispute_id, &seller);
+    assert_eq!(result, Err(Ok(SettlementError::InsufficientArbitrators)));
+
+    let seniors = vec![
+        &env,
+        staked_arbitrator(&env, &client, &stake, 900),
+        staked_arbitrator(&env, &client, &stake, 900),
+    ];
+    client.file_appeal(&dispute_id, &seller);
+    assert_eq!(count_events(&env, symbol_short!("dsp_appl")), 1);
+    let dispute = env.as_contract(&client.address, || DisputeStore::get(&env, dispute_id).unwrap());
+    assert_eq!(dispute.resolution, DISPUTE_RESOLUTION_NOT_RESOLVED);
+    assert_eq!(dispute.appeal_filed_by, Some(seller.clone()));
+    assert_eq!(dispute.arbitrators.len(), 2);
+    assert!(seniors.iter().all(|senior| dispute.arbitrators.contains(senior)));
+
+    // The original panel is off the case and the ruling can't be executed meanwhile
+    let result = client.try_vote_on_dispute(&dispute_id, &arbitrators.get(0).unwrap(), &1);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    let result = client.try_execute_dispute_resolution(&dispute_id, &buyer);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+    let result = client.try_file_appeal(&dispute_id, &buyer);
+    assert_eq!(result, Err(Ok(SettlementError::AlreadyExists)));
+    assert_eq!(client.get_arbitrator_stats(&arbitrators.get(0).unwrap()).total_appealed, 1);
+    assert_eq!(client.get_arbitrator_stats(&seniors.get(0).unwrap()).total_assigned, 1);
+
+    // The senior panel's ruling is final and can be executed straight away
+    for senior in seniors.iter() {
+        client.vote_on_dispute(&dispute_id, &senior, &0);
+    }
+    let result = client.try_file_appeal(&dispute_id, &buyer);
+    assert_eq!(result, Err(Ok(SettlementError::AlreadyExists)));
+    client.execute_dispute_resolution(&dispute_id, &seller);
+    assert_eq!(balance(&env, &currency, &seller), 10_001);
+    assert_eq!(balance(&env, &currency, &buyer), 0);
+}
+
+#[test]
+fn test_dispute_appeal_window_expires() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let arbitrators = register_arbitrators(&env, &client);
+    let (_sale_id, dispute_id, seller, buyer, currency) = disputed_sale(&env, &client);
+    for arbitrator in arbitrators.iter() {
+        client.vote_on_dispute(&dispute_id, &arbitrator, &1);
+    }
+
+    // The ruling waits out the 48 hour appeal window
+    set_time(&env, 173_800);
+    let result = client.try_execute_dispute_resolution(&dispute_id, &buyer);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+
+    set_time(&env, 173_801);
+    let result = client.try_file_appeal(&dispute_id, &seller);
+    assert_eq!(result, Err(Ok(SettlementError::Expired)));
+    client.execute_dispute_resolution(&dispute_id, &buyer);
+    assert_eq!(balance(&env, &currency, &buyer), 10_001);
+}
+
+#[test]
+fn test_dispute_evidence_from_both_parties() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let arbitrators = register_arbitrators(&env, &client);
+    let (_sale_id, dispute_id, seller, buyer, _currency) = disputed_sale(&env, &client);
+    let receipt = Bytes::from_slice(&env, b"ipfs://receipt");
+    let photo = Bytes::from_slice(&env, b"ipfs://photo");
+
+    for _ in 0..10 {
+        client.submit_evidence(&dispute_id, &buyer, &receipt, &false);
+    }
+    let result = client.try_submit_evidence(&dispute_id, &buyer, &receipt, &false);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+
+    // Each party can only add to its own side
+    client.submit_evidence(&dispute_id, &seller, &photo, &true);
+    let result = client.try_submit_evidence(&dispute_id, &seller, &photo, &false);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    let result = client.try_submit_evidence(&dispute_id, &buyer, &photo, &true);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    let result = client.try_submit_evidence(&dispute_id, &arbitrators.get(0).unwrap(), &photo, &true);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    let (initiator_evidence, respondent_evidence) = client.get_all_evidence(&dispute_id);
+    assert_eq!(initiator_evidence.len(), 10);
+    assert_eq!(initiator_evidence.get(0), Some(receipt));
+    assert_eq!(respondent_evidence, vec![&env, photo.clone()]);
+
+    set_time(&env, 605_801);
+    let result = client.try_submit_evidence(&dispute_id, &seller, &photo, &true);
+    assert_eq!(result, Err(Ok(SettlementError::Expired)));
+}
+
+#[test]
+fn test_top_arbitrators_ranking() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let arbitrators = register_weighted_arbitrators(&env, &client, &[100, 100, 100, 100]);
+    let (a, b, c, d) = (
+        arbitrators.get(0).unwrap(),
+        arbitrators.get(1).unwrap(),
+        arbitrators.get(2).unwrap(),
+        arbitrators.get(3).unwrap(),
+    );
+
+    // The buyer wins the first dispute and the seller the second
+    let (_, first, _, _, _) = disputed_sale(&env, &client);
+    set_time(&env, 1_600);
+    client.vote_on_dispute(&first, &a, &1);
+    client.vote_on_dispute(&first, &b, &1);
+    client.vote_on_dispute(&first, &c, &0);
+    let (_, second, _, _, _) = disputed_sale(&env, &client);
+    set_time(&env, 2_800);
+    client.vote_on_dispute(&second, &a, &0);
+    client.vote_on_dispute(&second, &b, &1);
+    client.vote_on_dispute(&second, &c, &0);
+
+    let expected = ArbitratorStats {
+        total_assigned: 2,
+        total_voted: 2,
+        total_correct: 2,
+        total_appealed: 0,
+        average_resolution_time_secs: 900,
+        last_active: 2_800,
+    };
+    assert_eq!(client.get_arbitrator_stats(&a), expected);
+    let stats = client.get_arbitrator_stats(&d);
+    assert_eq!((stats.total_assigned, stats.total_voted, stats.average_resolution_time_secs), (2, 0, 0));
+    let result = client.try_get_arbitrator_stats(&Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::NotFound)));
+
+    let ranking = client.get_top_arbitrators(&10);
+    let correct: Vec<u64> = Vec::from_iter(&env, ranking.iter().map(|(_, stats)| stats.total_correct));
+    assert_eq!(correct, vec![&env, 2, 1, 1, 0]);
+    assert_eq!(ranking.get(0).unwrap().0, a);
+    assert_eq!(ranking.get(3).unwrap().0, d);
+
+    let top = client.get_top_arbitrators(&2);
+    assert_eq!(top.len(), 2);
+    assert_eq!(top.get(0).unwrap(), (a, expected));
+    assert!(top.get(1).unwrap().0 == b || top.get(1).unwrap().0 == c);
+}
+
+#[test]
+fn test_evidence_hash_verification() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    register_arbitrators(&env, &client);
+    let (_sale_id, dispute_id, seller, buyer, _currency) = disputed_sale(&env, &client);
+
+    let document = Bytes::from_slice(&env, b"shipping label #4821");
+    let hash: BytesN<32> = env.crypto().sha256(&document).into();
+    client.submit_evidence_with_hash(&dispute_id, &document, &hash, &buyer);
+
+    // Tampered data or a wrong hash is rejected
+    let tampered = Bytes::from_slice(&env, b"shipping label #4822");
+    let result = client.try_submit_evidence_with_hash(&dispute_id, &tampered, &hash, &seller);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidSignature)));
+    let wrong_hash = BytesN::from_array(&env, &[7; 32]);
+    let result = client.try_submit_evidence_with_hash(&dispute_id, &document, &wrong_hash, &seller);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidSignature)));
+    let result = client.try_submit_evidence_with_hash(&dispute_id, &document, &hash, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    let tampered_hash: BytesN<32> = env.crypto().sha256(&tampered).into();
+    client.submit_evidence_with_hash(&dispute_id, &tampered, &tampered_hash, &seller);
+
+    let records = client.get_evidence_records(&dispute_id);
+    assert_eq!(records.len(), 2);
+    let first = records.get(0).unwrap();
+    assert_eq!((first.submitter, first.hash, first.data), (buyer, hash, document));
+    assert_eq!(records.get(1).unwrap().submitter, seller);
+}
+
+#[test]
+fn test_dispute_releases_to_seller() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let arbitrators = register_arbitrators(&env, &client);
+    let (sale_id, dispute_id, seller, buyer, currency) = disputed_sale(&env, &client);
+
+    // Each vote has to be signed by the arbitrator casting it
+    for arbitrator in arbitrators.iter() {
+        client.vote_on_dispute(&dispute_id, &arbitrator, &0);
+        assert!(env.auths().iter().any(|(address, _)| *address == arbitrator));
+    }
+    close_appeal_window(&env);
+    client.execute_dispute_resolution(&dispute_id, &seller);
+    assert_eq!(client.get_sale(&sale_id).state, TransactionState::Resolved);
+    assert_eq!(balance(&env, &currency, &seller), 10_001);
+    assert_eq!(balance(&env, &currency, &buyer), 0);
+}
+
+#[test]
+fn test_only_sale_parties_open_disputes() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let arbitrators = register_arbitrators(&env, &client);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let currency = test_asset(&env);
+    let nft = nft_collection(&env);
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &10_001, &currency, &86_400);
+    fund(&env, &currency, &buyer, 10_001);
+    client.fund_sale(&sale_id, &buyer);
+
+    // A stranger can't open a dispute to claim the buyer's escrow
+    let reason = Bytes::from_slice(&env, b"not delivered");
+    let stranger = Address::generate(&env);
+    let result = client.try_initiate_dispute(&sale_id, &reason, &None, &stranger);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    // The seller can open one too, and a panel ruling against them refunds the recorded buyer
+    let dispute_id = client.initiate_dispute(&sale_id, &reason, &None, &seller);
+    client.submit_evidence(&dispute_id, &buyer, &Bytes::from_slice(&env, b"ipfs://receipt"), &true);
+    for arbitrator in arbitrators.iter() {
+        client.vote_on_dispute(&dispute_id, &arbitrator, &0);
+    }
+    close_appeal_window(&env);
+    client.execute_dispute_resolution(&dispute_id, &seller);
+    assert_eq!(balance(&env, &currency, &buyer), 10_001);
+    assert_eq!(balance(&env, &currency, &seller), 0);
+}
+
+#[test]
+fn test_dispute_splits_funds() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    register_arbitrators(&env, &client);
+
+    // A sale splits 50/50, with the odd unit going to the seller
+    let (sale_id, dispute_id, seller, buyer, currency) = disputed_sale(&env, &client);
+    env.as_contract(&client.address, || {
+        DisputeResolutionManager::force_resolve_dispute(&env, dispute_id, DISPUTE_RESOLUTION_SPLIT_FUNDS, &admin).unwrap();
+    });
+    close_appeal_window(&env);
+    client.execute_dispute_resolution(&dispute_id, &admin);
+    assert_eq!(client.get_sale(&sale_id).state, TransactionState::Resolved);
+    assert_eq!(balance(&env, &currency, &buyer), 5_000);
+    assert_eq!(balance(&env, &currency, &seller), 5_001);
+
+    // An auction splits the leading bid out of the bidder's escrow
+    let auction_id = create_english_auction(&env, &client, None);
+    let auction = client.get_auction(&auction_id);
+    let bidder = funded_bidder(&env, &client, auction_id);
+    set_time(&env, 174_140);
+    client.place_bid(&auction_id, &bidder, &2_200, &None);
+
+    let dispute_id = env.as_contract(&client.address, || {
+        let reason = Bytes::from_slice(&env, b"counterfeit");
+        let dispute_id = DisputeResolutionManager::initiate_dispute(&env, 99, Some(auction_id), &bidder, &reason, None).unwrap();
+        DisputeResolutionManager::force_resolve_dispute(&env, dispute_id, DISPUTE_RESOLUTION_SPLIT_FUNDS, &admin).unwrap();
+        dispute_id
+    });
+    close_appeal_window(&env);
+    client.execute_dispute_resolution(&dispute_id, &admin);
+    assert_eq!(client.get_auction(&auction_id).state, TransactionState::Resolved);
+    assert_eq!(client.get_bid_escrow(&auction_id, &bidder), 0);
+    assert_eq!(balance(&env, &auction.currency, &bidder), 1_000_000 - 1_100);
+    assert_eq!(balance(&env, &auction.currency, &auction.seller), 1_100);
+}
+
+#[test]
+fn test_place_bid_rate_limit() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    assert_eq!(client.get_rate_limit_config(), RateLimitConfig { max_calls: 10, window_seconds: 60 });
+
+    let config = RateLimitConfig { max_calls: 2, window_seconds: 100 };
+    let result = client.try_update_rate_limit_config(&config, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    let result = client.try_update_rate_limit_config(&RateLimitConfig { max_calls: 0, window_seconds: 100 }, &admin);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+    client.update_rate_limit_config(&config, &admin);
+    assert!(env.auths().iter().any(|(address, _)| *address == admin));
+
+    let auction_id = create_english_auction(&env, &client, None);
+    let bidder = funded_bidder(&env, &client, auction_id);
+    let rival = funded_bidder(&env, &client, auction_id);
+
+    set_time(&env, 1_340);
+    client.place_bid(&auction_id, &bidder, &2_200, &None);
+    set_time(&env, 1_377);
+    client.place_bid(&auction_id, &bidder, &2_450, &None);
+
+    // A third call inside the window is refused, while other callers are unaffected
+    set_time(&env, 1_391);
+    let result = client.try_place_bid(&auction_id, &bidder, &2_730, &None);
+    assert_eq!(result, Err(Ok(SettlementError::CooldownActive)));
+    client.place_bid(&auction_id, &rival, &2_730, &None);
+
+    // The window resets once it has passed
+    set_time(&env, 1_452);
+    client.place_bid(&auction_id, &bidder, &2_980, &None);
+    assert_eq!(client.get_auction(&auction_id).highest_bidder, Some(bidder));
+}
+
+#[test]
+fn test_banned_address_cannot_trade() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let auction_id = create_english_auction(&env, &client, None);
+    let banned = funded_bidder(&env, &client, auction_id);
+    let currency = client.get_auction(&auction_id).currency;
+
+    let result = client.try_ban_address(&banned, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    client.ban_address(&banned, &admin);
+    assert_eq!(count_events(&env, symbol_short!("addr_ban")), 1);
+    assert!(client.is_banned(&banned));
+    assert_eq!(client.try_ban_address(&banned, &admin), Err(Ok(SettlementError::AlreadyExists)));
+
+    set_time(&env, 1_340);
+    let result = client.try_place_bid(&auction_id, &banned, &2_200, &None);
+    assert_eq!(result, Err(Ok(SettlementError::AddressBanned)));
+    let nft = Address::generate(&env);
+    let result = client.try_create_sale(&banned, &nft, &1, &10_000, &currency, &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::AddressBanned)));
+    let result = client.try_make_offer(&banned, &nft, &1, &10_000, &currency, &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::AddressBanned)));
+    let result = client.try_create_auction(
+        &banned,
+        &nft,
+        &1,
+        &2_000,
+        &1_500,
+        &3_600,
+        &100,
+        &AuctionType::English,
+        &currency,
+        &AuctionOptions { buyout_price: None, allowed_bidders: None, dutch_half_life: None, start_time: None },
+    );
+    assert_eq!(result, Err(Ok(SettlementError::AddressBanned)));
+
+    // Lifting the ban restores access
+    client.unban_address(&banned, &admin);
+    assert_eq!(count_events(&env, symbol_short!("addr_unbn")), 1);
+    assert!(!client.is_banned(&banned));
+    assert_eq!(client.try_unban_address(&banned, &admin), Err(Ok(SettlementError::NotFound)));
+    client.place_bid(&auction_id, &banned, &2_200, &None);
+    assert_eq!(client.get_auction(&auction_id).highest_bidder, Some(banned));
+}
+
+#[test]
+fn test_circuit_breaker_halts_large_settlements() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let mut config = client.get_admin_config();
+    config.circuit_breaker_threshold = 1_000_000;
+    config.max_single_transaction_amount = 500_000;
+    let result = client.try_propose_admin_config_update(&config, &admin);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+    config.circuit_breaker_threshold = 50_000;
+    config.max_single_transaction_amount = 500_000;
+    apply_admin_config(&env, &client, &config, &admin);
+    let start = env.ledger().timestamp();
+
+    // Settlements at or below the threshold go through
+    let small = create_english_auction(&env, &client, None);
+    let large = create_english_auction(&env, &client, None);
+    let bidder = funded_bidder(&env, &client, small);
+    fund(&env, &client.get_auction(&large).currency, &bidder, 1_000_000);
+    set_time(&env, start + 340);
+    client.place_bid(&small, &bidder, &50_000, &None);
+    set_time(&env, start + 517);
+    client.place_bid(&large, &bidder, &60_000, &None);
+    set_time(&env, start + 3_700);
+    client.end_auction(&small, &admin, &0);
+    assert_eq!(client.get_auction(&small).state, TransactionState::Executed);
+
+    // Amounts past the hard ceiling are rejected without tripping the breaker
+    let seller = Address::generate(&env);
+    let nft = nft_collection(&env);
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let currency = test_asset(&env);
+    let buyer = Address::generate(&env);
+    fund(&env, &currency, &buyer, 1_000_000);
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &600_000, &currency, &86_400);
+    client.fund_sale(&sale_id, &buyer);
+    let result = client.try_execute_sale(&sale_id, &buyer, &600_000);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+    assert!(!client.is_circuit_breaker_tripped());
+
+    // A winning bid above the threshold trips the breaker and leaves the auction unsettled
+    client.end_auction(&large, &admin, &0);
+    assert_eq!(count_events(&env, symbol_short!("cb_trip")), 1);
+    assert!(client.is_circuit_breaker_tripped());
+    assert_eq!(client.get_auction(&large).state, TransactionState::Pending);
+
+    // Nothing settles until the admin resets it
+    let result = client.try_end_auction(&large, &admin, &0);
+    assert_eq!(result, Err(Ok(SettlementError::CircuitBreakerTripped)));
+    let result = client.try_reset_circuit_breaker(&Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    client.reset_circuit_breaker(&admin);
+    assert!(!client.is_circuit_breaker_tripped());
+    assert_eq!(client.try_reset_circuit_breaker(&admin), Err(Ok(SettlementError::InvalidState)));
+
+    // A sale above the threshold trips it as well instead of executing
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &80_000, &currency, &86_400);
+    client.fund_sale(&sale_id, &buyer);
+    let result = client.execute_sale(&sale_id, &buyer, &80_000);
+    assert!(!result.success);
+    assert!(client.is_circuit_breaker_tripped());
+    assert_eq!(client.get_sale(&sale_id).state, TransactionState::Funded);
+    assert_eq!(client.get_sale_escrow(&sale_id), 80_000);
+
+    // Direct purchases are held to the same limits
+    client.reset_circuit_breaker(&admin);
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &600_000, &currency, &86_400);
+    let result = client.try_buy(&sale_id, &buyer, &ANY_PRICE, &None);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+    assert!(!client.is_circuit_breaker_tripped());
+
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let sale_id = client.create_sale(&seller, &nft.address, &token_id, &80_000, &currency, &86_400);
+    let paid = balance(&env, &currency, &buyer);
+    let result = client.buy(&sale_id, &buyer, &ANY_PRICE, &None);
+    assert!(!result.success);
+    assert!(client.is_circuit_breaker_tripped());
+    assert_eq!(client.get_sale(&sale_id).state, TransactionState::Pending);
+    assert_eq!(balance(&env, &currency, &buyer), paid);
+}
+
+#[test]
+fn test_nft_whitelist() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let currency = test_asset(&env);
+    let nft = nft_collection(&env);
+    let options = AuctionOptions { buyout_price: None, allowed_bidders: None, dutch_half_life: None, start_time: None };
+
+    // Any collection can be listed while the whitelist is bypassed
+    assert!(client.get_admin_config().bypass_whitelist);
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    client.create_sale(&seller, &nft.address, &token_id, &10_000, &currency, &3_600);
+
+    let mut config = client.get_admin_config();
+    config.bypass_whitelist = false;
+    apply_admin_config(&env, &client, &config, &admin);
+
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let result = client.try_create_sale(&seller, &nft.address, &token_id, &10_000, &currency, &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::AssetNotSupported)));
+    let result = client.try_create_auction(
+        &seller, &nft.address, &token_id, &2_000, &1_500, &3_600, &100, &AuctionType::English, &currency, &options,
+    );
+    assert_eq!(result, Err(Ok(SettlementError::AssetNotSupported)));
+    let buyer = Address::generate(&env);
+    let result = client.try_make_offer(&buyer, &nft.address, &token_id, &5_000, &currency, &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::AssetNotSupported)));
+
+    let result = client.try_add_allowed_nft_contract(&nft.address, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    client.add_allowed_nft_contract(&nft.address, &admin);
+    assert_eq!(client.get_allowed_nft_contracts(), vec![&env, nft.address.clone()]);
+    let result = client.try_add_allowed_nft_contract(&nft.address, &admin);
+    assert_eq!(result, Err(Ok(SettlementError::AlreadyExists)));
+    client.create_auction(
+        &seller, &nft.address, &token_id, &2_000, &1_500, &3_600, &100, &AuctionType::English, &currency, &options,
+    );
+
+    client.remove_allowed_nft_contract(&nft.address, &admin);
+    let result = client.try_remove_allowed_nft_contract(&nft.address, &admin);
+    assert_eq!(result, Err(Ok(SettlementError::NotFound)));
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let result = client.try_create_sale(&seller, &nft.address, &token_id, &10_000, &currency, &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::AssetNotSupported)));
+}
+
+#[test]
+fn test_emergency_pause() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let auction_id = create_english_auction(&env, &client, None);
+    let bidder = funded_bidder(&env, &client, auction_id);
+    let currency = client.get_auction(&auction_id).currency;
+    set_time(&env, 1_340);
+    client.place_bid(&auction_id, &bidder, &2_200, &None);
+
+    let result = client.try_emergency_pause(&Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    client.emergency_pause(&admin);
+    assert_eq!(count_events(&env, symbol_short!("emrg_paus")), 1);
+    assert!(client.is_emergency_paused());
+    assert_eq!(client.try_emergency_pause(&admin), Err(Ok(SettlementError::InvalidState)));
+
+    // State-changing calls are halted
+    set_time(&env, 1_517);
+    let result = client.try_place_bid(&auction_id, &bidder, &2_450, &None);
+    assert_eq!(result, Err(Ok(SettlementError::EmergencyPaused)));
+    let result = client.try_withdraw_bid_escrow(&auction_id, &bidder);
+    assert_eq!(result, Err(Ok(SettlementError::EmergencyPaused)));
+    let nft = Address::generate(&env);
+    let result = client.try_create_sale(&bidder, &nft, &1, &10_000, &currency, &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::EmergencyPaused)));
+    let result = client.try_make_offer(&bidder, &nft, &1, &10_000, &currency, &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::EmergencyPaused)));
+    set_time(&env, 4_700);
+    let result = client.try_end_auction(&auction_id, &admin, &0);
+    assert_eq!(result, Err(Ok(SettlementError::EmergencyPaused)));
+
+    // Queries keep working
+    assert_eq!(client.get_auction(&auction_id).highest_bid, 2_200);
+    assert_eq!(client.get_bid_escrow(&auction_id, &bidder), 2_200);
+
+    // Unpausing waits out a 24 hour cooldown from the pause
+    set_time(&env, 1_340 + 86_399);
+    let result = client.try_emergency_unpause(&admin);
+    assert_eq!(result, Err(Ok(SettlementError::CooldownActive)));
+    set_time(&env, 1_340 + 86_400);
+    let result = client.try_emergency_unpause(&Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    client.emergency_unpause(&admin);
+    assert_eq!(count_events(&env, symbol_short!("emrg_unps")), 1);
+    assert!(!client.is_emergency_paused());
+    assert_eq!(client.try_emergency_unpause(&admin), Err(Ok(SettlementError::InvalidState)));
+
+    client.end_auction(&auction_id, &admin, &0);
+    assert_eq!(client.get_auction(&auction_id).state, TransactionState::Executed);
+}
+
+#[test]
+fn test_bids_require_funds() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let auction_id = create_english_auction(&env, &client, None);
+    let currency = client.get_auction(&auction_id).currency;
+
+    let bidder = Address::generate(&env);
+    fund(&env, &currency, &bidder, 2_500);
+
+    set_time(&env, 1_340);
+    let result = client.try_place_bid(&auction_id, &bidder, &3_000, &None);
+    assert_eq!(result, Err(Ok(SettlementError::InsufficientFunds)));
+
+    client.place_bid(&auction_id, &bidder, &2_200, &None);
+    assert_eq!(balance(&env, &currency, &bidder), 300);
+    assert_eq!(balance(&env, &currency, &client.address), 2_200);
+
+    // Outbid funds come back to the bidder's account
+    set_time(&env, 1_910);
+    client.place_bid(&auction_id, &funded_bidder(&env, &client, auction_id), &2_650, &None);
+    client.withdraw_bid_escrow(&auction_id, &bidder);
+    assert_eq!(balance(&env, &currency, &bidder), 2_500);
+}
+
+#[test]
+fn test_nft_ownership_is_enforced() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let nft = nft_collection(&env);
+    let owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let token_id = mint_for_sale(&env, &nft, &owner, &client.address);
+    let currency = test_asset(&env);
+
+    // Only the holder can list the token or accept offers on it
+    let result = client.try_create_sale(&stranger, &nft.address, &token_id, &10_000, &currency, &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    let buyer = Address::generate(&env);
+    fund(&env, &currency, &buyer, 10_000);
+    let offer_id = client.make_offer(&buyer, &nft.address, &token_id, &10_000, &currency, &3_600);
+    let result = client.try_accept_offer(&offer_id, &stranger);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    // Without marketplace approval the transfer is refused by the NFT contract
+    nft.set_approval_for_all(&client.address, &false, &owner);
+    let result = client.try_accept_offer(&offer_id, &owner);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    assert!(holds(&nft, &owner, token_id));
+
+    // Unknown tokens and non-NFT addresses are rejected too
+    let result = client.try_create_sale(&owner, &nft.address, &999, &10_000, &currency, &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::NotFound)));
+    let result = client.try_create_sale(&owner, &Address::generate(&env), &token_id, &10_000, &currency, &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+}
+
+/// Sign `listing` with the seller's off-chain key
+fn sign_listing(env: &Env, client: &MarketplaceSettlementClient, key: &SigningKey, mut listing: OffChainListing) -> OffChainListing {
+    let message = client.get_listing_message(&listing);
+    let mut buffer = [0u8; 512];
+    let bytes = &mut buffer[..message.len() as usize];
+    message.copy_into_slice(bytes);
+    listing.signature = Bytes::from_slice(env, &key.sign(bytes).to_bytes());
+    listing
+}
+
+#[test]
+fn test_off_chain_listing() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let currency = test_asset(&env);
+    let nft = nft_collection(&env);
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    fund(&env, &currency, &buyer, 10_000);
+
+    let key = SigningKey::from_bytes(&[1; 32]);
+    let listing = OffChainListing {
+        seller: seller.clone(),
+        nft_address: nft.address.clone(),
+        token_id,
+        price: 10_000,
+        currency: currency.clone(),
+        expiry: 5_000,
+        nonce: 7,
+        signature: Bytes::new(&env),
+    };
+    let signed = sign_listing(&env, &client, &key, listing.clone());
+
+    // The seller has to register the key they sign with
+    let result = client.try_execute_off_chain_listing(&signed, &buyer);
+    assert_eq!(result, Err(Ok(SettlementError::NotFound)));
+    client.register_listing_key(&seller, &BytesN::from_array(&env, &key.verifying_key().to_bytes()));
+
+    // Changing a signed field or the signature itself is rejected
+    let tampered = OffChainListing { price: 1, ..signed.clone() };
+    assert!(client.try_execute_off_chain_listing(&tampered, &buyer).is_err());
+    let forged = sign_listing(&env, &client, &SigningKey::from_bytes(&[2; 32]), listing.clone());
+    assert!(client.try_execute_off_chain_listing(&forged, &buyer).is_err());
+    let truncated = OffChainListing { signature: Bytes::from_slice(&env, &[0; 10]), ..signed.clone() };
+    let result = client.try_execute_off_chain_listing(&truncated, &buyer);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidSignature)));
+
+    let result = client.execute_off_chain_listing(&signed, &buyer);
+    assert!(result.success);
+    assert!(holds(&nft, &buyer, token_id));
+    assert_eq!(balance(&env, &currency, &seller), 9_000);
+    assert_eq!(balance(&env, &currency, &buyer), 0);
+    assert_eq!(client.get_sale_history(&nft.address, &token_id, &0, &10).len(), 1);
+
+    // The nonce can't be replayed
+    let result = client.try_execute_off_chain_listing(&signed, &buyer);
+    assert_eq!(result, Err(Ok(SettlementError::AlreadyExists)));
+
+    let next_token = mint_for_sale(&env, &nft, &seller, &client.address);
+    let next = sign_listing(&env, &client, &key, OffChainListing { token_id: next_token, nonce: 8, ..listing });
+    set_time(&env, 5_001);
+    let result = client.try_execute_off_chain_listing(&next, &buyer);
+    assert_eq!(result, Err(Ok(SettlementError::Expired)));
+}
+
+#[test]
+fn test_off_chain_listing_nonce_goes_stale() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let currency = test_asset(&env);
+    let nft = nft_collection(&env);
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    fund(&env, &currency, &buyer, 10_000);
+    let key = SigningKey::from_bytes(&[1; 32]);
+    client.register_listing_key(&seller, &BytesN::from_array(&env, &key.verifying_key().to_bytes()));
+
+    assert_eq!(client.get_nonce(&seller), 0);
+    let listing = OffChainListing {
+        seller: seller.clone(),
+        nft_address: nft.address.clone(),
+        token_id,
+        price: 10_000,
+        currency: currency.clone(),
+        expiry: 5_000,
+        nonce: client.get_nonce(&seller),
+        signature: Bytes::new(&env),
+    };
+    let stale = sign_listing(&env, &client, &key, listing.clone());
+
+    // Any activity by the seller moves them past the signed nonce
+    let other_token = mint_for_sale(&env, &nft, &seller, &client.address);
+    client.create_sale(&seller, &nft.address, &other_token, &10_000, &currency, &86_400);
+    assert_eq!(client.get_nonce(&seller), 1);
+    let result = client.try_execute_off_chain_listing(&stale, &buyer);
+    assert_eq!(result, Err(Ok(SettlementError::AlreadyExists)));
+
+    // Re-signed at the current nonce it goes through once, and the buyer's nonce moves too
+    let current = sign_listing(&env, &client, &key, OffChainListing { nonce: 1, ..listing });
+    client.execute_off_chain_listing(&current, &buyer);
+    assert_eq!(client.get_nonce(&buyer), 1);
+    let result = client.try_execute_off_chain_listing(&current, &buyer);
+    assert_eq!(result, Err(Ok(SettlementError::AlreadyExists)));
+}
+
+/// Bundle items for `token_ids` of `nft`
+fn bundle_items(env: &Env, nft: &Address, token_ids: &[u64]) -> Vec<NFTItem> {
+    let mut items = Vec::new(env);
+    for token_id in token_ids {
+        items.push_back(NFTItem {
+            nft_address: nft.clone(),
+            token_id: *token_id,
+            royalty_info: RoyaltyDistribution {
+                creator_address: nft.clone(),
+                creator_percentage: 0,
+                seller_percentage: 10_000,
+                platform_percentage: 0,
+                total_amount: 0,
+                amounts: Map::new(env),
+            },
+            individual_price: None,
+        });
+    }
+    items
+}
+
+/// A three-NFT bundle auction with a 4_000 reserve and a 6_000 bid on it
+fn bid_bundle_auction<'a>(
+    env: &Env,
+    client: &MarketplaceSettlementClient
+) -> (u64, Address, Address, NftContractClient<'a>, Vec<NFTItem>) {
+    let seller = Address::generate(env);
+    let nft = nft_collection(env);
+    let token_ids = [
+        mint_for_sale(env, &nft, &seller, &client.address),
+        mint_for_sale(env, &nft, &seller, &client.address),
+        mint_for_sale(env, &nft, &seller, &client.address),
+    ];
+    let items = bundle_items(env, &nft.address, &token_ids);
+    let auction_id = client.create_bundle_auction(&seller, &items, &5_000, &4_000, &3_600, &100, &test_asset(env));
+
+    let bidder = funded_bidder(env, client, auction_id);
+    set_time(env, 1_340);
+    client.place_bid(&auction_id, &bidder, &6_000, &None);
+    (auction_id, seller, bidder, nft, items)
+}
+
+#[test]
+fn test_bundle_auction_delivers_whole_lot() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let (auction_id, seller, bidder, nft, items) = bid_bundle_auction(&env, &client);
+    let currency = client.get_auction(&auction_id).currency;
+    assert_eq!(client.get_bundle_auction(&auction_id).items, items);
+
+    // Bundles only settle through their own path, once bidding has closed
+    let result = client.try_end_bundle_auction(&auction_id, &seller);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+    set_time(&env, 4_601);
+    let result = client.try_end_auction(&auction_id, &seller, &0);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+
+    let result = client.end_bundle_auction(&auction_id, &seller);
+    assert!(result.success);
+    assert_eq!(count_events(&env, symbol_short!("bndl_end")), 1);
+    assert_eq!(client.get_auction(&auction_id).state, TransactionState::Executed);
+    for item in items.iter() {
+        assert!(holds(&nft, &bidder, item.token_id));
+    }
+    assert_eq!(balance(&env, &currency, &seller), 5_000);
+    assert_eq!(client.get_bid_escrow(&auction_id, &bidder), 0);
+}
+
+#[test]
+fn test_bundle_auction_validates_lot() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let currency = test_asset(&env);
+    let nft = nft_collection(&env);
+    let token_id = mint_for_sale(&env, &nft, &seller, &client.address);
+    let other_token = mint_for_sale(&env, &nft, &Address::generate(&env), &client.address);
+
+    let single = bundle_items(&env, &nft.address, &[token_id]);
+    let result = client.try_create_bundle_auction(&seller, &single, &5_000, &4_000, &3_600, &100, &currency);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+    let repeated = bundle_items(&env, &nft.address, &[token_id, token_id]);
+    let result = client.try_create_bundle_auction(&seller, &repeated, &5_000, &4_000, &3_600, &100, &currency);
+    assert_eq!(result, Err(Ok(SettlementError::AlreadyExists)));
+    let foreign = bundle_items(&env, &nft.address, &[token_id, other_token]);
+    let result = client.try_create_bundle_auction(&seller, &foreign, &5_000, &4_000, &3_600, &100, &currency);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    let options = AuctionOptions { buyout_price: None, allowed_bidders: None, dutch_half_life: None, start_time: None };
+    let result = client.try_create_auction(
+        &seller,
+        &nft.address,
+        &token_id,
+        &3_000,
+        &5_000,
+        &3_600,
+        &100,
+        &AuctionType::Bundle,
+        &currency,
+        &options,
+    );
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+}
+
+#[test]
+fn test_bundle_auction_missing_item_disputes_without_transfers() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let (auction_id, seller, bidder, nft, items) = bid_bundle_auction(&env, &client);
+
+    // The seller parts with one NFT of the lot before the auction ends
+    let sold_elsewhere = items.get(1).unwrap().token_id;
+    nft.transfer_with_data(&seller, &seller, &Address::generate(&env), &sold_elsewhere, &Bytes::new(&env));
+
+    set_time(&env, 4_601);
+    let result = client.end_bundle_auction(&auction_id, &seller);
+    assert!(!result.success);
+    assert!(!result.transferred_nft);
+    assert_eq!(client.get_auction(&auction_id).state, TransactionState::Disputed);
+    assert!(holds(&nft, &seller, items.get(0).unwrap().token_id));
+    assert!(holds(&nft, &seller, items.get(2).unwrap().token_id));
+    assert_eq!(client.get_bid_escrow(&auction_id, &bidder), 6_000);
+}
+
+#[test]
+fn test_bundle_auction_failed_transfer_reverts_batch() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let seller = Address::generate(&env);
+    let first = nft_collection(&env);
+    let second = nft_collection(&env);
+    let mut items = bundle_items(&env, &first.address, &[mint_for_sale(&env, &first, &seller, &client.address)]);
+    items.append(&bundle_items(&env, &second.address, &[mint_for_sale(&env, &second, &seller, &client.address)]));
+    let auction_id = client.create_bundle_auction(&seller, &items, &5_000, &4_000, &3_600, &100, &test_asset(&env));
+    let bidder = funded_bidder(&env, &client, auction_id);
+    set_time(&env, 1_340);
+    client.place_bid(&auction_id, &bidder, &6_000, &None);
+
+    // The second collection refuses the transfer after the first NFT has moved
+    second.set_approval_for_all(&client.address, &false, &seller);
+    set_time(&env, 4_601);
+    assert!(client.try_end_bundle_auction(&auction_id, &seller).is_err());
+    assert!(holds(&first, &seller, items.get(0).unwrap().token_id));
+    assert_eq!(client.get_auction(&auction_id).state, TransactionState::Pending);
+    assert_eq!(client.get_bid_escrow(&auction_id, &bidder), 6_000);
+}
+
+/// A three-NFT bundle sale for 25_000, with its first two items also sold alone for 10_000
+fn partial_bundle<'a>(
+    env: &Env,
+    client: &MarketplaceSettlementClient,
+    prices: [Option<i128>; 3]
+) -> (u64, Address, NftContractClient<'a>, Vec<NFTItem>, Asset) {
+    let seller = Address::generate(env);
+    let currency = test_asset(env);
+    let nft = nft_collection(env);
+    let token_ids = [
+        mint_for_sale(env, &nft, &seller, &client.address),
+        mint_for_sale(env, &nft, &seller, &client.address),
+        mint_for_sale(env, &nft, &seller, &client.address),
+    ];
+    let mut items = bundle_items(env, &nft.address, &token_ids);
+    for (i, price) in prices.iter().enumerate() {
+        let mut item = items.get(i as u32).unwrap();
+        item.individual_price = *price;
+        items.set(i as u32, item);
+    }
+    let bundle_id = client.create_bundle(&seller, &items, &25_000, &currency, &3_600);
+    (bundle_id, seller, nft, items, currency)
+}
+
+#[test]
+fn test_partial_bundle_purchase_then_bulk() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let (bundle_id, seller, nft, items, currency) = partial_bundle(&env, &client, [Some(10_000), Some(10_000), None]);
+    let first = items.get(0).unwrap().token_id;
+    let last = items.get(2).unwrap().token_id;
+
+    let collector = Address::generate(&env);
+    fund(&env, &currency, &collector, 10_000);
+    client.purchase_bundle_item(&bundle_id, &first, &collector);
+    assert!(holds(&nft, &collector, first));
+    assert!(client.is_bundle_item_sold(&bundle_id, &first));
+    assert_eq!(client.get_bundle(&bundle_id).state, TransactionState::Pending);
+    assert_eq!(count_events(&env, symbol_short!("bndl_exec")), 0);
+    assert_eq!(balance(&env, &currency, &seller), 9_000);
+
+    let result = client.try_purchase_bundle_item(&bundle_id, &first, &collector);
+    assert_eq!(result, Err(Ok(SettlementError::AlreadyExists)));
+    let result = client.try_purchase_bundle_item(&bundle_id, &last, &collector);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+    let result = client.try_purchase_bundle_item(&bundle_id, &999, &collector);
+    assert_eq!(result, Err(Ok(SettlementError::NotFound)));
+
+    // The rest of the bundle sells for the total less what the first item fetched
+    let buyer = Address::generate(&env);
+    fund(&env, &currency, &buyer, 15_000);
+    client.purchase_bundle(&bundle_id, &buyer);
+    assert_eq!(count_events(&env, symbol_short!("bndl_exec")), 1);
+    assert!(holds(&nft, &buyer, items.get(1).unwrap().token_id));
+    assert!(holds(&nft, &buyer, last));
+    assert_eq!(balance(&env, &currency, &buyer), 0);
+    assert_eq!(balance(&env, &currency, &seller), 23_000);
+
+    let bundle = client.get_bundle(&bundle_id);
+    assert_eq!(bundle.state, TransactionState::Executed);
+    assert_eq!(bundle.buyer, Some(buyer.clone()));
+    let result = client.try_purchase_bundle_item(&bundle_id, &items.get(1).unwrap().token_id, &collector);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+}
+
+#[test]
+fn test_bundle_executes_when_last_item_sells() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let (bundle_id, seller, nft, items, currency) = partial_bundle(&env, &client, [Some(10_000), Some(10_000), Some(5_000)]);
+
+    let buyer = Address::generate(&env);
+    fund(&env, &currency, &buyer, 25_000);
+    let result = client.try_purchase_bundle_item(&bundle_id, &items.get(0).unwrap().token_id, &seller);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    for (i, item) in items.iter().enumerate() {
+        assert_eq!(client.get_bundle(&bundle_id).state, TransactionState::Pending);
+        client.purchase_bundle_item(&bundle_id, &item.token_id, &buyer);
+        assert_eq!(count_events(&env, symbol_short!("bndl_exec")), (i == 2) as u32);
+        assert!(holds(&nft, &buyer, item.token_id));
+    }
+    assert_eq!(client.get_bundle(&bundle_id).state, TransactionState::Executed);
+
+    // Nothing is left for a bulk purchase
+    let result = client.try_purchase_bundle(&bundle_id, &buyer);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+
+    let repeated = bundle_items(&env, &nft.address, &[1, 1]);
+    let result = client.try_create_bundle(&seller, &repeated, &25_000, &currency, &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::AlreadyExists)));
+    let mut free = bundle_items(&env, &nft.address, &[1, 2]);
+    let mut item = free.get(0).unwrap();
+    item.individual_price = Some(0);
+    free.set(0, item);
+    let result = client.try_create_bundle(&seller, &free, &25_000, &currency, &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+
+    // The sold items now belong to the buyer and can't be bundled again by the seller
+    let sold = bundle_items(&env, &nft.address, &[1, 2]);
+    let result = client.try_create_bundle(&seller, &sold, &25_000, &currency, &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+}
+
+/// A one-for-one NFT trade between two fresh accounts, topped up by `top_up_amount`
+fn hybrid_trade<'a>(
+    env: &Env,
+    client: &MarketplaceSettlementClient,
+    top_up_amount: i128
+) -> (u64, Address, Address, NftContractClient<'a>, [u64; 2], Asset) {
+    let initiator = Address::generate(env);
+    let counterparty = Address::generate(env);
+    let currency = test_asset(env);
+    let nft = nft_collection(env);
+    let offered = mint_for_sale(env, &nft, &initiator, &client.address);
+    let wanted = mint_for_sale(env, &nft, &counterparty, &client.address);
+
+    let trade_id = client.create_trade(
+        &initiator,
+        &Some(counterparty.clone()),
+        &bundle_items(env, &nft.address, &[offered]),
+        &bundle_items(env, &nft.address, &[wanted]),
+        &top_up_amount,
+        &Some(currency.contract.clone()),
+        &3_600,
+    );
+    (trade_id, initiator, counterparty, nft, [offered, wanted], currency)
+}
+
+#[test]
+fn test_trade_with_counterparty_top_up() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let (trade_id, initiator, counterparty, nft, [offered, wanted], currency) = hybrid_trade(&env, &client, 5_000);
+
+    let result = client.try_accept_trade(&trade_id, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+    client.accept_trade(&trade_id, &counterparty);
+
+    // The counterparty can't cover the top-up, so nothing moves
+    let result = client.try_execute_trade(&trade_id, &counterparty);
+    assert_eq!(result, Err(Ok(SettlementError::InsufficientFunds)));
+    assert!(holds(&nft, &initiator, offered));
+
+    fund(&env, &currency, &counterparty, 5_000);
+    client.execute_trade(&trade_id, &counterparty);
+    assert!(env.auths().iter().any(|(address, _)| *address == initiator));
+    assert!(env.auths().iter().any(|(address, _)| *address == counterparty));
+    assert!(holds(&nft, &counterparty, offered));
+    assert!(holds(&nft, &initiator, wanted));
+    assert_eq!(balance(&env, &currency, &initiator), 5_000);
+    assert_eq!(balance(&env, &currency, &counterparty), 0);
+    assert_eq!(client.get_trade(&trade_id).state, TransactionState::Executed);
+}
+
+#[test]
+fn test_trade_with_initiator_top_up() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let (trade_id, initiator, counterparty, nft, [offered, wanted], currency) = hybrid_trade(&env, &client, -3_000);
+    fund(&env, &currency, &initiator, 3_000);
+
+    let result = client.try_execute_trade(&trade_id, &initiator);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+    client.accept_trade(&trade_id, &counterparty);
+    client.execute_trade(&trade_id, &initiator);
+    assert!(holds(&nft, &counterparty, offered));
+    assert!(holds(&nft, &initiator, wanted));
+    assert_eq!(balance(&env, &currency, &initiator), 0);
+    assert_eq!(balance(&env, &currency, &counterparty), 3_000);
+
+    let items = bundle_items(&env, &nft.address, &[wanted]);
+    let result = client.try_create_trade(&initiator, &None, &items, &Vec::new(&env), &1_000, &None, &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidCurrency)));
+}
+
+/// Three parties each holding one NFT, in a cycle where each passes theirs to the next
+fn multi_trade_cycle<'a>(
+    env: &Env,
+    client: &MarketplaceSettlementClient
+) -> (Vec<Address>, NftContractClient<'a>, Vec<NFTItem>, Vec<u32>) {
+    let nft = nft_collection(env);
+    let mut parties = Vec::new(env);
+    let mut token_ids = [0; 3];
+    for token_id in token_ids.iter_mut() {
+        let party = Address::generate(env);
+        *token_id = mint_for_sale(env, &nft, &party, &client.address);
+        parties.push_back(party);
+    }
+    let offered = bundle_items(env, &nft.address, &token_ids);
+    (parties, nft, offered, Vec::from_array(env, [1, 2, 0]))
+}
+
+#[test]
+fn test_multi_party_trade_executes_when_all_sign() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let (parties, nft, offered, recipients) = multi_trade_cycle(&env, &client);
+    let currency = test_asset(&env);
+    fund(&env, &currency, &parties.get(0).unwrap(), 1_000);
+
+    let top_ups = Vec::from_array(&env, [1_000, 0, -1_000]);
+    let proposer = parties.get(0).unwrap();
+    let trade_id = client.propose_multi_trade(
+        &proposer, &parties, &offered, &recipients, &top_ups, &Some(currency.contract.clone()), &3_600,
+    );
+    assert_eq!(client.try_sign_multi_trade(&trade_id, &proposer), Err(Ok(SettlementError::AlreadyExists)));
+    let outsider = Address::generate(&env);
+    assert_eq!(client.try_sign_multi_trade(&trade_id, &outsider), Err(Ok(SettlementError::Unauthorized)));
+
+    assert!(!client.sign_multi_trade(&trade_id, &parties.get(1).unwrap()));
+    assert!(holds(&nft, &proposer, offered.get(0).unwrap().token_id));
+    assert!(client.sign_multi_trade(&trade_id, &parties.get(2).unwrap()));
+    assert_eq!(count_events(&env, symbol_short!("mtrd_exec")), 1);
+
+    for (item, recipient) in offered.iter().zip(recipients.iter()) {
+        assert!(holds(&nft, &parties.get(recipient).unwrap(), item.token_id));
+    }
+    assert_eq!(balance(&env, &currency, &proposer), 0);
+    assert_eq!(balance(&env, &currency, &parties.get(2).unwrap()), 1_000);
+    assert_eq!(client.get_multi_trade(&trade_id).state, TransactionState::Executed);
+}
+
+#[test]
+fn test_multi_party_trade_is_all_or_nothing() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let (parties, nft, offered, recipients) = multi_trade_cycle(&env, &client);
+    let proposer = parties.get(0).unwrap();
+    let no_top_ups = Vec::from_array(&env, [0, 0, 0]);
+
+    let pair = Vec::from_array(&env, [proposer.clone(), parties.get(1).unwrap()]);
+    let result = client.try_propose_multi_trade(&proposer, &pair, &offered, &recipients, &no_top_ups, &None, &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+    let mut repeated = parties.clone();
+    repeated.set(2, proposer.clone());
+    let result = client.try_propose_multi_trade(&proposer, &repeated, &offered, &recipients, &no_top_ups, &None, &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::AlreadyExists)));
+    let to_self = Vec::from_array(&env, [0, 2, 0]);
+    let result = client.try_propose_multi_trade(&proposer, &parties, &offered, &to_self, &no_top_ups, &None, &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+    let unbalanced = Vec::from_array(&env, [1_000, 0, 0]);
+    let currency = Some(test_asset(&env).contract);
+    let result = client.try_propose_multi_trade(&proposer, &parties, &offered, &recipients, &unbalanced, &currency, &3_600);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidAmount)));
+    let foreign = bundle_items(&env, &nft.address, &[mint_for_sale(&env, &nft, &Address::generate(&env), &client.address)]);
+    let result = client.try_propose_multi_trade(
+        &proposer, &parties, &foreign, &Vec::from_array(&env, [1]), &no_top_ups, &None, &3_600,
+    );
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    // Cancelling hands escrowed top-ups back
+    let top_ups = Vec::from_array(&env, [500, -500, 0]);
+    let asset = test_asset(&env);
+    fund(&env, &asset, &proposer, 500);
+    let cancelled = client.propose_multi_trade(
+        &proposer, &parties, &offered, &recipients, &top_ups, &Some(asset.contract.clone()), &3_600,
+    );
+    assert_eq!(balance(&env, &asset, &proposer), 0);
+    client.cancel_multi_trade(&cancelled, &parties.get(2).unwrap());
+    assert_eq!(balance(&env, &asset, &proposer), 500);
+    assert_eq!(client.get_multi_trade(&cancelled).state, TransactionState::Cancelled);
+
+    // One NFT leaves its party before the final signature, so no NFT moves at all
+    let trade_id = client.propose_multi_trade(&proposer, &parties, &offered, &recipients, &no_top_ups, &None, &3_600);
+    client.sign_multi_trade(&trade_id, &parties.get(1).unwrap());
+    let last = parties.get(2).unwrap();
+    let moved = offered.get(2).unwrap().token_id;
+    nft.transfer_with_data(&last, &last, &Address::generate(&env), &moved, &Bytes::new(&env));
+    assert!(client.try_sign_multi_trade(&trade_id, &last).is_err());
+    for (i, item) in offered.iter().enumerate().take(2) {
+        assert!(holds(&nft, &parties.get(i as u32).unwrap(), item.token_id));
+    }
+    let trade = client.get_multi_trade(&trade_id);
+    assert_eq!(trade.state, TransactionState::Pending);
+    assert_eq!(trade.signatures.len(), 2);
+}
+
+#[test]
+fn test_trade_counter_offers() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let (trade_id, initiator, counterparty, nft, [offered, wanted], currency) = hybrid_trade(&env, &client, 0);
+    let spare = mint_for_sale(&env, &nft, &counterparty, &client.address);
+    let outsider = Address::generate(&env);
+    let result = client.try_counter_offer(&trade_id, &outsider, &Vec::new(&env), &0);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    // The counterparty offers a different NFT plus tokens, on a fresh window
+    set_time(&env, 4_000);
+    client.counter_offer(&trade_id, &counterparty, &bundle_items(&env, &nft.address, &[spare]), &2_000);
+    assert_eq!(count_events(&env, symbol_short!("trd_cntr")), 1);
+    let trade = client.get_trade(&trade_id);
+    assert_eq!(trade.counter_expires_at, 7_600);
+    assert_eq!(trade.counter_rounds, 1);
+    let result = client.try_accept_trade(&trade_id, &counterparty);
+    assert_eq!(result, Err(Ok(SettlementError::Unauthorized)));
+
+    // Both sides counter in the same ledger; the later counter stands
+    client.counter_offer(&trade_id, &initiator, &bundle_items(&env, &nft.address, &[wanted]), &1_000);
+    client.counter_offer(&trade_id, &counterparty, &bundle_items(&env, &nft.address, &[spare]), &1_500);
+    let trade = client.get_trade(&trade_id);
+    assert_eq!(trade.top_up_amount, 1_500);
+    assert_eq!(trade.last_countered_by, Some(counterparty.clone()));
+
+    client.accept_trade(&trade_id, &initiator);
+    fund(&env, &currency, &counterparty, 1_500);
+    client.execute_trade(&trade_id, &initiator);
+    assert!(holds(&nft, &counterparty, offered));
+    assert!(holds(&nft, &initiator, spare));
+    assert!(holds(&nft, &counterparty, wanted));
+    assert_eq!(balance(&env, &currency, &initiator), 1_500);
+}
+
+#[test]
+fn test_trade_counter_offer_deadlock_and_expiry() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let (trade_id, initiator, counterparty, nft, [_, wanted], _) = hybrid_trade(&env, &client, 0);
+    let items = bundle_items(&env, &nft.address, &[wanted]);
+
+    for round in 0..5 {
+        let party = if round % 2 == 0 { &counterparty } else { &initiator };
+        client.counter_offer(&trade_id, party, &items, &(round * 100));
+    }
+    let result = client.try_counter_offer(&trade_id, &initiator, &items, &0);
+    assert_eq!(result, Err(Ok(SettlementError::InvalidState)));
+
+    // The last counter came from the counterparty; it lapses with its own window
+    client.accept_trade(&trade_id, &initiator);
+    set_time(&env, 4_601);
+    let result = client.try_execute_trade(&trade_id, &initiator);
+    assert_eq!(result, Err(Ok(SettlementError::Expired)));
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, Vec, Map, Symbol, Bytes};
+use soroban_sdk::{contracttype, Address, Vec, Map, Symbol, Bytes, BytesN};
 
 // Transaction state enum
 #[contracttype]
@@ -10,6 +10,8 @@ pub enum TransactionState {
     Cancelled = 3,
     Disputed = 4,
     Resolved = 5,
+    Paused = 6,
+    Scheduled = 7,
 }
 
 // Asset type for multi-asset support
@@ -39,6 +41,78 @@ pub struct SaleTransaction {
     pub platform_fee: i128,
 }
 
+// Fixed-price listing signed by the seller off-chain, settled when someone buys it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OffChainListing {
+    pub seller: Address,
+    pub nft_address: Address,
+    pub token_id: u64,
+    pub price: i128,
+    pub currency: Asset,
+    pub expiry: u64,
+    pub nonce: u64,
+    pub signature: Bytes, // ed25519 signature over the listing fields, see ListingSignature::message
+}
+
+// Lowest active listing price for an NFT collection
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollectionFloor {
+    pub price: i128,
+    pub expires_at: u64,           // When the listing setting the floor expires
+    pub stale_since: Option<u64>,  // Set when the floor listing leaves the market
+}
+
+// Trading statistics for an NFT collection
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollectionStats {
+    pub total_volume: i128,
+    pub total_sales: u64,
+    pub unique_buyers: u64,  // Approximate once many addresses have traded
+    pub unique_sellers: u64, // Approximate once many addresses have traded
+}
+
+// Marketplace-wide auction statistics
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformStats {
+    pub total_auctions_created: u64,
+    pub total_auctions_completed: u64, // Auctions that closed with a winner
+    pub total_auctions_cancelled: u64,
+    pub total_volume: i128,
+    pub total_unique_sellers: u64, // Approximate once many addresses have traded
+    pub total_unique_buyers: u64,  // Approximate once many addresses have traded
+    pub average_final_price: i128,
+}
+
+// Standing offer from a buyer on a specific NFT
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Offer {
+    pub offer_id: u64,
+    pub buyer: Address,
+    pub nft_address: Address,
+    pub token_id: u64,
+    pub amount: i128, // Held in escrow until accepted, cancelled or expired
+    pub currency: Asset,
+    pub expires_at: u64,
+}
+
+// Standing floor offer for any token from a collection
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollectionOffer {
+    pub offer_id: u64,
+    pub buyer: Address,
+    pub nft_address: Address,
+    pub amount: i128,       // Paid per token; amount * max_quantity is held in escrow
+    pub currency: Asset,
+    pub expires_at: u64,
+    pub max_quantity: u32,  // Tokens still wanted; the offer closes at zero
+}
+
 // Auction transaction structure
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -60,6 +134,31 @@ pub struct AuctionTransaction {
     pub currency: Asset,
     pub royalty_info: RoyaltyDistribution,
     pub platform_fee: i128,
+    pub buyout_price: Option<i128>, // Instant purchase price for English auctions
+    pub max_extensions: u32,        // Anti-sniping extension cap
+    pub extension_count: u32,       // Extensions applied so far
+    pub last_extended_at: u64,      // Timestamp of the last extension (0 = never)
+    pub allowed_bidders: Option<Vec<Address>>, // None = open auction
+    pub paused_at: u64,             // When the seller paused the auction (0 = not paused)
+}
+
+// Optional settings supplied when creating an auction
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionOptions {
+    pub buyout_price: Option<i128>,            // English auctions only
+    pub allowed_bidders: Option<Vec<Address>>, // None = open auction
+    pub dutch_half_life: Option<u64>,          // Dutch only; Some = exponential decay
+    pub start_time: Option<u64>,               // None = open now; later = scheduled
+}
+
+// Standing proxy bid that raises automatically up to a limit
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProxyBid {
+    pub bidder: Address,
+    pub max_amount: i128,
+    pub current_bid: i128, // Last amount bid on the bidder's behalf (0 = none yet)
 }
 
 // Bid structure
@@ -73,6 +172,15 @@ pub struct Bid {
     pub commitment_hash: Option<Bytes>,
 }
 
+// A bid as listed for its bidder across auctions
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BidderBid {
+    pub auction_id: u64,
+    pub bid: Bid,
+    pub stale: bool, // The auction was cancelled; kept for the bidder's history
+}
+
 // Royalty distribution structure
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -94,13 +202,16 @@ pub struct Dispute {
     pub auction_id: Option<u64>,
     pub initiator: Address,
     pub reason: Bytes, // String stored as bytes for efficiency
-    pub evidence_uri: Option<Bytes>,
+    pub initiator_evidence: Vec<Bytes>,  // Evidence URIs from the initiator, oldest first
+    pub respondent_evidence: Vec<Bytes>, // Evidence URIs from the other party, oldest first
     pub arbitrators: Vec<Address>,
     pub votes: Map<Address, u64>, // 1 = for initiator, 0 = against
     pub required_votes: u64,
     pub created_at: u64,
     pub resolved_at: u64, // 0 = not resolved
     pub resolution: u64, // 0 = not resolved, 1 = refund buyer, 2 = release to seller, 3 = split funds, 4 = cancel transaction
+    pub appeal_deadline: u64, // Last moment to appeal the ruling; when the appeal was filed once appealed
+    pub appeal_filed_by: Option<Address>,
 }
 
 // Fee configuration structure
@@ -114,6 +225,11 @@ pub struct FeeConfig {
     pub dynamic_fee_enabled: bool,
     pub volume_discounts: Vec<VolumeTier>,
     pub vip_exemptions: Vec<Address>,
+    pub referral_fee_bps: u64, // Share of the platform fee paid to referrers
+    pub volume_period_seconds: u64, // Trading volume counts toward discounts for this long (0 = forever)
+    pub auto_swap_enabled: bool,    // Swap accumulated fees into the protocol token through the DEX
+    pub protocol_token: Asset,      // Token that accumulated fees are swapped into
+    pub swap_threshold: i128,       // Accumulated fees in an asset are swapped once they exceed this
 }
 
 // Volume tier for dynamic fees
@@ -124,7 +240,34 @@ pub struct VolumeTier {
     pub fee_discount_bps: u64,
 }
 
-// Trade transaction for NFT-for-NFT swaps
+// Minimum ledgers live auction and dispute data is kept from eviction
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StorageTTLConfig {
+    pub auction_min_ttl: u32, // bumped on every bid
+    pub dispute_min_ttl: u32, // bumped when a dispute opens
+}
+
+// Time-limited fee discount for a marketing campaign
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PromotionalFeeOverride {
+    pub discount_bps: u64,                         // taken off the fee on top of any volume discount
+    pub starts_at: u64,                            // first moment the promotion applies
+    pub ends_at: u64,                              // the promotion stops applying at this moment
+    pub eligible_collections: Option<Vec<Address>>, // None = every collection
+}
+
+// Named VIP tier users are promoted into as their trading volume grows
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VipTier {
+    pub name: Bytes,
+    pub min_volume: i128,
+    pub fee_discount_bps: u64,
+}
+
+// Trade transaction for NFT-for-NFT swaps, optionally topped up with tokens
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TradeTransaction {
@@ -133,12 +276,34 @@ pub struct TradeTransaction {
     pub counterparty: Option<Address>,
     pub initiator_nfts: Vec<NFTItem>,
     pub counterparty_nfts: Vec<NFTItem>,
+    pub top_up_amount: i128,              // tokens the counterparty adds; negative when the initiator pays
+    pub top_up_currency: Option<Address>, // token contract, required whenever top_up_amount is non-zero
     pub state: TransactionState,
     pub created_at: u64,
-    pub expires_at: u64,
+    pub expires_at: u64,                  // deadline for the original proposal
+    pub counter_expires_at: u64,          // deadline for the latest counter-offer, once countered
+    pub counter_rounds: u32,
+    pub last_countered_by: Option<Address>,
     pub platform_fee: i128,
 }
 
+// Trade between three or more parties, settled only once every party has signed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MultiPartyTrade {
+    pub trade_id: u64,
+    pub parties: Vec<Address>,
+    pub offered_nfts: Vec<NFTItem>,
+    pub offerer_index: Vec<u32>,        // party holding offered_nfts[i] when the trade was proposed
+    pub recipient_index: Vec<u32>,      // party receiving offered_nfts[i]
+    pub top_up_amounts: Vec<i128>,      // per party: positive pays in, negative is paid out; sums to zero
+    pub top_up_currency: Option<Address>, // token contract, required whenever a top-up is non-zero
+    pub signatures: Vec<Address>,       // parties who have signed, in signing order
+    pub state: TransactionState,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
 // NFT item structure for bundles and trades
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -146,6 +311,7 @@ pub struct NFTItem {
     pub nft_address: Address,
     pub token_id: u64,
     pub royalty_info: RoyaltyDistribution,
+    pub individual_price: Option<i128>, // set when a bundle item can also be bought on its own
 }
 
 // Bundle transaction for multi-item sales
@@ -196,6 +362,20 @@ pub struct DistributionResult {
 pub enum AuctionType {
     English = 0, // Price increases with bidding
     Dutch = 1,   // Price decreases over time
+    Vickrey = 2, // Sealed bids, winner pays the second-highest price
+    Candle = 3,  // Ends at a hidden random time inside a window
+    SealedFirstPrice = 4, // Sealed bids, winner pays their own bid
+    Bundle = 5,  // Several of the seller's NFTs sold as one lot
+}
+
+// Criteria for listing auctions; None or an empty list matches anything
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionFilter {
+    pub state: Vec<TransactionState>,  // any of these states
+    pub seller: Option<Address>,
+    pub nft_address: Option<Address>,
+    pub auction_type: Vec<AuctionType>, // any of these types
 }
 
 // Dutch auction specific data
@@ -208,6 +388,59 @@ pub struct DutchAuctionData {
     pub time_unit: u64,        // Time unit in seconds for decrement
     pub current_price: i128,
     pub last_price_update: u64,
+    pub allowed_bidders: Option<Vec<Address>>, // None = open auction
+    pub decay_type: DecayType,
+    pub half_life_seconds: u64, // Exponential decay only
+}
+
+// Dutch auction price curve
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DecayType {
+    Linear = 0,      // Straight line from starting to ending price
+    Exponential = 1, // Halves the distance to the ending price every half-life
+}
+
+// Vickrey (second-price sealed-bid) auction specific data
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VickreyAuctionData {
+    pub reveal_end: u64,                // Reveals accepted until this timestamp
+    pub committed_bidders: Vec<Address>, // Bidders that submitted a commitment
+    pub revealed_bids: Map<Address, i128>,
+    pub highest_bidder: Option<Address>,
+    pub highest_bid: i128,
+    pub second_highest_bid: i128,
+}
+
+// Sealed first-price auction specific data
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SealedAuctionData {
+    pub reveal_end: u64,                // Reveals accepted until this timestamp
+    pub committed_bidders: Vec<Address>, // Bidders that submitted a commitment
+    pub revealed_bids: Map<Address, i128>,
+    pub highest_bidder: Option<Address>,
+    pub highest_bid: i128,
+}
+
+// Candle auction specific data
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CandleAuctionData {
+    pub earliest_end: u64,
+    pub latest_end: u64,
+    pub end_commitment: BytesN<32>, // sha256(secret end || salt)
+    pub revealed_end: u64,          // 0 until the auction is settled
+}
+
+// Bundle auction specific data
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BundleAuctionData {
+    pub items: Vec<NFTItem>, // Delivered together to the winner
+    pub starting_price: i128,
+    pub reserve_price: i128,
 }
 
 // Admin configuration
@@ -222,4 +455,22 @@ pub struct AdminConfig {
     pub max_royalty_percentage: u64, // Maximum royalty percentage
     pub dispute_cooling_period: u64, // Cooling period before dispute resolution
     pub arbitration_quorum: u64, // Required votes for arbitration
+    pub max_single_transaction_amount: i128, // Settlements above this are rejected outright
+    pub circuit_breaker_threshold: i128, // Settlements above this halt the marketplace
+    pub bypass_whitelist: bool, // Accept any NFT contract, e.g. during initial deployment
+    pub max_history_per_token: u32, // Sales kept in each token's provenance history
+    pub max_price_history_per_token: u32, // (price, timestamp) points kept per token
+}
+
+// Token-weighted proposal to replace the admin configuration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceProposal {
+    pub proposal_id: u64,
+    pub proposer: Address,
+    pub proposed_config: AdminConfig,
+    pub votes_for: i128,
+    pub votes_against: i128,
+    pub voting_deadline: u64, // Votes are accepted until this timestamp
+    pub executed: bool,
 }
\ No newline at end of file
@@ -1,6 +1,8 @@
-use soroban_sdk::{Address, Env, Symbol, Vec, Bytes};
+use soroban_sdk::{token, vec, Address, Env, IntoVal, InvokeError, Symbol, Val, Vec, Bytes};
 use crate::error::SettlementError;
 use crate::types::Asset;
+use crate::utils::nft_client::INftClient;
+use crate::security::nft_whitelist::NftWhitelist;
 
 /// Create a native XLM asset
 pub fn native_asset() -> Asset {
@@ -68,24 +70,54 @@ pub fn is_valid_token_contract(_address: &Address, _env: &Env) -> bool {
 
 /// Get token balance for an account
 pub fn get_token_balance(
-    _token_contract: &Address,
-    _account: &Address,
-    _env: &Env,
+    token_contract: &Address,
+    account: &Address,
+    env: &Env,
 ) -> Result<i128, SettlementError> {
-    // For now, return a placeholder
-    Err(SettlementError::NotFound) // Placeholder
+    match token::Client::new(env, token_contract).try_balance(account) {
+        Ok(Ok(balance)) => Ok(balance),
+        _ => Err(SettlementError::InvalidCurrency),
+    }
 }
 
 /// Transfer tokens between accounts
 pub fn transfer_tokens(
-    _token_contract: &Address,
-    _from: &Address,
-    _to: &Address,
-    _amount: i128,
-    _env: &Env,
+    token_contract: &Address,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+    env: &Env,
 ) -> Result<(), SettlementError> {
-    // For now, return success
-    Ok(())
+    if amount == 0 {
+        return Ok(());
+    }
+    if amount < 0 {
+        return Err(SettlementError::InvalidAmount);
+    }
+
+    match token::Client::new(env, token_contract).try_transfer(from, to, &amount) {
+        Ok(Ok(())) => Ok(()),
+        _ => Err(SettlementError::PaymentFailed),
+    }
+}
+
+/// Transfer tokens after confirming the sender can cover the amount
+pub fn transfer_tokens_checked(
+    token_contract: &Address,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+    env: &Env,
+) -> Result<(), SettlementError> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    if get_token_balance(token_contract, from, env)? < amount {
+        return Err(SettlementError::InsufficientFunds);
+    }
+
+    transfer_tokens(token_contract, from, to, amount, env)
 }
 
 /// Approve token spending
@@ -124,28 +156,52 @@ pub fn validate_nft_contract(nft_contract: &Address, env: &Env) -> Result<(), Se
     if !is_valid_token_contract(nft_contract, env) {
         return Err(SettlementError::InvalidState);
     }
+    if !NftWhitelist::is_allowed(env, nft_contract) {
+        return Err(SettlementError::AssetNotSupported);
+    }
     Ok(())
 }
 
 /// Check NFT ownership
 pub fn check_nft_ownership(
-    _nft_contract: &Address,
-    _token_id: u64,
-    _owner: &Address,
-    _env: &Env,
+    nft_contract: &Address,
+    token_id: u64,
+    owner: &Address,
+    env: &Env,
 ) -> Result<bool, SettlementError> {
-    Ok(true) // Placeholder
+    INftClient::new(env, nft_contract).owns(owner, token_id)
 }
 
-/// Transfer NFT
+/// Transfer NFT, with this contract acting as the approved operator
 pub fn transfer_nft(
-    _nft_contract: &Address,
-    _from: &Address,
-    _to: &Address,
-    _token_id: u64,
-    _env: &Env,
+    nft_contract: &Address,
+    from: &Address,
+    to: &Address,
+    token_id: u64,
+    env: &Env,
 ) -> Result<(), SettlementError> {
-    Ok(())
+    INftClient::new(env, nft_contract).safe_transfer_from(
+        &env.current_contract_address(),
+        from,
+        to,
+        token_id,
+        &Bytes::new(env),
+    )
+}
+
+/// Call the token's mutation hook, if its owner registered one, with the final
+/// sale price. A failing hook is ignored so it can never block a sale.
+pub fn notify_nft_sold(
+    nft_contract: &Address,
+    token_id: u64,
+    final_price: i128,
+    env: &Env,
+) {
+    let Some(hook) = INftClient::new(env, nft_contract).get_mutation_hook(token_id) else {
+        return;
+    };
+    let args: Vec<Val> = vec![env, token_id.into_val(env), final_price.into_val(env)];
+    let _ = env.try_invoke_contract::<(), InvokeError>(&hook, &Symbol::new(env, "on_sold"), args);
 }
 
 /// Get NFT metadata URI
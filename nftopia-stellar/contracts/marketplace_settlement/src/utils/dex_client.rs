@@ -0,0 +1,58 @@
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+use soroban_sdk::{Address, Env, IntoVal, InvokeError, Symbol, Val, Vec, vec};
+use crate::error::SettlementError;
+
+/// Minimal client for a Soroswap-style router, invoked by function name
+pub struct IDexClient<'a> {
+    env: &'a Env,
+    address: &'a Address,
+}
+
+impl<'a> IDexClient<'a> {
+    /// Create a client for the DEX router at `address`
+    pub fn new(env: &'a Env, address: &'a Address) -> Self {
+        Self { env, address }
+    }
+
+    /// Swap exactly `amount_in` of `path[0]` for as much of the last token in `path` as the
+    /// pools give, sent to this contract
+    ///
+    /// The router pulls the input from this contract, so that one transfer is authorized
+    /// ahead of the call. Returns the amount at each hop of the path.
+    pub fn swap_exact_tokens_for_tokens(
+        &self,
+        amount_in: i128,
+        amount_out_min: i128,
+        path: &Vec<Address>,
+        deadline: u64
+    ) -> Result<Vec<i128>, SettlementError> {
+        let token_in = path.get(0).ok_or(SettlementError::InvalidCurrency)?;
+        let this = self.env.current_contract_address();
+
+        self.env.authorize_as_current_contract(vec![
+            self.env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: token_in,
+                    fn_name: Symbol::new(self.env, "transfer"),
+                    args: (this.clone(), self.address.clone(), amount_in).into_val(self.env),
+                },
+                sub_invocations: Vec::new(self.env),
+            }),
+        ]);
+
+        let args: Vec<Val> = vec![
+            self.env,
+            amount_in.into_val(self.env),
+            amount_out_min.into_val(self.env),
+            path.into_val(self.env),
+            this.into_val(self.env),
+            deadline.into_val(self.env),
+        ];
+        let function = Symbol::new(self.env, "swap_exact_tokens_for_tokens");
+        match self.env.try_invoke_contract::<Vec<i128>, InvokeError>(self.address, &function, args) {
+            Ok(Ok(amounts)) => Ok(amounts),
+            _ => Err(SettlementError::PaymentFailed),
+        }
+    }
+}
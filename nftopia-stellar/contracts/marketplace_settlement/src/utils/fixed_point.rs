@@ -0,0 +1,111 @@
+use crate::error::SettlementError;
+
+/// Largest scale whose power of ten still fits in an i128
+pub const MAX_SCALE: u32 = 38;
+
+/// Basis points as a fixed-point scale (10000 = 100%)
+pub const BPS_SCALE: u32 = 4;
+
+/// A decimal number stored as `value / 10^scale`
+///
+/// Every operation is checked: results that don't fit in an i128 fail with `Overflow`,
+/// or `Underflow` for subtraction, instead of wrapping.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FixedPoint128 {
+    pub value: i128,
+    pub scale: u32,
+}
+
+// The arithmetic is fallible, so it can't implement the std::ops traits
+#[allow(clippy::should_implement_trait)]
+impl FixedPoint128 {
+    /// A fixed-point number equal to `value / 10^scale`
+    pub fn new(value: i128, scale: u32) -> Result<Self, SettlementError> {
+        if scale > MAX_SCALE {
+            return Err(SettlementError::Overflow);
+        }
+        Ok(Self { value, scale })
+    }
+
+    /// A whole number
+    pub fn from_i128(value: i128) -> Self {
+        Self { value, scale: 0 }
+    }
+
+    /// A percentage in basis points
+    pub fn from_bps(basis_points: u64) -> Self {
+        Self { value: basis_points as i128, scale: BPS_SCALE }
+    }
+
+    /// The same number with `scale` decimal places, which must not be fewer than it has
+    pub fn rescale(self, scale: u32) -> Result<Self, SettlementError> {
+        if scale < self.scale {
+            return Err(SettlementError::Underflow);
+        }
+        let value = self.value
+            .checked_mul(pow10(scale - self.scale)?)
+            .ok_or(SettlementError::Overflow)?;
+        Ok(Self { value, scale })
+    }
+
+    /// Exact sum, at the larger of the two scales
+    pub fn add(self, other: Self) -> Result<Self, SettlementError> {
+        let (a, b) = Self::align(self, other)?;
+        let value = a.value.checked_add(b.value).ok_or(SettlementError::Overflow)?;
+        Ok(Self { value, scale: a.scale })
+    }
+
+    /// Exact difference, at the larger of the two scales
+    pub fn sub(self, other: Self) -> Result<Self, SettlementError> {
+        let (a, b) = Self::align(self, other)?;
+        let value = a.value.checked_sub(b.value).ok_or(SettlementError::Underflow)?;
+        Ok(Self { value, scale: a.scale })
+    }
+
+    /// Exact product, whose scale is the sum of the two scales
+    pub fn mul(self, other: Self) -> Result<Self, SettlementError> {
+        let value = self.value.checked_mul(other.value).ok_or(SettlementError::Overflow)?;
+        Self::new(value, self.scale + other.scale)
+    }
+
+    /// Quotient at the larger of the two scales, truncated toward zero
+    pub fn div(self, other: Self) -> Result<Self, SettlementError> {
+        if other.value == 0 {
+            return Err(SettlementError::DivisionByZero);
+        }
+        let scale = self.scale.max(other.scale);
+        let numerator = self.value
+            .checked_mul(pow10(scale + other.scale - self.scale)?)
+            .ok_or(SettlementError::Overflow)?;
+        let value = numerator.checked_div(other.value).ok_or(SettlementError::Overflow)?;
+        Ok(Self { value, scale })
+    }
+
+    /// The nearest whole number, with halves rounded away from zero
+    pub fn to_i128_rounded(self) -> Result<i128, SettlementError> {
+        let unit = pow10(self.scale)?;
+        let whole = self.value / unit;
+        let remainder = self.value % unit;
+        if remainder.unsigned_abs() * 2 >= unit.unsigned_abs() && remainder != 0 {
+            let step = if self.value < 0 { -1 } else { 1 };
+            return whole.checked_add(step).ok_or(SettlementError::Overflow);
+        }
+        Ok(whole)
+    }
+
+    /// The whole part, truncated toward zero
+    pub fn to_i128_truncated(self) -> Result<i128, SettlementError> {
+        Ok(self.value / pow10(self.scale)?)
+    }
+
+    /// Internal: bring two numbers to the larger of their scales
+    fn align(a: Self, b: Self) -> Result<(Self, Self), SettlementError> {
+        let scale = a.scale.max(b.scale);
+        Ok((a.rescale(scale)?, b.rescale(scale)?))
+    }
+}
+
+/// Internal: 10^exponent, failing past what an i128 holds
+fn pow10(exponent: u32) -> Result<i128, SettlementError> {
+    10i128.checked_pow(exponent).ok_or(SettlementError::Overflow)
+}
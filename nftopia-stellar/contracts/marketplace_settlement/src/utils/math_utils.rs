@@ -1,5 +1,6 @@
 use soroban_sdk::{Env, Vec};
 use crate::error::SettlementError;
+use crate::utils::fixed_point::FixedPoint128;
 
 /// Safe multiplication that checks for overflow
 pub fn safe_mul(a: i128, b: i128, _env: &Env) -> Result<i128, SettlementError> {
@@ -34,14 +35,15 @@ pub fn safe_div(a: i128, b: i128, _env: &Env) -> Result<i128, SettlementError> {
 }
 
 /// Calculate percentage using basis points (10000 = 100%)
-pub fn calculate_percentage(amount: i128, basis_points: u64, env: &Env) -> Result<i128, SettlementError> {
+pub fn calculate_percentage(amount: i128, basis_points: u64, _env: &Env) -> Result<i128, SettlementError> {
     if basis_points > 10000 {
         return Err(SettlementError::InvalidRoyaltyPercentage);
     }
 
-    // amount * basis_points / 10000
-    let scaled_amount = safe_mul(amount, basis_points as i128, env)?;
-    safe_div(scaled_amount, 10000, env)
+    // amount * basis_points / 10000, truncated
+    FixedPoint128::from_i128(amount)
+        .mul(FixedPoint128::from_bps(basis_points))?
+        .to_i128_truncated()
 }
 
 /// Calculate fee based on amount and fee structure
@@ -143,6 +145,77 @@ pub fn calculate_time_weighted_price(
     safe_sub(start_price, time_weighted_diff, env)
 }
 
+/// Fold a new price sample into an exponential moving average over `window_size` samples
+///
+/// new_avg = (current_avg * (window_size - 1) + new_sample) / window_size
+pub fn update_moving_average(
+    current_avg: i128,
+    new_sample: i128,
+    window_size: u32,
+    env: &Env,
+) -> Result<i128, SettlementError> {
+    if window_size == 0 {
+        return Err(SettlementError::InvalidAmount);
+    }
+
+    let weighted = safe_mul(current_avg, window_size as i128 - 1, env)?;
+    safe_div(safe_add(weighted, new_sample, env)?, window_size as i128, env)
+}
+
+/// Fixed-point scale used by the exponential decay helpers (1e18)
+const DECAY_SCALE: i128 = 1_000_000_000_000_000_000;
+
+/// ln(2) scaled by DECAY_SCALE
+const LN2_SCALED: i128 = 693_147_180_559_945_309;
+
+/// Calculate an exponentially decaying price that halves its distance to `end_price`
+/// every `half_life_seconds`
+pub fn calculate_exponential_decay_price(
+    start_price: i128,
+    end_price: i128,
+    half_life_seconds: u64,
+    elapsed_seconds: u64,
+    env: &Env,
+) -> Result<i128, SettlementError> {
+    if half_life_seconds == 0 {
+        return Err(SettlementError::DivisionByZero);
+    }
+
+    // 2^(-t/h) = 2^(-whole) * e^(-frac * ln2)
+    let whole_half_lives = elapsed_seconds / half_life_seconds;
+    if whole_half_lives >= 64 {
+        return Ok(end_price);
+    }
+    let remainder = (elapsed_seconds % half_life_seconds) as i128;
+    let exponent = safe_div(safe_mul(remainder, LN2_SCALED, env)?, half_life_seconds as i128, env)?;
+    let factor = exp_neg_scaled(exponent, env)? >> whole_half_lives;
+
+    let price_diff = safe_sub(start_price, end_price, env)?;
+    let decayed_diff = safe_div(safe_mul(price_diff, factor, env)?, DECAY_SCALE, env)?;
+    safe_add(end_price, decayed_diff, env)
+}
+
+/// Approximate e^(-x) for a DECAY_SCALE fixed-point x in [0, 1) using its Taylor series
+fn exp_neg_scaled(x: i128, env: &Env) -> Result<i128, SettlementError> {
+    let mut result = DECAY_SCALE;
+    let mut term = DECAY_SCALE;
+
+    // Terms shrink by at least x/n each step; 20 terms is far below 1e-18 for x < 1
+    for n in 1..=20i128 {
+        term = safe_div(safe_mul(term, x, env)?, DECAY_SCALE * n, env)?;
+        if term == 0 {
+            break;
+        }
+        if n % 2 == 1 {
+            result -= term;
+        } else {
+            result += term;
+        }
+    }
+
+    Ok(result)
+}
+
 /// Calculate compound interest (simple implementation)
 pub fn calculate_compound_interest(
     principal: i128,
@@ -1,3 +1,6 @@
 pub mod math_utils;
+pub mod fixed_point;
 pub mod time_utils;
-pub mod asset_utils;
\ No newline at end of file
+pub mod asset_utils;
+pub mod nft_client;
+pub mod dex_client;
@@ -0,0 +1,87 @@
+use soroban_sdk::{Address, Bytes, Env, IntoVal, InvokeError, Symbol, Val, Vec, vec};
+use crate::error::SettlementError;
+
+// Error codes returned by `nft_contract::ContractError`
+const NFT_UNAUTHORIZED: u32 = 3;
+const NFT_NOT_PERMITTED: u32 = 4;
+const NFT_TOKEN_NOT_FOUND: u32 = 5;
+const NFT_NOT_OWNER: u32 = 11;
+
+/// Minimal client for the NFT contract, invoked by function name
+pub struct INftClient<'a> {
+    env: &'a Env,
+    address: &'a Address,
+}
+
+impl<'a> INftClient<'a> {
+    /// Create a client for the NFT contract at `address`
+    pub fn new(env: &'a Env, address: &'a Address) -> Self {
+        Self { env, address }
+    }
+
+    /// Check whether `owner` currently holds `token_id`
+    pub fn owns(&self, owner: &Address, token_id: u64) -> Result<bool, SettlementError> {
+        let args: Vec<Val> = vec![self.env, token_id.into_val(self.env)];
+        let holder: Address = self.invoke("owner_of", args)?;
+        Ok(holder == *owner)
+    }
+
+    /// Move `token_id` from `from` to `to`, with `operator` as the approved spender
+    pub fn safe_transfer_from(
+        &self,
+        operator: &Address,
+        from: &Address,
+        to: &Address,
+        token_id: u64,
+        data: &Bytes
+    ) -> Result<(), SettlementError> {
+        let args: Vec<Val> = vec![
+            self.env,
+            operator.into_val(self.env),
+            from.into_val(self.env),
+            to.into_val(self.env),
+            token_id.into_val(self.env),
+            data.into_val(self.env),
+        ];
+        self.invoke("safe_transfer_from", args)
+    }
+
+    /// Ask the NFT contract who is owed a royalty on `token_id` at `sale_price`, and how much
+    pub fn get_royalty_info(&self, token_id: u64, sale_price: i128) -> Result<(Address, i128), SettlementError> {
+        let args: Vec<Val> = vec![self.env, token_id.into_val(self.env), sale_price.into_val(self.env)];
+        self.invoke("get_royalty_info", args)
+    }
+
+    /// Look up the mutation hook registered for `token_id`; collections
+    /// without hook support report none
+    pub fn get_mutation_hook(&self, token_id: u64) -> Option<Address> {
+        let args: Vec<Val> = vec![self.env, token_id.into_val(self.env)];
+        self.invoke::<Option<Address>>("get_mutation_hook", args).ok().flatten()
+    }
+
+    /// Internal: Invoke the NFT contract, converting failures into settlement errors
+    fn invoke<T>(&self, function: &str, args: Vec<Val>) -> Result<T, SettlementError>
+    where
+        T: soroban_sdk::TryFromVal<Env, Val>,
+    {
+        let function = Symbol::new(self.env, function);
+        match self.env.try_invoke_contract::<T, InvokeError>(self.address, &function, args) {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(_)) => Err(SettlementError::InvalidState),
+            Err(Ok(error)) => Err(map_nft_error(error)),
+            Err(Err(error)) => Err(map_nft_error(error)),
+        }
+    }
+}
+
+/// Map an NFT contract failure to the closest settlement error
+fn map_nft_error(error: InvokeError) -> SettlementError {
+    match error {
+        InvokeError::Contract(NFT_TOKEN_NOT_FOUND) => SettlementError::NotFound,
+        InvokeError::Contract(NFT_UNAUTHORIZED | NFT_NOT_PERMITTED | NFT_NOT_OWNER) => {
+            SettlementError::Unauthorized
+        }
+        // Soulbound, paused, pending transfer, rejected by receiver, or not an NFT contract
+        _ => SettlementError::InvalidState,
+    }
+}
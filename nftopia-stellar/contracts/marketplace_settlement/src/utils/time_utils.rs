@@ -50,12 +50,7 @@ pub fn has_time_elapsed(reference: u64, required_seconds: u64, env: &Env) -> boo
 
 /// Calculate remaining time until expiration
 pub fn remaining_time(expires_at: u64, env: &Env) -> u64 {
-    let now = current_timestamp(env);
-    if now >= expires_at {
-        0
-    } else {
-        expires_at - now
-    }
+    expires_at.saturating_sub(current_timestamp(env))
 }
 
 /// Validate auction timing parameters
@@ -67,7 +62,7 @@ pub fn validate_auction_timing(
 ) -> Result<(), SettlementError> {
     let now = current_timestamp(env);
 
-    // Start time must be in the future or now
+    // Start time must be now, or later for a scheduled auction
     if start_time < now {
         return Err(SettlementError::InvalidAmount);
     }
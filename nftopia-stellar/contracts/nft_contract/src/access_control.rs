@@ -0,0 +1,260 @@
+use crate::error::ContractError;
+use crate::events;
+use crate::storage::DataKey;
+use crate::types::{PendingAdmin, Role};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Default window (7 days) a proposed admin has to accept the handoff.
+pub const DEFAULT_ADMIN_TRANSFER_TIMEOUT: u64 = 7 * 24 * 60 * 60;
+
+pub fn initialize(env: &Env, admin: &Address) {
+    env.storage().instance().set(&DataKey::Admin, admin);
+}
+
+pub fn get_admin(env: &Env) -> Result<Address, ContractError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(ContractError::NotInitialized)
+}
+
+pub fn set_admin(env: &Env, new_admin: &Address) {
+    env.storage().instance().set(&DataKey::Admin, new_admin);
+}
+
+pub fn get_admin_transfer_timeout(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::AdminTransferTimeout)
+        .unwrap_or(DEFAULT_ADMIN_TRANSFER_TIMEOUT)
+}
+
+pub fn set_admin_transfer_timeout(env: &Env, timeout: u64) -> Result<(), ContractError> {
+    if timeout == 0 {
+        return Err(ContractError::InvalidInput);
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::AdminTransferTimeout, &timeout);
+    Ok(())
+}
+
+pub fn get_pending_admin(env: &Env) -> Option<PendingAdmin> {
+    env.storage().instance().get(&DataKey::PendingAdmin)
+}
+
+/// Records `new_admin` as the pending admin. The handoff only completes once
+/// the pending admin calls `accept_admin` before the proposal expires.
+pub fn propose_admin(env: &Env, current_admin: &Address, new_admin: &Address) -> PendingAdmin {
+    let expires_at = env
+        .ledger()
+        .timestamp()
+        .saturating_add(get_admin_transfer_timeout(env));
+    let pending = PendingAdmin {
+        new_admin: new_admin.clone(),
+        expires_at,
+    };
+    env.storage()
+        .instance()
+        .set(&DataKey::PendingAdmin, &pending);
+
+    events::emit_admin_proposed(env, current_admin.clone(), new_admin.clone(), expires_at);
+    pending
+}
+
+/// Completes a pending handoff. Requires the pending admin's signature.
+pub fn accept_admin(env: &Env) -> Result<Address, ContractError> {
+    let pending = get_pending_admin(env).ok_or(ContractError::NoPendingAdmin)?;
+    if env.ledger().timestamp() > pending.expires_at {
+        return Err(ContractError::AdminProposalExpired);
+    }
+    pending.new_admin.require_auth();
+
+    let previous_admin = get_admin(env)?;
+    set_admin(env, &pending.new_admin);
+    env.storage().instance().remove(&DataKey::PendingAdmin);
+
+    events::emit_admin_changed(env, previous_admin, pending.new_admin.clone());
+    Ok(pending.new_admin)
+}
+
+/// Hands the collection to `new_owner` in one step: it becomes admin, and
+/// every Minter or Burner role held by `role_holders` moves to it. Any pending
+/// admin proposal is dropped.
+pub fn transfer_ownership(
+    env: &Env,
+    previous_owner: &Address,
+    new_owner: &Address,
+    role_holders: &Vec<Address>,
+) -> Result<(), ContractError> {
+    set_admin(env, new_owner);
+    env.storage().instance().remove(&DataKey::PendingAdmin);
+
+    for role in [Role::Minter, Role::Burner] {
+        let mut held = false;
+        for holder in role_holders.iter() {
+            if holder != *new_owner && has_role(env, role, &holder) {
+                revoke_role(env, role, &holder)?;
+                held = true;
+            }
+        }
+        if held {
+            grant_role(env, role, new_owner)?;
+        }
+    }
+
+    events::emit_collection_owner_changed(
+        env,
+        previous_owner.clone(),
+        new_owner.clone(),
+        role_holders.clone(),
+    );
+    Ok(())
+}
+
+pub fn cancel_admin_proposal(env: &Env) -> Result<(), ContractError> {
+    if get_pending_admin(env).is_none() {
+        return Err(ContractError::NoPendingAdmin);
+    }
+    env.storage().instance().remove(&DataKey::PendingAdmin);
+    Ok(())
+}
+
+/// Loads the admin and requires its signature on the current invocation.
+pub fn require_admin(env: &Env) -> Result<Address, ContractError> {
+    let admin = get_admin(env)?;
+    admin.require_auth();
+    Ok(admin)
+}
+
+pub fn get_nonce(env: &Env, address: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AccountNonce(address.clone()))
+        .unwrap_or(0)
+}
+
+pub fn increment_nonce(env: &Env, address: &Address) -> u64 {
+    let nonce = get_nonce(env, address).saturating_add(1);
+    env.storage()
+        .persistent()
+        .set(&DataKey::AccountNonce(address.clone()), &nonce);
+    nonce
+}
+
+pub fn is_paused(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKey::Paused)
+        .unwrap_or(false)
+}
+
+pub fn set_paused(env: &Env, paused: bool) {
+    env.storage().instance().set(&DataKey::Paused, &paused);
+}
+
+/// Guard for state-changing token operations while the contract is paused.
+pub fn require_not_paused(env: &Env) -> Result<(), ContractError> {
+    if is_paused(env) {
+        return Err(ContractError::ContractPaused);
+    }
+    Ok(())
+}
+
+pub fn role_from_u32(role: u32) -> Result<Role, ContractError> {
+    match role {
+        0 => Ok(Role::Admin),
+        1 => Ok(Role::Minter),
+        2 => Ok(Role::Burner),
+        3 => Ok(Role::MetadataUpdater),
+        _ => Err(ContractError::InvalidRole),
+    }
+}
+
+/// The admin implicitly holds every role. Time-limited grants stop counting
+/// once the ledger timestamp passes their expiry.
+pub fn has_role(env: &Env, role: Role, address: &Address) -> bool {
+    if let Ok(admin) = get_admin(env)
+        && admin == *address
+    {
+        return true;
+    }
+
+    let granted: bool = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Role(address.clone(), role))
+        .unwrap_or(false);
+    if !granted {
+        return false;
+    }
+
+    match get_role_expiry(env, role, address) {
+        Some(expiry) => env.ledger().timestamp() < expiry,
+        None => true,
+    }
+}
+
+pub fn get_role_expiry(env: &Env, role: Role, address: &Address) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RoleExpiry(address.clone(), role))
+}
+
+pub fn require_role(env: &Env, role: Role, address: &Address) -> Result<(), ContractError> {
+    if !has_role(env, role, address) {
+        return Err(ContractError::Unauthorized);
+    }
+    Ok(())
+}
+
+pub fn grant_role(env: &Env, role: Role, address: &Address) -> Result<(), ContractError> {
+    if role == Role::Admin {
+        return Err(ContractError::InvalidRole);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::Role(address.clone(), role), &true);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::RoleExpiry(address.clone(), role));
+
+    events::emit_role_granted(env, role, address.clone());
+    Ok(())
+}
+
+/// Grants `role` until `expiry_timestamp` (ledger seconds).
+pub fn grant_role_with_expiry(
+    env: &Env,
+    role: Role,
+    address: &Address,
+    expiry_timestamp: u64,
+) -> Result<(), ContractError> {
+    if expiry_timestamp <= env.ledger().timestamp() {
+        return Err(ContractError::InvalidInput);
+    }
+
+    grant_role(env, role, address)?;
+    env.storage().persistent().set(
+        &DataKey::RoleExpiry(address.clone(), role),
+        &expiry_timestamp,
+    );
+    Ok(())
+}
+
+pub fn revoke_role(env: &Env, role: Role, address: &Address) -> Result<(), ContractError> {
+    if role == Role::Admin {
+        return Err(ContractError::InvalidRole);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Role(address.clone(), role));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::RoleExpiry(address.clone(), role));
+
+    events::emit_role_revoked(env, role, address.clone());
+    Ok(())
+}
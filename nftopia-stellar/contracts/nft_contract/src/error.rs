@@ -0,0 +1,36 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ContractError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    NotPermitted = 4,
+    TokenNotFound = 5,
+    TokenAlreadyExists = 6,
+    InvalidRole = 7,
+    InvalidRoyalty = 8,
+    MetadataFrozen = 9,
+    InvalidInput = 10,
+    NotOwner = 11,
+    NoPendingAdmin = 12,
+    AdminProposalExpired = 13,
+    MaxSupplyReached = 14,
+    TokenIsSoulbound = 15,
+    ContractPaused = 16,
+    ProvenanceAlreadySet = 17,
+    IndexOutOfBounds = 18,
+    InvalidReceiver = 19,
+    TransferPending = 20,
+    TransferNotClaimable = 21,
+    VoucherExpired = 22,
+    VoucherAlreadyUsed = 23,
+    BatchTooLarge = 24,
+    TokenLocked = 25,
+    TokenHasChildren = 26,
+    TokenIsAttached = 27,
+    TokenExpired = 28,
+    MintlistAlreadyClaimed = 29,
+}
@@ -0,0 +1,258 @@
+use crate::types::{Role, TokenAttribute};
+use soroban_sdk::{Address, Env, Vec, contractevent};
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct MintEvent {
+    pub to: Address,
+    pub token_id: u64,
+}
+
+/// A single event for a whole airdrop; token IDs `first_token_id` through
+/// `last_token_id` were minted in recipient order.
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AirdropEvent {
+    pub sender: Address,
+    pub first_token_id: u64,
+    pub last_token_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct BurnEvent {
+    pub from: Address,
+    pub token_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct TransferEvent {
+    pub from: Address,
+    pub to: Address,
+    pub token_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct BatchTransferEvent {
+    pub from: Address,
+    pub to: Address,
+    pub token_ids: Vec<u64>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct TokenLockedEvent {
+    pub token_id: u64,
+    pub locker: Address,
+    pub unlock_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct TokenUnlockedEvent {
+    pub token_id: u64,
+    pub locker: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct TokenRenewedEvent {
+    pub token_id: u64,
+    pub expires_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ApprovalEvent {
+    pub owner: Address,
+    pub approved: Address,
+    pub token_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ApprovalForAllEvent {
+    pub owner: Address,
+    pub operator: Address,
+    pub approved: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AdminProposedEvent {
+    pub current_admin: Address,
+    pub new_admin: Address,
+    pub expires_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AdminChangedEvent {
+    pub previous_admin: Address,
+    pub new_admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CollectionOwnerChangedEvent {
+    pub previous_owner: Address,
+    pub new_owner: Address,
+    pub role_holders: Vec<Address>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct MetadataFrozenEvent {
+    pub admin: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct TokenAttributesUpdatedEvent {
+    pub token_id: u64,
+    pub old_attributes: Vec<TokenAttribute>,
+    pub new_attributes: Vec<TokenAttribute>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RoleGrantedEvent {
+    pub role: Role,
+    pub account: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RoleRevokedEvent {
+    pub role: Role,
+    pub account: Address,
+}
+
+pub fn emit_mint(env: &Env, to: Address, token_id: u64) {
+    MintEvent { to, token_id }.publish(env);
+}
+
+pub fn emit_airdrop(env: &Env, sender: Address, first_token_id: u64, last_token_id: u64) {
+    AirdropEvent {
+        sender,
+        first_token_id,
+        last_token_id,
+    }
+    .publish(env);
+}
+
+pub fn emit_burn(env: &Env, from: Address, token_id: u64) {
+    BurnEvent { from, token_id }.publish(env);
+}
+
+pub fn emit_transfer(env: &Env, from: Address, to: Address, token_id: u64) {
+    TransferEvent { from, to, token_id }.publish(env);
+}
+
+pub fn emit_batch_transfer(env: &Env, from: Address, to: Address, token_ids: Vec<u64>) {
+    BatchTransferEvent {
+        from,
+        to,
+        token_ids,
+    }
+    .publish(env);
+}
+
+pub fn emit_token_locked(env: &Env, token_id: u64, locker: Address, unlock_at: u64) {
+    TokenLockedEvent {
+        token_id,
+        locker,
+        unlock_at,
+    }
+    .publish(env);
+}
+
+pub fn emit_token_unlocked(env: &Env, token_id: u64, locker: Address) {
+    TokenUnlockedEvent { token_id, locker }.publish(env);
+}
+
+pub fn emit_token_renewed(env: &Env, token_id: u64, expires_at: u64) {
+    TokenRenewedEvent {
+        token_id,
+        expires_at,
+    }
+    .publish(env);
+}
+
+pub fn emit_approval(env: &Env, owner: Address, approved: Address, token_id: u64) {
+    ApprovalEvent {
+        owner,
+        approved,
+        token_id,
+    }
+    .publish(env);
+}
+
+pub fn emit_approval_for_all(env: &Env, owner: Address, operator: Address, approved: bool) {
+    ApprovalForAllEvent {
+        owner,
+        operator,
+        approved,
+    }
+    .publish(env);
+}
+
+pub fn emit_role_granted(env: &Env, role: Role, account: Address) {
+    RoleGrantedEvent { role, account }.publish(env);
+}
+
+pub fn emit_role_revoked(env: &Env, role: Role, account: Address) {
+    RoleRevokedEvent { role, account }.publish(env);
+}
+
+pub fn emit_admin_proposed(env: &Env, current_admin: Address, new_admin: Address, expires_at: u64) {
+    AdminProposedEvent {
+        current_admin,
+        new_admin,
+        expires_at,
+    }
+    .publish(env);
+}
+
+pub fn emit_admin_changed(env: &Env, previous_admin: Address, new_admin: Address) {
+    AdminChangedEvent {
+        previous_admin,
+        new_admin,
+    }
+    .publish(env);
+}
+
+pub fn emit_collection_owner_changed(
+    env: &Env,
+    previous_owner: Address,
+    new_owner: Address,
+    role_holders: Vec<Address>,
+) {
+    CollectionOwnerChangedEvent {
+        previous_owner,
+        new_owner,
+        role_holders,
+    }
+    .publish(env);
+}
+
+pub fn emit_metadata_frozen(env: &Env, admin: Address, timestamp: u64) {
+    MetadataFrozenEvent { admin, timestamp }.publish(env);
+}
+
+pub fn emit_token_attributes_updated(
+    env: &Env,
+    token_id: u64,
+    old_attributes: Vec<TokenAttribute>,
+    new_attributes: Vec<TokenAttribute>,
+) {
+    TokenAttributesUpdatedEvent {
+        token_id,
+        old_attributes,
+        new_attributes,
+    }
+    .publish(env);
+}
@@ -0,0 +1,70 @@
+use crate::error::ContractError;
+use crate::storage::DataKey;
+use crate::token;
+use crate::transfer;
+use soroban_sdk::{Address, Env, token as sac};
+
+/// Returns `(fraction_token_contract, fractions)` while `token_id` is
+/// fractionalized.
+pub fn get_fractionalization(env: &Env, token_id: u64) -> Option<(Address, u64)> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FractionalizationData(token_id))
+}
+
+/// Locks `token_id` to this contract with no expiry and mints `fractions`
+/// units of `fraction_token_contract` to the owner. This contract must be the
+/// fraction token's admin. Only the owner may fractionalize.
+pub fn fractionalize(
+    env: &Env,
+    token_id: u64,
+    fractions: u64,
+    fraction_token_contract: &Address,
+    sender: &Address,
+) -> Result<(), ContractError> {
+    if fractions == 0 {
+        return Err(ContractError::InvalidInput);
+    }
+    if token::get_token(env, token_id)?.soulbound {
+        return Err(ContractError::TokenIsSoulbound);
+    }
+    token::lock_token(
+        env,
+        token_id,
+        &env.current_contract_address(),
+        u64::MAX,
+        sender,
+    )?;
+
+    env.storage().persistent().set(
+        &DataKey::FractionalizationData(token_id),
+        &(fraction_token_contract.clone(), fractions),
+    );
+    sac::StellarAssetClient::new(env, fraction_token_contract).mint(sender, &(fractions as i128));
+    Ok(())
+}
+
+/// Burns every fraction of `token_id` from `sender` and unlocks the NFT,
+/// moving it to `sender` if someone else holds it. The sender's authorization
+/// is the caller's responsibility.
+pub fn reconstitute(env: &Env, token_id: u64, sender: &Address) -> Result<(), ContractError> {
+    let (fraction_token_contract, fractions) =
+        get_fractionalization(env, token_id).ok_or(ContractError::NotPermitted)?;
+    let fraction_token = sac::TokenClient::new(env, &fraction_token_contract);
+    if fraction_token.balance(sender) < fractions as i128 {
+        return Err(ContractError::NotOwner);
+    }
+
+    fraction_token.burn(sender, &(fractions as i128));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::FractionalizationData(token_id));
+    token::unlock_token(env, token_id, &env.current_contract_address())?;
+
+    let mut nft = token::get_token(env, token_id)?;
+    if nft.owner != *sender {
+        let owner = nft.owner.clone();
+        transfer::move_token(env, &mut nft, &owner, sender);
+    }
+    Ok(())
+}
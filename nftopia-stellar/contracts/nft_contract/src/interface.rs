@@ -0,0 +1,112 @@
+use crate::error::ContractError;
+use crate::types::{RoyaltyInfo, TokenAttribute};
+use soroban_sdk::{Address, Bytes, Env, String, Vec, contractclient};
+
+/// Value `on_nft_received` must return to accept a token (mirrors the
+/// ERC-721 receiver selector).
+pub const NFT_RECEIVED_SELECTOR: [u8; 4] = [0x15, 0x0b, 0x7a, 0x02];
+
+/// Hook implemented by contracts that want to receive tokens through
+/// `transfer_with_data` / `safe_transfer_from`.
+#[contractclient(name = "NftReceiverClient")]
+pub trait INftReceiver {
+    fn on_nft_received(
+        env: Env,
+        operator: Address,
+        from: Address,
+        token_id: u64,
+        data: Bytes,
+    ) -> Bytes;
+}
+
+/// Hook a token owner can register for dynamic NFTs. The marketplace calls
+/// `on_sold` after settling a sale of the token, typically so a game can
+/// update the token's attributes based on `final_price`.
+#[contractclient(name = "MutationHookClient")]
+pub trait IMutationHook {
+    fn on_sold(env: Env, token_id: u64, final_price: i128);
+}
+
+/// Core NFT interface implemented by `NftContract`.
+pub trait INft {
+    fn mint(
+        env: Env,
+        to: Address,
+        metadata_uri: String,
+        attributes: Vec<TokenAttribute>,
+        royalty_override: Option<RoyaltyInfo>,
+        soulbound: Option<bool>,
+        expires_at: Option<u64>,
+        sender: Address,
+    ) -> Result<u64, ContractError>;
+
+    fn batch_mint(
+        env: Env,
+        recipients: Vec<Address>,
+        metadata_uris: Vec<String>,
+        attributes: Vec<Vec<TokenAttribute>>,
+        minter: Address,
+    ) -> Result<Vec<u64>, ContractError>;
+
+    fn burn(env: Env, token_id: u64, sender: Address) -> Result<(), ContractError>;
+
+    fn transfer(env: Env, from: Address, to: Address, token_id: u64) -> Result<(), ContractError>;
+
+    fn safe_transfer_from(
+        env: Env,
+        operator: Address,
+        from: Address,
+        to: Address,
+        token_id: u64,
+        data: Bytes,
+    ) -> Result<(), ContractError>;
+
+    fn batch_transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        token_ids: Vec<u64>,
+    ) -> Result<(), ContractError>;
+
+    fn approve(
+        env: Env,
+        approved: Address,
+        token_id: u64,
+        sender: Address,
+    ) -> Result<(), ContractError>;
+
+    fn set_approval_for_all(
+        env: Env,
+        operator: Address,
+        approved: bool,
+        sender: Address,
+    ) -> Result<(), ContractError>;
+
+    fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool;
+
+    fn get_approved(env: Env, token_id: u64) -> Option<Address>;
+
+    fn owner_of(env: Env, token_id: u64) -> Result<Address, ContractError>;
+
+    fn balance_of(env: Env, owner: Address) -> u32;
+
+    fn total_supply(env: Env) -> u64;
+
+    fn get_royalty_info(
+        env: Env,
+        token_id: u64,
+        sale_price: i128,
+    ) -> Result<(Address, i128), ContractError>;
+
+    fn get_royalty_split_info(
+        env: Env,
+        token_id: u64,
+        sale_price: i128,
+    ) -> Result<Vec<(Address, i128)>, ContractError>;
+
+    fn token_uri(env: Env, token_id: u64) -> Result<String, ContractError>;
+
+    fn name(env: Env) -> Result<String, ContractError>;
+
+    fn symbol(env: Env) -> Result<String, ContractError>;
+}
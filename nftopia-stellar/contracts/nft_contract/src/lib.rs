@@ -1 +1,777 @@
+#![no_std]
+#![allow(clippy::too_many_arguments)]
+pub mod access_control;
+pub mod error;
+pub mod events;
+pub mod fraction;
+pub mod interface;
+pub mod metadata;
+pub mod mintlist;
+pub mod payment;
+pub mod royalty;
+pub mod storage;
+pub mod token;
+pub mod transfer;
+pub mod types;
+pub mod voucher;
 
+use crate::error::ContractError;
+use crate::interface::INft;
+use crate::storage::DataKey;
+use crate::types::{
+    CollectionConfig, LazyMintVoucher, PendingAdmin, Role, RoyaltyInfo, RoyaltySplit,
+    ScheduledTransfer, TokenAttribute, TokenData,
+};
+use soroban_sdk::{Address, Bytes, BytesN, Env, String, Vec, contract, contractimpl};
+
+#[contract]
+pub struct NftContract;
+
+#[contractimpl]
+impl NftContract {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        config: CollectionConfig,
+    ) -> Result<(), ContractError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+        royalty::validate_royalty(&config.royalty_default)?;
+
+        access_control::initialize(&env, &admin);
+        metadata::set_collection_config(&env, &config)
+    }
+
+    pub fn get_admin(env: Env) -> Result<Address, ContractError> {
+        access_control::get_admin(&env)
+    }
+
+    /// Starts a two-step admin handoff; `new_admin` must call `accept_admin`
+    /// before the transfer timeout elapses.
+    pub fn propose_admin(env: Env, new_admin: Address) -> Result<PendingAdmin, ContractError> {
+        let admin = access_control::require_admin(&env)?;
+        Ok(access_control::propose_admin(&env, &admin, &new_admin))
+    }
+
+    pub fn accept_admin(env: Env) -> Result<Address, ContractError> {
+        access_control::accept_admin(&env)
+    }
+
+    pub fn cancel_admin_proposal(env: Env) -> Result<(), ContractError> {
+        access_control::require_admin(&env)?;
+        access_control::cancel_admin_proposal(&env)
+    }
+
+    /// Makes `new_owner` the admin straight away, moving the Minter and
+    /// Burner roles of `role_holders` to it. `sender` must be the current
+    /// admin.
+    pub fn transfer_collection_ownership(
+        env: Env,
+        new_owner: Address,
+        role_holders: Vec<Address>,
+        sender: Address,
+    ) -> Result<(), ContractError> {
+        let admin = access_control::require_admin(&env)?;
+        if admin != sender {
+            return Err(ContractError::Unauthorized);
+        }
+        access_control::transfer_ownership(&env, &admin, &new_owner, &role_holders)
+    }
+
+    pub fn get_pending_admin(env: Env) -> Option<PendingAdmin> {
+        access_control::get_pending_admin(&env)
+    }
+
+    pub fn set_admin_transfer_timeout(env: Env, timeout: u64) -> Result<(), ContractError> {
+        access_control::require_admin(&env)?;
+        access_control::set_admin_transfer_timeout(&env, timeout)
+    }
+
+    /// Halts minting, burning and transfers until `unpause` is called.
+    pub fn pause(env: Env) -> Result<(), ContractError> {
+        access_control::require_admin(&env)?;
+        access_control::set_paused(&env, true);
+        Ok(())
+    }
+
+    pub fn unpause(env: Env) -> Result<(), ContractError> {
+        access_control::require_admin(&env)?;
+        access_control::set_paused(&env, false);
+        Ok(())
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        access_control::is_paused(&env)
+    }
+
+    pub fn grant_role(env: Env, role: u32, address: Address) -> Result<(), ContractError> {
+        access_control::require_admin(&env)?;
+        let role = access_control::role_from_u32(role)?;
+        access_control::grant_role(&env, role, &address)
+    }
+
+    pub fn grant_role_with_expiry(
+        env: Env,
+        role: u32,
+        address: Address,
+        expiry_timestamp: u64,
+    ) -> Result<(), ContractError> {
+        access_control::require_admin(&env)?;
+        let role = access_control::role_from_u32(role)?;
+        access_control::grant_role_with_expiry(&env, role, &address, expiry_timestamp)
+    }
+
+    pub fn revoke_role(env: Env, role: u32, address: Address) -> Result<(), ContractError> {
+        access_control::require_admin(&env)?;
+        let role = access_control::role_from_u32(role)?;
+        access_control::revoke_role(&env, role, &address)
+    }
+
+    pub fn has_role(env: Env, role: u32, address: Address) -> Result<bool, ContractError> {
+        let role = access_control::role_from_u32(role)?;
+        Ok(access_control::has_role(&env, role, &address))
+    }
+
+    pub fn get_collection_config(env: Env) -> Result<CollectionConfig, ContractError> {
+        metadata::get_collection_config(&env)
+    }
+
+    pub fn set_collection_config(
+        env: Env,
+        config: CollectionConfig,
+        sender: Address,
+    ) -> Result<(), ContractError> {
+        sender.require_auth();
+        access_control::require_role(&env, Role::Admin, &sender)?;
+        royalty::validate_royalty(&config.royalty_default)?;
+        if let Some(current) = metadata::get_provenance_hash(&env)?
+            && config.provenance_hash != Some(current)
+        {
+            return Err(ContractError::ProvenanceAlreadySet);
+        }
+        metadata::set_collection_config(&env, &config)
+    }
+
+    /// Permanently locks collection and token metadata.
+    pub fn freeze_metadata(env: Env) -> Result<(), ContractError> {
+        let admin = access_control::require_admin(&env)?;
+        metadata::freeze_metadata(&env)?;
+        events::emit_metadata_frozen(&env, admin, env.ledger().timestamp());
+        Ok(())
+    }
+
+    pub fn set_mintlist_root(env: Env, root: Bytes) -> Result<(), ContractError> {
+        access_control::require_admin(&env)?;
+        mintlist::set_root(&env, &root)
+    }
+
+    pub fn get_mintlist_root(env: Env) -> Option<Bytes> {
+        mintlist::get_root(&env)
+    }
+
+    /// Presale mint for addresses included in the mintlist Merkle tree. The
+    /// recipient signs and is recorded as the creator. Each address mints once.
+    pub fn mint_with_proof(
+        env: Env,
+        to: Address,
+        metadata_uri: String,
+        attributes: Vec<TokenAttribute>,
+        proof: Vec<Bytes>,
+    ) -> Result<u64, ContractError> {
+        to.require_auth();
+        mintlist::verify(&env, &to, &proof)?;
+        mintlist::claim(&env, &to)?;
+        token::mint_token(&env, &to, metadata_uri, attributes, false, &to, None)
+    }
+
+    pub fn approve_operator_with_expiry(
+        env: Env,
+        operator: Address,
+        approved: bool,
+        expiry: u64,
+        sender: Address,
+    ) -> Result<(), ContractError> {
+        sender.require_auth();
+        transfer::approve_operator_with_expiry(&env, &sender, &operator, approved, expiry)
+    }
+
+    /// Burns every token in `token_ids`, each of which must be signed for by
+    /// its owner. Any failure reverts the whole batch.
+    pub fn batch_burn(env: Env, token_ids: Vec<u64>) -> Result<(), ContractError> {
+        for token_id in token_ids.iter() {
+            let owner = token::load_token(&env, token_id)?.owner;
+            owner.require_auth();
+            token::burn_token(&env, token_id, &owner)?;
+        }
+        Ok(())
+    }
+
+    pub fn get_tokens_of_owner(env: Env, owner: Address, offset: u32, limit: u32) -> Vec<u64> {
+        token::tokens_of_owner(&env, &owner, offset, limit)
+    }
+
+    /// Sets the asset accepted by `mint_with_payment`.
+    pub fn set_mint_payment_asset(env: Env, asset: Address) -> Result<(), ContractError> {
+        access_control::require_admin(&env)?;
+        payment::set_payment_asset(&env, &asset);
+        Ok(())
+    }
+
+    /// Public mint that charges the collection's `mint_price` in the
+    /// configured payment asset before minting to `to`.
+    pub fn mint_with_payment(
+        env: Env,
+        to: Address,
+        metadata_uri: String,
+        attributes: Vec<TokenAttribute>,
+        payment_asset: Address,
+    ) -> Result<u64, ContractError> {
+        to.require_auth();
+
+        let price = metadata::get_collection_config(&env)?
+            .mint_price
+            .ok_or(ContractError::NotPermitted)?;
+        if payment::get_payment_asset(&env) != Some(payment_asset.clone()) {
+            return Err(ContractError::NotPermitted);
+        }
+
+        payment::collect_payment(&env, &payment_asset, &to, price)?;
+        token::mint_token(&env, &to, metadata_uri, attributes, false, &to, None)
+    }
+
+    pub fn get_mint_proceeds(env: Env, asset: Address) -> i128 {
+        payment::get_mint_proceeds(&env, &asset)
+    }
+
+    /// Sends all accumulated mint proceeds in `asset` to `to`.
+    pub fn withdraw_mint_proceeds(
+        env: Env,
+        asset: Address,
+        to: Address,
+    ) -> Result<i128, ContractError> {
+        access_control::require_admin(&env)?;
+        Ok(payment::withdraw_proceeds(&env, &asset, &to))
+    }
+
+    pub fn get_token_data(env: Env, token_id: u64) -> Result<TokenData, ContractError> {
+        token::get_token(&env, token_id)
+    }
+
+    pub fn get_token_attributes(
+        env: Env,
+        token_id: u64,
+    ) -> Result<Vec<TokenAttribute>, ContractError> {
+        Ok(token::get_token(&env, token_id)?.attributes)
+    }
+
+    /// Mints a token with the same metadata to every recipient. The sender
+    /// needs the Minter role; at most `get_max_airdrop_batch_size`
+    /// recipients are accepted per call.
+    pub fn airdrop_batch(
+        env: Env,
+        recipients: Vec<Address>,
+        metadata_uri: String,
+        attributes: Vec<TokenAttribute>,
+        sender: Address,
+    ) -> Result<Vec<u64>, ContractError> {
+        sender.require_auth();
+        access_control::require_role(&env, Role::Minter, &sender)?;
+        token::airdrop(&env, &recipients, metadata_uri, attributes, &sender)
+    }
+
+    pub fn get_max_airdrop_batch_size(env: Env) -> u32 {
+        token::get_max_airdrop_batch_size(&env)
+    }
+
+    pub fn set_max_airdrop_batch_size(env: Env, size: u32) -> Result<(), ContractError> {
+        access_control::require_admin(&env)?;
+        token::set_max_airdrop_batch_size(&env, size)
+    }
+
+    pub fn token_by_index(env: Env, index: u32) -> Result<u64, ContractError> {
+        token::token_by_index(&env, index)
+    }
+
+    pub fn token_of_owner_by_index(
+        env: Env,
+        owner: Address,
+        index: u32,
+    ) -> Result<u64, ContractError> {
+        token::token_of_owner_by_index(&env, &owner, index)
+    }
+
+    /// Locks a token in place for `locker` for `duration_seconds`; returns
+    /// when the owner may unlock it. Owner only.
+    pub fn lock_token(
+        env: Env,
+        token_id: u64,
+        locker: Address,
+        duration_seconds: u64,
+        sender: Address,
+    ) -> Result<u64, ContractError> {
+        sender.require_auth();
+        token::lock_token(&env, token_id, &locker, duration_seconds, &sender)
+    }
+
+    /// Releases a lock: any time for the locker, after it lapses for the
+    /// owner.
+    pub fn unlock_token(env: Env, token_id: u64, sender: Address) -> Result<(), ContractError> {
+        sender.require_auth();
+        token::unlock_token(&env, token_id, &sender)
+    }
+
+    pub fn get_token_lock(env: Env, token_id: u64) -> Option<(Address, u64)> {
+        token::get_lock(&env, token_id)
+    }
+
+    /// Locks a token and issues `fractions` units of
+    /// `fraction_token_contract`, which this contract must administer, to
+    /// its owner.
+    pub fn fractionalize_token(
+        env: Env,
+        token_id: u64,
+        fractions: u64,
+        fraction_token_contract: Address,
+        sender: Address,
+    ) -> Result<(), ContractError> {
+        sender.require_auth();
+        fraction::fractionalize(&env, token_id, fractions, &fraction_token_contract, &sender)
+    }
+
+    /// Burns all of a token's fractions from `sender`, who gets the unlocked
+    /// token.
+    pub fn reconstitute_token(
+        env: Env,
+        token_id: u64,
+        sender: Address,
+    ) -> Result<(), ContractError> {
+        sender.require_auth();
+        fraction::reconstitute(&env, token_id, &sender)
+    }
+
+    pub fn get_fractionalization(env: Env, token_id: u64) -> Option<(Address, u64)> {
+        fraction::get_fractionalization(&env, token_id)
+    }
+
+    /// Attaches `child_id` below `parent_id`; both must belong to `sender`.
+    /// The child then moves with its parent.
+    pub fn attach_child(
+        env: Env,
+        parent_id: u64,
+        child_id: u64,
+        sender: Address,
+    ) -> Result<(), ContractError> {
+        sender.require_auth();
+        token::attach_child(&env, parent_id, child_id, &sender)
+    }
+
+    pub fn detach_child(
+        env: Env,
+        parent_id: u64,
+        child_id: u64,
+        sender: Address,
+    ) -> Result<(), ContractError> {
+        sender.require_auth();
+        token::detach_child(&env, parent_id, child_id, &sender)
+    }
+
+    pub fn get_token_children(env: Env, token_id: u64) -> Vec<u64> {
+        token::get_children(&env, token_id)
+    }
+
+    /// Moves several of `from`'s tokens to `to` as owner or approved
+    /// operator `sender`. Fails as a whole if any token can't be moved.
+    pub fn batch_transfer_from(
+        env: Env,
+        from: Address,
+        to: Address,
+        token_ids: Vec<u64>,
+        sender: Address,
+    ) -> Result<(), ContractError> {
+        sender.require_auth();
+        transfer::batch_transfer_from(&env, &from, &to, &token_ids, &sender)
+    }
+
+    /// Transfers `token_id` and forwards `data` to the recipient's
+    /// `on_nft_received` hook when the recipient is a contract.
+    pub fn transfer_with_data(
+        env: Env,
+        operator: Address,
+        from: Address,
+        to: Address,
+        token_id: u64,
+        data: Bytes,
+    ) -> Result<(), ContractError> {
+        operator.require_auth();
+        transfer::safe_transfer(&env, &operator, &from, &to, token_id, data)
+    }
+
+    /// Commits `token_id` to `to`, who can claim it from `claimable_at`.
+    pub fn schedule_transfer(
+        env: Env,
+        to: Address,
+        token_id: u64,
+        claimable_at: u64,
+        sender: Address,
+    ) -> Result<(), ContractError> {
+        sender.require_auth();
+        transfer::schedule_transfer(&env, &to, token_id, claimable_at, &sender)
+    }
+
+    pub fn claim_scheduled_transfer(env: Env, token_id: u64) -> Result<(), ContractError> {
+        let scheduled =
+            transfer::get_scheduled_transfer(&env, token_id).ok_or(ContractError::NotPermitted)?;
+        scheduled.to.require_auth();
+        transfer::claim_scheduled_transfer(&env, token_id)?;
+        Ok(())
+    }
+
+    pub fn get_scheduled_transfer(env: Env, token_id: u64) -> Option<ScheduledTransfer> {
+        transfer::get_scheduled_transfer(&env, token_id)
+    }
+
+    /// Registers the ed25519 public key whose signatures authorize lazy-mint
+    /// vouchers.
+    pub fn set_voucher_signer(env: Env, public_key: BytesN<32>) -> Result<(), ContractError> {
+        access_control::require_admin(&env)?;
+        voucher::set_signer(&env, &public_key);
+        Ok(())
+    }
+
+    /// Returns an address's account nonce. Vouchers must carry the admin's
+    /// current nonce, which only `revoke_vouchers` advances.
+    pub fn get_nonce(env: Env, address: Address) -> u64 {
+        access_control::get_nonce(&env, &address)
+    }
+
+    /// Retires every voucher signed so far by advancing the admin's nonce.
+    pub fn revoke_vouchers(env: Env) -> Result<u64, ContractError> {
+        let admin = access_control::require_admin(&env)?;
+        Ok(access_control::increment_nonce(&env, &admin))
+    }
+
+    /// Redeems a signed voucher: verifies it, charges `price` in `currency`
+    /// from `buyer` and mints the token to `buyer`.
+    pub fn redeem_voucher(
+        env: Env,
+        voucher: LazyMintVoucher,
+        buyer: Address,
+    ) -> Result<u64, ContractError> {
+        buyer.require_auth();
+        voucher::consume(&env, &voucher)?;
+
+        let admin = access_control::get_admin(&env)?;
+        payment::collect_payment(&env, &voucher.currency, &buyer, voucher.price)?;
+        token::mint_token(
+            &env,
+            &buyer,
+            voucher.metadata_uri,
+            Vec::new(&env),
+            false,
+            &admin,
+            None,
+        )
+    }
+
+    /// Returns `(edition_number, total_editions)` for tokens minted as part
+    /// of an edition batch.
+    pub fn get_edition_info(env: Env, token_id: u64) -> Option<(u32, u32)> {
+        token::edition_info(&env, token_id)
+    }
+
+    pub fn get_total_supply(env: Env) -> u64 {
+        token::total_supply(&env)
+    }
+
+    /// Sets a per-token royalty. Callable by the admin or the token's creator.
+    pub fn set_token_royalty(
+        env: Env,
+        token_id: u64,
+        recipient: Address,
+        percentage: u32,
+        sender: Address,
+    ) -> Result<(), ContractError> {
+        sender.require_auth();
+        let token = token::get_token(&env, token_id)?;
+        if token.creator != sender {
+            access_control::require_role(&env, Role::Admin, &sender)?;
+        }
+        royalty::set_token_royalty(&env, token_id, recipient, percentage)
+    }
+
+    /// Splits a token's royalty between several recipients. Callable by the
+    /// admin or the token's creator.
+    pub fn set_token_royalty_split(
+        env: Env,
+        token_id: u64,
+        split: RoyaltySplit,
+        sender: Address,
+    ) -> Result<(), ContractError> {
+        sender.require_auth();
+        let token = token::get_token(&env, token_id)?;
+        if token.creator != sender {
+            access_control::require_role(&env, Role::Admin, &sender)?;
+        }
+        royalty::set_token_royalty_split(&env, token_id, &split)
+    }
+
+    pub fn get_token_royalty(
+        env: Env,
+        token_id: u64,
+    ) -> Result<Option<RoyaltyInfo>, ContractError> {
+        if !token::exists(&env, token_id) {
+            return Err(ContractError::TokenNotFound);
+        }
+        Ok(royalty::get_token_royalty(&env, token_id))
+    }
+
+    pub fn set_base_uri(env: Env, new_uri: String, sender: Address) -> Result<(), ContractError> {
+        sender.require_auth();
+        metadata::set_base_uri(&env, new_uri, &sender)
+    }
+
+    pub fn set_provenance_hash(
+        env: Env,
+        hash: Bytes,
+        sender: Address,
+    ) -> Result<(), ContractError> {
+        sender.require_auth();
+        access_control::require_role(&env, Role::Admin, &sender)?;
+        metadata::set_provenance_hash(&env, hash)
+    }
+
+    pub fn get_provenance_hash(env: Env) -> Result<Option<Bytes>, ContractError> {
+        metadata::get_provenance_hash(&env)
+    }
+
+    pub fn set_token_uri(
+        env: Env,
+        token_id: u64,
+        uri: String,
+        sender: Address,
+    ) -> Result<(), ContractError> {
+        sender.require_auth();
+        access_control::require_role(&env, Role::MetadataUpdater, &sender)?;
+        metadata::set_token_uri(&env, token_id, uri)
+    }
+
+    pub fn update_token_attributes(
+        env: Env,
+        token_id: u64,
+        new_attributes: Vec<TokenAttribute>,
+        sender: Address,
+    ) -> Result<(), ContractError> {
+        sender.require_auth();
+        metadata::update_token_attributes(&env, token_id, new_attributes, &sender)
+    }
+
+    /// Registers a contract implementing `IMutationHook` to be told when the
+    /// marketplace sells `token_id`. Owner only.
+    pub fn register_mutation_hook(
+        env: Env,
+        token_id: u64,
+        hook_contract: Address,
+        sender: Address,
+    ) -> Result<(), ContractError> {
+        sender.require_auth();
+        metadata::register_mutation_hook(&env, token_id, &hook_contract, &sender)
+    }
+
+    pub fn get_mutation_hook(env: Env, token_id: u64) -> Option<Address> {
+        metadata::get_mutation_hook(&env, token_id)
+    }
+
+    /// Extends an expiring token by `extension_seconds`, reviving it if it
+    /// has lapsed. Requires the MetadataUpdater role.
+    pub fn renew_token(
+        env: Env,
+        token_id: u64,
+        extension_seconds: u64,
+        sender: Address,
+    ) -> Result<u64, ContractError> {
+        sender.require_auth();
+        token::renew_token(&env, token_id, extension_seconds, &sender)
+    }
+}
+
+#[contractimpl]
+impl INft for NftContract {
+    fn mint(
+        env: Env,
+        to: Address,
+        metadata_uri: String,
+        attributes: Vec<TokenAttribute>,
+        royalty_override: Option<RoyaltyInfo>,
+        soulbound: Option<bool>,
+        expires_at: Option<u64>,
+        sender: Address,
+    ) -> Result<u64, ContractError> {
+        sender.require_auth();
+        access_control::require_role(&env, Role::Minter, &sender)?;
+
+        let token_id = token::mint_token(
+            &env,
+            &to,
+            metadata_uri,
+            attributes,
+            soulbound.unwrap_or(false),
+            &sender,
+            None,
+        )?;
+        if let Some(royalty) = royalty_override {
+            royalty::set_mint_royalty(&env, token_id, royalty, &sender)?;
+        }
+        if let Some(expires_at) = expires_at {
+            token::set_expiry(&env, token_id, expires_at)?;
+        }
+        Ok(token_id)
+    }
+
+    fn batch_mint(
+        env: Env,
+        recipients: Vec<Address>,
+        metadata_uris: Vec<String>,
+        attributes: Vec<Vec<TokenAttribute>>,
+        minter: Address,
+    ) -> Result<Vec<u64>, ContractError> {
+        minter.require_auth();
+        access_control::require_role(&env, Role::Minter, &minter)?;
+        if recipients.is_empty()
+            || recipients.len() != metadata_uris.len()
+            || recipients.len() != attributes.len()
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        // In a capped collection each batch is an edition, numbered in order
+        let is_edition = metadata::get_collection_config(&env)?.max_supply.is_some();
+
+        let mut token_ids = Vec::new(&env);
+        for i in 0..recipients.len() {
+            let token_id = token::mint_token(
+                &env,
+                &recipients.get_unchecked(i),
+                metadata_uris.get_unchecked(i),
+                attributes.get_unchecked(i),
+                false,
+                &minter,
+                is_edition.then_some((i + 1, recipients.len())),
+            )?;
+            token_ids.push_back(token_id);
+        }
+        Ok(token_ids)
+    }
+
+    fn burn(env: Env, token_id: u64, sender: Address) -> Result<(), ContractError> {
+        sender.require_auth();
+        token::burn_token(&env, token_id, &sender)
+    }
+
+    fn transfer(env: Env, from: Address, to: Address, token_id: u64) -> Result<(), ContractError> {
+        from.require_auth();
+        transfer::transfer(&env, &from, &to, token_id, &from)
+    }
+
+    fn safe_transfer_from(
+        env: Env,
+        operator: Address,
+        from: Address,
+        to: Address,
+        token_id: u64,
+        data: Bytes,
+    ) -> Result<(), ContractError> {
+        operator.require_auth();
+        transfer::safe_transfer(&env, &operator, &from, &to, token_id, data)
+    }
+
+    fn batch_transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        token_ids: Vec<u64>,
+    ) -> Result<(), ContractError> {
+        from.require_auth();
+        for token_id in token_ids.iter() {
+            transfer::transfer(&env, &from, &to, token_id, &from)?;
+        }
+        Ok(())
+    }
+
+    fn approve(
+        env: Env,
+        approved: Address,
+        token_id: u64,
+        sender: Address,
+    ) -> Result<(), ContractError> {
+        sender.require_auth();
+        transfer::approve(&env, &approved, token_id, &sender)
+    }
+
+    fn set_approval_for_all(
+        env: Env,
+        operator: Address,
+        approved: bool,
+        sender: Address,
+    ) -> Result<(), ContractError> {
+        sender.require_auth();
+        transfer::set_approval_for_all(&env, &sender, &operator, approved)
+    }
+
+    fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool {
+        transfer::is_approved_for_all(&env, &owner, &operator)
+    }
+
+    fn get_approved(env: Env, token_id: u64) -> Option<Address> {
+        token::get_token(&env, token_id).ok()?.approved
+    }
+
+    fn owner_of(env: Env, token_id: u64) -> Result<Address, ContractError> {
+        Ok(token::get_token(&env, token_id)?.owner)
+    }
+
+    fn balance_of(env: Env, owner: Address) -> u32 {
+        token::balance_of(&env, &owner)
+    }
+
+    fn total_supply(env: Env) -> u64 {
+        token::total_supply(&env)
+    }
+
+    fn get_royalty_info(
+        env: Env,
+        token_id: u64,
+        sale_price: i128,
+    ) -> Result<(Address, i128), ContractError> {
+        royalty::get_royalty_info(&env, token_id, sale_price)
+    }
+
+    fn get_royalty_split_info(
+        env: Env,
+        token_id: u64,
+        sale_price: i128,
+    ) -> Result<Vec<(Address, i128)>, ContractError> {
+        royalty::calculate_royalty_split(&env, token_id, sale_price)
+    }
+
+    fn token_uri(env: Env, token_id: u64) -> Result<String, ContractError> {
+        match metadata::get_token_uri(&env, token_id)? {
+            Some(uri) => Ok(uri),
+            None => {
+                let base_uri = metadata::get_collection_config(&env)?.base_uri;
+                Ok(metadata::compose_token_uri(&env, &base_uri, token_id))
+            }
+        }
+    }
+
+    fn name(env: Env) -> Result<String, ContractError> {
+        Ok(metadata::get_collection_config(&env)?.name)
+    }
+
+    fn symbol(env: Env) -> Result<String, ContractError> {
+        Ok(metadata::get_collection_config(&env)?.symbol)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,175 @@
+use crate::access_control;
+use crate::error::ContractError;
+use crate::events;
+use crate::storage::DataKey;
+use crate::token;
+use crate::types::{CollectionConfig, Role, TokenAttribute};
+use soroban_sdk::{Address, Bytes, Env, String, Vec};
+
+pub fn get_collection_config(env: &Env) -> Result<CollectionConfig, ContractError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::CollectionConfig)
+        .ok_or(ContractError::NotInitialized)
+}
+
+pub fn is_frozen(env: &Env) -> bool {
+    get_collection_config(env)
+        .map(|config| config.metadata_is_frozen)
+        .unwrap_or(false)
+}
+
+pub fn set_collection_config(env: &Env, config: &CollectionConfig) -> Result<(), ContractError> {
+    if is_frozen(env) {
+        return Err(ContractError::MetadataFrozen);
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::CollectionConfig, config);
+    Ok(())
+}
+
+/// Permanently locks collection and token metadata.
+pub fn freeze_metadata(env: &Env) -> Result<(), ContractError> {
+    let mut config = get_collection_config(env)?;
+    if config.metadata_is_frozen {
+        return Err(ContractError::MetadataFrozen);
+    }
+    config.metadata_is_frozen = true;
+    env.storage()
+        .instance()
+        .set(&DataKey::CollectionConfig, &config);
+    Ok(())
+}
+
+/// Replaces the collection base URI. Requires the MetadataUpdater role and
+/// unfrozen metadata.
+pub fn set_base_uri(env: &Env, new_uri: String, sender: &Address) -> Result<(), ContractError> {
+    access_control::require_role(env, Role::MetadataUpdater, sender)?;
+
+    let mut config = get_collection_config(env)?;
+    if config.metadata_is_frozen {
+        return Err(ContractError::MetadataFrozen);
+    }
+    config.base_uri = new_uri;
+    env.storage()
+        .instance()
+        .set(&DataKey::CollectionConfig, &config);
+    Ok(())
+}
+
+/// Builds `base_uri` followed by the decimal `token_id`.
+pub fn compose_token_uri(env: &Env, base_uri: &String, token_id: u64) -> String {
+    let mut digits = [0u8; 20];
+    let mut start = digits.len();
+    let mut n = token_id;
+    loop {
+        start -= 1;
+        digits[start] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+
+    let mut uri = base_uri.to_bytes();
+    uri.append(&Bytes::from_slice(env, &digits[start..]));
+    uri.into()
+}
+
+/// SHA-256 digest length expected for provenance hashes.
+pub const PROVENANCE_HASH_LEN: u32 = 32;
+
+pub fn get_provenance_hash(env: &Env) -> Result<Option<Bytes>, ContractError> {
+    Ok(get_collection_config(env)?.provenance_hash)
+}
+
+/// Commits the collection's provenance hash. It can be set exactly once and
+/// only before metadata is frozen.
+pub fn set_provenance_hash(env: &Env, hash: Bytes) -> Result<(), ContractError> {
+    let mut config = get_collection_config(env)?;
+    if config.metadata_is_frozen {
+        return Err(ContractError::MetadataFrozen);
+    }
+    if config.provenance_hash.is_some() {
+        return Err(ContractError::ProvenanceAlreadySet);
+    }
+    if hash.len() != PROVENANCE_HASH_LEN {
+        return Err(ContractError::InvalidInput);
+    }
+
+    config.provenance_hash = Some(hash);
+    env.storage()
+        .instance()
+        .set(&DataKey::CollectionConfig, &config);
+    Ok(())
+}
+
+/// Returns the token-specific URI, or `None` when the token was minted
+/// without one.
+pub fn get_token_uri(env: &Env, token_id: u64) -> Result<Option<String>, ContractError> {
+    let token = token::get_token(env, token_id)?;
+    if token.metadata_uri.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(token.metadata_uri))
+}
+
+pub fn set_token_uri(env: &Env, token_id: u64, uri: String) -> Result<(), ContractError> {
+    if is_frozen(env) {
+        return Err(ContractError::MetadataFrozen);
+    }
+    let mut token = token::get_token(env, token_id)?;
+    token.metadata_uri = uri;
+    token::put_token(env, &token);
+    Ok(())
+}
+
+/// Replaces a token's attributes, for NFTs that evolve after minting.
+/// Requires the MetadataUpdater role and unfrozen metadata; the rest of the
+/// token record is written back unchanged.
+pub fn update_token_attributes(
+    env: &Env,
+    token_id: u64,
+    new_attributes: Vec<TokenAttribute>,
+    sender: &Address,
+) -> Result<(), ContractError> {
+    access_control::require_role(env, Role::MetadataUpdater, sender)?;
+    if is_frozen(env) {
+        return Err(ContractError::MetadataFrozen);
+    }
+
+    let mut token = token::get_token(env, token_id)?;
+    let old_attributes = token.attributes;
+    token.attributes = new_attributes.clone();
+    token::put_token(env, &token);
+
+    events::emit_token_attributes_updated(env, token_id, old_attributes, new_attributes);
+    Ok(())
+}
+
+/// Returns the contract told about `token_id`'s sales, if one is registered.
+pub fn get_mutation_hook(env: &Env, token_id: u64) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MutationHookContract(token_id))
+}
+
+/// Registers `hook_contract` to receive `on_sold` when the marketplace
+/// settles a sale of `token_id`, replacing any earlier hook. Only the owner
+/// may register one.
+pub fn register_mutation_hook(
+    env: &Env,
+    token_id: u64,
+    hook_contract: &Address,
+    sender: &Address,
+) -> Result<(), ContractError> {
+    if token::get_token(env, token_id)?.owner != *sender {
+        return Err(ContractError::NotOwner);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::MutationHookContract(token_id), hook_contract);
+    Ok(())
+}
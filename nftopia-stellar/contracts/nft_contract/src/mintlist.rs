@@ -0,0 +1,65 @@
+use crate::error::ContractError;
+use crate::storage::DataKey;
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Address, Bytes, Env, Vec};
+
+pub const MERKLE_ROOT_LEN: u32 = 32;
+
+pub fn get_root(env: &Env) -> Option<Bytes> {
+    env.storage().instance().get(&DataKey::MintlistRoot)
+}
+
+pub fn set_root(env: &Env, root: &Bytes) -> Result<(), ContractError> {
+    if root.len() != MERKLE_ROOT_LEN {
+        return Err(ContractError::InvalidInput);
+    }
+    env.storage().instance().set(&DataKey::MintlistRoot, root);
+    Ok(())
+}
+
+pub fn is_claimed(env: &Env, account: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::MintlistClaimed(account.clone()))
+}
+
+/// Records that `account` has used its presale mint, rejecting a second one.
+pub fn claim(env: &Env, account: &Address) -> Result<(), ContractError> {
+    if is_claimed(env, account) {
+        return Err(ContractError::MintlistAlreadyClaimed);
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::MintlistClaimed(account.clone()), &true);
+    Ok(())
+}
+
+/// Leaf for `account`: sha256 of its XDR encoding.
+pub fn leaf(env: &Env, account: &Address) -> Bytes {
+    env.crypto().sha256(&account.clone().to_xdr(env)).into()
+}
+
+/// Hashes a sibling pair in sorted order so proofs don't need to carry
+/// left/right position flags.
+pub fn hash_pair(env: &Env, a: &Bytes, b: &Bytes) -> Bytes {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    let mut data = first.clone();
+    data.append(second);
+    env.crypto().sha256(&data).into()
+}
+
+/// Walks `proof` from the leaf for `account` and checks the result against
+/// the stored mintlist root.
+pub fn verify(env: &Env, account: &Address, proof: &Vec<Bytes>) -> Result<(), ContractError> {
+    let root = get_root(env).ok_or(ContractError::NotPermitted)?;
+
+    let mut computed = leaf(env, account);
+    for sibling in proof.iter() {
+        computed = hash_pair(env, &computed, &sibling);
+    }
+
+    if computed != root {
+        return Err(ContractError::NotPermitted);
+    }
+    Ok(())
+}
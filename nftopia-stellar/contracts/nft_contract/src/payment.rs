@@ -0,0 +1,61 @@
+use crate::error::ContractError;
+use crate::storage::DataKey;
+use soroban_sdk::{Address, Env, token};
+
+pub fn get_payment_asset(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::MintPaymentAsset)
+}
+
+pub fn set_payment_asset(env: &Env, asset: &Address) {
+    env.storage()
+        .instance()
+        .set(&DataKey::MintPaymentAsset, asset);
+}
+
+pub fn get_mint_proceeds(env: &Env, asset: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MintProceeds(asset.clone()))
+        .unwrap_or(0)
+}
+
+fn set_mint_proceeds(env: &Env, asset: &Address, amount: i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::MintProceeds(asset.clone()), &amount);
+}
+
+/// Pulls `amount` of `asset` from `payer` into the contract and books it as
+/// mint proceeds. The payer's authorization is the caller's responsibility.
+pub fn collect_payment(
+    env: &Env,
+    asset: &Address,
+    payer: &Address,
+    amount: i128,
+) -> Result<(), ContractError> {
+    if amount < 0 {
+        return Err(ContractError::InvalidInput);
+    }
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let contract = env.current_contract_address();
+    token::Client::new(env, asset).transfer(payer, &contract, &amount);
+    let proceeds = get_mint_proceeds(env, asset)
+        .checked_add(amount)
+        .ok_or(ContractError::InvalidInput)?;
+    set_mint_proceeds(env, asset, proceeds);
+    Ok(())
+}
+
+/// Sends all accumulated proceeds in `asset` to `to` and returns the amount.
+pub fn withdraw_proceeds(env: &Env, asset: &Address, to: &Address) -> i128 {
+    let amount = get_mint_proceeds(env, asset);
+    if amount > 0 {
+        set_mint_proceeds(env, asset, 0);
+        let contract = env.current_contract_address();
+        token::Client::new(env, asset).transfer(&contract, to, &amount);
+    }
+    amount
+}
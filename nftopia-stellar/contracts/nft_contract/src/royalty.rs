@@ -0,0 +1,178 @@
+use crate::access_control;
+use crate::error::ContractError;
+use crate::metadata;
+use crate::storage::DataKey;
+use crate::token;
+use crate::types::{Role, RoyaltyInfo, RoyaltySplit};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Royalties are expressed in basis points (10000 = 100%).
+pub const MAX_ROYALTY_BPS: u32 = 10000;
+
+pub fn validate_royalty(royalty: &RoyaltyInfo) -> Result<(), ContractError> {
+    if royalty.percentage > MAX_ROYALTY_BPS {
+        return Err(ContractError::InvalidRoyalty);
+    }
+    Ok(())
+}
+
+pub fn get_default_royalty(env: &Env) -> Result<RoyaltyInfo, ContractError> {
+    Ok(metadata::get_collection_config(env)?.royalty_default)
+}
+
+pub fn get_token_royalty(env: &Env, token_id: u64) -> Option<RoyaltyInfo> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TokenRoyalty(token_id))
+}
+
+/// Stores a royalty for `token_id` that takes precedence over the collection
+/// default.
+pub fn set_token_royalty(
+    env: &Env,
+    token_id: u64,
+    recipient: Address,
+    percentage: u32,
+) -> Result<(), ContractError> {
+    if !token::exists(env, token_id) {
+        return Err(ContractError::TokenNotFound);
+    }
+    let royalty = RoyaltyInfo {
+        recipient,
+        percentage,
+    };
+    validate_royalty(&royalty)?;
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::TokenRoyalty(token_id), &royalty);
+    Ok(())
+}
+
+/// Stores the royalty a token was minted with. Only the admin may mint with
+/// a higher percentage than the collection default.
+pub fn set_mint_royalty(
+    env: &Env,
+    token_id: u64,
+    royalty: RoyaltyInfo,
+    sender: &Address,
+) -> Result<(), ContractError> {
+    if royalty.percentage > get_default_royalty(env)?.percentage
+        && !access_control::has_role(env, Role::Admin, sender)
+    {
+        return Err(ContractError::InvalidRoyalty);
+    }
+    set_token_royalty(env, token_id, royalty.recipient, royalty.percentage)
+}
+
+pub fn remove_token_royalty(env: &Env, token_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::TokenRoyalty(token_id));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::TokenRoyaltySplit(token_id));
+}
+
+pub fn validate_royalty_split(split: &RoyaltySplit) -> Result<(), ContractError> {
+    if split.recipients.is_empty() {
+        return Err(ContractError::InvalidRoyalty);
+    }
+
+    let mut total: u32 = 0;
+    for (_, share) in split.recipients.iter() {
+        total = total
+            .checked_add(share)
+            .ok_or(ContractError::InvalidRoyalty)?;
+    }
+    if total != MAX_ROYALTY_BPS {
+        return Err(ContractError::InvalidRoyalty);
+    }
+    Ok(())
+}
+
+pub fn get_token_royalty_split(env: &Env, token_id: u64) -> Option<RoyaltySplit> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TokenRoyaltySplit(token_id))
+}
+
+pub fn set_token_royalty_split(
+    env: &Env,
+    token_id: u64,
+    split: &RoyaltySplit,
+) -> Result<(), ContractError> {
+    if !token::exists(env, token_id) {
+        return Err(ContractError::TokenNotFound);
+    }
+    validate_royalty_split(split)?;
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::TokenRoyaltySplit(token_id), split);
+    Ok(())
+}
+
+/// Splits the royalty owed on a sale of `token_id` between the token's split
+/// recipients. Rounding dust goes to the recipient with the largest share so
+/// the parts always add up to the full royalty. Without a split the whole
+/// royalty goes to the single royalty recipient.
+pub fn calculate_royalty_split(
+    env: &Env,
+    token_id: u64,
+    sale_price: i128,
+) -> Result<Vec<(Address, i128)>, ContractError> {
+    let (recipient, royalty_amount) = get_royalty_info(env, token_id, sale_price)?;
+
+    let split = match get_token_royalty_split(env, token_id) {
+        Some(split) => split,
+        None => return Ok(Vec::from_array(env, [(recipient, royalty_amount)])),
+    };
+
+    let mut payouts = Vec::new(env);
+    let mut distributed: i128 = 0;
+    let mut largest = 0;
+    let mut largest_share = 0;
+    for (i, (address, share)) in split.recipients.iter().enumerate() {
+        let amount = calculate_royalty_amount(royalty_amount, share);
+        distributed += amount;
+        if share > largest_share {
+            largest = i as u32;
+            largest_share = share;
+        }
+        payouts.push_back((address, amount));
+    }
+
+    let dust = royalty_amount - distributed;
+    if dust > 0 {
+        let (address, amount) = payouts.get_unchecked(largest);
+        payouts.set(largest, (address, amount + dust));
+    }
+    Ok(payouts)
+}
+
+pub fn calculate_royalty_amount(sale_price: i128, percentage: u32) -> i128 {
+    sale_price * percentage as i128 / MAX_ROYALTY_BPS as i128
+}
+
+/// Returns the royalty recipient and amount owed for a sale of `token_id`,
+/// preferring a token-level override over the collection default.
+pub fn get_royalty_info(
+    env: &Env,
+    token_id: u64,
+    sale_price: i128,
+) -> Result<(Address, i128), ContractError> {
+    if !token::exists(env, token_id) {
+        return Err(ContractError::TokenNotFound);
+    }
+    if sale_price < 0 {
+        return Err(ContractError::InvalidInput);
+    }
+
+    let royalty = match get_token_royalty(env, token_id) {
+        Some(royalty) => royalty,
+        None => get_default_royalty(env)?,
+    };
+    let amount = calculate_royalty_amount(sale_price, royalty.percentage);
+    Ok((royalty.recipient, amount))
+}
@@ -0,0 +1,41 @@
+use crate::types::Role;
+use soroban_sdk::{Address, contracttype};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    // Instance keys
+    Admin,
+    CollectionConfig,
+    NextTokenId,
+    PendingAdmin,
+    AdminTransferTimeout,
+    TotalSupply,
+    MintlistRoot,
+    Paused,
+    MintPaymentAsset,
+    VoucherSigner,
+    MaxAirdropBatchSize,
+
+    // Persistent keys
+    Token(u64),
+    Balance(Address),
+    OwnedTokens(Address),
+    OwnedTokenIndex(Address, u64),
+    AllTokens,
+    OperatorApproval(Address, Address),
+    OperatorExpiry(Address, Address),
+    Role(Address, Role),
+    RoleExpiry(Address, Role),
+    TokenRoyalty(u64),
+    TokenRoyaltySplit(u64),
+    MintProceeds(Address),
+    PendingTransfer(u64),
+    UsedVoucher(u64),
+    AccountNonce(Address),
+    TokenLock(u64),
+    TokenChildren(u64),
+    FractionalizationData(u64),
+    MutationHookContract(u64),
+    MintlistClaimed(Address),
+}
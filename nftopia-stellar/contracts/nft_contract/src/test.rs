@@ -0,0 +1,1856 @@
+#![cfg(test)]
+extern crate std;
+
+use crate::error::ContractError;
+use crate::interface::{INftReceiver, NFT_RECEIVED_SELECTOR};
+use crate::mintlist;
+use crate::types::{
+    CollectionConfig, LazyMintVoucher, Role, RoyaltyInfo, RoyaltySplit, TokenAttribute,
+};
+use crate::voucher;
+use crate::{NftContract, NftContractClient};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::testutils::{Address as _, Events, Ledger};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{Address, Bytes, BytesN, Env, String, Vec, contract, contractimpl};
+
+fn create_config(env: &Env, royalty_recipient: &Address) -> CollectionConfig {
+    CollectionConfig {
+        name: String::from_str(env, "Test NFT"),
+        symbol: String::from_str(env, "TNFT"),
+        description: String::from_str(env, "Test Description"),
+        base_uri: String::from_str(env, "https://test.com/"),
+        max_supply: None,
+        mint_price: None,
+        royalty_default: RoyaltyInfo {
+            recipient: royalty_recipient.clone(),
+            percentage: 500, // 5%
+        },
+        metadata_is_frozen: false,
+        provenance_hash: None,
+    }
+}
+
+fn setup(env: &Env) -> (NftContractClient<'_>, Address) {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let contract_id = env.register(NftContract, ());
+    let client = NftContractClient::new(env, &contract_id);
+    client.initialize(&admin, &create_config(env, &admin));
+
+    (client, admin)
+}
+
+fn mint_one(env: &Env, client: &NftContractClient, to: &Address, sender: &Address) -> u64 {
+    client.mint(
+        to,
+        &String::from_str(env, "ipfs://hash"),
+        &Vec::new(env),
+        &None,
+        &None,
+        &None,
+        sender,
+    )
+}
+
+#[test]
+fn test_initialize_twice_fails() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let result = client.try_initialize(&admin, &create_config(&env, &admin));
+    assert_eq!(result, Err(Ok(ContractError::AlreadyInitialized)));
+}
+
+#[test]
+fn test_grant_and_revoke_minter_role() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+
+    let minter = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    client.grant_role(&(Role::Minter as u32), &minter);
+    assert!(client.has_role(&(Role::Minter as u32), &minter));
+    mint_one(&env, &client, &user, &minter);
+
+    client.revoke_role(&(Role::Minter as u32), &minter);
+    assert!(!client.has_role(&(Role::Minter as u32), &minter));
+
+    let result = client.try_mint(
+        &user,
+        &String::from_str(&env, "ipfs://hash"),
+        &Vec::new(&env),
+        &None,
+        &None,
+        &None,
+        &minter,
+    );
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_revoke_invalid_role_fails() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+
+    let user = Address::generate(&env);
+    let result = client.try_revoke_role(&99, &user);
+    assert_eq!(result, Err(Ok(ContractError::InvalidRole)));
+}
+
+#[test]
+fn test_two_step_admin_transfer() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let new_admin = Address::generate(&env);
+    client.propose_admin(&new_admin);
+
+    // The handoff is not effective until accepted.
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_pending_admin().unwrap().new_admin, new_admin);
+
+    assert_eq!(client.accept_admin(), new_admin);
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+}
+
+#[test]
+fn test_transfer_collection_ownership_moves_roles() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let buyer = Address::generate(&env);
+    let minter = Address::generate(&env);
+    let burner = Address::generate(&env);
+    let both = Address::generate(&env);
+    let updater = Address::generate(&env);
+    let minter_role = Role::Minter as u32;
+    let burner_role = Role::Burner as u32;
+    client.grant_role(&minter_role, &minter);
+    client.grant_role(&burner_role, &burner);
+    client.grant_role(&minter_role, &both);
+    client.grant_role(&burner_role, &both);
+    client.grant_role(&(Role::MetadataUpdater as u32), &updater);
+    client.propose_admin(&Address::generate(&env));
+
+    let holders = Vec::from_array(&env, [minter.clone(), burner.clone(), both.clone()]);
+    let result = client.try_transfer_collection_ownership(&buyer, &holders, &minter);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+
+    client.transfer_collection_ownership(&buyer, &holders, &admin);
+    assert_eq!(client.get_admin(), buyer);
+    assert_eq!(client.get_pending_admin(), None);
+    for holder in holders.iter() {
+        assert!(!client.has_role(&minter_role, &holder));
+        assert!(!client.has_role(&burner_role, &holder));
+    }
+    assert!(client.has_role(&(Role::MetadataUpdater as u32), &updater));
+    assert!(!client.has_role(&minter_role, &admin));
+
+    // The old admin can no longer act, the new one can
+    let result = client.try_transfer_collection_ownership(&admin, &holders, &admin);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+    mint_one(&env, &client, &buyer, &buyer);
+
+    // The moved roles are held outright, not only through the admin
+    let next = Address::generate(&env);
+    client.transfer_collection_ownership(&next, &Vec::new(&env), &buyer);
+    assert!(client.has_role(&minter_role, &buyer));
+    assert!(client.has_role(&burner_role, &buyer));
+    assert!(!client.has_role(&(Role::MetadataUpdater as u32), &buyer));
+}
+
+#[test]
+fn test_admin_proposal_expires() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    client.set_admin_transfer_timeout(&3600);
+    let new_admin = Address::generate(&env);
+    client.propose_admin(&new_admin);
+
+    env.ledger().with_mut(|li| li.timestamp += 3601);
+
+    let result = client.try_accept_admin();
+    assert_eq!(result, Err(Ok(ContractError::AdminProposalExpired)));
+    assert_eq!(client.get_admin(), admin);
+}
+
+#[test]
+fn test_accept_admin_rejected_without_pending_signature() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let new_admin = Address::generate(&env);
+    client.propose_admin(&new_admin);
+
+    // No signature from the pending admin.
+    env.mock_auths(&[]);
+    assert!(client.try_accept_admin().is_err());
+
+    env.mock_all_auths();
+    client.cancel_admin_proposal();
+    let result = client.try_accept_admin();
+    assert_eq!(result, Err(Ok(ContractError::NoPendingAdmin)));
+    assert_eq!(client.get_admin(), admin);
+}
+
+#[test]
+fn test_role_expires_with_ledger_time() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+
+    let minter = Address::generate(&env);
+    let user = Address::generate(&env);
+    let thirty_days = 30 * 24 * 60 * 60;
+    let expiry = env.ledger().timestamp() + thirty_days;
+
+    client.grant_role_with_expiry(&(Role::Minter as u32), &minter, &expiry);
+    assert!(client.has_role(&(Role::Minter as u32), &minter));
+    mint_one(&env, &client, &user, &minter);
+
+    env.ledger().with_mut(|li| li.timestamp = expiry);
+    assert!(!client.has_role(&(Role::Minter as u32), &minter));
+
+    let result = client.try_mint(
+        &user,
+        &String::from_str(&env, "ipfs://hash"),
+        &Vec::new(&env),
+        &None,
+        &None,
+        &None,
+        &minter,
+    );
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_max_supply_enforced() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let contract_id = env.register(NftContract, ());
+    let client = NftContractClient::new(&env, &contract_id);
+    let mut config = create_config(&env, &admin);
+    config.max_supply = Some(2);
+    client.initialize(&admin, &config);
+
+    let first = mint_one(&env, &client, &user, &admin);
+    mint_one(&env, &client, &user, &admin);
+    assert_eq!(client.get_total_supply(), 2);
+
+    let result = client.try_mint(
+        &user,
+        &String::from_str(&env, "ipfs://hash"),
+        &Vec::new(&env),
+        &None,
+        &None,
+        &None,
+        &admin,
+    );
+    assert_eq!(result, Err(Ok(ContractError::MaxSupplyReached)));
+
+    // Burning frees up room under the cap.
+    client.burn(&first, &user);
+    assert_eq!(client.get_total_supply(), 1);
+    mint_one(&env, &client, &user, &admin);
+    assert_eq!(client.get_total_supply(), 2);
+}
+
+#[test]
+fn test_batch_mint_numbers_editions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(NftContract, ());
+    let client = NftContractClient::new(&env, &contract_id);
+    let mut config = create_config(&env, &admin);
+    config.max_supply = Some(101);
+    client.initialize(&admin, &config);
+
+    let mut recipients = Vec::new(&env);
+    let mut metadata_uris = Vec::new(&env);
+    let mut attributes = Vec::new(&env);
+    for _ in 0..100 {
+        recipients.push_back(Address::generate(&env));
+        metadata_uris.push_back(String::from_str(&env, "ipfs://hash"));
+        attributes.push_back(Vec::new(&env));
+    }
+
+    let token_ids = client.batch_mint(&recipients, &metadata_uris, &attributes, &admin);
+    assert_eq!(token_ids.len(), 100);
+    for (i, token_id) in token_ids.iter().enumerate() {
+        assert_eq!(
+            client.get_edition_info(&token_id),
+            Some((i as u32 + 1, 100))
+        );
+    }
+
+    // Single mints stand alone
+    let single = mint_one(&env, &client, &admin, &admin);
+    assert_eq!(client.get_edition_info(&single), None);
+    assert_eq!(client.get_edition_info(&999), None);
+}
+
+#[test]
+fn test_batch_mint_without_cap_has_no_editions() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let token_ids = client.batch_mint(
+        &Vec::from_array(&env, [Address::generate(&env), Address::generate(&env)]),
+        &Vec::from_array(
+            &env,
+            [
+                String::from_str(&env, "ipfs://a"),
+                String::from_str(&env, "ipfs://b"),
+            ],
+        ),
+        &Vec::from_array(&env, [Vec::new(&env), Vec::new(&env)]),
+        &admin,
+    );
+    for token_id in token_ids.iter() {
+        assert_eq!(client.get_edition_info(&token_id), None);
+    }
+}
+
+#[test]
+fn test_batch_mint_requires_minter() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let minter = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let recipients = Vec::from_array(&env, [recipient.clone(), minter.clone()]);
+    let metadata_uris = Vec::from_array(
+        &env,
+        [
+            String::from_str(&env, "ipfs://a"),
+            String::from_str(&env, "ipfs://b"),
+        ],
+    );
+    let attributes = Vec::from_array(&env, [Vec::new(&env), Vec::new(&env)]);
+
+    // Being a recipient, even the first, grants nothing
+    let result = client.try_batch_mint(&recipients, &metadata_uris, &attributes, &recipient);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+    let result = client.try_batch_mint(&recipients, &metadata_uris, &attributes, &minter);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+    assert_eq!(client.total_supply(), 0);
+
+    client.grant_role(&(Role::Minter as u32), &minter);
+    let token_ids = client.batch_mint(&recipients, &metadata_uris, &attributes, &minter);
+    assert_eq!(client.owner_of(&token_ids.get(0).unwrap()), recipient);
+    assert_eq!(
+        client.get_token_data(&token_ids.get(1).unwrap()).creator,
+        minter
+    );
+
+    let result = client.try_batch_mint(&Vec::new(&env), &Vec::new(&env), &Vec::new(&env), &admin);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+}
+
+#[test]
+fn test_airdrop_batch() {
+    let env = Env::default();
+    // The test host keeps every ledger entry in one storage map, so each write
+    // costs more as the collection grows in a way a real footprint doesn't
+    env.cost_estimate().budget().reset_unlimited();
+    let (client, admin) = setup(&env);
+
+    let recipients = Vec::from_array(
+        &env,
+        core::array::from_fn::<_, 500, _>(|_| Address::generate(&env)),
+    );
+    let uri = String::from_str(&env, "ipfs://drop");
+    let result = client.try_airdrop_batch(&recipients, &uri, &Vec::new(&env), &admin);
+    assert_eq!(result, Err(Ok(ContractError::BatchTooLarge)));
+
+    // 500 recipients go out in batches of the default size, one event each
+    let batch_size = client.get_max_airdrop_batch_size();
+    let mut token_ids = Vec::new(&env);
+    for start in (0..recipients.len()).step_by(batch_size as usize) {
+        let batch = recipients.slice(start..start + batch_size);
+        token_ids.append(&client.airdrop_batch(&batch, &uri, &Vec::new(&env), &admin));
+        assert_eq!(env.events().all().len(), 1);
+    }
+
+    assert_eq!(token_ids.len(), 500);
+    assert_eq!(client.total_supply(), 500);
+    for (i, recipient) in recipients.iter().enumerate() {
+        let token_id = token_ids.get_unchecked(i as u32);
+        assert_eq!(token_id, i as u64 + 1);
+        assert_eq!(client.owner_of(&token_id), recipient);
+        assert_eq!(client.balance_of(&recipient), 1);
+    }
+    assert_eq!(client.token_by_index(&499), 500);
+    assert_eq!(client.get_token_data(&250).metadata_uri, uri);
+
+    // Minting carries on after the reserved ranges
+    assert_eq!(mint_one(&env, &client, &admin, &admin), 501);
+}
+
+#[test]
+fn test_airdrop_batch_limits() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let uri = String::from_str(&env, "ipfs://drop");
+    let recipients = Vec::from_array(
+        &env,
+        core::array::from_fn::<_, 3, _>(|_| Address::generate(&env)),
+    );
+    let outsider = Address::generate(&env);
+    let result = client.try_airdrop_batch(&recipients, &uri, &Vec::new(&env), &outsider);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+    let result = client.try_airdrop_batch(&Vec::new(&env), &uri, &Vec::new(&env), &admin);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    assert_eq!(client.get_max_airdrop_batch_size(), 50);
+    client.set_max_airdrop_batch_size(&2);
+    let result = client.try_airdrop_batch(&recipients, &uri, &Vec::new(&env), &admin);
+    assert_eq!(result, Err(Ok(ContractError::BatchTooLarge)));
+    assert_eq!(client.total_supply(), 0);
+
+    client.set_max_airdrop_batch_size(&3);
+    client.airdrop_batch(&recipients, &uri, &Vec::new(&env), &admin);
+    assert_eq!(client.total_supply(), 3);
+}
+
+fn mint_soulbound(env: &Env, client: &NftContractClient, to: &Address, sender: &Address) -> u64 {
+    client.mint(
+        to,
+        &String::from_str(env, "ipfs://badge"),
+        &Vec::new(env),
+        &None,
+        &Some(true),
+        &None,
+        sender,
+    )
+}
+
+#[test]
+fn test_soulbound_token_cannot_be_transferred() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let holder = Address::generate(&env);
+    let other = Address::generate(&env);
+    let token_id = mint_soulbound(&env, &client, &holder, &admin);
+
+    let result = client.try_transfer(&holder, &other, &token_id);
+    assert_eq!(result, Err(Ok(ContractError::TokenIsSoulbound)));
+}
+
+#[test]
+fn test_soulbound_token_can_be_burned() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let holder = Address::generate(&env);
+    let token_id = mint_soulbound(&env, &client, &holder, &admin);
+
+    client.burn(&token_id, &holder);
+    assert_eq!(client.get_total_supply(), 0);
+}
+
+#[test]
+fn test_soulbound_token_rejects_approvals() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let holder = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let token_id = mint_soulbound(&env, &client, &holder, &admin);
+
+    let result = client.try_approve(&operator, &token_id, &holder);
+    assert_eq!(result, Err(Ok(ContractError::TokenIsSoulbound)));
+
+    // Holding a badge doesn't stop the holder appointing operators, but they
+    // can't move the badge either.
+    client.set_approval_for_all(&operator, &true, &holder);
+    let recipient = Address::generate(&env);
+    let token_ids = Vec::from_array(&env, [token_id]);
+    let result = client.try_batch_transfer_from(&holder, &recipient, &token_ids, &operator);
+    assert_eq!(result, Err(Ok(ContractError::TokenIsSoulbound)));
+}
+
+#[test]
+fn test_mint_with_proof() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+
+    let listed = Address::generate(&env);
+    let other_listed = Address::generate(&env);
+    let outsider = Address::generate(&env);
+
+    let listed_leaf = mintlist::leaf(&env, &listed);
+    let other_leaf = mintlist::leaf(&env, &other_listed);
+    let root = mintlist::hash_pair(&env, &listed_leaf, &other_leaf);
+    client.set_mintlist_root(&root);
+
+    let uri = String::from_str(&env, "ipfs://presale");
+    let proof = Vec::from_array(&env, [other_leaf.clone()]);
+    let token_id = client.mint_with_proof(&listed, &uri, &Vec::new(&env), &proof);
+    assert_eq!(token_id, 1);
+
+    // One presale mint per listed address
+    let result = client.try_mint_with_proof(&listed, &uri, &Vec::new(&env), &proof);
+    assert_eq!(result, Err(Ok(ContractError::MintlistAlreadyClaimed)));
+
+    let result = client.try_mint_with_proof(&outsider, &uri, &Vec::new(&env), &proof);
+    assert_eq!(result, Err(Ok(ContractError::NotPermitted)));
+}
+
+#[test]
+fn test_set_mintlist_root_rejects_bad_length() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+
+    let result = client.try_set_mintlist_root(&Bytes::from_array(&env, &[0u8; 16]));
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+}
+
+#[test]
+fn test_token_royalty_overrides_default() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let user = Address::generate(&env);
+    let artist = Address::generate(&env);
+    let one_of_one = mint_one(&env, &client, &user, &admin);
+    let edition = mint_one(&env, &client, &user, &admin);
+
+    assert_eq!(client.get_token_royalty(&one_of_one), None);
+    client.set_token_royalty(&one_of_one, &artist, &1000, &admin);
+    assert_eq!(
+        client.get_token_royalty(&one_of_one),
+        Some(RoyaltyInfo {
+            recipient: artist.clone(),
+            percentage: 1000,
+        })
+    );
+
+    assert_eq!(
+        client.get_royalty_info(&one_of_one, &10_000),
+        (artist, 1000)
+    );
+    assert_eq!(client.get_royalty_info(&edition, &10_000), (admin, 500));
+}
+
+fn mint_with_royalty(
+    env: &Env,
+    client: &NftContractClient,
+    royalty: &RoyaltyInfo,
+    sender: &Address,
+) -> Result<u64, ContractError> {
+    client
+        .try_mint(
+            sender,
+            &String::from_str(env, "ipfs://hash"),
+            &Vec::new(env),
+            &Some(royalty.clone()),
+            &None,
+            &None,
+            sender,
+        )
+        .map(|result| result.unwrap())
+        .map_err(|error| error.unwrap())
+}
+
+#[test]
+fn test_mint_with_royalty_override() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let minter = Address::generate(&env);
+    let artist = Address::generate(&env);
+    client.grant_role(&(Role::Minter as u32), &minter);
+
+    // Minters can set up to the collection default of 5%
+    let royalty = RoyaltyInfo {
+        recipient: artist.clone(),
+        percentage: 300,
+    };
+    let token_id = mint_with_royalty(&env, &client, &royalty, &minter).unwrap();
+    assert_eq!(client.get_token_royalty(&token_id), Some(royalty));
+    assert_eq!(
+        client.get_royalty_info(&token_id, &10_000),
+        (artist.clone(), 300)
+    );
+
+    let royalty = RoyaltyInfo {
+        recipient: artist.clone(),
+        percentage: 501,
+    };
+    let result = mint_with_royalty(&env, &client, &royalty, &minter);
+    assert_eq!(result, Err(ContractError::InvalidRoyalty));
+    assert_eq!(client.total_supply(), 1);
+
+    // Only the admin may go above it, and never past 100%
+    let token_id = mint_with_royalty(&env, &client, &royalty, &admin).unwrap();
+    assert_eq!(client.get_token_royalty(&token_id), Some(royalty));
+    let royalty = RoyaltyInfo {
+        recipient: artist,
+        percentage: 10_001,
+    };
+    let result = mint_with_royalty(&env, &client, &royalty, &admin);
+    assert_eq!(result, Err(ContractError::InvalidRoyalty));
+}
+
+#[test]
+fn test_token_royalty_validation() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let user = Address::generate(&env);
+    let token_id = mint_one(&env, &client, &user, &admin);
+
+    let result = client.try_set_token_royalty(&token_id, &user, &10_001, &admin);
+    assert_eq!(result, Err(Ok(ContractError::InvalidRoyalty)));
+
+    let result = client.try_set_token_royalty(&99, &user, &100, &admin);
+    assert_eq!(result, Err(Ok(ContractError::TokenNotFound)));
+
+    let result = client.try_get_royalty_info(&99, &10_000);
+    assert_eq!(result, Err(Ok(ContractError::TokenNotFound)));
+}
+
+#[test]
+fn test_royalty_split_between_co_creators() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let user = Address::generate(&env);
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let c = Address::generate(&env);
+    let token_id = mint_one(&env, &client, &user, &admin);
+
+    let split = RoyaltySplit {
+        recipients: Vec::from_array(
+            &env,
+            [(a.clone(), 5000), (b.clone(), 3333), (c.clone(), 1667)],
+        ),
+    };
+    client.set_token_royalty_split(&token_id, &split, &admin);
+
+    // 5% of 1001 = 50; the rounding dust lands on the largest share.
+    let payouts = client.get_royalty_split_info(&token_id, &1001);
+    assert_eq!(payouts, Vec::from_array(&env, [(a, 26), (b, 16), (c, 8)]));
+}
+
+#[test]
+fn test_royalty_split_with_zero_share() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let user = Address::generate(&env);
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let token_id = mint_one(&env, &client, &user, &admin);
+
+    let split = RoyaltySplit {
+        recipients: Vec::from_array(&env, [(a.clone(), 0), (b.clone(), 10000)]),
+    };
+    client.set_token_royalty_split(&token_id, &split, &admin);
+
+    let payouts = client.get_royalty_split_info(&token_id, &10_000);
+    assert_eq!(payouts, Vec::from_array(&env, [(a, 0), (b, 500)]));
+}
+
+#[test]
+fn test_royalty_split_must_sum_to_full() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let user = Address::generate(&env);
+    let token_id = mint_one(&env, &client, &user, &admin);
+
+    let split = RoyaltySplit {
+        recipients: Vec::from_array(&env, [(user.clone(), 6000), (admin.clone(), 3000)]),
+    };
+    let result = client.try_set_token_royalty_split(&token_id, &split, &admin);
+    assert_eq!(result, Err(Ok(ContractError::InvalidRoyalty)));
+
+    let empty = RoyaltySplit {
+        recipients: Vec::new(&env),
+    };
+    let result = client.try_set_token_royalty_split(&token_id, &empty, &admin);
+    assert_eq!(result, Err(Ok(ContractError::InvalidRoyalty)));
+
+    // Without a split, the whole royalty goes to the single recipient.
+    let payouts = client.get_royalty_split_info(&token_id, &10_000);
+    assert_eq!(payouts, Vec::from_array(&env, [(admin, 500)]));
+}
+
+#[test]
+fn test_operator_approval_expires() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let first = mint_one(&env, &client, &owner, &admin);
+    let second = mint_one(&env, &client, &owner, &admin);
+
+    let expiry = env.ledger().timestamp() + 3600;
+    client.approve_operator_with_expiry(&operator, &true, &expiry, &owner);
+
+    let data = Bytes::new(&env);
+    client.safe_transfer_from(&operator, &owner, &buyer, &first, &data);
+    assert!(client.is_approved_for_all(&owner, &operator));
+
+    env.ledger().with_mut(|li| li.timestamp = expiry);
+    assert!(!client.is_approved_for_all(&owner, &operator));
+    let result = client.try_safe_transfer_from(&operator, &owner, &buyer, &second, &data);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_ownership_and_approval_queries() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let first = mint_one(&env, &client, &owner, &admin);
+    let second = mint_one(&env, &client, &owner, &admin);
+    assert_eq!(client.owner_of(&first), owner);
+    assert_eq!(client.balance_of(&owner), 2);
+    assert_eq!(client.balance_of(&buyer), 0);
+    assert_eq!(client.get_approved(&first), None);
+
+    client.approve(&spender, &first, &owner);
+    assert_eq!(client.get_approved(&first), Some(spender.clone()));
+    assert!(!client.is_approved_for_all(&owner, &spender));
+
+    client.transfer(&owner, &buyer, &first);
+    assert_eq!(client.owner_of(&first), buyer);
+    assert_eq!(client.get_approved(&first), None);
+    assert_eq!(client.balance_of(&owner), 1);
+    assert_eq!(client.balance_of(&buyer), 1);
+
+    client.burn(&second, &owner);
+    let result = client.try_owner_of(&second);
+    assert_eq!(result, Err(Ok(ContractError::TokenNotFound)));
+    assert_eq!(client.get_approved(&second), None);
+    assert_eq!(client.balance_of(&owner), 0);
+}
+
+#[test]
+fn test_total_supply_counts_live_tokens() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let token_ids: [u64; 10] = core::array::from_fn(|_| mint_one(&env, &client, &owner, &admin));
+    assert_eq!(client.total_supply(), 10);
+
+    for token_id in &token_ids[..3] {
+        client.burn(token_id, &owner);
+    }
+    assert_eq!(client.total_supply(), 7);
+    assert_eq!(client.get_total_supply(), 7);
+}
+
+#[test]
+fn test_token_data_queries() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let attributes = Vec::from_array(
+        &env,
+        [TokenAttribute {
+            trait_type: String::from_str(&env, "Background"),
+            value: String::from_str(&env, "Blue"),
+        }],
+    );
+    let token_id = client.mint(
+        &owner,
+        &String::from_str(&env, "ipfs://hash"),
+        &attributes,
+        &None,
+        &None,
+        &None,
+        &admin,
+    );
+    client.approve(&spender, &token_id, &owner);
+
+    let token = client.get_token_data(&token_id);
+    assert_eq!(token.id, token_id);
+    assert_eq!(token.owner, owner);
+    assert_eq!(token.approved, Some(spender));
+    assert_eq!(token.creator, admin);
+    assert_eq!(token.attributes, attributes);
+    assert_eq!(client.get_token_attributes(&token_id), attributes);
+
+    let result = client.try_get_token_data(&99);
+    assert_eq!(result, Err(Ok(ContractError::TokenNotFound)));
+    let result = client.try_get_token_attributes(&99);
+    assert_eq!(result, Err(Ok(ContractError::TokenNotFound)));
+}
+
+#[test]
+fn test_batch_burn() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let first = mint_one(&env, &client, &owner, &admin);
+    let second = mint_one(&env, &client, &owner, &admin);
+    mint_one(&env, &client, &owner, &admin);
+
+    client.batch_burn(&Vec::from_array(&env, [first, second]));
+    assert_eq!(client.get_total_supply(), 1);
+}
+
+#[test]
+fn test_batch_burn_is_atomic() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let first = mint_one(&env, &client, &owner, &admin);
+    let second = mint_one(&env, &client, &owner, &admin);
+
+    let result = client.try_batch_burn(&Vec::from_array(&env, [first, 99, second]));
+    assert_eq!(result, Err(Ok(ContractError::TokenNotFound)));
+
+    // Nothing was burned.
+    assert_eq!(client.get_total_supply(), 2);
+    client.burn(&first, &owner);
+}
+
+#[test]
+fn test_get_tokens_of_owner() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+    for _ in 0..100 {
+        mint_one(&env, &client, &owner, &admin);
+    }
+    assert_eq!(client.get_tokens_of_owner(&owner, &0, &200).len(), 100);
+    assert_eq!(
+        client.get_tokens_of_owner(&owner, &10, &3),
+        Vec::from_array(&env, [11, 12, 13])
+    );
+
+    client.transfer(&owner, &other, &5);
+    client.burn(&50, &owner);
+
+    let owned = client.get_tokens_of_owner(&owner, &0, &200);
+    assert_eq!(owned.len(), 98);
+    assert!(!owned.contains(5));
+    assert!(!owned.contains(50));
+    assert_eq!(
+        client.get_tokens_of_owner(&other, &0, &10),
+        Vec::from_array(&env, [5])
+    );
+
+    client.batch_transfer(&owner, &other, &Vec::from_array(&env, [1, 2]));
+    assert_eq!(
+        client.get_tokens_of_owner(&other, &0, &10),
+        Vec::from_array(&env, [5, 1, 2])
+    );
+    assert_eq!(client.get_tokens_of_owner(&owner, &200, &10).len(), 0);
+}
+
+#[test]
+fn test_batch_transfer_from_by_operator() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    for _ in 0..5 {
+        mint_one(&env, &client, &owner, &admin);
+    }
+    mint_one(&env, &client, &buyer, &admin);
+    client.approve(&buyer, &2, &owner);
+    client.set_approval_for_all(&operator, &true, &owner);
+
+    let token_ids = Vec::from_array(&env, [2, 5, 1]);
+    client.batch_transfer_from(&owner, &buyer, &token_ids, &operator);
+    assert_eq!(env.events().all().len(), 1);
+
+    assert_eq!(client.balance_of(&owner), 2);
+    assert_eq!(client.balance_of(&buyer), 4);
+    assert_eq!(client.get_approved(&2), None);
+    for token_id in token_ids.iter() {
+        assert_eq!(client.owner_of(&token_id), buyer);
+    }
+    assert_eq!(
+        client.get_tokens_of_owner(&owner, &0, &10),
+        Vec::from_array(&env, [3, 4])
+    );
+    assert_eq!(
+        client.get_tokens_of_owner(&buyer, &0, &10),
+        Vec::from_array(&env, [6, 2, 5, 1])
+    );
+
+    // The owner index stays usable for later single transfers
+    client.transfer(&owner, &buyer, &4);
+    assert_eq!(
+        client.get_tokens_of_owner(&owner, &0, &10),
+        Vec::from_array(&env, [3])
+    );
+    assert_eq!(client.token_of_owner_by_index(&buyer, &4), 4);
+}
+
+#[test]
+fn test_batch_transfer_from_fails_as_a_whole() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let first = mint_one(&env, &client, &owner, &admin);
+    let second = mint_one(&env, &client, &owner, &admin);
+    let badge = mint_soulbound(&env, &client, &owner, &admin);
+    let theirs = mint_one(&env, &client, &buyer, &admin);
+    client.approve(&spender, &first, &owner);
+
+    let batch = |token_ids: &[u64], sender: &Address| {
+        let token_ids = Vec::from_slice(&env, token_ids);
+        client.try_batch_transfer_from(&owner, &buyer, &token_ids, sender)
+    };
+    // Approval for one token doesn't cover the rest
+    let result = batch(&[first, second], &spender);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+    let result = batch(&[first, theirs], &owner);
+    assert_eq!(result, Err(Ok(ContractError::NotOwner)));
+    let result = batch(&[first, badge], &owner);
+    assert_eq!(result, Err(Ok(ContractError::TokenIsSoulbound)));
+    let result = batch(&[first, 99], &owner);
+    assert_eq!(result, Err(Ok(ContractError::TokenNotFound)));
+    let result = batch(&[first, second, first], &owner);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+    let result = batch(&[], &owner);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    // Nothing moved
+    assert_eq!(client.balance_of(&owner), 3);
+    assert_eq!(client.balance_of(&buyer), 1);
+    assert_eq!(client.get_approved(&first), Some(spender.clone()));
+
+    batch(&[first], &spender).unwrap().unwrap();
+    assert_eq!(client.owner_of(&first), buyer);
+}
+
+#[test]
+fn test_token_expiry_boundary() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+    let updater = Address::generate(&env);
+    let mint_expiring = |expires_at: u64| {
+        client.try_mint(
+            &owner,
+            &String::from_str(&env, "ipfs://ticket"),
+            &Vec::new(&env),
+            &None,
+            &None,
+            &Some(expires_at),
+            &admin,
+        )
+    };
+
+    // The expiry must be in the future
+    assert_eq!(mint_expiring(1_000), Err(Ok(ContractError::InvalidInput)));
+    let token_id = mint_expiring(2_000).unwrap().unwrap();
+    assert_eq!(client.get_token_data(&token_id).expires_at, Some(2_000));
+
+    // Still valid at the expiry timestamp itself
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert_eq!(client.owner_of(&token_id), owner);
+    client.approve(&other, &token_id, &owner);
+
+    // One second later the token is expired
+    env.ledger().with_mut(|li| li.timestamp = 2_001);
+    assert_eq!(
+        client.try_owner_of(&token_id),
+        Err(Ok(ContractError::TokenExpired))
+    );
+    assert_eq!(
+        client.try_transfer(&owner, &other, &token_id),
+        Err(Ok(ContractError::TokenExpired))
+    );
+    assert_eq!(
+        client.try_approve(&other, &token_id, &owner),
+        Err(Ok(ContractError::TokenExpired))
+    );
+
+    // Renewal needs the MetadataUpdater role and counts from now
+    assert_eq!(
+        client.try_renew_token(&token_id, &500, &owner),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.grant_role(&(Role::MetadataUpdater as u32), &updater);
+    assert_eq!(client.renew_token(&token_id, &500, &updater), 2_501);
+    client.transfer(&owner, &other, &token_id);
+    assert_eq!(client.owner_of(&token_id), other);
+
+    // Tokens minted without an expiry never lapse and cannot be renewed
+    let permanent = mint_one(&env, &client, &owner, &admin);
+    assert_eq!(
+        client.try_renew_token(&permanent, &500, &updater),
+        Err(Ok(ContractError::NotPermitted))
+    );
+}
+
+#[test]
+fn test_burn_expired_token() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let mint_ticket = || {
+        client.mint(
+            &owner,
+            &String::from_str(&env, "ipfs://ticket"),
+            &Vec::new(&env),
+            &None,
+            &None,
+            &Some(2_000),
+            &admin,
+        )
+    };
+    let ticket = mint_ticket();
+    let batched = mint_ticket();
+    client.set_approval_for_all(&operator, &true, &owner);
+    env.ledger().with_mut(|li| li.timestamp = 2_001);
+
+    // An operator's approval lapses with the token, but the owner can still
+    // clear it out of their balance
+    assert_eq!(
+        client.try_burn(&ticket, &operator),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.burn(&ticket, &owner);
+    client.batch_burn(&Vec::from_array(&env, [batched]));
+    assert_eq!(client.balance_of(&owner), 0);
+    assert_eq!(client.get_total_supply(), 0);
+}
+
+#[test]
+fn test_token_lock_lifecycle() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let lender = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let token_id = mint_one(&env, &client, &owner, &admin);
+    let other = mint_one(&env, &client, &owner, &admin);
+
+    let result = client.try_lock_token(&token_id, &lender, &3_600, &lender);
+    assert_eq!(result, Err(Ok(ContractError::NotOwner)));
+    let unlock_at = client.lock_token(&token_id, &lender, &3_600, &owner);
+    assert_eq!(
+        client.get_token_lock(&token_id),
+        Some((lender.clone(), unlock_at))
+    );
+    let result = client.try_lock_token(&token_id, &buyer, &3_600, &owner);
+    assert_eq!(result, Err(Ok(ContractError::TokenLocked)));
+
+    // Locked tokens stay with the owner and can't leave or be approved
+    assert_eq!(client.owner_of(&token_id), owner);
+    let result = client.try_transfer(&owner, &buyer, &token_id);
+    assert_eq!(result, Err(Ok(ContractError::TokenLocked)));
+    let result = client.try_approve(&buyer, &token_id, &owner);
+    assert_eq!(result, Err(Ok(ContractError::TokenLocked)));
+    let result = client.try_burn(&token_id, &owner);
+    assert_eq!(result, Err(Ok(ContractError::TokenLocked)));
+    let token_ids = Vec::from_array(&env, [other, token_id]);
+    let result = client.try_batch_transfer_from(&owner, &buyer, &token_ids, &owner);
+    assert_eq!(result, Err(Ok(ContractError::TokenLocked)));
+    client.transfer(&owner, &buyer, &other);
+
+    // The owner has to wait for the lock to lapse
+    let result = client.try_unlock_token(&token_id, &owner);
+    assert_eq!(result, Err(Ok(ContractError::TokenLocked)));
+    env.ledger().with_mut(|li| li.timestamp = unlock_at);
+    client.unlock_token(&token_id, &owner);
+    assert_eq!(client.get_token_lock(&token_id), None);
+    let result = client.try_unlock_token(&token_id, &owner);
+    assert_eq!(result, Err(Ok(ContractError::NotPermitted)));
+    client.transfer(&owner, &buyer, &token_id);
+    assert_eq!(client.owner_of(&token_id), buyer);
+}
+
+#[test]
+fn test_locker_can_release_early() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let lender = Address::generate(&env);
+    let token_id = mint_one(&env, &client, &owner, &admin);
+
+    client.lock_token(&token_id, &lender, &86_400, &owner);
+    let result = client.try_unlock_token(&token_id, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(ContractError::TokenLocked)));
+    client.unlock_token(&token_id, &lender);
+    client.burn(&token_id, &owner);
+}
+
+#[test]
+fn test_children_move_with_their_parent() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let player = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let character = mint_one(&env, &client, &player, &admin);
+    let sword = mint_one(&env, &client, &player, &admin);
+    let gem = mint_one(&env, &client, &player, &admin);
+    let theirs = mint_one(&env, &client, &buyer, &admin);
+
+    let result = client.try_attach_child(&character, &theirs, &player);
+    assert_eq!(result, Err(Ok(ContractError::NotOwner)));
+    client.attach_child(&character, &sword, &player);
+    client.attach_child(&sword, &gem, &player);
+    assert_eq!(
+        client.get_token_children(&character),
+        Vec::from_array(&env, [sword])
+    );
+    assert_eq!(client.get_token_data(&gem).child_of, Some(sword));
+
+    // No second parent and no cycles
+    let result = client.try_attach_child(&character, &gem, &player);
+    assert_eq!(result, Err(Ok(ContractError::TokenIsAttached)));
+    let result = client.try_attach_child(&gem, &character, &player);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    // Attached tokens only move with their parent
+    let result = client.try_transfer(&player, &buyer, &sword);
+    assert_eq!(result, Err(Ok(ContractError::TokenIsAttached)));
+    let result = client.try_burn(&character, &player);
+    assert_eq!(result, Err(Ok(ContractError::TokenHasChildren)));
+    let result = client.try_burn(&gem, &player);
+    assert_eq!(result, Err(Ok(ContractError::TokenIsAttached)));
+
+    client.transfer(&player, &buyer, &character);
+    for token_id in [character, sword, gem] {
+        assert_eq!(client.owner_of(&token_id), buyer);
+    }
+    assert_eq!(client.balance_of(&player), 0);
+    assert_eq!(client.balance_of(&buyer), 4);
+
+    // Only the new owner can take the tree apart
+    let result = client.try_detach_child(&sword, &gem, &player);
+    assert_eq!(result, Err(Ok(ContractError::NotOwner)));
+    let result = client.try_detach_child(&character, &gem, &buyer);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+    client.detach_child(&character, &sword, &buyer);
+    assert_eq!(client.get_token_children(&character).len(), 0);
+    client.burn(&character, &buyer);
+    assert_eq!(
+        client.get_token_children(&sword),
+        Vec::from_array(&env, [gem])
+    );
+}
+
+#[test]
+fn test_batch_transfer_from_carries_children() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let player = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let character = mint_one(&env, &client, &player, &admin);
+    let sword = mint_one(&env, &client, &player, &admin);
+    let shield = mint_one(&env, &client, &player, &admin);
+    let loose = mint_one(&env, &client, &player, &admin);
+    client.attach_child(&character, &sword, &player);
+    client.attach_child(&character, &shield, &player);
+
+    let result = client.try_batch_transfer_from(
+        &player,
+        &buyer,
+        &Vec::from_array(&env, [loose, shield]),
+        &player,
+    );
+    assert_eq!(result, Err(Ok(ContractError::TokenIsAttached)));
+
+    client.batch_transfer_from(
+        &player,
+        &buyer,
+        &Vec::from_array(&env, [character, loose]),
+        &player,
+    );
+    assert_eq!(client.balance_of(&player), 0);
+    assert_eq!(client.balance_of(&buyer), 4);
+    assert_eq!(
+        client.get_tokens_of_owner(&buyer, &0, &10),
+        Vec::from_array(&env, [character, loose, sword, shield])
+    );
+}
+
+#[test]
+fn test_pause_blocks_mint_until_unpaused() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+    let token_id = mint_one(&env, &client, &user, &admin);
+
+    client.pause();
+    assert!(client.is_paused());
+
+    let result = client.try_mint(
+        &user,
+        &String::from_str(&env, "ipfs://hash"),
+        &Vec::new(&env),
+        &None,
+        &None,
+        &None,
+        &admin,
+    );
+    assert_eq!(result, Err(Ok(ContractError::ContractPaused)));
+    assert_eq!(
+        client.try_transfer(&user, &other, &token_id),
+        Err(Ok(ContractError::ContractPaused))
+    );
+    assert_eq!(
+        client.try_burn(&token_id, &user),
+        Err(Ok(ContractError::ContractPaused))
+    );
+
+    client.unpause();
+    assert!(!client.is_paused());
+    mint_one(&env, &client, &user, &admin);
+    client.transfer(&user, &other, &token_id);
+}
+
+#[test]
+fn test_provenance_hash_set_once() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    assert_eq!(client.get_provenance_hash(), None);
+
+    let hash: Bytes = env
+        .crypto()
+        .sha256(&Bytes::from_slice(&env, b"image-1image-2"))
+        .into();
+    client.set_provenance_hash(&hash, &admin);
+    assert_eq!(client.get_provenance_hash(), Some(hash.clone()));
+
+    let other = Bytes::from_array(&env, &[7u8; 32]);
+    let result = client.try_set_provenance_hash(&other, &admin);
+    assert_eq!(result, Err(Ok(ContractError::ProvenanceAlreadySet)));
+
+    // Replacing the whole config cannot swap the committed hash either.
+    let mut config = client.get_collection_config();
+    config.provenance_hash = Some(other);
+    let result = client.try_set_collection_config(&config, &admin);
+    assert_eq!(result, Err(Ok(ContractError::ProvenanceAlreadySet)));
+    assert_eq!(client.get_provenance_hash(), Some(hash));
+}
+
+#[test]
+fn test_set_base_uri_composes_token_uri() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let user = Address::generate(&env);
+    let updater = Address::generate(&env);
+    let with_uri = mint_one(&env, &client, &user, &admin);
+    let bare = client.mint(
+        &user,
+        &String::from_str(&env, ""),
+        &Vec::new(&env),
+        &None,
+        &None,
+        &None,
+        &admin,
+    );
+    assert_eq!(
+        client.token_uri(&bare),
+        String::from_str(&env, "https://test.com/2")
+    );
+
+    let new_uri = String::from_str(&env, "ipfs://revealed/");
+    let result = client.try_set_base_uri(&new_uri, &updater);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+
+    client.grant_role(&(Role::MetadataUpdater as u32), &updater);
+    client.set_base_uri(&new_uri, &updater);
+    assert_eq!(
+        client.token_uri(&bare),
+        String::from_str(&env, "ipfs://revealed/2")
+    );
+    assert_eq!(
+        client.token_uri(&with_uri),
+        String::from_str(&env, "ipfs://hash")
+    );
+}
+
+#[test]
+fn test_update_token_attributes() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let updater = Address::generate(&env);
+    let token_id = mint_one(&env, &client, &owner, &admin);
+    let attributes = Vec::from_array(
+        &env,
+        [TokenAttribute {
+            trait_type: String::from_str(&env, "Level"),
+            value: String::from_str(&env, "2"),
+        }],
+    );
+
+    // Neither the owner nor an address without the role may update
+    let result = client.try_update_token_attributes(&token_id, &attributes, &owner);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+    let result = client.try_update_token_attributes(&token_id, &attributes, &updater);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+
+    client.grant_role(&(Role::MetadataUpdater as u32), &updater);
+    client.update_token_attributes(&token_id, &attributes, &updater);
+    assert_eq!(client.get_token_attributes(&token_id), attributes);
+    let token = client.get_token_data(&token_id);
+    assert_eq!(token.owner, owner);
+    assert_eq!(token.metadata_uri, String::from_str(&env, "ipfs://hash"));
+
+    let result = client.try_update_token_attributes(&99, &attributes, &updater);
+    assert_eq!(result, Err(Ok(ContractError::TokenNotFound)));
+}
+
+#[test]
+fn test_register_mutation_hook() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let hook = Address::generate(&env);
+    let token_id = mint_one(&env, &client, &owner, &admin);
+    assert_eq!(client.get_mutation_hook(&token_id), None);
+
+    // Only the owner may point the token at a hook
+    let result = client.try_register_mutation_hook(&token_id, &hook, &admin);
+    assert_eq!(result, Err(Ok(ContractError::NotOwner)));
+    let result = client.try_register_mutation_hook(&99, &hook, &owner);
+    assert_eq!(result, Err(Ok(ContractError::TokenNotFound)));
+
+    client.register_mutation_hook(&token_id, &hook, &owner);
+    assert_eq!(client.get_mutation_hook(&token_id), Some(hook));
+
+    client.burn(&token_id, &owner);
+    assert_eq!(client.get_mutation_hook(&token_id), None);
+}
+
+#[test]
+fn test_freeze_metadata_blocks_updates() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let user = Address::generate(&env);
+    let token_id = mint_one(&env, &client, &user, &admin);
+    let hash = Bytes::from_array(&env, &[1u8; 32]);
+    client.set_provenance_hash(&hash, &admin);
+    let config = client.get_collection_config();
+
+    client.freeze_metadata();
+    assert!(client.get_collection_config().metadata_is_frozen);
+
+    let uri = String::from_str(&env, "ipfs://changed");
+    assert_eq!(
+        client.try_set_collection_config(&config, &admin),
+        Err(Ok(ContractError::MetadataFrozen))
+    );
+    assert_eq!(
+        client.try_set_base_uri(&uri, &admin),
+        Err(Ok(ContractError::MetadataFrozen))
+    );
+    assert_eq!(
+        client.try_set_token_uri(&token_id, &uri, &admin),
+        Err(Ok(ContractError::MetadataFrozen))
+    );
+    assert_eq!(
+        client.try_set_provenance_hash(&hash, &admin),
+        Err(Ok(ContractError::MetadataFrozen))
+    );
+    assert_eq!(
+        client.try_freeze_metadata(),
+        Err(Ok(ContractError::MetadataFrozen))
+    );
+    assert_eq!(
+        client.try_update_token_attributes(&token_id, &Vec::new(&env), &admin),
+        Err(Ok(ContractError::MetadataFrozen))
+    );
+
+    // Frozen data stays readable.
+    assert_eq!(client.get_provenance_hash(), Some(hash));
+    assert_eq!(
+        client.token_uri(&token_id),
+        String::from_str(&env, "ipfs://hash")
+    );
+}
+
+#[test]
+fn test_metadata_updates_before_freeze() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let user = Address::generate(&env);
+    let token_id = mint_one(&env, &client, &user, &admin);
+
+    let uri = String::from_str(&env, "ipfs://updated");
+    client.set_token_uri(&token_id, &uri, &admin);
+    assert_eq!(client.token_uri(&token_id), uri);
+
+    let mut config = client.get_collection_config();
+    config.name = String::from_str(&env, "Renamed");
+    client.set_collection_config(&config, &admin);
+    assert_eq!(client.name(), String::from_str(&env, "Renamed"));
+}
+
+fn setup_paid_mint<'a>(env: &'a Env, price: i128) -> (NftContractClient<'a>, Address, Address) {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let contract_id = env.register(NftContract, ());
+    let client = NftContractClient::new(env, &contract_id);
+    let mut config = create_config(env, &admin);
+    config.mint_price = Some(price);
+    client.initialize(&admin, &config);
+
+    let asset = env.register_stellar_asset_contract_v2(admin.clone());
+    client.set_mint_payment_asset(&asset.address());
+
+    (client, admin, asset.address())
+}
+
+#[test]
+fn test_mint_with_payment_and_withdraw() {
+    let env = Env::default();
+    let (client, _admin, asset) = setup_paid_mint(&env, 100);
+    let token = TokenClient::new(&env, &asset);
+
+    let buyer = Address::generate(&env);
+    StellarAssetClient::new(&env, &asset).mint(&buyer, &250);
+
+    let uri = String::from_str(&env, "ipfs://paid");
+    client.mint_with_payment(&buyer, &uri, &Vec::new(&env), &asset);
+    client.mint_with_payment(&buyer, &uri, &Vec::new(&env), &asset);
+    assert_eq!(token.balance(&buyer), 50);
+    assert_eq!(client.get_mint_proceeds(&asset), 200);
+
+    let treasury = Address::generate(&env);
+    assert_eq!(client.withdraw_mint_proceeds(&asset, &treasury), 200);
+    assert_eq!(token.balance(&treasury), 200);
+    assert_eq!(client.get_mint_proceeds(&asset), 0);
+}
+
+#[test]
+fn test_mint_with_payment_rejects_wrong_asset() {
+    let env = Env::default();
+    let (client, admin, _asset) = setup_paid_mint(&env, 100);
+
+    let buyer = Address::generate(&env);
+    let other_asset = env.register_stellar_asset_contract_v2(admin).address();
+    StellarAssetClient::new(&env, &other_asset).mint(&buyer, &1000);
+
+    let uri = String::from_str(&env, "ipfs://paid");
+    let result = client.try_mint_with_payment(&buyer, &uri, &Vec::new(&env), &other_asset);
+    assert_eq!(result, Err(Ok(ContractError::NotPermitted)));
+}
+
+#[test]
+fn test_mint_with_payment_requires_price() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let asset = env.register_stellar_asset_contract_v2(admin).address();
+    client.set_mint_payment_asset(&asset);
+
+    let buyer = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://paid");
+    let result = client.try_mint_with_payment(&buyer, &uri, &Vec::new(&env), &asset);
+    assert_eq!(result, Err(Ok(ContractError::NotPermitted)));
+}
+
+#[test]
+fn test_token_enumeration_after_burn() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+    for _ in 0..4 {
+        mint_one(&env, &client, &owner, &admin);
+    }
+    mint_one(&env, &client, &other, &admin);
+
+    client.burn(&2, &owner);
+
+    // The global index compacts while keeping mint order.
+    assert_eq!(client.token_by_index(&0), 1);
+    assert_eq!(client.token_by_index(&1), 3);
+    assert_eq!(client.token_by_index(&3), 5);
+    assert_eq!(
+        client.try_token_by_index(&4),
+        Err(Ok(ContractError::IndexOutOfBounds))
+    );
+
+    // The owner's last token moves into the burned slot.
+    assert_eq!(client.token_of_owner_by_index(&owner, &0), 1);
+    assert_eq!(client.token_of_owner_by_index(&owner, &1), 4);
+    assert_eq!(client.token_of_owner_by_index(&owner, &2), 3);
+    assert_eq!(
+        client.try_token_of_owner_by_index(&owner, &3),
+        Err(Ok(ContractError::IndexOutOfBounds))
+    );
+
+    client.transfer(&owner, &other, &1);
+    assert_eq!(client.token_of_owner_by_index(&owner, &0), 3);
+    assert_eq!(client.token_of_owner_by_index(&other, &1), 1);
+    client.burn(&4, &owner);
+    assert_eq!(client.token_of_owner_by_index(&owner, &0), 3);
+}
+
+#[contract]
+struct AcceptingReceiver;
+
+#[contractimpl]
+impl INftReceiver for AcceptingReceiver {
+    fn on_nft_received(
+        env: Env,
+        _operator: Address,
+        _from: Address,
+        _token_id: u64,
+        data: Bytes,
+    ) -> Bytes {
+        env.storage()
+            .instance()
+            .set(&soroban_sdk::symbol_short!("data"), &data);
+        Bytes::from_array(&env, &NFT_RECEIVED_SELECTOR)
+    }
+}
+
+#[contract]
+struct RejectingReceiver;
+
+#[contractimpl]
+impl INftReceiver for RejectingReceiver {
+    fn on_nft_received(
+        env: Env,
+        _operator: Address,
+        _from: Address,
+        _token_id: u64,
+        _data: Bytes,
+    ) -> Bytes {
+        Bytes::new(&env)
+    }
+}
+
+#[test]
+fn test_transfer_with_data_calls_receiver() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let token_id = mint_one(&env, &client, &owner, &admin);
+    let receiver = env.register(AcceptingReceiver, ());
+
+    let data = Bytes::from_slice(&env, b"order-42");
+    client.transfer_with_data(&owner, &owner, &receiver, &token_id, &data);
+
+    assert_eq!(
+        client.get_tokens_of_owner(&receiver, &0, &1),
+        Vec::from_array(&env, [token_id])
+    );
+    let stored: Bytes = env.as_contract(&receiver, || {
+        env.storage()
+            .instance()
+            .get(&soroban_sdk::symbol_short!("data"))
+            .unwrap()
+    });
+    assert_eq!(stored, data);
+}
+
+#[test]
+fn test_transfer_with_data_reverts_on_bad_selector() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let token_id = mint_one(&env, &client, &owner, &admin);
+    let receiver = env.register(RejectingReceiver, ());
+
+    let data = Bytes::new(&env);
+    let result = client.try_transfer_with_data(&owner, &owner, &receiver, &token_id, &data);
+    assert_eq!(result, Err(Ok(ContractError::InvalidReceiver)));
+    assert_eq!(client.token_of_owner_by_index(&owner, &0), token_id);
+
+    // Contracts without the hook are rejected too.
+    let no_hook = env.register(NftContract, ());
+    let result = client.try_safe_transfer_from(&owner, &owner, &no_hook, &token_id, &data);
+    assert_eq!(result, Err(Ok(ContractError::InvalidReceiver)));
+}
+
+#[test]
+fn test_scheduled_transfer_claim() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    let token_id = mint_one(&env, &client, &owner, &admin);
+    let claimable_at = env.ledger().timestamp() + 1000;
+
+    client.schedule_transfer(&beneficiary, &token_id, &claimable_at, &owner);
+
+    // Locked against normal transfers and early claims.
+    assert_eq!(
+        client.try_transfer(&owner, &beneficiary, &token_id),
+        Err(Ok(ContractError::TransferPending))
+    );
+    assert_eq!(
+        client.try_burn(&token_id, &owner),
+        Err(Ok(ContractError::TransferPending))
+    );
+    assert_eq!(
+        client.try_claim_scheduled_transfer(&token_id),
+        Err(Ok(ContractError::TransferNotClaimable))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = claimable_at);
+    client.claim_scheduled_transfer(&token_id);
+
+    assert_eq!(client.token_of_owner_by_index(&beneficiary, &0), token_id);
+    assert_eq!(client.get_scheduled_transfer(&token_id), None);
+    client.transfer(&beneficiary, &owner, &token_id);
+}
+
+fn sign_voucher(
+    env: &Env,
+    client: &NftContractClient,
+    key: &SigningKey,
+    mut voucher: LazyMintVoucher,
+) -> LazyMintVoucher {
+    let message = env.as_contract(&client.address, || voucher::message(env, &voucher));
+    let message: std::vec::Vec<u8> = message.iter().collect();
+    voucher.signature = Bytes::from_slice(env, &key.sign(&message).to_bytes());
+    voucher
+}
+
+fn setup_vouchers<'a>(env: &'a Env) -> (NftContractClient<'a>, SigningKey, Address) {
+    let (client, admin) = setup(env);
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    client.set_voucher_signer(&BytesN::from_array(env, &key.verifying_key().to_bytes()));
+    let currency = env.register_stellar_asset_contract_v2(admin).address();
+    (client, key, currency)
+}
+
+#[test]
+fn test_redeem_voucher() {
+    let env = Env::default();
+    let (client, key, currency) = setup_vouchers(&env);
+
+    let buyer = Address::generate(&env);
+    StellarAssetClient::new(&env, &currency).mint(&buyer, &100);
+
+    let voucher = sign_voucher(
+        &env,
+        &client,
+        &key,
+        LazyMintVoucher {
+            voucher_id: 77,
+            metadata_uri: String::from_str(&env, "ipfs://lazy"),
+            price: 60,
+            currency: currency.clone(),
+            expiry: env.ledger().timestamp() + 100,
+            nonce: client.get_nonce(&client.get_admin()),
+            signature: Bytes::new(&env),
+        },
+    );
+
+    // The voucher id only guards against replay; the token takes the next id.
+    let token_id = client.redeem_voucher(&voucher, &buyer);
+    assert_eq!(token_id, 1);
+    assert_eq!(
+        client.token_uri(&token_id),
+        String::from_str(&env, "ipfs://lazy")
+    );
+    assert_eq!(TokenClient::new(&env, &currency).balance(&buyer), 40);
+    assert_eq!(client.get_mint_proceeds(&currency), 60);
+
+    // The same voucher cannot be redeemed twice.
+    let result = client.try_redeem_voucher(&voucher, &buyer);
+    assert_eq!(result, Err(Ok(ContractError::VoucherAlreadyUsed)));
+}
+
+#[test]
+fn test_vouchers_revoked_by_nonce_epoch() {
+    let env = Env::default();
+    let (client, key, currency) = setup_vouchers(&env);
+    let admin = client.get_admin();
+    let buyer = Address::generate(&env);
+
+    let nonce = client.get_nonce(&admin);
+    let voucher = sign_voucher(
+        &env,
+        &client,
+        &key,
+        LazyMintVoucher {
+            voucher_id: 5,
+            metadata_uri: String::from_str(&env, "ipfs://lazy"),
+            price: 0,
+            currency,
+            expiry: env.ledger().timestamp() + 100,
+            nonce,
+            signature: Bytes::new(&env),
+        },
+    );
+    let sibling = sign_voucher(
+        &env,
+        &client,
+        &key,
+        LazyMintVoucher {
+            voucher_id: 6,
+            ..voucher.clone()
+        },
+    );
+    let pending = sign_voucher(
+        &env,
+        &client,
+        &key,
+        LazyMintVoucher {
+            voucher_id: 7,
+            ..voucher.clone()
+        },
+    );
+
+    // Vouchers signed at the same nonce each redeem once, and other admin
+    // calls leave them valid.
+    client.pause();
+    client.unpause();
+    client.redeem_voucher(&voucher, &buyer);
+    client.redeem_voucher(&sibling, &buyer);
+    assert_eq!(client.get_nonce(&admin), nonce);
+    let result = client.try_redeem_voucher(&sibling, &buyer);
+    assert_eq!(result, Err(Ok(ContractError::VoucherAlreadyUsed)));
+
+    // Revoking retires every voucher signed at an earlier nonce.
+    assert_eq!(client.revoke_vouchers(), nonce + 1);
+    let result = client.try_redeem_voucher(&pending, &buyer);
+    assert_eq!(result, Err(Ok(ContractError::VoucherAlreadyUsed)));
+
+    let current = sign_voucher(
+        &env,
+        &client,
+        &key,
+        LazyMintVoucher {
+            nonce: nonce + 1,
+            ..pending
+        },
+    );
+    client.redeem_voucher(&current, &buyer);
+}
+
+#[test]
+fn test_redeem_voucher_rejects_expired_and_forged() {
+    let env = Env::default();
+    let (client, key, currency) = setup_vouchers(&env);
+
+    let buyer = Address::generate(&env);
+    let voucher = sign_voucher(
+        &env,
+        &client,
+        &key,
+        LazyMintVoucher {
+            voucher_id: 1,
+            metadata_uri: String::from_str(&env, "ipfs://lazy"),
+            price: 0,
+            currency,
+            expiry: env.ledger().timestamp() + 100,
+            nonce: client.get_nonce(&client.get_admin()),
+            signature: Bytes::new(&env),
+        },
+    );
+
+    let mut forged = voucher.clone();
+    forged.metadata_uri = String::from_str(&env, "ipfs://forged");
+    assert!(client.try_redeem_voucher(&forged, &buyer).is_err());
+
+    env.ledger().with_mut(|li| li.timestamp += 101);
+    let result = client.try_redeem_voucher(&voucher, &buyer);
+    assert_eq!(result, Err(Ok(ContractError::VoucherExpired)));
+}
+
+#[test]
+fn test_fractionalize_and_reconstitute() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let fractions = env
+        .register_stellar_asset_contract_v2(client.address.clone())
+        .address();
+    let fraction_token = TokenClient::new(&env, &fractions);
+
+    let owner = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let token_id = mint_one(&env, &client, &owner, &admin);
+
+    let result = client.try_fractionalize_token(&token_id, &1_000, &fractions, &buyer);
+    assert_eq!(result, Err(Ok(ContractError::NotOwner)));
+    let result = client.try_fractionalize_token(&token_id, &0, &fractions, &owner);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    client.fractionalize_token(&token_id, &1_000, &fractions, &owner);
+    assert_eq!(fraction_token.balance(&owner), 1_000);
+    assert_eq!(
+        client.get_fractionalization(&token_id),
+        Some((fractions.clone(), 1_000))
+    );
+
+    // The NFT is held in place until every fraction comes back
+    let result = client.try_transfer(&owner, &buyer, &token_id);
+    assert_eq!(result, Err(Ok(ContractError::TokenLocked)));
+    let result = client.try_unlock_token(&token_id, &owner);
+    assert_eq!(result, Err(Ok(ContractError::TokenLocked)));
+    let result = client.try_fractionalize_token(&token_id, &10, &fractions, &owner);
+    assert_eq!(result, Err(Ok(ContractError::TokenLocked)));
+
+    fraction_token.transfer(&owner, &buyer, &400);
+    let result = client.try_reconstitute_token(&token_id, &owner);
+    assert_eq!(result, Err(Ok(ContractError::NotOwner)));
+    let result = client.try_reconstitute_token(&token_id, &buyer);
+    assert_eq!(result, Err(Ok(ContractError::NotOwner)));
+
+    // Whoever gathers all the fractions gets the NFT
+    fraction_token.transfer(&owner, &buyer, &600);
+    client.reconstitute_token(&token_id, &buyer);
+    assert_eq!(fraction_token.balance(&buyer), 0);
+    assert_eq!(client.owner_of(&token_id), buyer);
+    assert_eq!(client.get_fractionalization(&token_id), None);
+    assert_eq!(client.get_token_lock(&token_id), None);
+    let result = client.try_reconstitute_token(&token_id, &buyer);
+    assert_eq!(result, Err(Ok(ContractError::NotPermitted)));
+    client.transfer(&buyer, &owner, &token_id);
+}
@@ -0,0 +1,630 @@
+use crate::access_control;
+use crate::error::ContractError;
+use crate::events;
+use crate::metadata;
+use crate::royalty;
+use crate::storage::DataKey;
+use crate::transfer;
+use crate::types::{Role, TokenAttribute, TokenData};
+use soroban_sdk::{Address, Env, String, Vec};
+
+/// Default cap on the number of recipients in one `airdrop`. Each token
+/// writes several ledger entries, so larger drops are split across calls to
+/// stay within per-transaction resource limits.
+pub const DEFAULT_MAX_AIRDROP_BATCH_SIZE: u32 = 50;
+
+pub fn exists(env: &Env, token_id: u64) -> bool {
+    env.storage().persistent().has(&DataKey::Token(token_id))
+}
+
+/// Reads a live token. Tokens past their `expires_at` fail with
+/// `TokenExpired` until renewed.
+pub fn get_token(env: &Env, token_id: u64) -> Result<TokenData, ContractError> {
+    let token = load_token(env, token_id)?;
+    if is_expired(env, &token) {
+        return Err(ContractError::TokenExpired);
+    }
+    Ok(token)
+}
+
+/// Reads a token whether or not it has expired.
+pub fn load_token(env: &Env, token_id: u64) -> Result<TokenData, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Token(token_id))
+        .ok_or(ContractError::TokenNotFound)
+}
+
+pub fn is_expired(env: &Env, token: &TokenData) -> bool {
+    token
+        .expires_at
+        .is_some_and(|expires_at| expires_at < env.ledger().timestamp())
+}
+
+/// Gives a just-minted token an expiry, which must lie in the future.
+pub fn set_expiry(env: &Env, token_id: u64, expires_at: u64) -> Result<(), ContractError> {
+    if expires_at <= env.ledger().timestamp() {
+        return Err(ContractError::InvalidInput);
+    }
+    let mut token = load_token(env, token_id)?;
+    token.expires_at = Some(expires_at);
+    put_token(env, &token);
+    Ok(())
+}
+
+/// Pushes back the expiry of a token by `extension_seconds`, counting from
+/// now if it has already lapsed. Requires the MetadataUpdater role; tokens
+/// minted without an expiry cannot be renewed. Returns the new expiry.
+pub fn renew_token(
+    env: &Env,
+    token_id: u64,
+    extension_seconds: u64,
+    sender: &Address,
+) -> Result<u64, ContractError> {
+    access_control::require_role(env, Role::MetadataUpdater, sender)?;
+    let mut token = load_token(env, token_id)?;
+    let expires_at = token.expires_at.ok_or(ContractError::NotPermitted)?;
+    if extension_seconds == 0 {
+        return Err(ContractError::InvalidInput);
+    }
+
+    let renewed_at = expires_at
+        .max(env.ledger().timestamp())
+        .checked_add(extension_seconds)
+        .ok_or(ContractError::InvalidInput)?;
+    token.expires_at = Some(renewed_at);
+    put_token(env, &token);
+
+    events::emit_token_renewed(env, token_id, renewed_at);
+    Ok(renewed_at)
+}
+
+pub fn put_token(env: &Env, token: &TokenData) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Token(token.id), token);
+}
+
+pub fn balance_of(env: &Env, owner: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Balance(owner.clone()))
+        .unwrap_or(0)
+}
+
+pub fn increment_balance(env: &Env, owner: &Address) {
+    let balance = balance_of(env, owner);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(owner.clone()), &(balance + 1));
+}
+
+pub fn decrement_balance(env: &Env, owner: &Address) {
+    let balance = balance_of(env, owner);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(owner.clone()), &balance.saturating_sub(1));
+}
+
+pub fn set_balance(env: &Env, owner: &Address, balance: u32) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(owner.clone()), &balance);
+}
+
+pub fn get_owned_tokens(env: &Env, owner: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OwnedTokens(owner.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+fn set_owned_tokens(env: &Env, owner: &Address, tokens: &Vec<u64>) {
+    let key = DataKey::OwnedTokens(owner.clone());
+    if tokens.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, tokens);
+    }
+}
+
+pub fn add_owned_token(env: &Env, owner: &Address, token_id: u64) {
+    let mut tokens = get_owned_tokens(env, owner);
+    env.storage().persistent().set(
+        &DataKey::OwnedTokenIndex(owner.clone(), token_id),
+        &tokens.len(),
+    );
+    tokens.push_back(token_id);
+    set_owned_tokens(env, owner, &tokens);
+}
+
+/// Removes `token_id` from the owner's list by moving the last entry into its
+/// slot, keeping `OwnedTokenIndex` in sync.
+pub fn remove_owned_token(env: &Env, owner: &Address, token_id: u64) {
+    let index_key = DataKey::OwnedTokenIndex(owner.clone(), token_id);
+    let index: u32 = match env.storage().persistent().get(&index_key) {
+        Some(index) => index,
+        None => return,
+    };
+
+    let mut tokens = get_owned_tokens(env, owner);
+    let last_index = tokens.len() - 1;
+    if index != last_index {
+        let last_token = tokens.get_unchecked(last_index);
+        tokens.set(index, last_token);
+        env.storage()
+            .persistent()
+            .set(&DataKey::OwnedTokenIndex(owner.clone(), last_token), &index);
+    }
+    tokens.pop_back();
+    env.storage().persistent().remove(&index_key);
+    set_owned_tokens(env, owner, &tokens);
+}
+
+/// Moves `token_ids` from `from`'s list to the end of `to`'s the same way
+/// `remove_owned_token` and `add_owned_token` would, but reads and writes each
+/// list once. `from` and `to` must differ.
+pub fn move_owned_tokens(env: &Env, from: &Address, to: &Address, token_ids: &Vec<u64>) {
+    let mut from_tokens = get_owned_tokens(env, from);
+    let mut to_tokens = get_owned_tokens(env, to);
+    for token_id in token_ids.iter() {
+        let index_key = DataKey::OwnedTokenIndex(from.clone(), token_id);
+        if let Some(index) = env.storage().persistent().get::<_, u32>(&index_key) {
+            let last_index = from_tokens.len() - 1;
+            if index != last_index {
+                let last_token = from_tokens.get_unchecked(last_index);
+                from_tokens.set(index, last_token);
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::OwnedTokenIndex(from.clone(), last_token), &index);
+            }
+            from_tokens.pop_back();
+            env.storage().persistent().remove(&index_key);
+        }
+
+        env.storage().persistent().set(
+            &DataKey::OwnedTokenIndex(to.clone(), token_id),
+            &to_tokens.len(),
+        );
+        to_tokens.push_back(token_id);
+    }
+    set_owned_tokens(env, from, &from_tokens);
+    set_owned_tokens(env, to, &to_tokens);
+}
+
+pub fn token_of_owner_by_index(
+    env: &Env,
+    owner: &Address,
+    index: u32,
+) -> Result<u64, ContractError> {
+    get_owned_tokens(env, owner)
+        .get(index)
+        .ok_or(ContractError::IndexOutOfBounds)
+}
+
+pub fn get_all_tokens(env: &Env) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AllTokens)
+        .unwrap_or(Vec::new(env))
+}
+
+fn add_to_all_tokens(env: &Env, token_id: u64) {
+    let mut tokens = get_all_tokens(env);
+    tokens.push_back(token_id);
+    env.storage().persistent().set(&DataKey::AllTokens, &tokens);
+}
+
+/// Drops `token_id` from the global list, preserving mint order of the rest.
+fn remove_from_all_tokens(env: &Env, token_id: u64) {
+    let mut tokens = get_all_tokens(env);
+    if let Some(index) = tokens.first_index_of(token_id) {
+        tokens.remove(index);
+        env.storage().persistent().set(&DataKey::AllTokens, &tokens);
+    }
+}
+
+pub fn token_by_index(env: &Env, index: u32) -> Result<u64, ContractError> {
+    get_all_tokens(env)
+        .get(index)
+        .ok_or(ContractError::IndexOutOfBounds)
+}
+
+/// Returns up to `limit` token ids held by `owner`, starting at `offset`.
+pub fn tokens_of_owner(env: &Env, owner: &Address, offset: u32, limit: u32) -> Vec<u64> {
+    let tokens = get_owned_tokens(env, owner);
+    let start = offset.min(tokens.len());
+    let end = start.saturating_add(limit).min(tokens.len());
+    tokens.slice(start..end)
+}
+
+pub fn total_supply(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::TotalSupply)
+        .unwrap_or(0)
+}
+
+fn set_total_supply(env: &Env, supply: u64) {
+    env.storage().instance().set(&DataKey::TotalSupply, &supply);
+}
+
+fn next_token_id(env: &Env) -> u64 {
+    let token_id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::NextTokenId)
+        .unwrap_or(1);
+    env.storage()
+        .instance()
+        .set(&DataKey::NextTokenId, &(token_id + 1));
+    token_id
+}
+
+/// Mints a new token to `to`. Authorization of `sender` is the caller's
+/// responsibility; `sender` is recorded as the token's creator. Fails with
+/// `MaxSupplyReached` once the collection's `max_supply` is minted. Soulbound
+/// tokens can never leave `to` other than by burning. `edition` is the
+/// token's `(edition_number, total_editions)` when it is part of an edition;
+/// numbering starts at 1.
+pub fn mint_token(
+    env: &Env,
+    to: &Address,
+    metadata_uri: String,
+    attributes: Vec<TokenAttribute>,
+    soulbound: bool,
+    sender: &Address,
+    edition: Option<(u32, u32)>,
+) -> Result<u64, ContractError> {
+    access_control::require_not_paused(env)?;
+    if let Some((edition_number, total_editions)) = edition
+        && (edition_number == 0 || edition_number > total_editions)
+    {
+        return Err(ContractError::InvalidInput);
+    }
+
+    let supply = total_supply(env);
+    if let Some(max_supply) = metadata::get_collection_config(env)?.max_supply
+        && supply >= max_supply
+    {
+        return Err(ContractError::MaxSupplyReached);
+    }
+
+    let token_id = next_token_id(env);
+    if exists(env, token_id) {
+        return Err(ContractError::TokenAlreadyExists);
+    }
+
+    let token = TokenData {
+        id: token_id,
+        owner: to.clone(),
+        approved: None,
+        metadata_uri,
+        attributes,
+        creator: sender.clone(),
+        created_at: env.ledger().timestamp(),
+        edition_number: edition.map(|(edition_number, _)| edition_number),
+        total_editions: edition.map(|(_, total_editions)| total_editions),
+        soulbound,
+        child_of: None,
+        expires_at: None,
+    };
+
+    put_token(env, &token);
+    increment_balance(env, to);
+    add_owned_token(env, to, token_id);
+    add_to_all_tokens(env, token_id);
+    set_total_supply(env, supply + 1);
+
+    events::emit_mint(env, to.clone(), token_id);
+
+    Ok(token_id)
+}
+
+pub fn get_max_airdrop_batch_size(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::MaxAirdropBatchSize)
+        .unwrap_or(DEFAULT_MAX_AIRDROP_BATCH_SIZE)
+}
+
+pub fn set_max_airdrop_batch_size(env: &Env, size: u32) -> Result<(), ContractError> {
+    if size == 0 {
+        return Err(ContractError::InvalidInput);
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::MaxAirdropBatchSize, &size);
+    Ok(())
+}
+
+/// Mints one token with the same metadata to each recipient, with `sender`
+/// as creator. Token IDs are reserved as one consecutive range and the
+/// supply counters and global token list are written once for the batch,
+/// which emits a single `AirdropEvent` instead of a `MintEvent` per token.
+pub fn airdrop(
+    env: &Env,
+    recipients: &Vec<Address>,
+    metadata_uri: String,
+    attributes: Vec<TokenAttribute>,
+    sender: &Address,
+) -> Result<Vec<u64>, ContractError> {
+    access_control::require_not_paused(env)?;
+    let count = recipients.len();
+    if count == 0 {
+        return Err(ContractError::InvalidInput);
+    }
+    if count > get_max_airdrop_batch_size(env) {
+        return Err(ContractError::BatchTooLarge);
+    }
+
+    let supply = total_supply(env);
+    if let Some(max_supply) = metadata::get_collection_config(env)?.max_supply
+        && supply + count as u64 > max_supply
+    {
+        return Err(ContractError::MaxSupplyReached);
+    }
+
+    let first_token_id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::NextTokenId)
+        .unwrap_or(1);
+    env.storage()
+        .instance()
+        .set(&DataKey::NextTokenId, &(first_token_id + count as u64));
+
+    let created_at = env.ledger().timestamp();
+    let mut all_tokens = get_all_tokens(env);
+    let mut token_ids = Vec::new(env);
+    for (offset, to) in recipients.iter().enumerate() {
+        let token_id = first_token_id + offset as u64;
+        if exists(env, token_id) {
+            return Err(ContractError::TokenAlreadyExists);
+        }
+        put_token(
+            env,
+            &TokenData {
+                id: token_id,
+                owner: to.clone(),
+                approved: None,
+                metadata_uri: metadata_uri.clone(),
+                attributes: attributes.clone(),
+                creator: sender.clone(),
+                created_at,
+                edition_number: None,
+                total_editions: None,
+                soulbound: false,
+                child_of: None,
+                expires_at: None,
+            },
+        );
+        increment_balance(env, &to);
+        add_owned_token(env, &to, token_id);
+        all_tokens.push_back(token_id);
+        token_ids.push_back(token_id);
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::AllTokens, &all_tokens);
+    set_total_supply(env, supply + count as u64);
+
+    events::emit_airdrop(
+        env,
+        sender.clone(),
+        first_token_id,
+        first_token_id + count as u64 - 1,
+    );
+    Ok(token_ids)
+}
+
+/// Returns `(edition_number, total_editions)` for a token minted as part of
+/// an edition.
+pub fn edition_info(env: &Env, token_id: u64) -> Option<(u32, u32)> {
+    let token = get_token(env, token_id).ok()?;
+    Some((token.edition_number?, token.total_editions?))
+}
+
+pub fn get_children(env: &Env, parent_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TokenChildren(parent_id))
+        .unwrap_or(Vec::new(env))
+}
+
+fn set_children(env: &Env, parent_id: u64, children: &Vec<u64>) {
+    let key = DataKey::TokenChildren(parent_id);
+    if children.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, children);
+    }
+}
+
+/// Every token attached below `token_id`, children before grandchildren.
+pub fn descendants(env: &Env, token_id: u64) -> Vec<u64> {
+    let mut found = get_children(env, token_id);
+    let mut i = 0;
+    while i < found.len() {
+        found.append(&get_children(env, found.get_unchecked(i)));
+        i += 1;
+    }
+    found
+}
+
+/// Attaches `child_id` to `parent_id`, e.g. an item equipped to a character.
+/// Both must belong to `sender`. From then on the child moves with its
+/// parent and can't be transferred, locked or burned on its own; a parent
+/// can't be burned while it has children.
+pub fn attach_child(
+    env: &Env,
+    parent_id: u64,
+    child_id: u64,
+    sender: &Address,
+) -> Result<(), ContractError> {
+    access_control::require_not_paused(env)?;
+    let parent = get_token(env, parent_id)?;
+    let mut child = get_token(env, child_id)?;
+    if parent.owner != *sender || child.owner != *sender {
+        return Err(ContractError::NotOwner);
+    }
+    if child.child_of.is_some() {
+        return Err(ContractError::TokenIsAttached);
+    }
+    if child.soulbound {
+        return Err(ContractError::TokenIsSoulbound);
+    }
+    if transfer::has_scheduled_transfer(env, child_id) {
+        return Err(ContractError::TransferPending);
+    }
+    require_unlocked(env, child_id)?;
+
+    // Refuse to attach a token below itself
+    let mut ancestor = Some(parent_id);
+    while let Some(ancestor_id) = ancestor {
+        if ancestor_id == child_id {
+            return Err(ContractError::InvalidInput);
+        }
+        ancestor = get_token(env, ancestor_id)?.child_of;
+    }
+
+    child.child_of = Some(parent_id);
+    put_token(env, &child);
+    let mut children = get_children(env, parent_id);
+    children.push_back(child_id);
+    set_children(env, parent_id, &children);
+    Ok(())
+}
+
+/// Detaches `child_id` from `parent_id`, leaving it with the same owner.
+pub fn detach_child(
+    env: &Env,
+    parent_id: u64,
+    child_id: u64,
+    sender: &Address,
+) -> Result<(), ContractError> {
+    access_control::require_not_paused(env)?;
+    let mut child = get_token(env, child_id)?;
+    if child.child_of != Some(parent_id) {
+        return Err(ContractError::InvalidInput);
+    }
+    if child.owner != *sender {
+        return Err(ContractError::NotOwner);
+    }
+
+    child.child_of = None;
+    put_token(env, &child);
+    let mut children = get_children(env, parent_id);
+    if let Some(index) = children.first_index_of(child_id) {
+        children.remove(index);
+    }
+    set_children(env, parent_id, &children);
+    Ok(())
+}
+
+/// Returns `(locker, unlock_at)` while `token_id` is locked.
+pub fn get_lock(env: &Env, token_id: u64) -> Option<(Address, u64)> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TokenLock(token_id))
+}
+
+/// Guard for anything that moves, approves or destroys a token.
+pub fn require_unlocked(env: &Env, token_id: u64) -> Result<(), ContractError> {
+    if env
+        .storage()
+        .persistent()
+        .has(&DataKey::TokenLock(token_id))
+    {
+        return Err(ContractError::TokenLocked);
+    }
+    Ok(())
+}
+
+/// Locks `token_id` in place for `locker`, e.g. as loan collateral. The
+/// owner keeps the token but can't transfer, approve or burn it until it is
+/// unlocked. Only the owner may lock, and not while a transfer is scheduled.
+pub fn lock_token(
+    env: &Env,
+    token_id: u64,
+    locker: &Address,
+    duration_seconds: u64,
+    sender: &Address,
+) -> Result<u64, ContractError> {
+    let token = get_token(env, token_id)?;
+    if token.owner != *sender {
+        return Err(ContractError::NotOwner);
+    }
+    if token.child_of.is_some() {
+        return Err(ContractError::TokenIsAttached);
+    }
+    require_unlocked(env, token_id)?;
+    if transfer::has_scheduled_transfer(env, token_id) {
+        return Err(ContractError::TransferPending);
+    }
+    if duration_seconds == 0 {
+        return Err(ContractError::InvalidInput);
+    }
+
+    let unlock_at = env.ledger().timestamp().saturating_add(duration_seconds);
+    env.storage()
+        .persistent()
+        .set(&DataKey::TokenLock(token_id), &(locker.clone(), unlock_at));
+    events::emit_token_locked(env, token_id, locker.clone(), unlock_at);
+    Ok(unlock_at)
+}
+
+/// Releases a lock. The locker can release it at any time; the owner only
+/// once `unlock_at` has passed.
+pub fn unlock_token(env: &Env, token_id: u64, sender: &Address) -> Result<(), ContractError> {
+    let (locker, unlock_at) = get_lock(env, token_id).ok_or(ContractError::NotPermitted)?;
+    let is_owner = get_token(env, token_id)?.owner == *sender;
+    let unlocked = *sender == locker || (is_owner && env.ledger().timestamp() >= unlock_at);
+    if !unlocked {
+        return Err(ContractError::TokenLocked);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::TokenLock(token_id));
+    events::emit_token_unlocked(env, token_id, locker);
+    Ok(())
+}
+
+/// Burns `token_id`. The sender must own the token, be approved for it, or
+/// hold the Burner role.
+pub fn burn_token(env: &Env, token_id: u64, sender: &Address) -> Result<(), ContractError> {
+    access_control::require_not_paused(env)?;
+    // Expired tokens can still be burned, though only by their owner or a burner
+    let token = load_token(env, token_id)?;
+    let permitted = token.owner == *sender
+        || (!is_expired(env, &token) && transfer::is_approved_or_owner(env, sender, token_id)?)
+        || access_control::has_role(env, Role::Burner, sender);
+    if !permitted {
+        return Err(ContractError::Unauthorized);
+    }
+    if transfer::has_scheduled_transfer(env, token_id) {
+        return Err(ContractError::TransferPending);
+    }
+    require_unlocked(env, token_id)?;
+    if token.child_of.is_some() {
+        return Err(ContractError::TokenIsAttached);
+    }
+    if !get_children(env, token_id).is_empty() {
+        return Err(ContractError::TokenHasChildren);
+    }
+
+    env.storage().persistent().remove(&DataKey::Token(token_id));
+    royalty::remove_token_royalty(env, token_id);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::MutationHookContract(token_id));
+    decrement_balance(env, &token.owner);
+    remove_owned_token(env, &token.owner, token_id);
+    remove_from_all_tokens(env, token_id);
+    set_total_supply(env, total_supply(env).saturating_sub(1));
+
+    events::emit_burn(env, token.owner, token_id);
+
+    Ok(())
+}
@@ -0,0 +1,340 @@
+use crate::access_control;
+use crate::error::ContractError;
+use crate::events;
+use crate::interface::{NFT_RECEIVED_SELECTOR, NftReceiverClient};
+use crate::storage::DataKey;
+use crate::token;
+use crate::types::{ScheduledTransfer, TokenData};
+use soroban_sdk::{Address, Bytes, Env, Executable, Vec};
+
+/// Operator approvals granted with an expiry stop counting once the ledger
+/// timestamp reaches it.
+pub fn is_approved_for_all(env: &Env, owner: &Address, operator: &Address) -> bool {
+    let approved: bool = env
+        .storage()
+        .persistent()
+        .get(&DataKey::OperatorApproval(owner.clone(), operator.clone()))
+        .unwrap_or(false);
+    if !approved {
+        return false;
+    }
+
+    match get_operator_expiry(env, owner, operator) {
+        Some(expiry) => env.ledger().timestamp() < expiry,
+        None => true,
+    }
+}
+
+pub fn get_operator_expiry(env: &Env, owner: &Address, operator: &Address) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OperatorExpiry(owner.clone(), operator.clone()))
+}
+
+pub fn is_approved_or_owner(
+    env: &Env,
+    spender: &Address,
+    token_id: u64,
+) -> Result<bool, ContractError> {
+    let token = token::get_token(env, token_id)?;
+
+    if token.owner == *spender {
+        return Ok(true);
+    }
+    if token.approved == Some(spender.clone()) {
+        return Ok(true);
+    }
+    Ok(is_approved_for_all(env, &token.owner, spender))
+}
+
+/// Moves `token_id` from `from` to `to` on behalf of `spender`. Tokens with a
+/// scheduled transfer are locked until it is claimed.
+pub fn transfer(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    token_id: u64,
+    spender: &Address,
+) -> Result<(), ContractError> {
+    access_control::require_not_paused(env)?;
+
+    let mut token = token::get_token(env, token_id)?;
+
+    if token.soulbound {
+        return Err(ContractError::TokenIsSoulbound);
+    }
+    if token.owner != *from {
+        return Err(ContractError::NotOwner);
+    }
+    if !is_approved_or_owner(env, spender, token_id)? {
+        return Err(ContractError::Unauthorized);
+    }
+    if has_scheduled_transfer(env, token_id) {
+        return Err(ContractError::TransferPending);
+    }
+    token::require_unlocked(env, token_id)?;
+    if token.child_of.is_some() {
+        return Err(ContractError::TokenIsAttached);
+    }
+
+    move_token(env, &mut token, from, to);
+    Ok(())
+}
+
+/// Reassigns ownership of a token and everything attached below it.
+pub fn move_token(env: &Env, token: &mut TokenData, from: &Address, to: &Address) {
+    move_single_token(env, token, from, to);
+    for child_id in token::descendants(env, token.id).iter() {
+        if let Ok(mut child) = token::load_token(env, child_id) {
+            move_single_token(env, &mut child, from, to);
+        }
+    }
+}
+
+/// Reassigns ownership, clearing any single-token approval, and updates
+/// balances and enumeration.
+fn move_single_token(env: &Env, token: &mut TokenData, from: &Address, to: &Address) {
+    let token_id = token.id;
+    token.owner = to.clone();
+    token.approved = None;
+    token::put_token(env, token);
+
+    token::decrement_balance(env, from);
+    token::increment_balance(env, to);
+    token::remove_owned_token(env, from, token_id);
+    token::add_owned_token(env, to, token_id);
+
+    events::emit_transfer(env, from.clone(), to.clone(), token_id);
+}
+
+/// Moves several of `from`'s tokens to `to` on behalf of `spender`, who must
+/// be allowed to transfer every one of them. All tokens are checked before
+/// any moves, so one bad token fails the whole batch. Balances and the two
+/// owners' token lists are written once for the batch, which emits a single
+/// `BatchTransferEvent`.
+pub fn batch_transfer_from(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    token_ids: &Vec<u64>,
+    spender: &Address,
+) -> Result<(), ContractError> {
+    access_control::require_not_paused(env)?;
+    if token_ids.is_empty() || from == to {
+        return Err(ContractError::InvalidInput);
+    }
+
+    let mut tokens = Vec::new(env);
+    for (i, token_id) in token_ids.iter().enumerate() {
+        if token_ids.first_index_of(token_id) != Some(i as u32) {
+            return Err(ContractError::InvalidInput);
+        }
+        let token = token::get_token(env, token_id)?;
+        if token.soulbound {
+            return Err(ContractError::TokenIsSoulbound);
+        }
+        if token.owner != *from {
+            return Err(ContractError::NotOwner);
+        }
+        if !is_approved_or_owner(env, spender, token_id)? {
+            return Err(ContractError::Unauthorized);
+        }
+        if has_scheduled_transfer(env, token_id) {
+            return Err(ContractError::TransferPending);
+        }
+        token::require_unlocked(env, token_id)?;
+        if token.child_of.is_some() {
+            return Err(ContractError::TokenIsAttached);
+        }
+        tokens.push_back(token);
+    }
+
+    // Attached children travel with their parents
+    let mut moving = token_ids.clone();
+    for token_id in token_ids.iter() {
+        moving.append(&token::descendants(env, token_id));
+    }
+    for child_id in moving.slice(token_ids.len()..).iter() {
+        tokens.push_back(token::load_token(env, child_id)?);
+    }
+
+    for mut token in tokens.iter() {
+        token.owner = to.clone();
+        token.approved = None;
+        token::put_token(env, &token);
+    }
+    let count = moving.len();
+    token::set_balance(
+        env,
+        from,
+        token::balance_of(env, from).saturating_sub(count),
+    );
+    token::set_balance(env, to, token::balance_of(env, to) + count);
+    token::move_owned_tokens(env, from, to, &moving);
+
+    events::emit_batch_transfer(env, from.clone(), to.clone(), moving);
+    Ok(())
+}
+
+/// Transfers like `transfer`, then, if `to` is a contract, calls its
+/// `on_nft_received` hook with `data`. Anything other than the receiver
+/// selector reverts the transfer.
+pub fn safe_transfer(
+    env: &Env,
+    operator: &Address,
+    from: &Address,
+    to: &Address,
+    token_id: u64,
+    data: Bytes,
+) -> Result<(), ContractError> {
+    transfer(env, from, to, token_id, operator)?;
+
+    if !matches!(to.executable(), Some(Executable::Wasm(_))) {
+        return Ok(());
+    }
+
+    let receiver = NftReceiverClient::new(env, to);
+    match receiver.try_on_nft_received(operator, from, &token_id, &data) {
+        Ok(Ok(response)) if response == Bytes::from_array(env, &NFT_RECEIVED_SELECTOR) => Ok(()),
+        _ => Err(ContractError::InvalidReceiver),
+    }
+}
+
+pub fn approve(
+    env: &Env,
+    approved: &Address,
+    token_id: u64,
+    sender: &Address,
+) -> Result<(), ContractError> {
+    let mut token = token::get_token(env, token_id)?;
+
+    if token.soulbound {
+        return Err(ContractError::TokenIsSoulbound);
+    }
+    if token.owner != *sender && !is_approved_for_all(env, &token.owner, sender) {
+        return Err(ContractError::Unauthorized);
+    }
+    token::require_unlocked(env, token_id)?;
+
+    token.approved = Some(approved.clone());
+    token::put_token(env, &token);
+
+    events::emit_approval(env, token.owner, approved.clone(), token_id);
+
+    Ok(())
+}
+
+/// Operators never gain a way around soulbound tokens: the transfer paths
+/// reject those tokens whoever the sender is.
+pub fn set_approval_for_all(
+    env: &Env,
+    owner: &Address,
+    operator: &Address,
+    approved: bool,
+) -> Result<(), ContractError> {
+    let key = DataKey::OperatorApproval(owner.clone(), operator.clone());
+    if approved {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+    env.storage()
+        .persistent()
+        .remove(&DataKey::OperatorExpiry(owner.clone(), operator.clone()));
+
+    events::emit_approval_for_all(env, owner.clone(), operator.clone(), approved);
+    Ok(())
+}
+
+/// Like `set_approval_for_all`, but an approval lapses at `expiry` (ledger
+/// seconds).
+pub fn approve_operator_with_expiry(
+    env: &Env,
+    owner: &Address,
+    operator: &Address,
+    approved: bool,
+    expiry: u64,
+) -> Result<(), ContractError> {
+    if approved && expiry <= env.ledger().timestamp() {
+        return Err(ContractError::InvalidInput);
+    }
+
+    set_approval_for_all(env, owner, operator, approved)?;
+    if approved {
+        env.storage().persistent().set(
+            &DataKey::OperatorExpiry(owner.clone(), operator.clone()),
+            &expiry,
+        );
+    }
+    Ok(())
+}
+
+pub fn get_scheduled_transfer(env: &Env, token_id: u64) -> Option<ScheduledTransfer> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PendingTransfer(token_id))
+}
+
+pub fn has_scheduled_transfer(env: &Env, token_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::PendingTransfer(token_id))
+}
+
+/// Commits `token_id` to `to`, claimable from `claimable_at` onwards. The
+/// token is locked against normal transfers until claimed.
+pub fn schedule_transfer(
+    env: &Env,
+    to: &Address,
+    token_id: u64,
+    claimable_at: u64,
+    sender: &Address,
+) -> Result<(), ContractError> {
+    access_control::require_not_paused(env)?;
+
+    let token = token::get_token(env, token_id)?;
+    if token.soulbound {
+        return Err(ContractError::TokenIsSoulbound);
+    }
+    if !is_approved_or_owner(env, sender, token_id)? {
+        return Err(ContractError::Unauthorized);
+    }
+    if has_scheduled_transfer(env, token_id) {
+        return Err(ContractError::TransferPending);
+    }
+    token::require_unlocked(env, token_id)?;
+    if token.child_of.is_some() {
+        return Err(ContractError::TokenIsAttached);
+    }
+    if claimable_at <= env.ledger().timestamp() {
+        return Err(ContractError::InvalidInput);
+    }
+
+    env.storage().persistent().set(
+        &DataKey::PendingTransfer(token_id),
+        &ScheduledTransfer {
+            to: to.clone(),
+            claimable_at,
+        },
+    );
+    Ok(())
+}
+
+/// Completes a scheduled transfer once its timestamp has passed.
+/// Authorization of the recipient is the caller's responsibility.
+pub fn claim_scheduled_transfer(env: &Env, token_id: u64) -> Result<Address, ContractError> {
+    access_control::require_not_paused(env)?;
+
+    let scheduled = get_scheduled_transfer(env, token_id).ok_or(ContractError::NotPermitted)?;
+    if env.ledger().timestamp() < scheduled.claimable_at {
+        return Err(ContractError::TransferNotClaimable);
+    }
+
+    let mut token = token::get_token(env, token_id)?;
+    let from = token.owner.clone();
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PendingTransfer(token_id));
+    move_token(env, &mut token, &from, &scheduled.to);
+    Ok(scheduled.to)
+}
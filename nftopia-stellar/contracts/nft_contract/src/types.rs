@@ -0,0 +1,94 @@
+use soroban_sdk::{Address, Bytes, String, Vec, contracttype};
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    Admin = 0,
+    Minter = 1,
+    Burner = 2,
+    MetadataUpdater = 3,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PendingAdmin {
+    pub new_admin: Address,
+    pub expires_at: u64,
+}
+
+/// A committed transfer that `to` can claim once `claimable_at` is reached.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ScheduledTransfer {
+    pub to: Address,
+    pub claimable_at: u64,
+}
+
+/// Off-chain mint authorization signed by the collection's voucher signer.
+/// `voucher_id` identifies the voucher for replay protection and is not a
+/// token id: the minted token takes the next sequential id. `nonce` is the admin's account nonce at
+/// signing time; `revoke_vouchers` advances it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LazyMintVoucher {
+    pub voucher_id: u64,
+    pub metadata_uri: String,
+    pub price: i128,
+    pub currency: Address,
+    pub expiry: u64,
+    pub nonce: u64,
+    pub signature: Bytes,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TokenAttribute {
+    pub trait_type: String,
+    pub value: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RoyaltyInfo {
+    pub recipient: Address,
+    pub percentage: u32, // Basis points (100 = 1%)
+}
+
+/// Divides a token's royalty between co-creators. Shares are basis points of
+/// the royalty amount and must sum to exactly 10000.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RoyaltySplit {
+    pub recipients: Vec<(Address, u32)>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CollectionConfig {
+    pub name: String,
+    pub symbol: String,
+    pub description: String,
+    pub base_uri: String,
+    pub max_supply: Option<u64>,
+    pub mint_price: Option<i128>,
+    pub royalty_default: RoyaltyInfo,
+    pub metadata_is_frozen: bool,
+    pub provenance_hash: Option<Bytes>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TokenData {
+    pub id: u64,
+    pub owner: Address,
+    pub approved: Option<Address>,
+    pub metadata_uri: String,
+    pub attributes: Vec<TokenAttribute>,
+    pub creator: Address,
+    pub created_at: u64,
+    pub edition_number: Option<u32>,
+    pub total_editions: Option<u32>,
+    pub soulbound: bool,
+    pub child_of: Option<u64>,
+    pub expires_at: Option<u64>,
+}
@@ -0,0 +1,66 @@
+use crate::access_control;
+use crate::error::ContractError;
+use crate::storage::DataKey;
+use crate::types::LazyMintVoucher;
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Bytes, BytesN, Env};
+
+pub fn get_signer(env: &Env) -> Option<BytesN<32>> {
+    env.storage().instance().get(&DataKey::VoucherSigner)
+}
+
+pub fn set_signer(env: &Env, public_key: &BytesN<32>) {
+    env.storage()
+        .instance()
+        .set(&DataKey::VoucherSigner, public_key);
+}
+
+pub fn is_used(env: &Env, voucher_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::UsedVoucher(voucher_id))
+}
+
+/// Bytes the signer signs: the XDR encoding of this contract's address and
+/// every voucher field except the signature.
+pub fn message(env: &Env, voucher: &LazyMintVoucher) -> Bytes {
+    (
+        env.current_contract_address(),
+        voucher.voucher_id,
+        voucher.metadata_uri.clone(),
+        voucher.price,
+        voucher.currency.clone(),
+        voucher.expiry,
+        voucher.nonce,
+    )
+        .to_xdr(env)
+}
+
+/// Checks expiry, replay and the signer's ed25519 signature, then marks the
+/// voucher as used. The admin's account nonce is a revocation epoch: a
+/// voucher whose nonce is behind it was signed before the admin last called
+/// `revoke_vouchers` and counts as used. An invalid signature traps in the
+/// host.
+pub fn consume(env: &Env, voucher: &LazyMintVoucher) -> Result<(), ContractError> {
+    let signer = get_signer(env).ok_or(ContractError::NotPermitted)?;
+    if env.ledger().timestamp() > voucher.expiry {
+        return Err(ContractError::VoucherExpired);
+    }
+    let admin = access_control::get_admin(env)?;
+    if is_used(env, voucher.voucher_id) || voucher.nonce < access_control::get_nonce(env, &admin) {
+        return Err(ContractError::VoucherAlreadyUsed);
+    }
+
+    let signature: BytesN<64> = voucher
+        .signature
+        .clone()
+        .try_into()
+        .map_err(|_| ContractError::InvalidInput)?;
+    env.crypto()
+        .ed25519_verify(&signer, &message(env, voucher), &signature);
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::UsedVoucher(voucher.voucher_id), &true);
+    Ok(())
+}